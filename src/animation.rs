@@ -0,0 +1,209 @@
+/// Ce module lit les images des animations (GIF, PNG animés et WebP animés). En mode « extraire les images », chaque
+/// image sélectionnée (ex: « 1-20 », une sur `step`) devient une image fixe nommée `{nom}_0001`, `{nom}_0002`...,
+/// qui suit ensuite le même traitement que les autres sources. En mode « conserver l'animation », les images traitées
+/// sont réencodées en WebP animé (libwebp, crate `webp`) ou en PNG animé (APNG, crate `png`), avec leur durée mise
+/// à l'échelle et le nombre de boucles choisi. Sans ces modes, seule la première image est convertie.
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, DynamicImage, Frames};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::error::ConverterError;
+use crate::pdf;
+use crate::tr;
+
+/// Réglages de l'extraction des images des animations.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrameOptions {
+    pub extract: bool,  // Extraire chaque image des animations au lieu de convertir la première seulement.
+    pub frames: String, // Images à extraire (ex: « 1-20,30 »), vide pour toutes.
+    pub step: u32,      // Ne garder qu'une image sur `step` parmi celles sélectionnées (1 = toutes).
+}
+
+impl Default for FrameOptions {
+    fn default() -> Self {
+        Self { extract: false, frames: String::new(), step: 1 }
+    }
+}
+
+impl FrameOptions {
+    /// Signature de l'extraction, intégrée à celle des options de sortie (manifeste). `None` sans extraction.
+    pub fn signature(&self) -> Option<String> {
+        self.extract.then(|| format!("frames={};step={}", self.frames.trim(), self.step.max(1)))
+    }
+
+    /// Numéros (à partir de 1) des images à extraire d'une animation de `frame_count` images : celles désignées
+    /// par `frames` (voir `pdf::parse_page_range`), puis une sur `step`.
+    pub fn selected_frames(&self, frame_count: usize) -> Result<Vec<usize>, ConverterError> {
+        let frames = pdf::parse_page_range(&self.frames, frame_count)?;
+        Ok(frames.into_iter().step_by(self.step.max(1) as usize).collect())
+    }
+}
+
+/// Durée minimale (ms) d'une image : en deçà (souvent 0 dans les GIF), les navigateurs affichent l'image 100 ms.
+const MIN_FRAME_DELAY_MS: u32 = 20;
+
+/// Durée (ms) donnée aux images plus brèves que `MIN_FRAME_DELAY_MS`.
+const DEFAULT_FRAME_DELAY_MS: u32 = 100;
+
+/// Réglages des animations conservées en sortie.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationOptions {
+    pub keep: bool,       // Conserver l'animation des sources animées en sortie WebP ou PNG, au lieu de leur première image.
+    pub delay_scale: f32, // Facteur appliqué à la durée de chaque image (0,5 = deux fois plus rapide).
+    pub loop_count: u32,  // Nombre de lectures de l'animation (0 = en boucle infinie).
+    pub quality: u8,      // Qualité de chaque image (0-100), sauf en WebP sans perte.
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self { keep: false, delay_scale: 1.0, loop_count: 0, quality: 75 }
+    }
+}
+
+impl AnimationOptions {
+    /// Signature de l'animation, intégrée à celle des options de sortie (manifeste). `None` si elle n'est pas conservée.
+    pub fn signature(&self) -> Option<String> {
+        self.keep.then(|| format!("anim=x{};loop={};q={}", self.delay_scale, self.loop_count, self.quality))
+    }
+
+    /// Durée en sortie (ms) d'une image affichée `delay_ms` dans la source.
+    fn scaled_delay(&self, delay_ms: u32) -> u32 {
+        let delay_ms = if delay_ms < MIN_FRAME_DELAY_MS { DEFAULT_FRAME_DELAY_MS } else { delay_ms };
+        ((delay_ms as f32 * self.delay_scale.max(0.01)).round() as u32).max(1)
+    }
+}
+
+/// Image d'une animation et sa durée d'affichage.
+#[derive(Clone)]
+pub struct AnimatedFrame {
+    pub image: DynamicImage, // Image à la taille du canevas de l'animation.
+    pub delay_ms: u32,       // Durée d'affichage (millisecondes).
+}
+
+/// Suffixe du nom des images extraites pour l'image `frame` (numérotée à partir de 1), ex: `_0003`.
+pub fn frame_suffix(frame: usize) -> String {
+    format!("_{:04}", frame)
+}
+
+/// Indique si `path` est une animation : GIF de plusieurs images, PNG animé (APNG) ou WebP animé.
+pub fn is_animated(path: &Path) -> bool {
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default().to_lowercase();
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    let reader = BufReader::new(file);
+    match extension.as_str() {
+        "gif" => GifDecoder::new(reader).is_ok_and(|decoder| decoder.into_frames().take(2).count() > 1),
+        "png" => PngDecoder::new(reader).and_then(|decoder| decoder.is_apng()).unwrap_or(false),
+        "webp" => WebPDecoder::new(reader).is_ok_and(|decoder| decoder.has_animation()),
+        _ => false,
+    }
+}
+
+/// Décode toutes les images de l'animation `path`, chacune à la taille du canevas de l'animation (les images
+/// partielles sont composées sur les précédentes).
+pub fn decode_frames(path: &Path) -> Result<Vec<AnimatedFrame>, ConverterError> {
+    let file = fs::File::open(path).map_err(|e| ConverterError::io(tr!("Impossible de lire {}", path.display()), e))?;
+    let reader = BufReader::new(file);
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default().to_lowercase();
+    let decode_error = |e| ConverterError::decode(path, e);
+    let frames: Frames = match extension.as_str() {
+        "gif" => GifDecoder::new(reader).map_err(decode_error)?.into_frames(),
+        "png" => PngDecoder::new(reader).and_then(|decoder| decoder.apng()).map_err(decode_error)?.into_frames(),
+        _ => WebPDecoder::new(reader).map_err(decode_error)?.into_frames(),
+    };
+    frames
+        .map(|frame| {
+            let frame = frame.map_err(decode_error)?;
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+            let delay_ms = numerator.checked_div(denominator).unwrap_or(0);
+            Ok(AnimatedFrame { image: DynamicImage::ImageRgba8(frame.into_buffer()), delay_ms })
+        })
+        .collect()
+}
+
+/// Encode `frames` (de mêmes dimensions) en WebP animé, avec ou sans perte.
+pub fn encode_webp(frames: &[AnimatedFrame], options: &AnimationOptions, lossless: bool) -> Result<Vec<u8>, ConverterError> {
+    let encode_error = |message: String| ConverterError::encode("WebP", message);
+    let Some(first) = frames.first() else {
+        return Err(encode_error(tr!("animation sans image").to_string()));
+    };
+    let (width, height) = (first.image.width(), first.image.height());
+    let mut config = webp::WebPConfig::new().map_err(|_| encode_error(tr!("configuration de libwebp invalide").to_string()))?;
+    config.lossless = i32::from(lossless);
+    config.quality = options.quality.min(100) as f32;
+
+    // libwebp n'accepte que le RGBA 8 bits pour les animations : la transparence est toujours conservée.
+    let pixels: Vec<_> = frames.iter().map(|frame| frame.image.to_rgba8()).collect();
+    let durations: Vec<u32> = frames.iter().map(|frame| options.scaled_delay(frame.delay_ms)).collect();
+    let mut encoder = webp::AnimEncoder::new(width, height, &config);
+    encoder.set_loop_count(options.loop_count.min(u16::MAX as u32) as i32);
+    let mut timestamp = 0u32;
+    for (rgba, duration) in pixels.iter().zip(&durations) {
+        encoder.add_frame(webp::AnimFrame::from_rgba(rgba, width, height, timestamp as i32));
+        timestamp = timestamp.saturating_add(*duration);
+    }
+    let bytes = encoder.try_encode().map_err(|e| encode_error(format!("{:?}", e)))?.to_vec();
+    Ok(set_last_duration(bytes, &durations))
+}
+
+/// Encode `frames` (de mêmes dimensions) en PNG animé (APNG), sans perte et avec la transparence. Les lecteurs
+/// qui ignorent l'animation affichent la première image.
+pub fn encode_apng(frames: &[AnimatedFrame], options: &AnimationOptions) -> Result<Vec<u8>, ConverterError> {
+    let encode_error = |e: png::EncodingError| ConverterError::encode("PNG", e);
+    let Some(first) = frames.first() else {
+        return Err(ConverterError::encode("PNG", tr!("animation sans image")));
+    };
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, first.image.width(), first.image.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let frame_count = u32::try_from(frames.len()).unwrap_or(u32::MAX);
+    encoder.set_animated(frame_count, options.loop_count).map_err(encode_error)?;
+    let mut writer = encoder.write_header().map_err(encode_error)?;
+    for frame in frames {
+        // Durée en millièmes de seconde, sur 16 bits.
+        let delay = options.scaled_delay(frame.delay_ms).min(u16::MAX as u32) as u16;
+        writer.set_frame_delay(delay, 1000).map_err(encode_error)?;
+        writer.write_image_data(frame.image.to_rgba8().as_raw()).map_err(encode_error)?;
+    }
+    writer.finish().map_err(encode_error)?;
+    Ok(bytes)
+}
+
+/// libwebp donne à la dernière image la durée moyenne des précédentes : corrige la durée du dernier chunk `ANMF`
+/// de `bytes`, dont les images encodées ont les durées `durations`. Un fichier mal formé est retourné tel quel.
+fn set_last_duration(mut bytes: Vec<u8>, durations: &[u32]) -> Vec<u8> {
+    let [previous @ .., last] = durations else {
+        return bytes;
+    };
+    if previous.is_empty() || bytes.len() < 12 || &bytes[8..12] != b"WEBP" {
+        return bytes;
+    }
+    let mut last_anmf = None;
+    let mut offset = 12;
+    while let Some(header) = bytes.get(offset..offset + 8) {
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        if &header[0..4] == b"ANMF" {
+            last_anmf = Some(offset);
+        }
+        offset += 8 + size + (size & 1); // Les chunks sont alignés sur deux octets.
+    }
+    let Some(duration_bytes) = last_anmf.and_then(|anmf| bytes.get_mut(anmf + 20..anmf + 23)) else {
+        return bytes;
+    };
+
+    // Moyenne ajoutée par libwebp : écart entre le début de la première et de la dernière image, par intervalle.
+    let average = previous.iter().sum::<u32>() / previous.len() as u32;
+    let encoded = u32::from_le_bytes([duration_bytes[0], duration_bytes[1], duration_bytes[2], 0]);
+    let duration = (encoded.saturating_sub(average) + last).min(0xFF_FFFF);
+    duration_bytes.copy_from_slice(&duration.to_le_bytes()[..3]);
+    bytes
+}
@@ -0,0 +1,99 @@
+/// Ce module regroupe les fichiers produits par une conversion dans une seule archive ZIP (`zip`), à la racine
+/// de la sortie, au lieu de fichiers épars : pratique pour envoyer un lot par courriel ou le téléverser dans un CMS.
+/// L'arborescence relative à la racine est conservée ; les fichiers archivés sont ensuite retirés de la sortie.
+/// L'archive est écrite dans un fichier `.part` renommé une fois complet, comme les images produites.
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::error::ConverterError;
+use crate::tr;
+use crate::undo;
+
+/// Extensions (en minuscules) des fichiers compressés dans l'archive ; les images déjà compressées
+/// (WebP, JPEG, PNG, AVIF...) y sont simplement stockées.
+const DEFLATED_EXTENSIONS: [&str; 4] = ["tif", "tiff", "bmp", "html"];
+
+/// Réglages de l'archive ZIP d'une conversion.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchiveOptions {
+    pub enabled: bool, // Regrouper les fichiers produits dans une archive à la fin de la conversion.
+    pub name: String,  // Nom de l'archive (sans extension), écrite à la racine de la sortie.
+}
+
+impl Default for ArchiveOptions {
+    fn default() -> Self {
+        Self { enabled: false, name: "conversion".to_string() }
+    }
+}
+
+impl ArchiveOptions {
+    /// Chemin de l'archive sous `output_root`, `conversion.zip` si le nom choisi est vide.
+    pub fn path(&self, output_root: &Path) -> PathBuf {
+        let name = match self.name.trim() {
+            "" => "conversion",
+            name => name,
+        };
+        output_root.join(format!("{}.zip", name))
+    }
+}
+
+/// Écrit dans le fichier partiel `partial` (ouvert en `file`) l'archive contenant `files`, nommés d'après leur chemin
+/// relatif à `output_root`, la renomme en `archive` une fois les données sur le disque, puis retire les fichiers
+/// archivés et les dossiers restés vides. En cas d'échec, le fichier partiel est supprimé : une archive existante
+/// et les fichiers produits restent intacts.
+pub fn pack(files: &[PathBuf], output_root: &Path, file: fs::File, partial: &Path, archive: &Path) -> Result<(), ConverterError> {
+    let write_error = |e: std::io::Error| ConverterError::io(tr!("Impossible d'écrire l'archive {}", archive.display()), e);
+    let zip_error = |e: zip::result::ZipError| write_error(std::io::Error::other(e));
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let written = files
+        .iter()
+        .try_for_each(|file| {
+            let data = fs::read(file).map_err(|e| ConverterError::io(tr!("Impossible de lire {}", file.display()), e))?;
+            zip.start_file(entry_name(file, output_root), entry_options(file)).map_err(zip_error)?;
+            zip.write_all(&data).map_err(write_error)
+        })
+        .and_then(|()| zip.finish().map_err(zip_error))
+        .and_then(|writer| writer.into_inner().map_err(|e| write_error(e.into_error())))
+        .and_then(|file| {
+            let synced = file.sync_all().map_err(write_error);
+            drop(file); // Windows refuse de renommer un fichier encore ouvert.
+            synced
+        })
+        .and_then(|()| fs::rename(partial, archive).map_err(write_error));
+    if written.is_err() {
+        let _ = fs::remove_file(partial);
+        return written;
+    }
+
+    for file in files {
+        if fs::remove_file(file).is_ok() {
+            undo::remove_empty_parents(file, output_root);
+        }
+    }
+    tracing::info!(archive = %archive.display(), files = files.len(), "fichiers produits archivés");
+    Ok(())
+}
+
+/// Nom de `file` dans l'archive : son chemin relatif à `output_root`, avec des `/` comme séparateurs.
+fn entry_name(file: &Path, output_root: &Path) -> String {
+    let relative = file.strip_prefix(output_root).unwrap_or(file);
+    relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+/// Méthode de compression de `file` : les formats déjà compressés sont stockés tels quels.
+fn entry_options(file: &Path) -> SimpleFileOptions {
+    let deflated = file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| DEFLATED_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+    let method = match deflated {
+        true => CompressionMethod::Deflated,
+        false => CompressionMethod::Stored,
+    };
+    SimpleFileOptions::default().compression_method(method)
+}
@@ -0,0 +1,47 @@
+/// Ce module lit la date de prise de vue d'une photo dans ses métadonnées EXIF (`kamadak-exif`),
+/// à défaut sa date de modification, pour ranger les fichiers produits dans des sous-dossiers `AAAA/MM/`.
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime};
+use exif::{In, Reader, Tag, Value};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// Format des dates EXIF (ex: "2024:06:03 14:32:05").
+const EXIF_DATE_FORMAT: &str = "%Y:%m:%d %H:%M:%S";
+
+/// Balises lues, par ordre de préférence : prise de vue, numérisation, puis dernière modification par l'appareil.
+const DATE_TAGS: [Tag; 3] = [Tag::DateTimeOriginal, Tag::DateTimeDigitized, Tag::DateTime];
+
+/// Date de prise de vue de `path` d'après son EXIF, à défaut sa date de modification.
+/// `None` si aucune des deux n'est lisible.
+pub fn capture_date(path: &Path) -> Option<NaiveDate> {
+    exif_date(path).or_else(|| {
+        let modified = fs::metadata(path).and_then(|metadata| metadata.modified()).ok()?;
+        Some(DateTime::<Local>::from(modified).date_naive())
+    })
+}
+
+/// Sous-dossier `AAAA/MM` de `path` d'après sa date de prise de vue, `inconnue` si elle est illisible.
+pub fn date_subfolder(path: &Path) -> PathBuf {
+    match capture_date(path) {
+        Some(date) => PathBuf::from(format!("{:04}", date.year())).join(format!("{:02}", date.month())),
+        None => PathBuf::from("inconnue"),
+    }
+}
+
+/// Date EXIF de `path` (JPEG, TIFF, PNG, WebP, RAW à base TIFF), `None` sans EXIF ou date valide.
+fn exif_date(path: &Path) -> Option<NaiveDate> {
+    let file = File::open(path).ok()?;
+    let exif = Reader::new().read_from_container(&mut BufReader::new(file)).ok()?;
+    DATE_TAGS.iter().find_map(|tag| {
+        let field = exif.get_field(*tag, In::PRIMARY)?;
+        let Value::Ascii(values) = &field.value else {
+            return None;
+        };
+        let text = std::str::from_utf8(values.first()?).ok()?;
+        // Les appareils mal réglés écrivent « 0000:00:00 00:00:00 » : la date est alors ignorée.
+        NaiveDateTime::parse_from_str(text.trim_end_matches('\0').trim(), EXIF_DATE_FORMAT)
+            .ok()
+            .map(|datetime| datetime.date())
+    })
+}
@@ -0,0 +1,871 @@
+/// Ce module fournit le mode ligne de commande : la conversion est lancée sans ouvrir de fenêtre,
+/// avec les mêmes fonctions de conversion que l'interface graphique, et le bilan est affiché sur la sortie standard.
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+use crate::animation::{AnimationOptions, FrameOptions};
+use crate::archive::ArchiveOptions;
+use crate::color_profile::ColorProfileMode;
+use crate::config::AppConfig;
+use crate::contact_sheet::{ContactSheetFormat, ContactSheetOptions};
+use crate::converter::{
+    self, BatchControl, ConversionOptions, ConversionReport, LargerOutputMode, OutputFormat, OutputStructure, OverwriteMode,
+    RenameSuffix,
+};
+use crate::duplicates::DuplicateOptions;
+use crate::filters::{FileFilter, PathFilter, TraversalOptions};
+use crate::i18n::{self, Language};
+use crate::job::{self, ConversionJob, JobInput};
+use crate::originals::{OriginalOutcome, OriginalsMode};
+use crate::pdf::{self, PdfOptions};
+use crate::pdf_bundle::PdfBundleOptions;
+use crate::report_export;
+use crate::responsive::ResponsiveOptions;
+use crate::run_folder::RunFolderOptions;
+use crate::sprite_sheet::SpriteSheetOptions;
+use crate::targets::{OutputTarget, OutputTargets};
+use crate::tr;
+use crate::transform::{CropAnchor, CropOptions, PadOptions, ResizeFilter, ResizeMode, ResizeOptions, Rotation, TrimOptions};
+use crate::verify::VerifyOptions;
+
+/// Arguments de la ligne de commande.
+#[derive(Debug, Parser)]
+#[command(
+    name = "image_converter",
+    version,
+    about = "Convertit des images (PNG, JPG, JPEG, BMP, TIFF, GIF, WebP, RAW, PDF) sans interface graphique.",
+    after_help = "Cette aide est en français ; le bilan et les messages d'erreur suivent `--lang` (par défaut, la langue de l'interface graphique)."
+)]
+pub struct Cli {
+    /// Image ou répertoire à convertir (les sous-répertoires sont parcourus).
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// Répertoire de sortie (créé si besoin).
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Format des fichiers produits.
+    #[arg(short, long, value_enum, default_value_t = CliFormat::Webp)]
+    format: CliFormat,
+
+    /// Qualité d'encodage (WebP et JPEG : 0-100, AVIF : 1-100). Par défaut, celle de l'interface.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    quality: Option<u8>,
+
+    /// Modèle du nom des fichiers produits : {stem}, {width}, {height}, {quality}, {format}.
+    #[arg(long, default_value = converter::DEFAULT_NAME_TEMPLATE)]
+    name: String,
+
+    /// Encoder chaque image en WebP, AVIF, PNG et JPEG et ne garder que le fichier le plus léger (`--format` ignoré).
+    #[arg(long)]
+    smallest: bool,
+
+    /// Encodage WebP sans perte.
+    #[arg(long)]
+    lossless: bool,
+
+    /// Optimiser les PNG produits sans perte (oxipng), avec cet effort (0 = rapide, 6 = le plus compact).
+    #[arg(long, value_name = "EFFORT", num_args = 0..=1, default_missing_value = "2", value_parser = clap::value_parser!(u8).range(0..=6))]
+    optimize_png: Option<u8>,
+
+    /// JPEG séquentiel (non progressif).
+    #[arg(long)]
+    baseline: bool,
+
+    /// Encoder le JPEG avec l'encodeur standard plutôt qu'avec mozjpeg (plus rapide, fichiers plus lourds).
+    #[arg(long)]
+    no_mozjpeg: bool,
+
+    /// Gestion des fichiers de sortie existants.
+    #[arg(long, value_enum, default_value_t = CliOverwrite::Skip)]
+    overwrite: CliOverwrite,
+
+    /// Suffixe des fichiers renommés (`--overwrite rename`) : numéro ou date et heure.
+    #[arg(long, value_enum, default_value_t = CliRenameSuffix::Counter)]
+    rename_suffix: CliRenameSuffix,
+
+    /// Traitement des sorties plus lourdes que leur source.
+    #[arg(long, value_enum, default_value_t = CliIfLarger::Keep)]
+    if_larger: CliIfLarger,
+
+    /// Sort des images sources après une conversion réussie : conservées, corbeille ou dossier `originals/` de la sortie.
+    #[arg(long, value_enum, default_value_t = CliOriginals::Keep)]
+    originals: CliOriginals,
+
+    /// Ignorer les images inchangées depuis la dernière conversion (empreintes SHA-256 du manifeste,
+    /// insensibles aux dates de modification). Incompatible avec `--zip`, qui retire les fichiers suivis par le manifeste.
+    #[arg(long, conflicts_with = "zip")]
+    incremental: bool,
+
+    /// Supprimer toutes les métadonnées (EXIF, XMP, profil ICC) des fichiers produits.
+    #[arg(long)]
+    strip_metadata: bool,
+
+    /// Ne pas redresser les images d'après leur orientation EXIF.
+    #[arg(long)]
+    no_auto_orient: bool,
+
+    /// Traitement des profils de couleur ICC des sources.
+    #[arg(long, value_enum, default_value_t = CliColorProfile::Srgb)]
+    color_profile: CliColorProfile,
+
+    /// Aplatir la transparence sur la couleur de fond, quel que soit le format (toujours le cas en JPEG).
+    #[arg(long)]
+    flatten_alpha: bool,
+
+    /// Couleur de fond remplaçant la transparence, en hexadécimal (ex: `ffffff`, `#000000`).
+    #[arg(long, value_name = "RRGGBB", value_parser = parse_color, default_value = "ffffff")]
+    background: [u8; 3],
+
+    /// Tourner ou retourner chaque image après le redressement EXIF.
+    #[arg(long, value_enum, default_value_t = CliRotation::None)]
+    rotate: CliRotation,
+
+    /// Rogner les bords uniformes (transparents ou de la couleur du coin supérieur gauche), avec cette tolérance
+    /// par canal (0 = couleur exacte).
+    #[arg(long, value_name = "TOLÉRANCE", num_args = 0..=1, default_missing_value = "10")]
+    trim: Option<u8>,
+
+    /// Recadrer chaque image à ces proportions avant le redimensionnement (ex: `16:9`, `1:1`).
+    #[arg(long, value_name = "LARGEUR:HAUTEUR", value_parser = parse_ratio)]
+    crop: Option<(u32, u32)>,
+
+    /// Placer le cadre de `--crop` sur la zone la plus détaillée de l'image plutôt qu'au centre.
+    #[arg(long, requires = "crop")]
+    smart_crop: bool,
+
+    /// Redimensionner chaque image à ce pourcentage de ses dimensions d'origine (1-400).
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=400), conflicts_with_all = ["max_width", "max_height"])]
+    scale: Option<u32>,
+
+    /// Largeur maximale des images produites (proportions conservées).
+    #[arg(long)]
+    max_width: Option<u32>,
+
+    /// Hauteur maximale des images produites (proportions conservées).
+    #[arg(long)]
+    max_height: Option<u32>,
+
+    /// Filtre de rééchantillonnage du redimensionnement, du plus rapide au plus net.
+    #[arg(long, value_enum, default_value_t = CliFilter::Lanczos3)]
+    filter: CliFilter,
+
+    /// Centrer chaque image sur un canevas de ces dimensions (ex: `800x800`), sans la déformer.
+    #[arg(long, value_name = "LARGEURxHAUTEUR", value_parser = parse_size)]
+    pad: Option<(u32, u32)>,
+
+    /// Couleur du fond du canevas de `--pad`, en hexadécimal, ou `transparent`.
+    #[arg(long, value_name = "RRGGBB", requires = "pad", value_parser = parse_pad_color, default_value = "ffffff")]
+    pad_color: CliPadColor,
+
+    /// Motifs glob des fichiers à convertir dans un répertoire, séparés par des virgules (ex: `*.png,photos/**`).
+    #[arg(long, default_value = "")]
+    include: String,
+
+    /// Motifs glob des fichiers à ignorer dans un répertoire, séparés par des virgules (ex: `**/thumbnails/**`).
+    #[arg(long, default_value = "")]
+    exclude: String,
+
+    /// Taille minimale (en Ko) des fichiers à convertir dans un répertoire.
+    #[arg(long, default_value_t = 0)]
+    min_size: u64,
+
+    /// Ne convertir que les fichiers d'un répertoire modifiés ce jour-là ou après (AAAA-MM-JJ).
+    #[arg(long, default_value = "")]
+    modified_after: String,
+
+    /// Repérer les images en double (ou presque) d'un répertoire par empreinte perceptuelle et ne convertir
+    /// que la plus grande de chaque groupe.
+    #[arg(long)]
+    duplicates: bool,
+
+    /// Avec `--duplicates`, convertir quand même les doublons (ils sont seulement signalés).
+    #[arg(long, requires = "duplicates")]
+    keep_duplicates: bool,
+
+    /// Écart maximal entre deux empreintes (bits, sur 64) pour que deux images soient des doublons.
+    #[arg(long, value_name = "BITS", default_value_t = 4, requires = "duplicates", value_parser = clap::value_parser!(u32).range(0..=64))]
+    duplicate_threshold: u32,
+
+    /// Placer toutes les sorties d'un répertoire dans le dossier de sortie, sans reproduire l'arborescence
+    /// (noms en double préfixés par leur dossier parent).
+    #[arg(long, conflicts_with = "by_date")]
+    flatten: bool,
+
+    /// Ranger les sorties d'un répertoire dans des sous-dossiers `AAAA/MM/` d'après la date de prise de vue EXIF
+    /// (à défaut, la date de modification).
+    #[arg(long)]
+    by_date: bool,
+
+    /// Écrire les sorties dans un sous-dossier de l'exécution, nommé d'après le modèle `MODELE` (par défaut `{date}`,
+    /// ex: `2024-06-03_14-32-05/` ; `{preset}` est remplacé par l'extension du format de sortie).
+    #[arg(long, value_name = "MODELE", num_args = 0..=1, default_missing_value = "{date}")]
+    run_folder: Option<String>,
+
+    /// Profondeur maximale du parcours d'un répertoire, racine comprise (0 = illimitée, 1 = racine seule).
+    #[arg(long, default_value_t = 0)]
+    max_depth: usize,
+
+    /// Suivre les liens symboliques lors du parcours d'un répertoire.
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Ignorer les fichiers et dossiers cachés lors du parcours d'un répertoire.
+    #[arg(long)]
+    skip_hidden: bool,
+
+    /// Destination supplémentaire, répétable : `SOUS-DOSSIER:FORMAT[:TAILLE]`, la taille étant `300` (cadre de 300 px),
+    /// `300x200` ou `50%` (ex: `--target web:webp --target thumbs:webp:300`). Remplace `--format` et le redimensionnement.
+    #[arg(long = "target", value_name = "DESTINATION", value_parser = parse_target)]
+    targets: Vec<OutputTarget>,
+
+    /// Jeu d'images responsives : produire chaque image dans ces largeurs, séparées par des virgules
+    /// (ex: `480,768,1280,1920`), avec le suffixe `-{largeur}w`. Remplace le redimensionnement.
+    #[arg(long, value_delimiter = ',', value_name = "LARGEURS")]
+    responsive: Vec<u32>,
+
+    /// Mode favicon : produire pour chaque image, dans un dossier `NOM-favicon`, un `favicon.ico` (16, 32, 48 et 256 px)
+    /// et les PNG usuels (16, 32, 180, 192 et 512 px) au lieu de la sortie habituelle.
+    #[arg(long)]
+    favicon: bool,
+
+    /// Résolution du rendu des pages des documents PDF (points par pouce).
+    #[arg(long, value_name = "PPP", default_value_t = pdf::DEFAULT_DPI, value_parser = clap::value_parser!(u32).range(36..=1200))]
+    pdf_dpi: u32,
+
+    /// Pages des documents PDF à convertir, pages et plages séparées par des virgules (ex: `1-3,5,8-`).
+    /// Par défaut, toutes. Chaque page produit une image suffixée `-p{page}`.
+    #[arg(long, value_name = "PAGES", value_parser = parse_selection)]
+    pdf_pages: Option<String>,
+
+    /// Extraire chaque image des GIF, PNG et WebP animés en image fixe suffixée `_0001`, `_0002`...,
+    /// au lieu de convertir la première seulement.
+    #[arg(long)]
+    extract_frames: bool,
+
+    /// Images des animations à extraire, images et plages séparées par des virgules (ex: `1-20,30-`). Par défaut, toutes.
+    #[arg(long, value_name = "IMAGES", requires = "extract_frames", value_parser = parse_selection)]
+    frames: Option<String>,
+
+    /// Ne garder qu'une image sur `N` parmi celles extraites par `--extract-frames`.
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "extract_frames", value_parser = clap::value_parser!(u32).range(1..))]
+    frame_step: u32,
+
+    /// Conserver l'animation des GIF, PNG et WebP animés en sortie WebP ou PNG (WebP animé, APNG), au lieu de leur première image.
+    #[arg(long, conflicts_with = "extract_frames")]
+    keep_animation: bool,
+
+    /// Facteur appliqué à la durée de chaque image des animations conservées (0.5 = deux fois plus rapide).
+    #[arg(long, value_name = "FACTEUR", default_value_t = 1.0, requires = "keep_animation")]
+    frame_delay_scale: f32,
+
+    /// Nombre de lectures des animations conservées (0 = en boucle infinie).
+    #[arg(long, value_name = "N", default_value_t = 0, requires = "keep_animation")]
+    loop_count: u32,
+
+    /// Qualité de chaque image des animations WebP conservées (0-100), sauf avec `--lossless`.
+    #[arg(long, value_name = "QUALITE", default_value_t = 75, requires = "keep_animation", value_parser = clap::value_parser!(u8).range(0..=100))]
+    animation_quality: u8,
+
+    /// Composer une planche contact (grille de vignettes légendées du nom des sources) à la racine de la sortie,
+    /// avec ce nombre de colonnes (5 par défaut).
+    #[arg(long, value_name = "COLONNES", num_args = 0..=1, default_missing_value = "5", value_parser = clap::value_parser!(u32).range(1..=20))]
+    contact_sheet: Option<u32>,
+
+    /// Lignes par page de la planche contact ; 0 pour une seule grande planche.
+    #[arg(long, value_name = "LIGNES", default_value_t = 6, value_parser = clap::value_parser!(u32).range(0..=50))]
+    sheet_rows: u32,
+
+    /// Taille maximale (pixels) des vignettes de la planche contact.
+    #[arg(long, value_name = "PX", default_value_t = 200, value_parser = clap::value_parser!(u32).range(32..=1024))]
+    sheet_cell_size: u32,
+
+    /// Écrire la planche contact en PNG plutôt qu'en WebP (sans limite de taille, lisible partout).
+    #[arg(long, requires = "contact_sheet")]
+    sheet_png: bool,
+
+    /// Ne pas légender les vignettes de la planche contact.
+    #[arg(long, requires = "contact_sheet")]
+    no_captions: bool,
+
+    /// Réunir les images produites dans un document PDF `NOM.pdf` (par défaut `images.pdf`) à la racine de la sortie,
+    /// une image par page, dans l'ordre des sources. Les JPEG sont intégrés sans nouvelle compression.
+    #[arg(long, value_name = "NOM", num_args = 0..=1, default_missing_value = "images")]
+    pdf_bundle: Option<String>,
+
+    /// Résolution des images dans le document de `--pdf-bundle` (points par pouce) : fixe la taille des pages.
+    #[arg(long, value_name = "PPP", default_value_t = 150, value_parser = clap::value_parser!(u32).range(36..=1200))]
+    pdf_bundle_dpi: u32,
+
+    /// Assembler les images produites en une planche de sprites `NOM.png` (par défaut `sprites.png`) à la racine
+    /// de la sortie, accompagnée de l'atlas `NOM.json` des coordonnées de chaque image (format de TexturePacker).
+    #[arg(long, value_name = "NOM", num_args = 0..=1, default_missing_value = "sprites")]
+    sprite_sheet: Option<String>,
+
+    /// Espace entre deux sprites de `--sprite-sheet` (pixels).
+    #[arg(long, value_name = "PX", default_value_t = 2, requires = "sprite_sheet")]
+    sprite_padding: u32,
+
+    /// Largeur maximale de la planche de `--sprite-sheet` (pixels), élargie si une image la dépasse.
+    #[arg(long, value_name = "PX", default_value_t = 2048, requires = "sprite_sheet", value_parser = clap::value_parser!(u32).range(1..))]
+    sprite_max_width: u32,
+
+    /// Regrouper les fichiers produits dans une archive `NOM.zip` (par défaut `conversion.zip`) à la racine de la sortie,
+    /// au lieu de fichiers épars (arborescence conservée).
+    #[arg(long, value_name = "NOM", num_args = 0..=1, default_missing_value = "conversion")]
+    zip: Option<String>,
+
+    /// Ne pas écrire l'extrait HTML `srcset` des jeux d'images responsives.
+    #[arg(long, requires = "responsive")]
+    no_srcset: bool,
+
+    /// Nombre de conversions simultanées (0 = un thread par cœur).
+    #[arg(short, long, default_value_t = 0)]
+    jobs: usize,
+
+    /// Convertir avec une priorité basse (valeur « nice » maximale), pour garder la machine réactive.
+    #[arg(long)]
+    low_priority: bool,
+
+    /// Relire et décoder chaque fichier produit pour vérifier qu'il est valide (code de sortie 1 en cas d'échec).
+    #[arg(long)]
+    verify: bool,
+
+    /// Mesurer aussi le PSNR et le SSIM de chaque sortie par rapport à l'image avant encodage (implique `--verify`).
+    #[arg(long)]
+    verify_quality: bool,
+
+    /// SSIM minimal (0 à 1) en dessous duquel une sortie est signalée comme en échec (implique `--verify-quality`).
+    #[arg(long)]
+    min_ssim: Option<f64>,
+
+    /// Simulation : afficher ce que ferait la conversion pour chaque fichier (conversion, écrasement, fichier ignoré)
+    /// sans rien écrire.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Exporter le résultat de chaque fichier dans ce fichier (JSON si l'extension est `.json`, CSV sinon).
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Langue du bilan et des messages d'erreur (par défaut, celle choisie dans l'interface graphique).
+    #[arg(long, value_enum)]
+    lang: Option<CliLanguage>,
+}
+
+/// Formats acceptés par `--format`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliFormat {
+    Webp,
+    Png,
+    #[value(alias = "jpg")]
+    Jpeg,
+    Avif,
+    #[value(alias = "tif")]
+    Tiff,
+}
+
+/// Langues acceptées par `--lang`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliLanguage {
+    Fr,
+    En,
+}
+
+impl CliLanguage {
+    fn language(self) -> Language {
+        match self {
+            CliLanguage::Fr => Language::French,
+            CliLanguage::En => Language::English,
+        }
+    }
+}
+
+/// Langue demandée par `--lang` (sinon celle de l'interface graphique), lue dans les arguments avant leur analyse :
+/// les erreurs des options (`parse_target`, `parse_color`...) sont produites pendant celle-ci.
+fn requested_language() -> Language {
+    let args: Vec<String> = std::env::args().collect();
+    let value = args.iter().enumerate().find_map(|(index, arg)| match arg.strip_prefix("--lang") {
+        Some("") => args.get(index + 1).cloned(),
+        Some(rest) => rest.strip_prefix('=').map(str::to_string),
+        None => None,
+    });
+    match value.and_then(|value| CliLanguage::from_str(&value, true).ok()) {
+        Some(language) => language.language(),
+        None => AppConfig::load().language,
+    }
+}
+
+/// Modes acceptés par `--color-profile`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliColorProfile {
+    Srgb,
+    Embed,
+    Ignore,
+}
+
+/// Transformations acceptées par `--rotate`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliRotation {
+    None,
+    #[value(name = "90")]
+    Rotate90,
+    #[value(name = "180")]
+    Rotate180,
+    #[value(name = "270")]
+    Rotate270,
+    FlipH,
+    FlipV,
+}
+
+/// Filtres acceptés par `--filter`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliFilter {
+    Nearest,
+    #[value(alias = "bilinear")]
+    Triangle,
+    #[value(alias = "bicubic")]
+    CatmullRom,
+    Lanczos3,
+}
+
+/// Fonds acceptés par `--pad-color`.
+#[derive(Debug, Clone, Copy)]
+enum CliPadColor {
+    Transparent,
+    Color([u8; 3]),
+}
+
+/// Modes acceptés par `--if-larger`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliIfLarger {
+    Keep,
+    Copy,
+    Skip,
+}
+
+/// Modes acceptés par `--originals`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliOriginals {
+    Keep,
+    Trash,
+    Backup,
+}
+
+/// Suffixes acceptés par `--rename-suffix`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliRenameSuffix {
+    Counter,
+    Timestamp,
+}
+
+/// Modes acceptés par `--overwrite`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliOverwrite {
+    Skip,
+    Overwrite,
+    Rename,
+    IfNewer,
+}
+
+impl CliFormat {
+    /// Format de sortie correspondant.
+    fn output_format(self) -> OutputFormat {
+        match self {
+            CliFormat::Webp => OutputFormat::WebP,
+            CliFormat::Png => OutputFormat::Png,
+            CliFormat::Jpeg => OutputFormat::Jpeg,
+            CliFormat::Avif => OutputFormat::Avif,
+            CliFormat::Tiff => OutputFormat::Tiff,
+        }
+    }
+}
+
+/// Lit une destination `SOUS-DOSSIER:FORMAT[:TAILLE]` de `--target`.
+fn parse_target(value: &str) -> Result<OutputTarget, String> {
+    let mut parts = value.splitn(3, ':');
+    let subfolder = parts.next().unwrap_or_default().to_string();
+    let format = parts.next().ok_or(tr!("format manquant (ex: thumbs:webp:300)"))?;
+    let format = CliFormat::from_str(format, true).map_err(|_| tr!("format inconnu « {} »", format))?;
+    let mut resize = ResizeOptions::default();
+    if let Some(size) = parts.next() {
+        let invalid = || tr!("taille invalide « {} » (ex: 300, 300x200 ou 50%)", size);
+        if let Some(percent) = size.strip_suffix('%') {
+            resize.mode = ResizeMode::Percentage;
+            resize.percent = percent.parse().ok().filter(|percent| (1..=400).contains(percent)).ok_or_else(invalid)?;
+        } else {
+            let (width, height) = size.split_once('x').unwrap_or((size, size));
+            resize.mode = ResizeMode::Dimensions;
+            resize.max_width = width.parse().map_err(|_| invalid())?;
+            resize.max_height = height.parse().map_err(|_| invalid())?;
+        }
+    }
+    Ok(OutputTarget { subfolder, output_format: format.output_format(), resize })
+}
+
+/// Lit une couleur hexadécimale `RRGGBB` (précédée ou non de `#`) de `--background`.
+fn parse_color(value: &str) -> Result<[u8; 3], String> {
+    let invalid = || tr!("couleur invalide « {} » (ex: ffffff ou #000000)", value);
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| invalid());
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Lit le fond `--pad-color` : `transparent` ou une couleur hexadécimale.
+fn parse_pad_color(value: &str) -> Result<CliPadColor, String> {
+    match value.trim().eq_ignore_ascii_case("transparent") {
+        true => Ok(CliPadColor::Transparent),
+        false => parse_color(value).map(CliPadColor::Color),
+    }
+}
+
+/// Lit des dimensions `LARGEURxHAUTEUR` de `--pad` (ex: `800x600`).
+fn parse_size(value: &str) -> Result<(u32, u32), String> {
+    let invalid = || tr!("dimensions invalides « {} » (ex: 800x800)", value);
+    let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+    let width: u32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: u32 = height.trim().parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+    Ok((width, height))
+}
+
+/// Lit des proportions `LARGEUR:HAUTEUR` de `--crop` (ex: `16:9`).
+fn parse_ratio(value: &str) -> Result<(u32, u32), String> {
+    let invalid = || tr!("proportions invalides « {} » (ex: 16:9 ou 1:1)", value);
+    let (width, height) = value.split_once(':').ok_or_else(invalid)?;
+    let width: u32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: u32 = height.trim().parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+    Ok((width, height))
+}
+
+/// Vérifie la sélection de pages de `--pdf-pages` ou d'images de `--frames` (ex: `1-3,5`).
+fn parse_selection(value: &str) -> Result<String, String> {
+    pdf::parse_page_range(value, 0).map(|_| value.to_string()).map_err(|e| e.to_string())
+}
+
+impl Cli {
+    /// Construit les options de conversion à partir des arguments ; les réglages non exposés gardent leur valeur par défaut.
+    fn conversion_options(&self) -> ConversionOptions {
+        let mut options = ConversionOptions {
+            output_format: self.format.output_format(),
+            overwrite_mode: match self.overwrite {
+                CliOverwrite::Skip => OverwriteMode::Skip,
+                CliOverwrite::Overwrite => OverwriteMode::Overwrite,
+                CliOverwrite::Rename => OverwriteMode::Rename,
+                CliOverwrite::IfNewer => OverwriteMode::IfNewer,
+            },
+            rename_suffix: match self.rename_suffix {
+                CliRenameSuffix::Counter => RenameSuffix::Counter,
+                CliRenameSuffix::Timestamp => RenameSuffix::Timestamp,
+            },
+            name_template: self.name.clone(),
+            smallest_format: self.smallest,
+            larger_output: match self.if_larger {
+                CliIfLarger::Keep => LargerOutputMode::Keep,
+                CliIfLarger::Copy => LargerOutputMode::CopyOriginal,
+                CliIfLarger::Skip => LargerOutputMode::Skip,
+            },
+            originals: match self.originals {
+                CliOriginals::Keep => OriginalsMode::Keep,
+                CliOriginals::Trash => OriginalsMode::Trash,
+                CliOriginals::Backup => OriginalsMode::Backup,
+            },
+            incremental: self.incremental,
+            strip_metadata: self.strip_metadata,
+            auto_orient: !self.no_auto_orient,
+            color_profile: match self.color_profile {
+                CliColorProfile::Srgb => ColorProfileMode::ConvertToSrgb,
+                CliColorProfile::Embed => ColorProfileMode::Embed,
+                CliColorProfile::Ignore => ColorProfileMode::Ignore,
+            },
+            background_color: self.background,
+            flatten_alpha: self.flatten_alpha,
+            rotation: match self.rotate {
+                CliRotation::None => Rotation::None,
+                CliRotation::Rotate90 => Rotation::Rotate90,
+                CliRotation::Rotate180 => Rotation::Rotate180,
+                CliRotation::Rotate270 => Rotation::Rotate270,
+                CliRotation::FlipH => Rotation::FlipHorizontal,
+                CliRotation::FlipV => Rotation::FlipVertical,
+            },
+            path_filter: PathFilter { include: self.include.clone(), exclude: self.exclude.clone() },
+            file_filter: FileFilter { min_size_kb: self.min_size, modified_after: self.modified_after.clone() },
+            duplicates: DuplicateOptions {
+                enabled: self.duplicates,
+                exclude: !self.keep_duplicates,
+                threshold: self.duplicate_threshold,
+            },
+            traversal: TraversalOptions {
+                max_depth: self.max_depth,
+                follow_symlinks: self.follow_symlinks,
+                skip_hidden: self.skip_hidden,
+            },
+            output_structure: match (self.flatten, self.by_date) {
+                (true, _) => OutputStructure::Flatten,
+                (_, true) => OutputStructure::ByDate,
+                _ => OutputStructure::Mirror,
+            },
+            run_folder: RunFolderOptions {
+                enabled: self.run_folder.is_some(),
+                template: self.run_folder.clone().unwrap_or_default(),
+            },
+            targets: OutputTargets { enabled: !self.targets.is_empty(), targets: self.targets.clone() },
+            responsive: ResponsiveOptions {
+                enabled: !self.responsive.is_empty(),
+                widths: self.responsive.clone(),
+                write_srcset: !self.no_srcset,
+            },
+            favicon: self.favicon,
+            contact_sheet: ContactSheetOptions {
+                enabled: self.contact_sheet.is_some(),
+                columns: self.contact_sheet.unwrap_or(5),
+                rows_per_page: self.sheet_rows,
+                cell_size: self.sheet_cell_size,
+                captions: !self.no_captions,
+                format: if self.sheet_png { ContactSheetFormat::Png } else { ContactSheetFormat::WebP },
+            },
+            pdf: PdfOptions { dpi: self.pdf_dpi, pages: self.pdf_pages.clone().unwrap_or_default() },
+            frames: FrameOptions {
+                extract: self.extract_frames,
+                frames: self.frames.clone().unwrap_or_default(),
+                step: self.frame_step,
+            },
+            animation: AnimationOptions {
+                keep: self.keep_animation,
+                delay_scale: self.frame_delay_scale,
+                loop_count: self.loop_count,
+                quality: self.animation_quality,
+            },
+            pdf_bundle: PdfBundleOptions {
+                enabled: self.pdf_bundle.is_some(),
+                name: self.pdf_bundle.clone().unwrap_or_default(),
+                dpi: self.pdf_bundle_dpi,
+            },
+            sprite_sheet: SpriteSheetOptions {
+                enabled: self.sprite_sheet.is_some(),
+                name: self.sprite_sheet.clone().unwrap_or_default(),
+                padding: self.sprite_padding,
+                max_width: self.sprite_max_width,
+            },
+            archive: ArchiveOptions { enabled: self.zip.is_some(), name: self.zip.clone().unwrap_or_default() },
+            workers: self.jobs,
+            low_priority: self.low_priority,
+            verify: VerifyOptions {
+                enabled: self.verify || self.verify_quality || self.min_ssim.is_some(),
+                measure_quality: self.verify_quality || self.min_ssim.is_some(),
+                min_ssim: self.min_ssim.unwrap_or(0.0),
+            },
+            dry_run: self.dry_run,
+            ..ConversionOptions::default()
+        };
+        options.webp.lossless = self.lossless;
+        options.jpeg.progressive = !self.baseline;
+        options.jpeg.mozjpeg = !self.no_mozjpeg;
+        if let Some(level) = self.optimize_png {
+            options.png.optimize = true;
+            options.png.optimization_level = level;
+        }
+        if let Some((width, height)) = self.pad {
+            options.pad = PadOptions { enabled: true, width, height, ..PadOptions::default() };
+            match self.pad_color {
+                CliPadColor::Transparent => options.pad.transparent = true,
+                CliPadColor::Color(color) => options.pad.color = color,
+            }
+        }
+        if let Some(tolerance) = self.trim {
+            options.trim = TrimOptions { enabled: true, tolerance };
+        }
+        if let Some((ratio_width, ratio_height)) = self.crop {
+            options.crop = CropOptions {
+                enabled: true,
+                ratio_width,
+                ratio_height,
+                anchor: if self.smart_crop { CropAnchor::Smart } else { CropAnchor::Center },
+            };
+        }
+        if let Some(percent) = self.scale {
+            options.resize.mode = ResizeMode::Percentage;
+            options.resize.percent = percent;
+        } else if self.max_width.is_some() || self.max_height.is_some() {
+            options.resize.mode = ResizeMode::Dimensions;
+            options.resize.max_width = self.max_width.unwrap_or(0);
+            options.resize.max_height = self.max_height.unwrap_or(0);
+        }
+        options.resize.filter = match self.filter {
+            CliFilter::Nearest => ResizeFilter::Nearest,
+            CliFilter::Triangle => ResizeFilter::Triangle,
+            CliFilter::CatmullRom => ResizeFilter::CatmullRom,
+            CliFilter::Lanczos3 => ResizeFilter::Lanczos3,
+        };
+        if let Some(quality) = self.quality {
+            options.webp.quality = quality;
+            options.jpeg.quality = quality.max(1);
+            options.avif.quality = quality.max(1);
+        }
+        options
+    }
+}
+
+/// Analyse les arguments, lance la conversion et affiche le bilan. Retourne le code de sortie du programme.
+pub fn run() -> i32 {
+    i18n::set_language(requested_language());
+    let cli = Cli::parse();
+    let options = cli.conversion_options();
+
+    let result = JobInput::from_path(cli.input.clone()).and_then(|input| {
+        let job = ConversionJob { input, output_dir: cli.output.clone(), options, run_folder: None };
+        job::run_job(&job, &|_| {}, &BatchControl::default())
+    });
+
+    match result {
+        Ok(report) => {
+            print_summary(&report);
+            if let Some(path) = &cli.report {
+                if let Err(e) = report_export::export(&report, path) {
+                    eprintln!("{}", tr!("Erreur lors de l'export du rapport : {}", e));
+                    return 2;
+                }
+            }
+            if report.failures.is_empty() && report.verification_failures() == 0 { 0 } else { 1 }
+        }
+        Err(e) => {
+            eprintln!("{}", tr!("Erreur lors de la conversion : {}", e));
+            2
+        }
+    }
+}
+
+/// Affiche le bilan de la conversion : fichiers produits, ignorés, gain de taille, échecs et avertissements.
+fn print_summary(report: &ConversionReport) {
+    if report.options.dry_run {
+        print_plan(report);
+        return;
+    }
+    println!("{}", tr!("{} fichier(s) converti(s) dans {}", report.converted(), report.output_root.display()));
+    if report.skipped_by_manifest > 0 {
+        println!("{}", tr!("{} fichier(s) inchangé(s) ignoré(s)", report.skipped_by_manifest));
+    }
+    if report.skipped_resumed > 0 {
+        println!("{}", tr!("{} fichier(s) déjà traité(s) avant l'interruption", report.skipped_resumed));
+    }
+    if report.skipped_existing > 0 {
+        println!("{}", tr!("{} fichier(s) ignoré(s) car déjà présent(s)", report.skipped_existing));
+    }
+    if report.skipped_larger > 0 {
+        println!("{}", tr!("{} fichier(s) ignoré(s) car leur sortie aurait été plus lourde", report.skipped_larger));
+    }
+    if report.kept_originals() > 0 {
+        println!("{}", tr!("{} original(aux) copié(s), leur sortie étant plus lourde", report.kept_originals()));
+    }
+    if report.removed_originals() > 0 {
+        println!("{}", tr!("{} original(aux) retiré(s)", report.removed_originals()));
+    }
+    for file in &report.files {
+        if matches!(file.original, OriginalOutcome::Failed(_)) {
+            println!("  • {} : {}", file.source.display(), file.original.description());
+        }
+    }
+    if report.filtered_by_dimensions > 0 {
+        println!("{}", tr!("{} fichier(s) filtré(s) par dimensions", report.filtered_by_dimensions));
+    }
+    if report.filtered_by_file > 0 {
+        println!("{}", tr!("{} fichier(s) filtré(s) par taille ou date", report.filtered_by_file));
+    }
+    if !report.duplicates.is_empty() {
+        match report.options.duplicates.exclude {
+            true => println!("{}", tr!("{} doublon(s) écarté(s) :", report.duplicates.len())),
+            false => println!("{}", tr!("{} doublon(s) repéré(s) :", report.duplicates.len())),
+        }
+        for duplicate in &report.duplicates {
+            let distance = tr!("(écart {})", duplicate.distance);
+            println!("  • {} ≈ {} {}", duplicate.source.display(), duplicate.original.display(), distance);
+        }
+    }
+    if let Some(adjustments) = report.options.adjustments.description() {
+        println!("{}", tr!("Réglages appliqués : {}", adjustments));
+    }
+    let paletted: Vec<_> = report.files.iter().filter_map(|file| Some((file, file.palette_size?))).collect();
+    if !paletted.is_empty() {
+        println!("{}", tr!("{} PNG indexé(s) :", paletted.len()));
+        for (file, palette_size) in paletted {
+            println!("  • {} : {}", file.output.display(), tr!("{} couleur(s)", palette_size));
+        }
+    }
+    if !report.srcset_snippets.is_empty() {
+        println!("{}", tr!("{} extrait(s) srcset écrit(s)", report.srcset_snippets.len()));
+    }
+    if !report.contact_sheets.is_empty() {
+        println!("{}", tr!("Planche contact : {} page(s) écrite(s)", report.contact_sheets.len()));
+    }
+    if let Some(document) = &report.pdf_bundle {
+        println!("{}", tr!("Document PDF écrit : {}", document.display()));
+    }
+    if let Some(sheet) = report.sprite_sheet.first() {
+        println!("{}", tr!("Planche de sprites écrite : {}", sheet.display()));
+    }
+    if let Some(archive) = &report.archive {
+        println!("{}", tr!("Fichiers produits archivés dans {}", archive.display()));
+    }
+    if let Some(percent) = report.savings_percent() {
+        println!(
+            "{}",
+            tr!(
+                "Taille : {} → {} octets ({} % d'espace gagné)",
+                report.input_bytes(),
+                report.output_bytes(),
+                format!("{:.1}", percent)
+            )
+        );
+    }
+    if let Some((psnr, ssim)) = report.average_quality() {
+        println!("{}", tr!("Fidélité moyenne : PSNR {} dB, SSIM {}", format!("{:.1}", psnr), format!("{:.4}", ssim)));
+    }
+    if report.verification_failures() > 0 {
+        println!("{}", tr!("{} sortie(s) en échec de vérification :", report.verification_failures()));
+        for file in report.files.iter().filter(|file| file.verification.is_failed()) {
+            println!("  • {} : {}", file.output.display(), file.verification.description());
+        }
+    }
+    if !report.failures.is_empty() {
+        println!("{}", tr!("{} fichier(s) en échec :", report.failures.len()));
+        for failure in &report.failures {
+            println!("  • {} : {}", failure.source.display(), failure.error);
+        }
+    }
+    for warning in &report.warnings {
+        println!("{}", tr!("Avertissement : {}", warning));
+    }
+}
+
+/// Affiche le bilan d'une simulation : l'action prévue pour chaque source, puis le décompte.
+fn print_plan(report: &ConversionReport) {
+    println!("{}", tr!("Simulation (aucun fichier écrit) :"));
+    for file in &report.planned {
+        match &file.output {
+            Some(output) => println!("  • {} → {} : {}", file.source.display(), output.display(), file.action.description()),
+            None => println!("  • {} : {}", file.source.display(), file.action.description()),
+        }
+    }
+    let (written, overwritten) = report.planned_counts();
+    println!(
+        "{}",
+        tr!(
+            "{} fichier(s) à convertir, dont {} écrasant une sortie existante ; {} ignoré(s)",
+            written,
+            overwritten,
+            report.planned.len() - written
+        )
+    );
+    for failure in &report.failures {
+        println!("{}", tr!("Échec prévisible : {} : {}", failure.source.display(), failure.error));
+    }
+}
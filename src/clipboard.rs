@@ -0,0 +1,128 @@
+/// Ce module gère le presse-papiers avec `arboard` : images collées (captures d'écran...) et copie du fichier produit.
+/// L'image collée reste en mémoire jusqu'à la conversion ; elle est alors écrite sans perte (PNG) dans un dossier
+/// temporaire pour passer par la même conversion qu'un fichier unique, puis ce fichier temporaire est supprimé.
+use image::RgbaImage;
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::converter::{self, BatchControl, ConversionOptions, ConversionReport, ProgressFn};
+use crate::error::ConverterError;
+use crate::originals::OriginalsMode;
+use crate::tr;
+
+/// Nom du dossier temporaire des images collées, dans le dossier temporaire du système.
+const TEMP_DIR_NAME: &str = "image_converter_clipboard";
+
+/// Format de l'horodatage du nom des images collées (ex: `capture_2024-06-03_14-32-05`).
+const NAME_TIMESTAMP_FORMAT: &str = "capture_%Y-%m-%d_%H-%M-%S";
+
+/// Accès au presse-papiers, ouvert au premier usage et gardé jusqu'à la fermeture : sous Linux (X11),
+/// le contenu copié n'est plus servi aux autres applications une fois l'accès refermé.
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+/// Contenu copié dans le presse-papiers pour un fichier produit.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CopyContent {
+    Image, // Les pixels de l'image, à coller dans une messagerie ou un document
+    Path,  // Le chemin complet du fichier, à coller dans un champ d'envoi de fichier
+}
+
+impl CopyContent {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            CopyContent::Image => tr!("Image"),
+            CopyContent::Path => tr!("Chemin du fichier"),
+        }
+    }
+}
+
+/// Exécute `action` avec l'accès au presse-papiers, ouvert si besoin.
+fn with_clipboard<T>(action: impl FnOnce(&mut arboard::Clipboard) -> Result<T, ConverterError>) -> Result<T, ConverterError> {
+    let mut clipboard = CLIPBOARD.lock().unwrap();
+    if clipboard.is_none() {
+        let opened = arboard::Clipboard::new()
+            .map_err(|e| ConverterError::PathValidation(tr!("Presse-papiers inaccessible : {}", e)))?;
+        *clipboard = Some(opened);
+    }
+    action(clipboard.as_mut().unwrap())
+}
+
+/// Image collée depuis le presse-papiers, en attente de conversion.
+#[derive(Debug, Clone)]
+pub struct PastedImage {
+    pub name: String,          // Nom de la source, qui sert de nom aux fichiers produits (sans extension).
+    pub image: Arc<RgbaImage>, // Pixels de l'image collée.
+}
+
+/// Deux images collées sont égales si elles proviennent du même collage (l'interface compare la sélection à chaque image).
+impl PartialEq for PastedImage {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Arc::ptr_eq(&self.image, &other.image)
+    }
+}
+
+/// Lit l'image du presse-papiers. Erreur si le presse-papiers est inaccessible ou ne contient pas d'image.
+pub fn read_image() -> Result<PastedImage, ConverterError> {
+    let data = with_clipboard(|clipboard| {
+        clipboard
+            .get_image()
+            .map_err(|_| ConverterError::PathValidation(tr!("Le presse-papiers ne contient pas d'image").to_string()))
+    })?;
+    let image = RgbaImage::from_raw(data.width as u32, data.height as u32, data.bytes.into_owned())
+        .ok_or_else(|| ConverterError::PathValidation(tr!("Image du presse-papiers illisible").to_string()))?;
+    Ok(PastedImage {
+        name: chrono::Local::now().format(NAME_TIMESTAMP_FORMAT).to_string(),
+        image: Arc::new(image),
+    })
+}
+
+/// Copie le fichier produit `path` dans le presse-papiers : ses pixels (décodés) ou son chemin.
+pub fn copy_output(path: &Path, content: CopyContent) -> Result<(), ConverterError> {
+    match content {
+        CopyContent::Image => {
+            let image = image::open(path).map_err(|e| ConverterError::decode(path, e))?.to_rgba8();
+            let data = arboard::ImageData {
+                width: image.width() as usize,
+                height: image.height() as usize,
+                bytes: Cow::Owned(image.into_raw()),
+            };
+            with_clipboard(|clipboard| {
+                clipboard.set_image(data).map_err(|e| ConverterError::PathValidation(tr!("Copie dans le presse-papiers impossible : {}", e)))
+            })
+        }
+        CopyContent::Path => with_clipboard(|clipboard| {
+            clipboard
+                .set_text(path.display().to_string())
+                .map_err(|e| ConverterError::PathValidation(tr!("Copie dans le presse-papiers impossible : {}", e)))
+        }),
+    }
+}
+
+/// Convertit l'image collée `pasted` dans `output_dir`, comme un fichier unique. Sa copie temporaire
+/// n'est jamais traitée comme un original (ni mise à la corbeille, ni sauvegardée) et elle est supprimée ensuite.
+pub fn convert_pasted_image(
+    pasted: &PastedImage,
+    output_dir: &Path,
+    options: &ConversionOptions,
+    progress: &ProgressFn,
+    control: &BatchControl,
+) -> Result<ConversionReport, ConverterError> {
+    let source = write_temp_source(pasted)?;
+    let options = ConversionOptions { originals: OriginalsMode::Keep, ..options.clone() };
+    let result = converter::convert_single_image(&source, output_dir, &options, progress, control);
+    let _ = fs::remove_file(&source); // Un fichier temporaire oublié sera nettoyé par le système.
+    result
+}
+
+/// Écrit l'image collée en PNG dans le dossier temporaire et retourne son chemin.
+fn write_temp_source(pasted: &PastedImage) -> Result<PathBuf, ConverterError> {
+    let temp_dir = std::env::temp_dir().join(TEMP_DIR_NAME);
+    fs::create_dir_all(&temp_dir)
+        .map_err(|e| ConverterError::io(tr!("Impossible de créer le dossier temporaire {}", temp_dir.display()), e))?;
+    let path = temp_dir.join(format!("{}.png", pasted.name));
+    pasted.image.save(&path).map_err(|e| ConverterError::encode("PNG", e))?;
+    Ok(path)
+}
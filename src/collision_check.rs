@@ -0,0 +1,73 @@
+/// Ce module relève en arrière-plan, à la demande, les sorties de la sélection déjà présentes dans le répertoire
+/// de sortie (contrôle préalable des collisions) : avant un long lot, l'interface indique combien de fichiers
+/// seraient ignorés, écrasés ou renommés selon le mode d'écrasement, pour en changer avant de lancer la conversion.
+use eframe::egui;
+use std::sync::{Arc, Mutex};
+
+use crate::converter::{BatchControl, CollisionPreflight, ConversionOptions, OverwriteMode};
+use crate::job::{self, ConversionJob};
+
+/// État du relevé de la sélection courante.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollisionCheck {
+    Checking,                 // Relevé en cours
+    Done(CollisionPreflight), // Relevé terminé
+    Failed(String),           // Relevé impossible, avec la raison
+}
+
+/// Gère le thread du relevé, son abandon et son dernier résultat.
+#[derive(Default)]
+pub struct CollisionChecker {
+    key: Option<String>,                        // Lot relevé (voir `key`), `None` sans relevé
+    state: Arc<Mutex<Option<CollisionCheck>>>,  // État publié par le thread du relevé
+    control: Arc<BatchControl>,                 // Abandon du relevé en cours
+}
+
+impl CollisionChecker {
+    /// Lance le relevé de `job`, en abandonnant le précédent.
+    pub fn start(&mut self, ctx: &egui::Context, job: ConversionJob) {
+        self.stop();
+        self.key = Some(key(&job));
+        let state = Arc::new(Mutex::new(Some(CollisionCheck::Checking)));
+        let control = Arc::new(BatchControl::default());
+        self.state = Arc::clone(&state);
+        self.control = Arc::clone(&control);
+        let ctx_clone = ctx.clone();
+
+        std::thread::spawn(move || {
+            let result = job::preflight_collisions(&job, &control);
+            if control.is_cancelled() {
+                return;
+            }
+            *state.lock().unwrap() = Some(match result {
+                Ok(preflight) => CollisionCheck::Done(preflight),
+                Err(e) => CollisionCheck::Failed(e.to_string()),
+            });
+            ctx_clone.request_repaint();
+        });
+    }
+
+    /// Abandonne le relevé en cours (ex: au lancement de la conversion).
+    pub fn stop(&mut self) {
+        self.control.cancel();
+        self.key = None;
+    }
+
+    /// État du relevé de `job`, `None` si le dernier relevé portait sur une autre sélection ou d'autres options.
+    /// Changer seulement de mode d'écrasement ne rend pas le relevé obsolète.
+    pub fn state(&self, job: &ConversionJob) -> Option<CollisionCheck> {
+        match &self.key {
+            Some(checked) if *checked == key(job) => self.state.lock().unwrap().clone(),
+            _ => None,
+        }
+    }
+}
+
+/// Clé du relevé : le lot sérialisé, hors mode d'écrasement et simulation (sans effet sur le relevé).
+fn key(job: &ConversionJob) -> String {
+    let job = ConversionJob {
+        options: ConversionOptions { overwrite_mode: OverwriteMode::Overwrite, dry_run: false, ..job.options.clone() },
+        ..job.clone()
+    };
+    serde_json::to_string(&job).unwrap_or_default()
+}
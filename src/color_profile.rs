@@ -0,0 +1,66 @@
+/// Ce module gère les profils de couleur ICC des sources (Adobe RGB, Display P3...).
+/// Sans traitement, les pixels de ces images sont interprétés comme du sRGB et paraissent délavés :
+/// ils sont soit convertis en sRGB (`qcms`), soit accompagnés de leur profil dans le fichier produit.
+use image::{DynamicImage, RgbImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use crate::tr;
+
+/// Traitement du profil ICC de la source.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ColorProfileMode {
+    ConvertToSrgb, // Convertir les pixels en sRGB (affichage correct partout, sans profil dans la sortie)
+    Embed,         // Conserver les pixels et intégrer le profil dans la sortie
+    Ignore,        // Ignorer le profil (comportement historique)
+}
+
+impl ColorProfileMode {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorProfileMode::ConvertToSrgb => tr!("Convertir en sRGB"),
+            ColorProfileMode::Embed => tr!("Intégrer le profil"),
+            ColorProfileMode::Ignore => tr!("Ignorer"),
+        }
+    }
+
+    /// Signature du mode pour le manifeste, `None` pour le mode par défaut.
+    pub fn signature(&self) -> Option<&'static str> {
+        match self {
+            ColorProfileMode::ConvertToSrgb => None,
+            ColorProfileMode::Embed => Some("icc=embed"),
+            ColorProfileMode::Ignore => Some("icc=ignore"),
+        }
+    }
+}
+
+/// Convertit les pixels de `img`, décrits par le profil `icc_profile`, en sRGB.
+/// L'image est retournée telle quelle si le profil est illisible, déjà sRGB ou d'un autre espace que RGB
+/// (niveaux de gris, CMJN) ; les images 16 bits sont converties en 8 bits.
+pub fn convert_to_srgb(img: DynamicImage, icc_profile: &[u8]) -> DynamicImage {
+    let Some(input) = qcms::Profile::new_from_slice(icc_profile, false) else {
+        return img;
+    };
+    if input.is_sRGB() {
+        return img;
+    }
+    let mut output = qcms::Profile::new_sRGB();
+    output.precache_output_transform();
+
+    let intent = qcms::Intent::default();
+    if img.color().has_alpha() {
+        let Some(transform) = qcms::Transform::new(&input, &output, qcms::DataType::RGBA8, intent) else {
+            return img;
+        };
+        let mut rgba: RgbaImage = img.to_rgba8();
+        transform.apply(&mut rgba);
+        DynamicImage::ImageRgba8(rgba)
+    } else {
+        let Some(transform) = qcms::Transform::new(&input, &output, qcms::DataType::RGB8, intent) else {
+            return img;
+        };
+        let mut rgb: RgbImage = img.to_rgb8();
+        transform.apply(&mut rgb);
+        DynamicImage::ImageRgb8(rgb)
+    }
+}
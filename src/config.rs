@@ -0,0 +1,111 @@
+/// Ce module gère la configuration conservée entre deux lancements (`AppConfig`) : répertoire de sortie, dossier de la dernière entrée,
+/// options de conversion, préréglages nommés, thème, langue, taille et position de la fenêtre. Elle est enregistrée au format TOML dans le dossier
+/// de configuration de l'utilisateur (ex: `~/.config/image_converter/config.toml`).
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::converter::ConversionOptions;
+use crate::error::ConverterError;
+use crate::i18n::Language;
+use crate::presets::{self, Preset};
+use crate::tr;
+
+/// Nom du dossier de l'application dans le dossier de configuration du système.
+const CONFIG_DIR_NAME: &str = "image_converter";
+
+/// Nom du fichier de configuration.
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Thème de l'interface.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum AppTheme {
+    System, // Suivre le thème clair ou sombre du système
+    Light,  // Toujours clair
+    Dark,   // Toujours sombre
+}
+
+impl AppTheme {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AppTheme::System => tr!("Système"),
+            AppTheme::Light => tr!("Clair"),
+            AppTheme::Dark => tr!("Sombre"),
+        }
+    }
+}
+
+/// Réglages conservés entre deux sessions.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub output_dir: PathBuf,                   // Répertoire de sortie.
+    pub last_input_dir: Option<PathBuf>,       // Dossier de la dernière sélection d'entrée (ouverture des boîtes de dialogue).
+    pub conversion_options: ConversionOptions, // Options de conversion.
+    pub presets: Vec<Preset>,                  // Préréglages nommés, dans l'ordre de la liste.
+    pub theme: AppTheme,                       // Thème de l'interface.
+    pub language: Language,                    // Langue de l'interface.
+    pub window_size: Option<[f32; 2]>,         // Taille intérieure de la fenêtre (points), `None` = taille par défaut.
+    pub window_position: Option<[f32; 2]>,     // Position du coin supérieur gauche de la fenêtre (points), `None` = choix du système.
+    pub window_maximized: bool,                // Fenêtre agrandie en plein écran.
+    pub context_menu_silent: bool,             // Menu contextuel de l'Explorateur : convertir sans ouvrir la fenêtre.
+    pub close_to_tray: bool,                   // Fermer la fenêtre la cache dans la zone de notification.
+    pub desktop_notifications: bool,           // Notification du système à la fin d'un lot, fenêtre en arrière-plan.
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        let desktop_dir = dirs::desktop_dir().unwrap_or(PathBuf::from("."));
+        let output_dir = desktop_dir.join("webp_converted");
+        Self {
+            presets: vec![presets::responsive_web(&output_dir), presets::favicon(&output_dir)],
+            output_dir,
+            last_input_dir: None,
+            conversion_options: ConversionOptions::default(),
+            theme: AppTheme::System,
+            language: Language::French,
+            window_size: None,
+            window_position: None,
+            window_maximized: false,
+            context_menu_silent: false,
+            close_to_tray: false,
+            desktop_notifications: true,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Charge la configuration enregistrée. Un fichier absent ou illisible donne la configuration par défaut ;
+    /// les réglages absents du fichier (ajoutés par une version plus récente) prennent leur valeur par défaut.
+    pub fn load() -> Self {
+        config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Enregistre la configuration, en créant son dossier si besoin.
+    pub fn save(&self) -> Result<(), ConverterError> {
+        let path = config_path().ok_or_else(|| ConverterError::Config(tr!("Dossier de configuration introuvable").to_string()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                ConverterError::io(tr!("Impossible de créer le dossier de configuration {}", parent.display()), e)
+            })?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| ConverterError::Config(tr!("Impossible de sérialiser la configuration : {}", e)))?;
+        fs::write(&path, content)
+            .map_err(|e| ConverterError::io(tr!("Impossible d'écrire la configuration {}", path.display()), e))
+    }
+}
+
+/// Dossier de l'application dans le dossier de configuration du système, `None` si le système n'en a pas.
+pub fn app_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(CONFIG_DIR_NAME))
+}
+
+/// Emplacement du fichier de configuration, `None` si le système n'a pas de dossier de configuration.
+fn config_path() -> Option<PathBuf> {
+    app_config_dir().map(|dir| dir.join(CONFIG_FILE_NAME))
+}
@@ -0,0 +1,221 @@
+/// Ce module compose les planches contact (grilles de vignettes légendées) d'une conversion, en WebP ou en PNG.
+/// Les planches sont composées page par page : seules les vignettes de la page en cours sont gardées en mémoire.
+/// Sans limite de lignes, toutes les images forment une seule grande planche.
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use image::{imageops, DynamicImage, ImageFormat, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::tr;
+
+/// Nom de base des planches contact écrites à la racine de la sortie.
+const CONTACT_SHEET_STEM: &str = "contact-sheet";
+
+/// Marge (en pixels) autour de chaque cellule.
+const CELL_PADDING: u32 = 8;
+
+/// Hauteur (en pixels) réservée à la légende sous chaque vignette.
+const CAPTION_HEIGHT: u32 = 20;
+
+/// Taille (en pixels) du texte des légendes.
+const CAPTION_FONT_SIZE: f32 = 14.0;
+
+/// Couleurs du fond de la planche et du texte des légendes.
+const BACKGROUND_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const CAPTION_COLOR: Rgba<u8> = Rgba([60, 60, 60, 255]);
+
+/// Format des planches contact.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ContactSheetFormat {
+    WebP, // Sans perte, le plus léger
+    Png,  // Lisible partout
+}
+
+impl ContactSheetFormat {
+    /// Extension des planches écrites dans ce format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ContactSheetFormat::WebP => "webp",
+            ContactSheetFormat::Png => "png",
+        }
+    }
+
+    fn image_format(&self) -> ImageFormat {
+        match self {
+            ContactSheetFormat::WebP => ImageFormat::WebP,
+            ContactSheetFormat::Png => ImageFormat::Png,
+        }
+    }
+}
+
+/// Réglages de la planche contact.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContactSheetOptions {
+    pub enabled: bool,              // Générer une planche contact après la conversion.
+    pub columns: u32,               // Nombre de colonnes de la grille.
+    pub rows_per_page: u32,         // Nombre de lignes par page (au-delà, une nouvelle page est créée), 0 = une seule planche.
+    pub cell_size: u32,             // Côté maximal (en pixels) de chaque vignette.
+    pub captions: bool,             // Légender chaque vignette du nom de la source.
+    pub format: ContactSheetFormat, // Format des planches.
+}
+
+impl Default for ContactSheetOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            columns: 5,
+            rows_per_page: 6,
+            cell_size: 200,
+            captions: true,
+            format: ContactSheetFormat::WebP,
+        }
+    }
+}
+
+/// Compose les planches au fil de la conversion à partir des images déjà décodées.
+pub struct ContactSheetBuilder {
+    options: ContactSheetOptions,
+    output_root: PathBuf,
+    font: FontRef<'static>,
+    cells: Vec<(RgbaImage, String)>, // Vignettes et légendes de la page en cours.
+    pages: Vec<PathBuf>,             // Pages déjà écrites.
+    error: Option<String>,           // Première erreur rencontrée : la composition s'arrête alors.
+}
+
+impl ContactSheetBuilder {
+    pub fn new(options: &ContactSheetOptions, output_root: &Path) -> Result<Self, String> {
+        let font = FontRef::try_from_slice(epaint_default_fonts::UBUNTU_LIGHT)
+            .map_err(|e| tr!("Police des légendes invalide : {}", e))?;
+        Ok(Self {
+            options: ContactSheetOptions {
+                columns: options.columns.max(1),
+                cell_size: options.cell_size.max(16),
+                ..options.clone()
+            },
+            output_root: output_root.to_path_buf(),
+            font,
+            cells: Vec::new(),
+            pages: Vec::new(),
+            error: None,
+        })
+    }
+
+    /// Ajoute une image (réduite à la taille de cellule) avec sa légende ; écrit la page quand elle est pleine.
+    pub fn add(&mut self, img: &DynamicImage, caption: &str) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let size = self.options.cell_size;
+        self.cells.push((img.thumbnail(size, size).to_rgba8(), caption.to_string()));
+
+        let rows_per_page = self.options.rows_per_page;
+        if rows_per_page > 0 && self.cells.len() as u32 >= self.options.columns * rows_per_page {
+            self.write_page();
+        }
+    }
+
+    /// Écrit la dernière page et retourne les planches produites ainsi que l'éventuelle erreur.
+    /// Une planche unique est nommée `contact-sheet.webp` (ou `.png`), sinon `contact-sheet-1.webp`, `-2`...
+    pub fn finish(mut self) -> (Vec<PathBuf>, Option<String>) {
+        if !self.cells.is_empty() && self.error.is_none() {
+            self.write_page();
+        }
+
+        if self.pages.len() == 1 && self.error.is_none() {
+            let single = self.output_root.join(format!("{}.{}", CONTACT_SHEET_STEM, self.options.format.extension()));
+            match std::fs::rename(&self.pages[0], &single) {
+                Ok(()) => self.pages[0] = single,
+                Err(e) => self.error = Some(tr!("Impossible de renommer la planche contact : {}", e)),
+            }
+        }
+
+        (self.pages, self.error)
+    }
+
+    /// Compose la page en cours et l'écrit dans la sortie.
+    fn write_page(&mut self) {
+        let cells = std::mem::take(&mut self.cells);
+        let page_number = self.pages.len() + 1;
+        let extension = self.options.format.extension();
+        let path = self.output_root.join(format!("{}-{}.{}", CONTACT_SHEET_STEM, page_number, extension));
+
+        let page = self.compose(&cells);
+        match page.save_with_format(&path, self.options.format.image_format()) {
+            Ok(()) => self.pages.push(path),
+            Err(e) => self.error = Some(tr!("Impossible d'écrire la planche contact {} : {}", path.display(), e)),
+        }
+    }
+
+    /// Dispose les vignettes en grille, chacune centrée dans sa cellule avec sa légende éventuelle en dessous.
+    fn compose(&self, cells: &[(RgbaImage, String)]) -> RgbaImage {
+        let columns = self.options.columns.min(cells.len() as u32).max(1);
+        let rows = (cells.len() as u32).div_ceil(columns);
+        let caption_height = if self.options.captions { CAPTION_HEIGHT } else { 0 };
+        let cell_width = self.options.cell_size + 2 * CELL_PADDING;
+        let cell_height = self.options.cell_size + caption_height + 2 * CELL_PADDING;
+
+        let mut page = RgbaImage::from_pixel(columns * cell_width, rows * cell_height, BACKGROUND_COLOR);
+
+        for (index, (thumbnail, caption)) in cells.iter().enumerate() {
+            let column = index as u32 % columns;
+            let row = index as u32 / columns;
+            let cell_x = column * cell_width + CELL_PADDING;
+            let cell_y = row * cell_height + CELL_PADDING;
+
+            let x = cell_x + (self.options.cell_size - thumbnail.width()) / 2;
+            let y = cell_y + (self.options.cell_size - thumbnail.height()) / 2;
+            imageops::overlay(&mut page, thumbnail, x as i64, y as i64);
+
+            if self.options.captions {
+                self.draw_caption(&mut page, caption, cell_x, cell_y + self.options.cell_size, self.options.cell_size);
+            }
+        }
+
+        page
+    }
+
+    /// Dessine la légende centrée sous la vignette, tronquée avec « … » si elle dépasse la cellule.
+    fn draw_caption(&self, page: &mut RgbaImage, caption: &str, x: u32, y: u32, max_width: u32) {
+        let font = self.font.as_scaled(PxScale::from(CAPTION_FONT_SIZE));
+        let text_width = |text: &str| -> f32 {
+            text.chars().map(|c| font.h_advance(font.glyph_id(c))).sum()
+        };
+
+        let mut text = caption.to_string();
+        if text_width(&text) > max_width as f32 {
+            while !text.is_empty() && text_width(&format!("{}…", text)) > max_width as f32 {
+                text.pop();
+            }
+            text.push('…');
+        }
+
+        let mut cursor = x as f32 + (max_width as f32 - text_width(&text)).max(0.0) / 2.0;
+        let baseline = y as f32 + (CAPTION_HEIGHT as f32 + font.ascent() + font.descent()) / 2.0;
+
+        for c in text.chars() {
+            let glyph_id = font.glyph_id(c);
+            let glyph = glyph_id.with_scale_and_position(CAPTION_FONT_SIZE, ab_glyph::point(cursor, baseline));
+            cursor += font.h_advance(glyph_id);
+
+            let Some(outline) = self.font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outline.px_bounds();
+            outline.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i64 + gx as i64;
+                let py = bounds.min.y as i64 + gy as i64;
+                if px < 0 || py < 0 || px >= page.width() as i64 || py >= page.height() as i64 {
+                    return;
+                }
+                let pixel = page.get_pixel_mut(px as u32, py as u32);
+                for channel in 0..3 {
+                    let background = pixel[channel] as f32;
+                    let foreground = CAPTION_COLOR[channel] as f32;
+                    pixel[channel] = (background + (foreground - background) * coverage) as u8;
+                }
+            });
+        }
+    }
+}
@@ -1,123 +1,2604 @@
 /// Ce module gère la conversion des images.
-use image::{ImageReader, ImageFormat}; // Correction: Utilisation directe de ImageReader
+use image::metadata::Orientation;
+use image::error::{DecodingError, ImageFormatHint};
+use image::{DynamicImage, ImageDecoder, ImageError, ImageReader}; // Correction: Utilisation directe de ImageReader
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::fs;
-use std::io::BufWriter;
-use walkdir::WalkDir; // Import de WalkDir
+use std::io::{Cursor, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, SystemTime};
+use rayon::prelude::*;
+
+use crate::animation::{self, AnimatedFrame, AnimationOptions, FrameOptions};
+use crate::archive::{self, ArchiveOptions};
+use crate::capture_date;
+use crate::color_profile::{self, ColorProfileMode};
+use crate::contact_sheet::{ContactSheetBuilder, ContactSheetOptions};
+use crate::encoder;
+use crate::duplicates::{self, Duplicate, DuplicateOptions};
+use crate::error::ConverterError;
+use crate::filters::{CompiledFileFilter, DimensionFilter, FileFilter, PathFilter, TraversalOptions};
+use crate::responsive::{self, ResponsiveOptions};
+use crate::run_folder::RunFolderOptions;
+use crate::targets::{Destination, OutputTargets};
+use crate::throughput::{Throughput, ThroughputMeter};
+use crate::transform::{self, Adjustments, CropOptions, PadOptions, ResizeOptions, Rotation, TrimOptions};
+use crate::verify::{self, Verification, VerifyOptions};
+use crate::manifest::{self, Manifest};
+use crate::metadata;
+use crate::originals::{self, OriginalOutcome, OriginalsMode};
+use crate::pdf::{self, PdfOptions};
+use crate::pdf_bundle::{PdfBundleBuilder, PdfBundleOptions};
+use crate::platform_utils;
+use crate::raw;
+use crate::sprite_sheet::{SpriteSheetBuilder, SpriteSheetOptions};
+use crate::tr;
+
+/// Extensions (en minuscules) des images prises en charge en entrée, fichiers RAW (voir `raw`) et documents PDF
+/// (voir `pdf`, chaque page devient une image) compris. Seule la première image d'une animation est convertie,
+/// sauf en mode « extraire les images » (voir `animation`).
+pub const SUPPORTED_EXTENSIONS: [&str; 13] =
+    ["png", "jpg", "jpeg", "bmp", "tif", "tiff", "gif", "webp", "cr2", "nef", "arw", "dng", "pdf"];
+
+/// Modèle du nom des fichiers produits par défaut : le nom de la source.
+pub const DEFAULT_NAME_TEMPLATE: &str = "{stem}";
+
+/// Côté maximal (en pixels) de l'échantillon encodé pour estimer la taille de sortie.
+const ESTIMATE_SAMPLE_SIZE: u32 = 512;
+
+/// Rappel de progression, appelé depuis les threads de conversion à chaque changement d'état d'un fichier
+/// et à chaque évolution du total (pendant le parcours d'un répertoire, il augmente au fil des fichiers découverts).
+pub type ProgressFn = dyn Fn(Progress) + Sync;
+
+/// Avancement d'une conversion transmis au rappel de progression.
+#[derive(Debug, Clone)]
+pub struct Progress<'a> {
+    pub completed: usize,                     // Fichiers traités (convertis, ignorés ou en échec).
+    pub total: usize,                         // Fichiers à traiter connus à ce stade.
+    pub file: Option<(&'a Path, FileStatus)>, // Fichier dont l'état vient de changer, s'il y en a un.
+    pub throughput: Throughput,               // Débit (moyenne glissante) et temps restant estimé.
+}
+
+/// État d'un fichier au cours d'une conversion.
+#[derive(Debug, PartialEq, Clone)]
+pub enum FileStatus {
+    Running,        // Conversion en cours.
+    Done(u64),      // Fichier écrit (taille de sortie en octets).
+    Skipped,        // Ignoré : sortie existante, source inchangée ou écartée par le filtre.
+    Failed(String), // Échec, avec son message.
+    Planned,        // Simulation : le fichier serait écrit (voir `ConversionReport::planned`).
+}
 
 /// Mode de gestion des fichiers existants.
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum OverwriteMode {
     Skip,      // Ignorer si le fichier existe
     Overwrite, // Écraser le fichier existant
     Rename,    // Renommer le nouveau fichier (ex: image-1.webp)
+    IfNewer,   // Reconvertir uniquement si la source est plus récente que la sortie existante
+    Ask,       // Demander à l'utilisateur pendant la conversion (écraser, ignorer ou renommer)
+}
+
+impl OverwriteMode {
+    /// Tous les modes, dans l'ordre des options de l'interface.
+    pub const ALL: [OverwriteMode; 5] =
+        [OverwriteMode::Skip, OverwriteMode::Overwrite, OverwriteMode::Rename, OverwriteMode::IfNewer, OverwriteMode::Ask];
+
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            OverwriteMode::Skip => tr!("Ignorer"),
+            OverwriteMode::Overwrite => tr!("Écraser"),
+            OverwriteMode::Rename => tr!("Renommer"),
+            OverwriteMode::IfNewer => tr!("Si plus récent"),
+            OverwriteMode::Ask => tr!("Demander"),
+        }
+    }
+
+    /// Indique si la sortie existante `output` rend la conversion inutile : elle existe (mode Ignorer),
+    /// ou elle est au moins aussi récente que la source modifiée à `source_modified` (mode Si plus récent).
+    fn keeps_existing(&self, output: &Path, source_modified: Option<SystemTime>) -> bool {
+        match self {
+            OverwriteMode::Skip => output.exists(),
+            OverwriteMode::IfNewer => {
+                let output_modified = fs::metadata(output).and_then(|metadata| metadata.modified());
+                match (output_modified, source_modified) {
+                    (Ok(output_modified), Some(source_modified)) => output_modified >= source_modified,
+                    _ => false, // Sortie absente ou date illisible : la conversion a lieu.
+                }
+            }
+            // En mode Demander, la sortie n'est conservée qu'une fois la réponse de l'utilisateur connue.
+            OverwriteMode::Overwrite | OverwriteMode::Rename | OverwriteMode::Ask => false,
+        }
+    }
+}
+
+/// Suffixe ajouté par le mode Renommer quand le nom de sortie est déjà pris.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum RenameSuffix {
+    Counter,   // Numéro (ex: image-1.webp, image-2.webp)
+    Timestamp, // Date et heure de la conversion (ex: image_2024-05-03_1432.webp)
+}
+
+impl RenameSuffix {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            RenameSuffix::Counter => tr!("Numéro"),
+            RenameSuffix::Timestamp => tr!("Date et heure"),
+        }
+    }
+}
+
+/// Format de l'horodatage du suffixe `RenameSuffix::Timestamp`.
+const RENAME_TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H%M";
+
+/// Extension ajoutée au nom d'un fichier de sortie pendant son écriture (ex: `photo.webp.part`).
+const PARTIAL_EXTENSION: &str = "part";
+
+/// Réponse de l'utilisateur à une sortie existante, en mode Demander.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ConflictChoice {
+    Overwrite, // Écraser la sortie existante
+    Skip,      // Conserver la sortie existante et ignorer la source
+    Rename,    // Écrire la nouvelle sortie sous un autre nom (ex: image-1.webp)
+}
+
+impl ConflictChoice {
+    /// Mode d'écrasement appliqué au fichier une fois la réponse connue.
+    fn overwrite_mode(self) -> OverwriteMode {
+        match self {
+            ConflictChoice::Overwrite => OverwriteMode::Overwrite,
+            ConflictChoice::Skip => OverwriteMode::Skip,
+            ConflictChoice::Rename => OverwriteMode::Rename,
+        }
+    }
+}
+
+/// Traitement d'une sortie plus lourde que sa source (ex: PNG déjà optimisé converti en WebP).
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum LargerOutputMode {
+    Keep,         // Écrire la sortie malgré tout
+    CopyOriginal, // Copier la source telle quelle à la place (avec son extension)
+    Skip,         // Ne rien écrire
+}
+
+impl LargerOutputMode {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LargerOutputMode::Keep => tr!("Conserver la sortie"),
+            LargerOutputMode::CopyOriginal => tr!("Copier l'original"),
+            LargerOutputMode::Skip => tr!("Ignorer"),
+        }
+    }
+}
+
+/// Stratégie de nommage quand plusieurs fichiers sélectionnés partagent le même nom (sans extension).
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum CollisionStrategy {
+    Ask,           // Demander à l'utilisateur avant la conversion
+    NumericSuffix, // Ajouter un suffixe numérique (ex: banner-1.webp)
+    ParentPrefix,  // Préfixer par le nom du dossier parent (ex: drafts_banner.webp)
+}
+
+/// Organisation des fichiers produits par la conversion d'un répertoire.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum OutputStructure {
+    Mirror,  // Reproduire l'arborescence de la source (ex: sortie/vacances/plage.webp)
+    Flatten, // Placer toutes les sorties à la racine de la sortie, noms en double départagés
+    ByDate,  // Ranger les sorties dans des sous-dossiers `AAAA/MM/` d'après la date de prise de vue
+}
+
+impl OutputStructure {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputStructure::Mirror => tr!("Reproduire l'arborescence"),
+            OutputStructure::Flatten => tr!("Tout dans un dossier"),
+            OutputStructure::ByDate => tr!("Par date (AAAA/MM)"),
+        }
+    }
+}
+
+/// Format des fichiers produits.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum OutputFormat {
+    WebP,
+    Png,
+    Jpeg,
+    Avif,
+    Tiff,
+}
+
+impl OutputFormat {
+    /// Tous les formats proposés dans l'interface, dans l'ordre d'affichage.
+    pub const ALL: [OutputFormat; 5] = [
+        OutputFormat::WebP,
+        OutputFormat::Png,
+        OutputFormat::Jpeg,
+        OutputFormat::Avif,
+        OutputFormat::Tiff,
+    ];
+
+    /// Formats essayés par le mode « format le plus léger », dans l'ordre de préférence à taille égale.
+    /// Le JPEG n'est retenu que pour les images sans transparence.
+    pub const SMALLEST_CANDIDATES: [OutputFormat; 4] = [
+        OutputFormat::WebP,
+        OutputFormat::Avif,
+        OutputFormat::Png,
+        OutputFormat::Jpeg,
+    ];
+
+    /// Extension (sans le point) des fichiers produits dans ce format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::WebP => "webp",
+            OutputFormat::Png => "png",
+            OutputFormat::Jpeg => "jpg",
+            OutputFormat::Avif => "avif",
+            OutputFormat::Tiff => "tiff",
+        }
+    }
+
+    /// Rapport habituel entre la taille d'un fichier produit dans ce format et celle de sa source (photo en JPEG ou PNG).
+    /// Volontairement pessimiste : il sert à vérifier l'espace libre avant un lot, pas à annoncer un gain.
+    pub fn typical_size_ratio(&self) -> f64 {
+        match self {
+            OutputFormat::WebP => 0.6,
+            OutputFormat::Png => 2.0,
+            OutputFormat::Jpeg => 0.8,
+            OutputFormat::Avif => 0.5,
+            OutputFormat::Tiff => 4.0,
+        }
+    }
+
+    /// Nom affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputFormat::WebP => "WebP",
+            OutputFormat::Png => "PNG",
+            OutputFormat::Jpeg => "JPEG",
+            OutputFormat::Avif => "AVIF",
+            OutputFormat::Tiff => "TIFF",
+        }
+    }
+}
+
+/// Options spécifiques à la sortie WebP.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebpOptions {
+    pub quality: u8,    // Qualité d'encodage avec perte (0 à 100).
+    pub lossless: bool, // Encodage sans perte (la qualité est alors ignorée).
+}
+
+impl Default for WebpOptions {
+    fn default() -> Self {
+        Self {
+            quality: 80,
+            lossless: false,
+        }
+    }
+}
+
+/// Options spécifiques à la sortie AVIF.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AvifOptions {
+    pub quality: u8, // Qualité d'encodage (1 à 100).
+    pub speed: u8,   // Vitesse d'encodage (1 = plus lent mais plus compact, 10 = plus rapide).
+}
+
+impl Default for AvifOptions {
+    fn default() -> Self {
+        Self {
+            quality: 70,
+            speed: 6,
+        }
+    }
+}
+
+/// Options spécifiques à la sortie PNG.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PngOptions {
+    pub quantize: bool,  // Réduire l'image à une palette indexée.
+    pub max_colors: u16, // Nombre maximal de couleurs de la palette (2 à 256).
+    pub dithering: bool, // Diffuser l'erreur de quantification (tramage).
+    pub optimize: bool,  // Passe d'optimisation sans perte (oxipng) après l'encodage.
+    pub optimization_level: u8, // Effort de l'optimisation (0 = rapide, 6 = le plus compact).
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self {
+            quantize: false,
+            max_colors: 256,
+            dithering: true,
+            optimize: false,
+            optimization_level: 2,
+        }
+    }
+}
+
+/// Sous-échantillonnage de la chrominance en sortie JPEG.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ChromaSubsampling {
+    S444, // Aucun sous-échantillonnage (meilleure qualité des couleurs)
+    S422, // Moitié de la résolution horizontale
+    S420, // Moitié de la résolution horizontale et verticale (fichiers plus légers)
+}
+
+impl ChromaSubsampling {
+    /// Nom affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChromaSubsampling::S444 => "4:4:4",
+            ChromaSubsampling::S422 => "4:2:2",
+            ChromaSubsampling::S420 => "4:2:0",
+        }
+    }
+}
+
+/// Options spécifiques à la sortie JPEG.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JpegOptions {
+    pub quality: u8,                     // Qualité d'encodage (1 à 100).
+    pub progressive: bool,               // Encodage progressif (affichage par passes successives).
+    pub subsampling: ChromaSubsampling,  // Sous-échantillonnage de la chrominance.
+    pub mozjpeg: bool,                   // Encodage avec mozjpeg (quantification en treillis, passes optimisées), plus compact.
+}
+
+impl Default for JpegOptions {
+    fn default() -> Self {
+        Self {
+            quality: 85,
+            progressive: true,
+            subsampling: ChromaSubsampling::S420,
+            mozjpeg: true,
+        }
+    }
+}
+
+/// Options de conversion partagées par toutes les fonctions `convert_*`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConversionOptions {
+    pub overwrite_mode: OverwriteMode,         // Gestion des fichiers de sortie existants.
+    pub rename_suffix: RenameSuffix,           // Suffixe des fichiers renommés (mode Renommer).
+    pub larger_output: LargerOutputMode,       // Traitement des sorties plus lourdes que leur source.
+    pub originals: OriginalsMode,              // Sort des sources après une conversion réussie.
+    pub collision_strategy: CollisionStrategy, // Départage des noms en double (plusieurs fichiers).
+    pub incremental: bool,                     // Ignorer les sources inchangées d'après le manifeste (sans effet avec l'archive ZIP).
+    pub output_format: OutputFormat,           // Format des fichiers produits.
+    pub name_template: String,                 // Modèle du nom des fichiers produits (voir `render_name_template`).
+    pub smallest_format: bool,                 // Essayer plusieurs formats et garder le plus léger (`output_format` ignoré).
+    pub webp: WebpOptions,                     // Options de la sortie WebP.
+    pub png: PngOptions,                       // Options de la sortie PNG.
+    pub jpeg: JpegOptions,                     // Options de la sortie JPEG.
+    pub avif: AvifOptions,                     // Options de la sortie AVIF.
+    pub background_color: [u8; 3],            // Couleur de fond pour aplatir la transparence (RGB).
+    pub flatten_alpha: bool,                   // Aplatir la transparence sur la couleur de fond, quel que soit le format.
+    pub strip_metadata: bool,                  // Retirer EXIF, XMP et profil ICC des fichiers produits.
+    pub auto_orient: bool,                     // Appliquer l'orientation EXIF de la source aux pixels.
+    pub color_profile: ColorProfileMode,       // Traitement du profil ICC de la source.
+    pub rotation: Rotation,                    // Rotation ou symétrie, appliquée avant le recadrage.
+    pub trim: TrimOptions,                     // Rognage des bords uniformes, avant le recadrage.
+    pub crop: CropOptions,                     // Recadrage à des proportions fixes, avant le redimensionnement.
+    pub resize: ResizeOptions,                 // Redimensionnement appliqué avant les réglages.
+    pub pad: PadOptions,                       // Canevas de dimensions fixes, après le redimensionnement.
+    pub adjustments: Adjustments,              // Réglages appliqués avant l'encodage.
+    pub contact_sheet: ContactSheetOptions,    // Planche contact générée à la fin de la conversion.
+    pub pdf_bundle: PdfBundleOptions,          // Document PDF réunissant les images produites, écrit à la fin de la conversion.
+    pub sprite_sheet: SpriteSheetOptions,      // Planche de sprites et atlas JSON des images produites, écrits à la fin de la conversion.
+    pub archive: ArchiveOptions,               // Archive ZIP regroupant les fichiers produits, au lieu de fichiers épars.
+    pub dimension_filter: DimensionFilter,     // Filtre de dimensions des lots (répertoire, plusieurs fichiers).
+    pub path_filter: PathFilter,               // Motifs glob d'inclusion et d'exclusion (répertoire).
+    pub file_filter: FileFilter,               // Taille minimale et date de modification (répertoire).
+    pub duplicates: DuplicateOptions,          // Détection des images en double, signalées ou écartées (répertoire).
+    pub traversal: TraversalOptions,           // Profondeur, liens symboliques et fichiers cachés (répertoire).
+    pub output_structure: OutputStructure,     // Arborescence reproduite ou sorties à plat (répertoire).
+    pub targets: OutputTargets,                // Destinations multiples, chacune avec son format et son redimensionnement.
+    pub responsive: ResponsiveOptions,         // Jeu d'images responsives : plusieurs largeurs par source, extrait `srcset`.
+    pub favicon: bool,                         // Mode favicon : `favicon.ico` multi-résolution et PNG usuels, au lieu de la sortie.
+    pub pdf: PdfOptions,                       // Résolution et pages rendues des documents PDF.
+    pub frames: FrameOptions,                  // Extraction des images des animations (GIF, PNG et WebP animés).
+    pub animation: AnimationOptions,           // Animations conservées en WebP ou PNG animé : durée des images, boucles, qualité.
+    pub run_folder: RunFolderOptions,          // Sous-dossier horodaté créé pour chaque exécution.
+    pub workers: usize,                        // Nombre de conversions simultanées (0 = un thread par cœur).
+    pub low_priority: bool,                    // Convertir avec une priorité basse, pour garder la machine réactive.
+    pub verify: VerifyOptions,                 // Relecture des fichiers produits et mesure de leur fidélité.
+    #[serde(skip)]
+    pub dry_run: bool, // Simulation : parcourir et prévoir chaque fichier sans rien écrire (jamais enregistré).
+}
+
+impl Default for ConversionOptions {
+    fn default() -> Self {
+        Self {
+            overwrite_mode: OverwriteMode::Skip, // Par défaut, ignorer les fichiers existants
+            rename_suffix: RenameSuffix::Counter,
+            larger_output: LargerOutputMode::Keep,
+            originals: OriginalsMode::Keep, // Par défaut, ne jamais toucher aux sources
+            collision_strategy: CollisionStrategy::Ask, // Par défaut, demander en cas de doublon
+            incremental: false,
+            output_format: OutputFormat::WebP,
+            name_template: DEFAULT_NAME_TEMPLATE.to_string(),
+            smallest_format: false,
+            webp: WebpOptions::default(),
+            png: PngOptions::default(),
+            jpeg: JpegOptions::default(),
+            avif: AvifOptions::default(),
+            background_color: [255, 255, 255], // Fond blanc par défaut
+            flatten_alpha: false,
+            strip_metadata: false,
+            auto_orient: true,
+            color_profile: ColorProfileMode::ConvertToSrgb,
+            rotation: Rotation::None,
+            trim: TrimOptions::default(),
+            crop: CropOptions::default(),
+            resize: ResizeOptions::default(),
+            pad: PadOptions::default(),
+            adjustments: Adjustments::default(),
+            contact_sheet: ContactSheetOptions::default(),
+            pdf_bundle: PdfBundleOptions::default(),
+            sprite_sheet: SpriteSheetOptions::default(),
+            archive: ArchiveOptions::default(),
+            dimension_filter: DimensionFilter::default(),
+            path_filter: PathFilter::default(),
+            file_filter: FileFilter::default(),
+            duplicates: DuplicateOptions::default(),
+            traversal: TraversalOptions::default(),
+            output_structure: OutputStructure::Mirror,
+            targets: OutputTargets::default(),
+            responsive: ResponsiveOptions::default(),
+            favicon: false,
+            pdf: PdfOptions::default(),
+            frames: FrameOptions::default(),
+            animation: AnimationOptions::default(),
+            run_folder: RunFolderOptions::default(),
+            workers: 0,
+            low_priority: false,
+            verify: VerifyOptions::default(),
+            dry_run: false,
+        }
+    }
+}
+
+impl ConversionOptions {
+    /// Signature des options qui déterminent quels fichiers d'un répertoire sont convertibles.
+    /// Sert de clé au cache de l'analyse préalable du répertoire.
+    pub fn scan_signature(&self) -> String {
+        format!(
+            "{};{};{};{};{}",
+            SUPPORTED_EXTENSIONS.join(","),
+            self.dimension_filter.signature(),
+            self.path_filter.signature(),
+            self.file_filter.signature(),
+            self.traversal.signature()
+        )
+    }
+
+    /// Signature des options qui influencent le contenu des fichiers produits.
+    /// Enregistrée dans le manifeste : une signature différente invalide les entrées existantes.
+    pub fn output_signature(&self) -> String {
+        let format_signature = if self.smallest_format {
+            let candidates: Vec<String> = OutputFormat::SMALLEST_CANDIDATES
+                .iter()
+                .map(|format| self.format_signature(*format))
+                .collect();
+            format!("smallest[{}]", candidates.join("|"))
+        } else {
+            self.format_signature(self.output_format)
+        };
+
+        let format_signature = if self.strip_metadata {
+            format!("{};strip", format_signature)
+        } else {
+            format_signature
+        };
+        let format_signature = if self.auto_orient {
+            format_signature
+        } else {
+            format!("{};no-orient", format_signature)
+        };
+        let format_signature = if self.flatten_alpha {
+            format!("{};flatten={:?}", format_signature, self.background_color)
+        } else {
+            format_signature
+        };
+        let format_signature = match self.name_template.trim() {
+            "" | DEFAULT_NAME_TEMPLATE => format_signature,
+            template => format!("{};name={}", format_signature, template),
+        };
+        let format_signature = match self.larger_output {
+            LargerOutputMode::Keep => format_signature,
+            LargerOutputMode::CopyOriginal => format!("{};larger=copy", format_signature),
+            LargerOutputMode::Skip => format!("{};larger=skip", format_signature),
+        };
+        let format_signature = match self.color_profile.signature() {
+            Some(profile_signature) => format!("{};{}", format_signature, profile_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.rotation.signature() {
+            Some(rotation_signature) => format!("{};{}", format_signature, rotation_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.trim.signature() {
+            Some(trim_signature) => format!("{};{}", format_signature, trim_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.crop.signature() {
+            Some(crop_signature) => format!("{};{}", format_signature, crop_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.resize.signature() {
+            Some(resize_signature) => format!("{};{}", format_signature, resize_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.pad.signature() {
+            Some(pad_signature) => format!("{};{}", format_signature, pad_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.targets.signature() {
+            Some(targets_signature) => format!("{};{}", format_signature, targets_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.responsive.signature() {
+            Some(responsive_signature) => format!("{};{}", format_signature, responsive_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.favicon {
+            true => format!("{};favicon", format_signature),
+            false => format_signature,
+        };
+        let format_signature = match self.pdf.signature() {
+            Some(pdf_signature) => format!("{};{}", format_signature, pdf_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.frames.signature() {
+            Some(frames_signature) => format!("{};{}", format_signature, frames_signature),
+            None => format_signature,
+        };
+        let format_signature = match self.animation.signature() {
+            Some(animation_signature) => format!("{};{}", format_signature, animation_signature),
+            None => format_signature,
+        };
+
+        if self.adjustments.is_identity() {
+            format_signature
+        } else {
+            let adjustments = &self.adjustments;
+            format!(
+                "{};brightness={};contrast={};saturation={};sharpen={}",
+                format_signature,
+                adjustments.brightness,
+                adjustments.contrast,
+                adjustments.saturation,
+                adjustments.effective_sharpen_amount()
+            )
+        }
+    }
+
+    /// Signature des options d'encodage propres au format `format`.
+    fn format_signature(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::WebP if self.webp.lossless => "webp;lossless".to_string(),
+            OutputFormat::WebP => format!("webp;q={}", self.webp.quality),
+            OutputFormat::Png => {
+                let palette = match self.png.quantize {
+                    true => format!(";palette={};dither={}", self.png.max_colors, self.png.dithering),
+                    false => String::new(),
+                };
+                let optimize = match self.png.optimize {
+                    true => format!(";oxipng={}", self.png.optimization_level),
+                    false => String::new(),
+                };
+                format!("png{}{}", palette, optimize)
+            }
+            OutputFormat::Jpeg => format!(
+                "jpeg;q={};progressive={};subsampling={};mozjpeg={};background={:?}",
+                self.jpeg.quality,
+                self.jpeg.progressive,
+                self.jpeg.subsampling.label(),
+                self.jpeg.mozjpeg,
+                self.background_color
+            ),
+            OutputFormat::Avif => format!("avif;q={};speed={}", self.avif.quality, self.avif.speed),
+            format => format.extension().to_string(),
+        }
+    }
+}
+
+/// Résultat de la conversion d'un fichier.
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub source: PathBuf,             // Image source.
+    pub output: PathBuf,             // Fichier produit.
+    pub input_size: u64,             // Taille de la source (octets).
+    pub output_size: u64,            // Taille du fichier produit (octets).
+    pub kept_original: bool,         // Vrai si la source a été copiée, la sortie étant plus lourde.
+    pub format: OutputFormat,        // Format du fichier produit.
+    pub candidate_sizes: Vec<(OutputFormat, u64)>, // Tailles des formats essayés (mode « format le plus léger »).
+    pub palette_size: Option<usize>, // Taille de la palette choisie (PNG quantifié uniquement).
+    pub original: OriginalOutcome,   // Sort de la source après la conversion (conservée, corbeille, sauvegarde).
+    pub verification: Verification,  // Relecture du fichier produit (option de vérification).
+    pub replaced: bool,              // Vrai si la sortie a remplacé un fichier existant (mode Écraser).
+}
+
+/// Action qu'une conversion réelle effectuerait pour un fichier (simulation).
+#[derive(Debug, PartialEq, Clone)]
+pub enum PlannedAction {
+    Convert,      // Nouvelle sortie écrite.
+    Overwrite,    // Sortie existante remplacée.
+    Rename,       // Sortie existante conservée, nouvelle sortie écrite sous un autre nom.
+    Ask,          // Sortie existante : la question serait posée (mode Demander).
+    Skip(String), // Source ignorée, avec la raison.
+}
+
+impl PlannedAction {
+    /// Description affichée dans le bilan de la simulation.
+    pub fn description(&self) -> String {
+        match self {
+            PlannedAction::Convert => tr!("à convertir").to_string(),
+            PlannedAction::Overwrite => tr!("à convertir (écrase la sortie existante)").to_string(),
+            PlannedAction::Rename => tr!("à convertir sous un nouveau nom").to_string(),
+            PlannedAction::Ask => tr!("sortie existante : question posée").to_string(),
+            PlannedAction::Skip(reason) => tr!("ignoré ({})", reason),
+        }
+    }
+}
+
+/// Fichier prévu par une simulation.
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    pub source: PathBuf,         // Image source.
+    pub output: Option<PathBuf>, // Sortie visée, `None` pour une source écartée avant le choix du nom.
+    pub action: PlannedAction,   // Action prévue.
+}
+
+/// Sorties déjà présentes dans le répertoire de sortie, relevées avant la conversion par une simulation
+/// en mode Écraser. Le relevé ne dépend pas du mode d'écrasement : `outcome` en déduit le sort des sorties
+/// existantes pour chaque mode, ce qui permet d'en changer sans refaire le relevé.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CollisionPreflight {
+    pub planned: usize,    // Sorties prévues (hors sources écartées ou inchangées)
+    pub existing: usize,   // Dont sorties déjà présentes
+    pub up_to_date: usize, // Dont sorties au moins aussi récentes que leur source (conservées en mode Si plus récent)
 }
 
-/// Convertit un seul fichier image en WebP.
-/// Retourne `Ok(())` en cas de succès, `Err(String)` en cas d'erreur.
+/// Sort des sorties existantes selon le mode d'écrasement (voir `CollisionPreflight::outcome`).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CollisionOutcome {
+    pub skipped: usize,     // Sources ignorées, leur sortie étant conservée
+    pub overwritten: usize, // Sorties remplacées
+    pub renamed: usize,     // Nouvelles sorties écrites sous un autre nom
+    pub asked: usize,       // Sorties pour lesquelles la question serait posée (mode Demander)
+}
+
+impl CollisionPreflight {
+    /// Relève les sorties existantes d'une simulation menée en mode Écraser.
+    pub fn from_report(report: &ConversionReport) -> Self {
+        let mut preflight = CollisionPreflight::default();
+        for file in &report.planned {
+            match (&file.action, &file.output) {
+                (PlannedAction::Convert, _) => preflight.planned += 1,
+                (PlannedAction::Overwrite, Some(output)) => {
+                    preflight.planned += 1;
+                    preflight.existing += 1;
+                    let source_modified = fs::metadata(&file.source).and_then(|metadata| metadata.modified()).ok();
+                    if OverwriteMode::IfNewer.keeps_existing(output, source_modified) {
+                        preflight.up_to_date += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        preflight
+    }
+
+    /// Sort des sorties existantes si la conversion était lancée en mode `mode`.
+    pub fn outcome(&self, mode: OverwriteMode) -> CollisionOutcome {
+        match mode {
+            OverwriteMode::Skip => CollisionOutcome { skipped: self.existing, ..Default::default() },
+            OverwriteMode::Overwrite => CollisionOutcome { overwritten: self.existing, ..Default::default() },
+            OverwriteMode::Rename => CollisionOutcome { renamed: self.existing, ..Default::default() },
+            OverwriteMode::IfNewer => CollisionOutcome {
+                skipped: self.up_to_date,
+                overwritten: self.existing - self.up_to_date,
+                ..Default::default()
+            },
+            OverwriteMode::Ask => CollisionOutcome { asked: self.existing, ..Default::default() },
+        }
+    }
+}
+
+/// Échec de la conversion d'un fichier, avec de quoi la relancer à l'identique.
+#[derive(Debug, Clone)]
+pub struct FileFailure {
+    pub source: PathBuf,     // Image source.
+    pub output_dir: PathBuf, // Répertoire de sortie prévu.
+    pub output_stem: String, // Nom de sortie prévu (sans extension).
+    pub error: ConverterError, // Cause de l'échec.
+}
+
+/// Bilan d'une conversion.
+#[derive(Debug, Default, Clone)]
+pub struct ConversionReport {
+    pub options: ConversionOptions, // Options utilisées (reprises pour relancer les échecs).
+    pub output_root: PathBuf,       // Racine de la sortie.
+    pub run_folder: Option<PathBuf>, // Sous-dossier de l'exécution, si l'option est active.
+    pub files: Vec<FileReport>,     // Fichiers effectivement écrits.
+    pub failures: Vec<FileFailure>, // Fichiers dont la conversion a échoué.
+    pub skipped_by_manifest: usize, // Sources inchangées ignorées grâce au manifeste (mode incrémental).
+    pub skipped_existing: usize,    // Sources ignorées car leur sortie existe déjà (modes Ignorer et Si plus récent).
+    pub skipped_larger: usize,      // Sources ignorées car leur sortie aurait été plus lourde.
+    pub skipped_resumed: usize,     // Sources déjà traitées avant une interruption, ignorées à la reprise.
+    pub skipped_sources: Vec<PathBuf>, // Sources ignorées (manifeste, sortie existante ou plus lourde), pour l'export du rapport.
+    pub filtered_by_dimensions: usize, // Sources écartées par le filtre de dimensions.
+    pub filtered_by_file: usize,    // Sources écartées par le filtre de taille ou de date (répertoire).
+    pub duplicates: Vec<Duplicate>, // Sources en double d'une autre (répertoire), écartées si `options.duplicates.exclude`.
+    pub contact_sheets: Vec<PathBuf>, // Pages de la planche contact écrites à la racine de la sortie.
+    pub pdf_bundle: Option<PathBuf>,  // Document PDF réunissant les images produites, écrit à la racine de la sortie.
+    pub sprite_sheet: Vec<PathBuf>,   // Planche de sprites et son atlas JSON, écrits à la racine de la sortie.
+    pub archive: Option<PathBuf>,     // Archive ZIP où les fichiers produits ont été regroupés (ils ne sont plus épars).
+    pub srcset_snippets: Vec<PathBuf>, // Extraits HTML `srcset` écrits à côté des jeux d'images responsives.
+    pub planned: Vec<PlannedFile>,  // Simulation : action prévue pour chaque source (rien n'est écrit).
+    pub warnings: Vec<String>,      // Problèmes n'ayant pas fait échouer la conversion.
+    pub cancelled: bool,            // Vrai si la conversion a été annulée avant la fin.
+}
+
+impl ConversionReport {
+    /// Fichiers écrits par la conversion : sorties, extraits `srcset`, pages de la planche contact, document PDF
+    /// et planche de sprites.
+    pub fn produced_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self.files.iter().map(|file| file.output.clone()).collect();
+        files.extend(self.srcset_snippets.iter().cloned());
+        files.extend(self.contact_sheets.iter().cloned());
+        files.extend(self.pdf_bundle.iter().cloned());
+        files.extend(self.sprite_sheet.iter().cloned());
+        files
+    }
+
+    /// Nombre de fichiers effectivement écrits.
+    pub fn converted(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Nombre de sources ignorées (sortie déjà présente, source inchangée ou sortie plus lourde).
+    pub fn skipped(&self) -> usize {
+        self.skipped_existing + self.skipped_by_manifest + self.skipped_larger + self.skipped_resumed
+    }
+
+    /// Nombre de fichiers produits dont la vérification a échoué.
+    pub fn verification_failures(&self) -> usize {
+        self.files.iter().filter(|file| file.verification.is_failed()).count()
+    }
+
+    /// Fidélité moyenne des fichiers produits dont la qualité a été mesurée (PSNR en dB, SSIM), s'il y en a.
+    pub fn average_quality(&self) -> Option<(f64, f64)> {
+        let scores: Vec<_> = self.files
+            .iter()
+            .filter_map(|file| match &file.verification {
+                Verification::Valid(Some(score)) => Some((score.psnr, score.ssim)),
+                _ => None,
+            })
+            .collect();
+        let count = scores.len() as f64;
+        (!scores.is_empty()).then(|| {
+            let (psnr, ssim) = scores.iter().fold((0.0, 0.0), |(psnr, ssim), score| (psnr + score.0, ssim + score.1));
+            (psnr / count, ssim / count)
+        })
+    }
+
+    /// Nombre de sources copiées telles quelles, leur sortie étant plus lourde.
+    pub fn kept_originals(&self) -> usize {
+        self.files.iter().filter(|file| file.kept_original).count()
+    }
+
+    /// Nombre de fichiers que la simulation prévoit d'écrire, dont ceux qui écraseraient une sortie existante.
+    pub fn planned_counts(&self) -> (usize, usize) {
+        let written = self.planned.iter().filter(|file| !matches!(file.action, PlannedAction::Skip(_))).count();
+        let overwritten = self.planned.iter().filter(|file| file.action == PlannedAction::Overwrite).count();
+        (written, overwritten)
+    }
+
+    /// Nombre de sources retirées après leur conversion (corbeille ou dossier de sauvegarde).
+    pub fn removed_originals(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| matches!(file.original, OriginalOutcome::Trashed | OriginalOutcome::MovedTo(_)))
+            .count()
+    }
+
+    /// Taille totale des sources des fichiers écrits (octets). Une source produite pour plusieurs
+    /// destinations ou largeurs n'est comptée qu'une fois.
+    pub fn input_bytes(&self) -> u64 {
+        let mut sources = HashSet::new();
+        self.files
+            .iter()
+            .filter(|file| sources.insert(&file.source))
+            .map(|file| file.input_size)
+            .sum()
+    }
+
+    /// Taille totale des fichiers écrits (octets).
+    pub fn output_bytes(&self) -> u64 {
+        self.files.iter().map(|file| file.output_size).sum()
+    }
+
+    /// Pourcentage d'espace gagné par rapport aux sources (négatif si les sorties sont plus lourdes),
+    /// `None` si aucun fichier n'a été écrit.
+    pub fn savings_percent(&self) -> Option<f64> {
+        let input_bytes = self.input_bytes();
+        (input_bytes > 0).then(|| (1.0 - self.output_bytes() as f64 / input_bytes as f64) * 100.0)
+    }
+
+    /// Échecs dont la source figure dans `sources` (sélection à relancer), ou tous les échecs sans sélection.
+    pub fn failures_among(&self, sources: Option<&HashSet<PathBuf>>) -> Vec<FileFailure> {
+        self.failures
+            .iter()
+            .filter(|failure| sources.is_none_or(|sources| sources.contains(&failure.source)))
+            .cloned()
+            .collect()
+    }
+
+    /// Intègre le bilan d'une relance des échecs : les sources relancées quittent la liste des échecs,
+    /// remplacées par le résultat de la relance (réussite ou nouvel échec, lui-même relançable).
+    pub fn merge_retry(&mut self, retry: ConversionReport) {
+        self.failures.retain(|failure| {
+            let retried = |source: &PathBuf| *source == failure.source;
+            !retry.files.iter().any(|f| retried(&f.source)) && !retry.failures.iter().any(|f| retried(&f.source))
+        });
+        self.files.extend(retry.files);
+        self.failures.extend(retry.failures);
+        self.skipped_by_manifest += retry.skipped_by_manifest;
+        self.skipped_existing += retry.skipped_existing;
+        self.skipped_larger += retry.skipped_larger;
+        self.skipped_resumed += retry.skipped_resumed;
+        self.skipped_sources.extend(retry.skipped_sources);
+        self.warnings.extend(retry.warnings);
+        self.cancelled = retry.cancelled;
+    }
+}
+
+/// Convertit un seul fichier image dans le format de sortie choisi.
 pub fn convert_single_image(
     input_path: &Path,
     output_dir: &Path,
-    overwrite_mode: &OverwriteMode,
-) -> Result<(), String> {
+    options: &ConversionOptions,
+    progress: &ProgressFn,
+    control: &BatchControl,
+) -> Result<ConversionReport, ConverterError> {
     // Le parent_dir n'est pas utilisé dans convert_image_internal pour le renommage,
     // car le renommage se fait par rapport au output_dir déjà.
-    convert_image_internal(input_path, output_dir, overwrite_mode)
+    create_output_dir(output_dir, options)
+        .map_err(|e| ConverterError::io(tr!("Impossible de créer le répertoire de sortie {}", output_dir.display()), e))?;
+
+    let batch = Batch::new(output_dir, options, progress, control);
+    batch.add_to_total(1);
+    batch.run(&[Job {
+        source: input_path.to_path_buf(),
+        output_dir: output_dir.to_path_buf(),
+        output_stem: file_stem_lossy(input_path),
+    }]);
+    Ok(batch.finish())
 }
 
-/// Convertit plusieurs fichiers image en WebP.
+/// Convertit plusieurs fichiers image dans le format de sortie choisi.
+/// Les noms de sortie en double sont départagés selon `options.collision_strategy` (voir `plan_output_stems`).
 pub fn convert_multiple_files(
     input_paths: &[PathBuf],
     output_dir: &Path,
-    overwrite_mode: &OverwriteMode,
-) -> Result<(), String> {
+    options: &ConversionOptions,
+    progress: &ProgressFn,
+    control: &BatchControl,
+) -> Result<ConversionReport, ConverterError> {
     // Crée le répertoire de sortie.
-    fs::create_dir_all(output_dir).map_err(|e| format!("Échec de la création du répertoire de sortie : {}", e))?;
+    create_output_dir(output_dir, options)
+        .map_err(|e| ConverterError::io(tr!("Impossible de créer le répertoire de sortie {}", output_dir.display()), e))?;
+
+    let batch = Batch::new(output_dir, options, progress, control);
+    // Le filtre est appliqué avant de choisir les noms : une source écartée ne provoque pas de suffixe.
+    let accepted_paths: Vec<PathBuf> = input_paths
+        .iter()
+        .filter(|path| batch.accepts(path))
+        .cloned()
+        .collect();
+    let output_stems = plan_output_stems(&accepted_paths, &options.collision_strategy);
+    let jobs: Vec<Job> = accepted_paths
+        .into_iter()
+        .zip(output_stems)
+        .map(|(source, output_stem)| Job { source, output_dir: output_dir.to_path_buf(), output_stem })
+        .collect();
+    batch.add_to_total(jobs.len());
+    batch.run(&jobs);
+    Ok(batch.finish())
+}
+
+/// Relance les fichiers en échec d'une conversion précédente, vers les mêmes destinations et avec
+/// les mêmes options. La planche contact, le document PDF, la planche de sprites et l'archive ne sont pas régénérés pour une relance partielle :
+/// les fichiers relancés sont écrits à côté de l'archive.
+pub fn retry_failures(
+    failures: &[FileFailure],
+    output_root: &Path,
+    options: &ConversionOptions,
+    progress: &ProgressFn,
+    control: &BatchControl,
+) -> Result<ConversionReport, ConverterError> {
+    let mut retry_options = options.clone();
+    retry_options.contact_sheet.enabled = false;
+    retry_options.pdf_bundle.enabled = false;
+    retry_options.sprite_sheet.enabled = false;
+    retry_options.archive.enabled = false;
+
+    let batch = Batch::new(output_root, &retry_options, progress, control);
+    let mut jobs = Vec::with_capacity(failures.len());
+    for failure in failures {
+        if let Err(e) = create_output_dir(&failure.output_dir, options) {
+            batch.record_failure(
+                &failure.source,
+                &failure.output_dir,
+                &failure.output_stem,
+                ConverterError::io(tr!("Impossible de créer le répertoire de sortie {}", failure.output_dir.display()), e),
+            );
+            continue;
+        }
+        jobs.push(Job {
+            source: failure.source.clone(),
+            output_dir: failure.output_dir.clone(),
+            output_stem: failure.output_stem.clone(),
+        });
+    }
+    batch.add_to_total(jobs.len());
+    batch.run(&jobs);
+
+    let mut report = batch.finish();
+    report.options = options.clone();
+    Ok(report)
+}
+
+/// Estime l'espace (octets) qu'occuperont les fichiers produits à partir de sources totalisant `input_bytes` :
+/// taille des sources × rapport habituel du format, pour chaque destination et chaque largeur responsive.
+/// En mode « format le plus léger », le rapport du WebP est retenu.
+pub fn estimate_output_bytes(input_bytes: u64, options: &ConversionOptions) -> u64 {
+    let widths = match options.responsive.is_active() {
+        true => options.responsive.widths.len().max(1),
+        false => 1,
+    };
+    let ratio: f64 = options
+        .targets
+        .resolve(options)
+        .iter()
+        .map(|destination| match destination.options.smallest_format {
+            true => OutputFormat::WebP.typical_size_ratio(),
+            false => destination.options.output_format.typical_size_ratio(),
+        })
+        .sum();
+    (input_bytes as f64 * ratio * widths as f64) as u64
+}
+
+/// Retourne les noms (sans extension) partagés par plusieurs fichiers de la sélection.
+/// La comparaison ignore la casse, les systèmes de fichiers Windows et macOS ne la distinguant pas.
+pub fn find_stem_collisions(input_paths: &[PathBuf]) -> Vec<String> {
+    let mut collisions: Vec<String> = Vec::new();
+    let stems: Vec<String> = input_paths.iter().map(|p| file_stem_lossy(p)).collect();
+
+    for (index, stem) in stems.iter().enumerate() {
+        let key = stem.to_lowercase();
+        let is_duplicate = stems[..index].iter().any(|other| other.to_lowercase() == key);
+        if is_duplicate && !collisions.iter().any(|c| c.to_lowercase() == key) {
+            collisions.push(stem.clone());
+        }
+    }
+    collisions
+}
+
+/// Construit les noms de sortie (sans extension) de chaque fichier, dans l'ordre de la sélection.
+/// `ParentPrefix` préfixe les noms en double par leur dossier parent, puis se rabat sur des suffixes
+/// numériques si des doublons subsistent. `Ask` doit être résolu par l'UI avant la conversion : s'il
+/// arrive jusqu'ici, il se comporte comme `NumericSuffix`.
+pub fn plan_output_stems(input_paths: &[PathBuf], collision_strategy: &CollisionStrategy) -> Vec<String> {
+    let collisions: Vec<String> = find_stem_collisions(input_paths)
+        .iter()
+        .map(|c| c.to_lowercase())
+        .collect();
+
+    let candidates: Vec<String> = input_paths
+        .iter()
+        .map(|path| {
+            let stem = file_stem_lossy(path);
+            let is_colliding = collisions.contains(&stem.to_lowercase());
+            match (collision_strategy, path.parent().and_then(|p| p.file_name())) {
+                (CollisionStrategy::ParentPrefix, Some(parent)) if is_colliding => {
+                    format!("{}_{}", parent.to_string_lossy(), stem)
+                }
+                _ => stem,
+            }
+        })
+        .collect();
 
-    for path in input_paths {
-        convert_image_internal(path, output_dir, overwrite_mode)?;
+    // Suffixes numériques pour les doublons restants (ex: banner, banner-1, banner-2).
+    let mut planned: Vec<String> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let mut name = candidate.clone();
+        let mut counter = 1;
+        while planned.iter().any(|p| p.to_lowercase() == name.to_lowercase()) {
+            name = format!("{}-{}", candidate, counter);
+            counter += 1;
+        }
+        planned.push(name);
     }
-    Ok(())
+    planned
 }
 
-/// Convertit toutes les images d'un répertoire et de ses sous-répertoires en WebP.
+/// Convertit toutes les images d'un répertoire et de ses sous-répertoires dans le format de sortie choisi.
+/// L'arborescence est reproduite dans la sortie, sauf en mode `OutputStructure::Flatten` (toutes les sorties à la racine)
+/// ou `OutputStructure::ByDate` (sous-dossiers `AAAA/MM/` de la date de prise de vue EXIF, à défaut de modification).
+/// Les noms en double d'un même dossier de sortie sont alors préfixés par leur dossier parent (voir `plan_output_stems`).
 pub fn convert_images_in_directory(
     input_dir: &Path,
     output_base_dir: &Path, // Nouveau: Le répertoire racine où les sorties doivent être créées
     current_walk_dir: &Path, // Le répertoire actuellement traversé par walkdir
-    overwrite_mode: &OverwriteMode,
-) -> Result<(), String> {
+    options: &ConversionOptions,
+    progress: &ProgressFn,
+    control: &BatchControl,
+) -> Result<ConversionReport, ConverterError> {
     // Crée le répertoire de sortie de base s'il n'existe pas
-    fs::create_dir_all(output_base_dir)
-        .map_err(|e| format!("Impossible de créer le répertoire de sortie {}: {}", output_base_dir.display(), e))?;
+    create_output_dir(output_base_dir, options)
+        .map_err(|e| ConverterError::io(tr!("Impossible de créer le répertoire de sortie {}", output_base_dir.display()), e))?;
 
-    for entry in WalkDir::new(current_walk_dir) {
-        let entry = entry.map_err(|e| format!("Erreur lors de la lecture du répertoire: {}", e))?;
-        let path = entry.path();
+    let path_filter = options.path_filter.compile()?;
+    let file_filter = options.file_filter.compile()?;
+    let batch = Batch::new(output_base_dir, options, progress, control);
+    let mut jobs = Vec::new();
 
-        if path.is_file() {
-            // Vérifier si l'extension est celle d'une image supportée
-            if let Some(extension) = path.extension().and_then(|s| s.to_str()) {
-                if ["png", "jpg", "jpeg", "bmp"].contains(&extension.to_lowercase().as_str()) {
-                    // Calculer le chemin de sortie relatif par rapport à input_dir
-                    let relative_path = path.strip_prefix(input_dir)
-                        .map_err(|e| format!("Erreur de chemin relatif : {}", e))?;
+    // Le parcours est séquentiel ; seules les conversions sont réparties sur les threads.
+    for entry in options.traversal.walk(current_walk_dir) {
+        if batch.is_cancelled() {
+            break;
+        }
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                // Un dossier illisible n'interrompt pas le parcours du reste de l'arborescence.
+                batch.warn(tr!("Erreur lors de la lecture du répertoire : {}", e));
+                continue;
+            }
+        };
+        let path = entry.path();
 
-                    let output_file_dir = output_base_dir.join(relative_path.parent().unwrap_or_else(|| Path::new("")));
+        // Le type de l'entrée est celui de la cible d'un lien symbolique seulement si les liens sont suivis.
+        if entry.file_type().is_file() {
+            // Vérifier si l'extension est celle d'une image supportée et si les motifs l'acceptent
+            let relative_path = path.strip_prefix(input_dir).unwrap_or(path);
+            if is_supported_image(path)
+                && path_filter.accepts(relative_path)
+                && batch.accepts_file(path, &file_filter, entry.metadata().ok())
+                && batch.accepts(path)
+            {
+                // Calculer le chemin de sortie relatif par rapport à input_dir
+                let relative_parent = relative_path.parent().unwrap_or_else(|| Path::new(""));
 
-                    // S'assurer que le sous-répertoire de sortie existe
-                    fs::create_dir_all(&output_file_dir)
-                        .map_err(|e| format!("Impossible de créer le sous-répertoire de sortie {}: {}", output_file_dir.display(), e))?;
+                let output_file_dir = match options.output_structure {
+                    OutputStructure::Mirror => output_base_dir.join(relative_parent),
+                    OutputStructure::Flatten => output_base_dir.to_path_buf(),
+                    OutputStructure::ByDate => output_base_dir.join(capture_date::date_subfolder(path)),
+                };
+                let output_stem = file_stem_lossy(path);
 
-                    convert_image_internal(path, &output_file_dir, overwrite_mode)?;
+                // S'assurer que le sous-répertoire de sortie existe (avec des destinations multiples,
+                // chaque destination crée le sien au moment de la conversion)
+                let created = match options.targets.is_active() {
+                    true => Ok(()),
+                    false => create_output_dir(&output_file_dir, options),
+                };
+                if let Err(e) = created {
+                    let error = ConverterError::io(
+                        tr!("Impossible de créer le sous-répertoire de sortie {}", output_file_dir.display()),
+                        e,
+                    );
+                    batch.record_failure(path, &output_file_dir, &output_stem, error);
+                    continue;
                 }
+
+                jobs.push(Job { source: path.to_path_buf(), output_dir: output_file_dir, output_stem });
+                batch.add_to_total(1);
+            }
+        }
+    }
+
+    if options.duplicates.enabled && !batch.is_cancelled() {
+        batch.find_duplicates(&mut jobs);
+    }
+
+    // Hors arborescence reproduite, des sources de dossiers différents partagent un dossier de sortie :
+    // leurs noms ne sont départagés qu'une fois l'arborescence entière parcourue.
+    if options.output_structure != OutputStructure::Mirror {
+        let mut groups: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+        for (index, job) in jobs.iter().enumerate() {
+            groups.entry(job.output_dir.clone()).or_default().push(index);
+        }
+        for indices in groups.values() {
+            let sources: Vec<PathBuf> = indices.iter().map(|&index| jobs[index].source.clone()).collect();
+            let output_stems = plan_output_stems(&sources, &CollisionStrategy::ParentPrefix);
+            for (&index, output_stem) in indices.iter().zip(output_stems) {
+                jobs[index].output_stem = output_stem;
             }
         }
     }
-    Ok(())
+
+    batch.run(&jobs);
+    Ok(batch.finish())
 }
 
-/// Fonction interne pour la logique de conversion unique, incluant la gestion du mode d'écrasement.
-fn convert_image_internal(
-    input_path: &Path,
-    output_dir: &Path,
-    overwrite_mode: &OverwriteMode,
-) -> Result<(), String> {
-    let image_name = input_path.file_stem().ok_or("Nom de fichier invalide")?;
-    let mut output_file_name = format!("{}.webp", image_name.to_string_lossy());
-    let mut output_full_path = output_dir.join(&output_file_name);
+/// Intervalle de vérification de la reprise pendant une pause.
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
-    match overwrite_mode {
-        OverwriteMode::Skip => {
-            if output_full_path.exists() {
-                println!("Skipping existing file: {}", output_full_path.display());
-                return Ok(()); // Ne rien faire si le fichier existe déjà
+/// Commandes de l'utilisateur sur une conversion en cours, partagées entre l'UI et les threads de conversion.
+/// Elles sont prises en compte entre deux fichiers : un fichier commencé va toujours jusqu'au bout.
+/// En mode Demander, elles portent aussi les questions posées à l'UI sur les sorties existantes et leurs réponses.
+#[derive(Debug, Default)]
+pub struct BatchControl {
+    cancel: AtomicBool,         // Demande d'annulation.
+    pause: AtomicBool,          // Lot en pause.
+    conflict: Mutex<Conflict>,  // Question en cours et réponse « pour tous » (mode Demander).
+    conflict_answered: Condvar, // Réveille le thread en attente d'une réponse.
+    conflict_asking: Mutex<()>, // Une seule question à la fois, même avec plusieurs threads de conversion.
+    completed: Mutex<HashSet<PathBuf>>, // Sources déjà traitées avant une interruption, ignorées à la reprise.
+}
+
+/// État des questions sur les sorties existantes (mode Demander).
+#[derive(Debug, Default)]
+struct Conflict {
+    pending: Option<PathBuf>,             // Sortie existante en attente d'une réponse.
+    answer: Option<ConflictChoice>,       // Réponse à la question en cours.
+    apply_to_all: Option<ConflictChoice>, // Réponse à appliquer aux sorties existantes suivantes du lot.
+}
+
+impl BatchControl {
+    /// Remet les commandes à zéro avant un nouveau lot.
+    pub fn reset(&self) {
+        self.cancel.store(false, Ordering::Relaxed);
+        self.pause.store(false, Ordering::Relaxed);
+        *self.conflict.lock().unwrap() = Conflict::default();
+        self.completed.lock().unwrap().clear();
+    }
+
+    /// Reprise d'un lot interrompu : les sources `sources`, déjà traitées, sont ignorées sans être relues.
+    /// Les noms de sortie des autres sources sont choisis comme lors du premier lancement.
+    pub fn skip_completed(&self, sources: impl IntoIterator<Item = PathBuf>) {
+        self.completed.lock().unwrap().extend(sources);
+    }
+
+    /// Indique si la source a déjà été traitée avant une interruption (voir `skip_completed`).
+    fn is_completed(&self, source: &Path) -> bool {
+        self.completed.lock().unwrap().contains(source)
+    }
+
+    /// Demande l'annulation du lot (un lot en pause est aussi débloqué).
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Indique si l'annulation a été demandée.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Met le lot en pause ou le reprend.
+    pub fn set_paused(&self, paused: bool) {
+        self.pause.store(paused, Ordering::Relaxed);
+    }
+
+    /// Indique si le lot est en pause.
+    pub fn is_paused(&self) -> bool {
+        self.pause.load(Ordering::Relaxed)
+    }
+
+    /// Sortie existante sur laquelle la conversion attend une réponse (mode Demander), s'il y en a une.
+    pub fn pending_conflict(&self) -> Option<PathBuf> {
+        self.conflict.lock().unwrap().pending.clone()
+    }
+
+    /// Répond à la question en cours ; avec `apply_to_all`, la réponse vaut pour les sorties existantes suivantes du lot.
+    pub fn resolve_conflict(&self, choice: ConflictChoice, apply_to_all: bool) {
+        let mut conflict = self.conflict.lock().unwrap();
+        conflict.pending = None;
+        conflict.answer = Some(choice);
+        if apply_to_all {
+            conflict.apply_to_all = Some(choice);
+        }
+        self.conflict_answered.notify_all();
+    }
+
+    /// Demande quoi faire de la sortie existante `output` et bloque le thread appelant jusqu'à la réponse.
+    /// Une réponse « pour tous » déjà donnée est réutilisée sans nouvelle question ; une annulation vaut Ignorer.
+    fn ask_conflict(&self, output: &Path) -> ConflictChoice {
+        let _asking = self.conflict_asking.lock().unwrap();
+        let mut conflict = self.conflict.lock().unwrap();
+        if let Some(choice) = conflict.apply_to_all {
+            return choice;
+        }
+        conflict.pending = Some(output.to_path_buf());
+        conflict.answer = None;
+        loop {
+            if let Some(choice) = conflict.answer.take() {
+                return choice;
+            }
+            if self.is_cancelled() {
+                conflict.pending = None;
+                return ConflictChoice::Skip;
             }
+            conflict = self.conflict_answered.wait_timeout(conflict, PAUSE_POLL_INTERVAL).unwrap().0;
         }
-        OverwriteMode::Rename => {
+    }
+
+    /// Bloque le thread appelant tant que le lot est en pause et n'a pas été annulé.
+    fn wait_while_paused(&self) {
+        while self.is_paused() && !self.is_cancelled() {
+            std::thread::sleep(PAUSE_POLL_INTERVAL);
+        }
+    }
+}
+
+/// Fichier à convertir au sein d'un lot, avec sa destination.
+struct Job {
+    source: PathBuf,     // Image source.
+    output_dir: PathBuf, // Répertoire de sortie (déjà créé).
+    output_stem: String, // Nom de sortie (sans extension).
+}
+
+/// État partagé par les fichiers d'une même conversion : options, manifeste et bilan.
+/// Les fichiers étant convertis en parallèle, l'état modifiable est protégé par des `Mutex`.
+struct Batch<'a> {
+    options: &'a ConversionOptions,
+    destinations: Vec<Destination>,    // Destinations de chaque fichier (une seule sans destinations multiples).
+    output_root: PathBuf,              // Racine de la sortie (dossier de sauvegarde des originaux).
+    manifest: Option<Mutex<Manifest>>, // Chargé uniquement en mode incrémental, sans archive ZIP.
+    contact_sheet: Option<Mutex<ContactSheetBuilder>>, // Présente uniquement si la planche contact est demandée.
+    pdf_bundle: Option<Mutex<PdfBundleBuilder>>,       // Présent uniquement si le document PDF est demandé.
+    sprite_sheet: Option<Mutex<SpriteSheetBuilder>>,   // Présente uniquement si la planche de sprites est demandée.
+    report: Mutex<ConversionReport>,
+    progress: &'a ProgressFn, // Rappel de progression.
+    control: &'a BatchControl, // Pause et annulation, vérifiées entre deux fichiers.
+    completed: AtomicUsize,   // Fichiers traités (convertis, ignorés ou en échec).
+    total: AtomicUsize,       // Fichiers à traiter connus à ce stade.
+    throughput: ThroughputMeter, // Débit des fichiers traités, pour le temps restant.
+}
+
+impl<'a> Batch<'a> {
+    fn new(
+        output_root: &Path,
+        options: &'a ConversionOptions,
+        progress: &'a ProgressFn,
+        control: &'a BatchControl,
+    ) -> Self {
+        let mut report = ConversionReport {
+            options: options.clone(),
+            output_root: output_root.to_path_buf(),
+            ..Default::default()
+        };
+        let contact_sheet = if options.contact_sheet.enabled && !options.dry_run {
+            match ContactSheetBuilder::new(&options.contact_sheet, output_root) {
+                Ok(builder) => Some(Mutex::new(builder)),
+                Err(e) => {
+                    report.warnings.push(tr!("Planche contact : {}", e));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        // L'archive retire les sorties que le manifeste désigne : il ne reconnaîtrait jamais une source inchangée.
+        let incremental = options.incremental && !options.archive.enabled;
+        if options.incremental && !incremental {
+            report.warnings.push(tr!("Mode incrémental ignoré : incompatible avec l'archive ZIP").to_string());
+        }
+
+        Self {
+            options,
+            destinations: options.targets.resolve(options),
+            output_root: output_root.to_path_buf(),
+            manifest: incremental.then(|| Mutex::new(Manifest::load(output_root))),
+            contact_sheet,
+            pdf_bundle: (options.pdf_bundle.enabled && !options.dry_run).then(|| {
+                Mutex::new(PdfBundleBuilder::new(&options.pdf_bundle, output_root, options.background_color))
+            }),
+            sprite_sheet: (options.sprite_sheet.enabled && !options.dry_run)
+                .then(|| Mutex::new(SpriteSheetBuilder::new(&options.sprite_sheet, output_root))),
+            report: Mutex::new(report),
+            progress,
+            control,
+            completed: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+            throughput: ThroughputMeter::default(),
+        }
+    }
+
+    /// Ajoute `count` fichiers au total à traiter et le signale au rappel de progression.
+    fn add_to_total(&self, count: usize) {
+        self.total.fetch_add(count, Ordering::Relaxed);
+        self.notify(None);
+    }
+
+    /// Signale l'avancement au rappel de progression, avec le nouvel état de `file` s'il est fourni.
+    fn notify(&self, file: Option<(&Path, FileStatus)>) {
+        let completed = self.completed.load(Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+        (self.progress)(Progress {
+            completed,
+            total,
+            file,
+            throughput: self.throughput.snapshot(total.saturating_sub(completed)),
+        });
+    }
+
+    /// Indique si la source passe le filtre de dimensions ; sinon, elle est comptée comme filtrée.
+    fn accepts(&self, input_path: &Path) -> bool {
+        let accepted = self.options.dimension_filter.accepts(input_path);
+        if !accepted {
+            self.filter_out(input_path, tr!("filtré par dimensions"), |report| report.filtered_by_dimensions += 1);
+        }
+        accepted
+    }
+
+    /// Indique si la source passe le filtre de taille et de date ; sinon, elle est comptée comme filtrée.
+    /// Un fichier dont les métadonnées sont illisibles est conservé.
+    fn accepts_file(&self, input_path: &Path, filter: &CompiledFileFilter, metadata: Option<fs::Metadata>) -> bool {
+        let accepted = !filter.is_active() || metadata.is_none_or(|metadata| filter.accepts(&metadata));
+        if !accepted {
+            self.filter_out(input_path, tr!("filtré par taille ou date"), |report| report.filtered_by_file += 1);
+        }
+        accepted
+    }
+
+    /// Repère les doublons parmi les sources de `jobs` (voir `duplicates::find`) et les consigne dans le bilan ;
+    /// avec `options.duplicates.exclude`, ils sont retirés de `jobs` comme des sources filtrées.
+    fn find_duplicates(&self, jobs: &mut Vec<Job>) {
+        let sources: Vec<PathBuf> = jobs.iter().map(|job| job.source.clone()).collect();
+        let (found, warnings) = duplicates::find(&sources, self.options, || self.is_cancelled());
+        tracing::info!(sources = sources.len(), duplicates = found.len(), "doublons repérés");
+        if self.options.duplicates.exclude {
+            let excluded: HashSet<&Path> = found.iter().map(|duplicate| duplicate.source.as_path()).collect();
+            jobs.retain(|job| !excluded.contains(job.source.as_path()));
+            self.total.fetch_sub(excluded.len(), Ordering::Relaxed);
+            for duplicate in &found {
+                let reason = tr!("doublon de {}", duplicate.original.display());
+                self.filter_out(&duplicate.source, &reason, |_| {});
+            }
+        }
+        let mut report = self.report.lock().unwrap();
+        report.warnings.extend(warnings);
+        report.duplicates = found;
+    }
+
+    /// Consigne une source écartée par un filtre : compteur du bilan, plan de la simulation et file d'attente.
+    fn filter_out(&self, input_path: &Path, reason: &str, count: impl FnOnce(&mut ConversionReport)) {
+        tracing::info!(source = %input_path.display(), reason, "fichier écarté");
+        let mut report = self.report.lock().unwrap();
+        count(&mut report);
+        if self.options.dry_run {
+            report.planned.push(PlannedFile {
+                source: input_path.to_path_buf(),
+                output: None,
+                action: PlannedAction::Skip(reason.to_string()),
+            });
+        }
+        drop(report);
+        self.notify(Some((input_path, FileStatus::Skipped)));
+    }
+
+    /// Consigne un avertissement dans le bilan.
+    fn warn(&self, warning: String) {
+        self.report.lock().unwrap().warnings.push(warning);
+    }
+
+    /// Convertit les fichiers du lot en parallèle, sur `options.workers` threads (0 = un par cœur),
+    /// de priorité basse si `options.low_priority` est actif.
+    fn run(&self, jobs: &[Job]) {
+        let mut builder = rayon::ThreadPoolBuilder::new().num_threads(self.options.workers);
+        if self.options.low_priority {
+            // Priorité « au mieux » : un refus du système n'empêche pas la conversion.
+            builder = builder.start_handler(|_| {
+                platform_utils::lower_current_thread_priority();
+            });
+        }
+        match builder.build() {
+            Ok(pool) => pool.install(|| jobs.par_iter().for_each(|job| self.convert(job))),
+            Err(e) => {
+                // Sans pool de threads, la conversion reste possible séquentiellement.
+                self.warn(tr!("Conversion parallèle indisponible ({}), conversion séquentielle", e));
+                jobs.iter().for_each(|job| self.convert(job));
+            }
+        }
+    }
+
+    /// Attend la reprise si le lot est en pause, puis indique si l'annulation a été demandée.
+    fn is_cancelled(&self) -> bool {
+        self.control.wait_while_paused();
+        self.control.is_cancelled()
+    }
+
+    /// Convertit un fichier ; un échec est consigné dans le bilan sans interrompre le lot.
+    /// En pause, le fichier attend la reprise avant de démarrer (ceux en cours se terminent).
+    /// Après une annulation, les fichiers restants ne sont plus traités.
+    fn convert(&self, job: &Job) {
+        if self.is_cancelled() {
+            return;
+        }
+        self.notify(Some((&job.source, FileStatus::Running)));
+        let status = match self.try_convert(job) {
+            Ok(status) => status,
+            Err(error) => {
+                let status = FileStatus::Failed(error.to_string());
+                self.record_failure(&job.source, &job.output_dir, &job.output_stem, error);
+                status
+            }
+        };
+        match &status {
+            FileStatus::Done(size) => tracing::info!(source = %job.source.display(), size, "fichier converti"),
+            FileStatus::Skipped => tracing::info!(source = %job.source.display(), "fichier ignoré"),
+            FileStatus::Failed(error) => tracing::warn!(source = %job.source.display(), error = %error, "échec de la conversion"),
+            FileStatus::Running | FileStatus::Planned => {}
+        }
+        self.throughput.record(fs::metadata(&job.source).map_or(0, |metadata| metadata.len()));
+        self.completed.fetch_add(1, Ordering::Relaxed);
+        self.notify(Some((&job.source, status)));
+    }
+
+    /// Consigne l'échec d'un fichier dans le bilan.
+    fn record_failure(&self, input_path: &Path, output_dir: &Path, output_stem: &str, error: ConverterError) {
+        self.report.lock().unwrap().failures.push(FileFailure {
+            source: input_path.to_path_buf(),
+            output_dir: output_dir.to_path_buf(),
+            output_stem: output_stem.to_string(),
+            error,
+        });
+    }
+
+    /// Convertit un fichier, sauf si le manifeste indique qu'il est inchangé depuis la dernière conversion.
+    /// Retourne l'état final du fichier (écrit ou ignoré).
+    fn try_convert(&self, job: &Job) -> Result<FileStatus, ConverterError> {
+        if job.output_stem.is_empty() {
+            return Err(ConverterError::PathValidation(tr!("Nom de fichier invalide").to_string()));
+        }
+        if self.control.is_completed(&job.source) {
+            let mut report = self.report.lock().unwrap();
+            report.skipped_resumed += 1;
+            report.skipped_sources.push(job.source.clone());
+            return Ok(FileStatus::Skipped);
+        }
+
+        let signature = self.options.output_signature();
+        let hash = match &self.manifest {
+            Some(manifest) => {
+                // L'empreinte est calculée hors du verrou : seule la consultation du manifeste est exclusive.
+                let hash = manifest::hash_file(&job.source)?;
+                if manifest.lock().unwrap().is_up_to_date(&job.source, &hash, &signature) {
+                    let mut report = self.report.lock().unwrap();
+                    report.skipped_by_manifest += 1;
+                    report.skipped_sources.push(job.source.clone());
+                    if self.options.dry_run {
+                        report.planned.push(PlannedFile {
+                            source: job.source.clone(),
+                            output: None,
+                            action: PlannedAction::Skip(tr!("inchangé depuis la dernière conversion").to_string()),
+                        });
+                    }
+                    return Ok(FileStatus::Skipped);
+                }
+                Some(hash)
+            }
+            None => None,
+        };
+
+        if self.options.favicon {
+            return self.convert_favicon(job, hash.map(|hash| (hash, signature)));
+        }
+        if pdf::is_pdf(&job.source) {
+            return self.convert_pdf(job, hash.map(|hash| (hash, signature)));
+        }
+        if self.options.frames.extract && animation::is_animated(&job.source) {
+            return self.convert_frames(job, hash.map(|hash| (hash, signature)));
+        }
+
+        // Pour un jeu d'images responsives, les largeurs produites dépendent de celle de la source
+        // (redressée, tournée et recadrée ; lue dans l'en-tête en simulation, sans décoder l'image).
+        let mut source = SourceCache::default();
+        let destinations: Cow<[Destination]> = match self.options.responsive.is_active() {
+            true => {
+                let (width, height) = match self.options.dry_run {
+                    true => image::image_dimensions(&job.source).unwrap_or((0, 0)),
+                    false => source.dimensions(&job.source, self.options)?,
+                };
+                let (width, height) = self.options.rotation.rotated_size(width, height);
+                let (source_width, _) = self.options.crop.cropped_size(width, height);
+                Cow::Owned(responsive::expand(&self.destinations, &self.options.responsive.widths_for(source_width)))
+            }
+            false => Cow::Borrowed(&self.destinations),
+        };
+
+        if self.options.dry_run {
+            let mut status = FileStatus::Skipped;
+            let mut report = self.report.lock().unwrap();
+            for destination in destinations.iter() {
+                let output_dir = destination.output_dir(&self.output_root, &job.output_dir);
+                let output_stem = format!("{}{}", job.output_stem, destination.name_suffix);
+                let (output, action) = plan_output(&job.source, &output_dir, &output_stem, &destination.options);
+                if !matches!(action, PlannedAction::Skip(_)) {
+                    status = FileStatus::Planned;
+                }
+                report.planned.push(PlannedFile { source: job.source.clone(), output: Some(output), action });
+            }
+            return Ok(status);
+        }
+
+        // La source est décodée une fois, puis produite pour chaque destination ; la planche contact
+        // reçoit l'image de la première.
+        source.reserve(destinations.len());
+        let mut written = Vec::new();
+        let mut skipped = None;
+        for (index, destination) in destinations.iter().enumerate() {
+            let output_dir = destination.output_dir(&self.output_root, &job.output_dir);
+            if self.options.targets.is_active() {
+                create_output_dir(&output_dir, self.options).map_err(|e| {
+                    ConverterError::io(tr!("Impossible de créer le répertoire de sortie {}", output_dir.display()), e)
+                })?;
+            }
+            match convert_image_internal(
+                &job.source,
+                &output_dir,
+                &format!("{}{}", job.output_stem, destination.name_suffix),
+                &destination.options,
+                self.contact_sheet.as_ref().filter(|_| index == 0),
+                self.control,
+                &mut source,
+            )? {
+                Outcome::Written(file_report) => {
+                    self.add_to_generators(index, &file_report);
+                    written.push((file_report, destination.width));
+                }
+                outcome => skipped = skipped.or(Some(outcome)),
+            }
+        }
+
+        Ok(self.record_outputs(job, hash.map(|hash| (hash, signature)), written, skipped))
+    }
+
+    /// Document PDF : chaque page sélectionnée est rendue (voir `pdf::PdfDocument`) puis produite pour chaque destination,
+    /// sous le nom `{nom}-p{page}` (voir `convert_sequence`).
+    /// `manifest_entry` est l'empreinte de la source et la signature des options, en mode incrémental.
+    fn convert_pdf(&self, job: &Job, manifest_entry: Option<(String, String)>) -> Result<FileStatus, ConverterError> {
+        let document = pdf::PdfDocument::open(&job.source)?;
+        let pages = document.selected_pages(&self.options.pdf)?;
+        self.convert_sequence(job, manifest_entry, &pages, pdf::page_suffix, |page| {
+            document.render_page(page, self.options.pdf.dpi)
+        })
+    }
+
+    /// Animation en mode « extraire les images » : chaque image sélectionnée (voir `animation::FrameOptions`) est produite
+    /// pour chaque destination, sous le nom `{nom}_0001` (voir `convert_sequence`). Toute l'animation est décodée
+    /// d'abord, son nombre d'images n'étant connu qu'à la fin.
+    fn convert_frames(&self, job: &Job, manifest_entry: Option<(String, String)>) -> Result<FileStatus, ConverterError> {
+        let mut frames: Vec<Option<DynamicImage>> = animation::decode_frames(&job.source)?.into_iter().map(|frame| Some(frame.image)).collect();
+        let selected = self.options.frames.selected_frames(frames.len())?;
+        if selected.is_empty() {
+            let error = ImageError::Decoding(DecodingError::new(
+                ImageFormatHint::PathExtension(job.source.extension().unwrap_or_default().into()),
+                tr!("Aucune image sélectionnée dans cette animation"),
+            ));
+            return Err(ConverterError::decode(&job.source, error));
+        }
+        self.convert_sequence(job, manifest_entry, &selected, animation::frame_suffix, |frame| {
+            Ok(frames[frame - 1].take().unwrap_or_default())
+        })
+    }
+
+    /// Produit pour chaque destination les images `items` d'une source qui en contient plusieurs (pages, images d'une
+    /// animation), rendues une à une par `render` et nommées `{nom}{suffix(item)}`. Les jeux d'images responsives ne
+    /// s'y appliquent pas, et une image n'est jamais comparée au poids de la source (le mode des sorties plus lourdes
+    /// est ignoré).
+    fn convert_sequence(
+        &self,
+        job: &Job,
+        manifest_entry: Option<(String, String)>,
+        items: &[usize],
+        suffix: fn(usize) -> String,
+        mut render: impl FnMut(usize) -> Result<DynamicImage, ConverterError>,
+    ) -> Result<FileStatus, ConverterError> {
+        let destinations: Vec<Destination> = self
+            .destinations
+            .iter()
+            .map(|destination| Destination {
+                options: ConversionOptions { larger_output: LargerOutputMode::Keep, ..destination.options.clone() },
+                ..destination.clone()
+            })
+            .collect();
+        let output_stem =
+            |item: usize, destination: &Destination| format!("{}{}{}", job.output_stem, suffix(item), destination.name_suffix);
+
+        if self.options.dry_run {
+            let mut status = FileStatus::Skipped;
+            let mut report = self.report.lock().unwrap();
+            for &item in items {
+                for destination in &destinations {
+                    let output_dir = destination.output_dir(&self.output_root, &job.output_dir);
+                    let (output, action) =
+                        plan_output(&job.source, &output_dir, &output_stem(item, destination), &destination.options);
+                    if !matches!(action, PlannedAction::Skip(_)) {
+                        status = FileStatus::Planned;
+                    }
+                    report.planned.push(PlannedFile { source: job.source.clone(), output: Some(output), action });
+                }
+            }
+            return Ok(status);
+        }
+
+        // Chaque image est rendue une fois pour toutes les destinations ; la planche contact reçoit celle de la première.
+        let mut written = Vec::new();
+        let mut skipped = None;
+        for &item in items {
+            if self.is_cancelled() {
+                break;
+            }
+            let mut source = SourceCache::with_image(render(item)?);
+            source.reserve(destinations.len());
+            for (index, destination) in destinations.iter().enumerate() {
+                let output_dir = destination.output_dir(&self.output_root, &job.output_dir);
+                if self.options.targets.is_active() {
+                    create_output_dir(&output_dir, self.options).map_err(|e| {
+                        ConverterError::io(tr!("Impossible de créer le répertoire de sortie {}", output_dir.display()), e)
+                    })?;
+                }
+                match convert_image_internal(
+                    &job.source,
+                    &output_dir,
+                    &output_stem(item, destination),
+                    &destination.options,
+                    self.contact_sheet.as_ref().filter(|_| index == 0),
+                    self.control,
+                    &mut source,
+                )? {
+                    Outcome::Written(file_report) => {
+                        self.add_to_generators(index, &file_report);
+                        written.push((file_report, None));
+                    }
+                    outcome => skipped = skipped.or(Some(outcome)),
+                }
+            }
+        }
+        Ok(self.record_outputs(job, manifest_entry, written, skipped))
+    }
+
+    /// Ajoute au document PDF et à la planche de sprites le fichier produit pour la destination `index` :
+    /// seule la première destination y figure.
+    fn add_to_generators(&self, index: usize, file_report: &FileReport) {
+        if index != 0 {
+            return;
+        }
+        if let Some(pdf_bundle) = &self.pdf_bundle {
+            pdf_bundle.lock().unwrap().add(&file_report.source, &file_report.output);
+        }
+        if let Some(sprite_sheet) = &self.sprite_sheet {
+            sprite_sheet.lock().unwrap().add(&file_report.output);
+        }
+    }
+
+    /// Consigne dans le bilan les fichiers produits pour `job` (avec la largeur visée de chacun), ou la raison de son
+    /// saut s'il n'en a produit aucun (`skipped`), puis inscrit la source au manifeste, traite l'original et écrit les
+    /// extraits `srcset`. `manifest_entry` est l'empreinte de la source et la signature des options, en mode incrémental.
+    fn record_outputs(
+        &self,
+        job: &Job,
+        manifest_entry: Option<(String, String)>,
+        written: Vec<(FileReport, Option<u32>)>,
+        skipped: Option<Outcome>,
+    ) -> FileStatus {
+        let Some((first, _)) = written.first() else {
+            let mut report = self.report.lock().unwrap();
+            match skipped {
+                Some(Outcome::SkippedLarger) => report.skipped_larger += 1,
+                _ => report.skipped_existing += 1,
+            }
+            report.skipped_sources.push(job.source.clone());
+            return FileStatus::Skipped;
+        };
+        if let (Some(manifest), Some((hash, signature))) = (&self.manifest, manifest_entry) {
+            manifest.lock().unwrap().record(&job.source, hash, signature, first.output.clone());
+        }
+        let relative_dir = job.output_dir.strip_prefix(&self.output_root).unwrap_or(Path::new(""));
+        // Une sortie dont la vérification échoue ne remplace pas sa source : celle-ci reste en place.
+        let original = match written.iter().any(|(file_report, _)| file_report.verification.is_failed()) {
+            true if self.options.originals != OriginalsMode::Keep => {
+                OriginalOutcome::Failed(tr!("vérification du fichier produit en échec").to_string())
+            }
+            _ => originals::dispose(
+                self.options.originals,
+                &job.source,
+                &first.output,
+                first.output_size,
+                &self.output_root,
+                relative_dir,
+            ),
+        };
+        let status = FileStatus::Done(written.iter().map(|(file_report, _)| file_report.output_size).sum());
+        let snippets = match self.options.responsive.is_active() && self.options.responsive.write_srcset {
+            true => self.write_srcset_snippets(&job.output_stem, &written),
+            false => Vec::new(),
+        };
+        let mut report = self.report.lock().unwrap();
+        for (mut file_report, _) in written {
+            file_report.original = original.clone();
+            report.files.push(file_report);
+        }
+        for snippet in snippets {
+            match snippet {
+                Ok(path) => report.srcset_snippets.push(path),
+                Err(error) => report.warnings.push(error.to_string()),
+            }
+        }
+        status
+    }
+
+    /// Mode favicon : produit dans le dossier `{nom}-favicon` un `favicon.ico` multi-résolution et les PNG usuels
+    /// (voir `write_favicon`), à la place de la sortie habituelle. Le dossier suit le mode d'écrasement d'après son
+    /// `favicon.ico` : conservé, remplacé, ou doublé d'un nouveau dossier numéroté (Renommer).
+    /// `manifest_entry` est l'empreinte de la source et la signature des options, en mode incrémental.
+    fn convert_favicon(&self, job: &Job, manifest_entry: Option<(String, String)>) -> Result<FileStatus, ConverterError> {
+        let folder = format!("{}-favicon", job.output_stem);
+        let mut output_dir = job.output_dir.join(&folder);
+        let ico_path = output_dir.join(FAVICON_ICO_NAME);
+        let source_modified = fs::metadata(&job.source).and_then(|metadata| metadata.modified()).ok();
+
+        if self.options.dry_run {
+            let action = match ico_path.exists() {
+                true => planned_action(&ico_path, source_modified, self.options.overwrite_mode),
+                false => PlannedAction::Convert,
+            };
+            let status = match action {
+                PlannedAction::Skip(_) => FileStatus::Skipped,
+                _ => FileStatus::Planned,
+            };
+            let mut report = self.report.lock().unwrap();
+            report.planned.push(PlannedFile { source: job.source.clone(), output: Some(ico_path), action });
+            return Ok(status);
+        }
+
+        let mut overwrite_mode = self.options.overwrite_mode;
+        if overwrite_mode == OverwriteMode::Ask && ico_path.exists() {
+            overwrite_mode = self.control.ask_conflict(&ico_path).overwrite_mode();
+        }
+        if overwrite_mode.keeps_existing(&ico_path, source_modified) {
+            let mut report = self.report.lock().unwrap();
+            report.skipped_existing += 1;
+            report.skipped_sources.push(job.source.clone());
+            return Ok(FileStatus::Skipped);
+        }
+        if overwrite_mode == OverwriteMode::Rename {
             let mut counter = 1;
-            while output_full_path.exists() {
-                output_file_name = format!("{}-{}.webp", image_name.to_string_lossy(), counter);
-                output_full_path = output_dir.join(&output_file_name);
+            while output_dir.exists() {
+                output_dir = job.output_dir.join(format!("{}-{}", folder, counter));
+                counter += 1;
+            }
+        }
+
+        // Les icônes sont en sRGB et redimensionnées ici : le redimensionnement des options ne s'applique pas.
+        let options = ConversionOptions {
+            resize: ResizeOptions::default(),
+            color_profile: match self.options.color_profile {
+                ColorProfileMode::Ignore => ColorProfileMode::Ignore,
+                _ => ColorProfileMode::ConvertToSrgb,
+            },
+            ..self.options.clone()
+        };
+        let (img, _) = decode_source(&job.source, &options)?;
+        let img = transform::process(img, &options);
+        fs::create_dir_all(&output_dir)
+            .map_err(|e| ConverterError::io(tr!("Impossible de créer le répertoire de sortie {}", output_dir.display()), e))?;
+        let files = write_favicon(&img, &output_dir)?;
+
+        let (ico_path, ico_size, _) = &files[0];
+        if let (Some(manifest), Some((hash, signature))) = (&self.manifest, manifest_entry) {
+            manifest.lock().unwrap().record(&job.source, hash, signature, ico_path.clone());
+        }
+        let relative_dir = job.output_dir.strip_prefix(&self.output_root).unwrap_or(Path::new(""));
+        let original =
+            originals::dispose(self.options.originals, &job.source, ico_path, *ico_size, &self.output_root, relative_dir);
+        let input_size = fs::metadata(&job.source).map_or(0, |metadata| metadata.len());
+        let status = FileStatus::Done(files.iter().map(|(_, size, _)| size).sum());
+        let mut report = self.report.lock().unwrap();
+        for (output, output_size, replaced) in files {
+            report.files.push(FileReport {
+                source: job.source.clone(),
+                output,
+                input_size,
+                output_size,
+                kept_original: false,
+                format: OutputFormat::Png, // Les images du `favicon.ico` sont elles aussi des PNG.
+                candidate_sizes: Vec::new(),
+                palette_size: None,
+                original: original.clone(),
+                verification: Verification::NotChecked,
+                replaced,
+            });
+        }
+        Ok(status)
+    }
+
+    /// Écrit l'extrait `srcset` de chaque dossier où des largeurs de la source ont été produites
+    /// (un par destination, avec des destinations multiples).
+    fn write_srcset_snippets(
+        &self,
+        output_stem: &str,
+        written: &[(FileReport, Option<u32>)],
+    ) -> Vec<Result<PathBuf, ConverterError>> {
+        let mut groups: Vec<(PathBuf, Vec<(PathBuf, u32)>)> = Vec::new();
+        for (file_report, width) in written {
+            let (Some(width), Some(directory)) = (width, file_report.output.parent()) else {
+                continue;
+            };
+            match groups.iter_mut().find(|(group_dir, _)| group_dir == directory) {
+                Some((_, images)) => images.push((file_report.output.clone(), *width)),
+                None => groups.push((directory.to_path_buf(), vec![(file_report.output.clone(), *width)])),
+            }
+        }
+        groups.iter().map(|(_, images)| responsive::write_srcset(output_stem, images)).collect()
+    }
+
+    /// Enregistre le manifeste, termine la planche contact et retourne le bilan.
+    /// En cas d'annulation, seuls les fichiers déjà convertis y figurent.
+    /// Un échec de l'un ou de l'autre n'est qu'un avertissement : les conversions restent valides.
+    /// Les fichiers et les échecs sont triés par source, l'ordre de fin des threads n'étant pas déterministe.
+    /// Les avertissements sont consignés dans le journal de l'application.
+    fn finish(self) -> ConversionReport {
+        let mut report = self.report.into_inner().unwrap();
+        report.cancelled = self.control.is_cancelled();
+        if let Some(manifest) = self.manifest.filter(|_| !self.options.dry_run) {
+            if let Err(error) = manifest.into_inner().unwrap().save() {
+                report.warnings.push(error.to_string());
+            }
+        }
+        if let Some(contact_sheet) = self.contact_sheet {
+            let (pages, error) = contact_sheet.into_inner().unwrap().finish();
+            report.contact_sheets = pages;
+            if let Some(error) = error {
+                report.warnings.push(error);
+            }
+        }
+        if let Some(pdf_bundle) = self.pdf_bundle {
+            let (document, warnings) = pdf_bundle.into_inner().unwrap().finish();
+            report.pdf_bundle = document;
+            report.warnings.extend(warnings);
+        }
+        if let Some(sprite_sheet) = self.sprite_sheet {
+            let (files, warnings) = sprite_sheet.into_inner().unwrap().finish();
+            report.sprite_sheet = files;
+            report.warnings.extend(warnings);
+        }
+        if self.options.archive.enabled && !self.options.dry_run {
+            let files = report.produced_files();
+            let path = self.options.archive.path(&self.output_root);
+            if !files.is_empty() {
+                let packed = match open_archive(&path, self.options, self.control) {
+                    Some(Ok((file, path, partial))) => {
+                        archive::pack(&files, &self.output_root, file, &partial, &path).map(|()| Some(path))
+                    }
+                    Some(Err(error)) => Err(error),
+                    None => {
+                        report.warnings.push(tr!("L'archive {} existe déjà : les fichiers produits restent épars", path.display()));
+                        Ok(None)
+                    }
+                };
+                match packed {
+                    Ok(archive) => report.archive = archive,
+                    Err(error) => report.warnings.push(error.to_string()),
+                }
+            }
+        }
+        report.files.sort_by(|a, b| a.source.cmp(&b.source));
+        report.failures.sort_by(|a, b| a.source.cmp(&b.source));
+        report.skipped_sources.sort();
+        report.planned.sort_by(|a, b| a.source.cmp(&b.source));
+        for warning in &report.warnings {
+            tracing::warn!("{}", warning);
+        }
+        report
+    }
+}
+
+/// Crée le répertoire de sortie `dir`, sauf en simulation où rien n'est écrit.
+fn create_output_dir(dir: &Path, options: &ConversionOptions) -> std::io::Result<()> {
+    match options.dry_run {
+        true => Ok(()),
+        false => fs::create_dir_all(dir),
+    }
+}
+
+/// Indique si le fichier porte l'extension d'une image prise en charge.
+pub fn is_supported_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .map(|ext| SUPPORTED_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Estime la taille (en octets) du fichier qui serait produit pour `input_path` avec ces options.
+/// Encode une version réduite de l'image puis extrapole selon le rapport des surfaces.
+/// Retourne `None` pour les entrées non supportées, animées ou illisibles.
+pub fn estimate_output_size(input_path: &Path, options: &ConversionOptions) -> Option<u64> {
+    if !is_supported_image(input_path) || animation::is_animated(input_path) {
+        return None;
+    }
+
+    let (img, _) = decode_source(input_path, options).ok()?;
+    if img.width() == 0 || img.height() == 0 {
+        return None;
+    }
+
+    // L'échantillon est réduit directement depuis l'original tourné, rogné et recadré, sans calculer l'image redimensionnée complète.
+    let img = transform::apply_trim(transform::apply_rotation(img, options.rotation), &options.trim);
+    let img = transform::apply_crop(img, &options.crop);
+    let (width, height) = options.resize.target_size(img.width(), img.height());
+    let sample_size = ESTIMATE_SAMPLE_SIZE.min(width.max(height));
+    let sample = if img.width().max(img.height()) > sample_size {
+        img.thumbnail(sample_size, sample_size)
+    } else {
+        img
+    };
+    // Après la réduction, comme dans `transform::process` où les réglages suivent le redimensionnement.
+    let sample = transform::apply_adjustments(sample, &options.adjustments);
+
+    // En mode « format le plus léger », l'estimation porte sur le format qui l'emporte pour l'échantillon.
+    let sample_bytes = if options.smallest_format {
+        encode_smallest(&sample, None, options).ok()?.bytes.len()
+    } else {
+        let mut buffer = Cursor::new(Vec::new());
+        encoder::encode(&sample, &mut buffer, options).ok()?;
+        buffer.get_ref().len()
+    };
+
+    let area_ratio = (width as f64 * height as f64) / (sample.width() as f64 * sample.height() as f64);
+    Some((sample_bytes as f64 * area_ratio) as u64)
+}
+
+/// Décode `input_path` en miniature d'au plus `max_size` pixels de côté, redressée et convertie en sRGB
+/// comme pour une conversion (options actuelles d'orientation et de profil de couleur).
+pub fn decode_thumbnail(input_path: &Path, options: &ConversionOptions, max_size: u32) -> Result<DynamicImage, ConverterError> {
+    let options = ConversionOptions {
+        color_profile: match options.color_profile {
+            ColorProfileMode::Ignore => ColorProfileMode::Ignore,
+            _ => ColorProfileMode::ConvertToSrgb, // L'écran attend des pixels sRGB.
+        },
+        ..options.clone()
+    };
+    let (img, _) = decode_source(input_path, &options)?;
+    if img.width().max(img.height()) > max_size {
+        Ok(img.thumbnail(max_size, max_size))
+    } else {
+        Ok(img)
+    }
+}
+
+/// Encode `img` en mémoire avec les options de sortie, puis le décode : l'image obtenue montre l'effet
+/// de la qualité et des réglages d'image choisis (comparaison avant/après). Retourne aussi la taille encodée, en octets.
+/// Les sorties AVIF ne peuvent pas être relues, faute de décodeur AVIF.
+pub fn encode_round_trip(img: &DynamicImage, options: &ConversionOptions) -> Result<(DynamicImage, u64), ConverterError> {
+    let adjusted = transform::apply_adjustments(img.clone(), &options.adjustments);
+    let EncodedOutput { format, bytes, .. } = if options.smallest_format {
+        encode_smallest(&adjusted, None, options)?
+    } else {
+        let format = options.output_format;
+        let (bytes, encode_info) = encode_output(&adjusted, None, options, format)?;
+        EncodedOutput { format, bytes, encode_info, candidate_sizes: Vec::new() }
+    };
+    let decoded = image::load_from_memory(&bytes)
+        .map_err(|e| ConverterError::encode(format.label(), tr!("relecture de l'aperçu impossible : {}", e)))?;
+    Ok((decoded, bytes.len() as u64))
+}
+
+/// Retourne le nom du fichier sans extension, vide s'il est invalide.
+fn file_stem_lossy(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Chemin du fichier partiel de la sortie `output`, écrit avant d'être renommé (voir `commit_output`).
+fn partial_path(output: &Path) -> PathBuf {
+    let mut name = output.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(PARTIAL_EXTENSION);
+    output.with_file_name(name)
+}
+
+/// Crée le fichier partiel de la sortie `output`, en écrasant celui qu'une conversion interrompue aurait laissé.
+fn create_partial(output: &Path) -> Result<(fs::File, PathBuf, PathBuf), ConverterError> {
+    let partial = partial_path(output);
+    let file = fs::File::create(&partial)
+        .map_err(|e| ConverterError::io(tr!("Impossible de créer le fichier de sortie {}", output.display()), e))?;
+    Ok((file, output.to_path_buf(), partial))
+}
+
+/// Écrit `bytes` dans le fichier partiel `partial`, puis le renomme en `output` une fois les données sur le disque :
+/// une conversion interrompue (arrêt de l'application ou de la machine) ne laisse jamais de sortie tronquée,
+/// seulement un fichier `.part`. En cas d'échec, le fichier partiel est supprimé.
+fn commit_output(mut file: fs::File, partial: &Path, output: &Path, bytes: &[u8]) -> Result<(), ConverterError> {
+    let written = file.write_all(bytes).and_then(|_| file.sync_all());
+    drop(file); // Windows refuse de renommer un fichier encore ouvert.
+    let result = written
+        .map_err(|e| ConverterError::io(tr!("Impossible d'écrire l'image dans {}", output.display()), e))
+        .and_then(|_| {
+            fs::rename(partial, output)
+                .map_err(|e| ConverterError::io(tr!("Impossible de créer le fichier de sortie {}", output.display()), e))
+        });
+    if result.is_err() {
+        let _ = fs::remove_file(partial);
+    }
+    result
+}
+
+/// Ouvre le fichier partiel de l'archive `path`, une archive existante étant traitée comme une image de sortie
+/// existante (ignorer, écraser, renommer ou demander). Retourne `None` si l'archive existante est conservée.
+/// Ses fichiers venant d'être produits, elle n'est jamais plus récente qu'eux : le mode Si plus récent l'écrase.
+fn open_archive(
+    path: &Path,
+    options: &ConversionOptions,
+    control: &BatchControl,
+) -> Option<Result<(fs::File, PathBuf, PathBuf), ConverterError>> {
+    let mut overwrite_mode = options.overwrite_mode;
+    if overwrite_mode == OverwriteMode::Ask && path.exists() {
+        overwrite_mode = control.ask_conflict(path).overwrite_mode();
+    }
+    if overwrite_mode.keeps_existing(path, None) {
+        return None;
+    }
+    Some(match overwrite_mode {
+        OverwriteMode::Rename => {
+            let output_dir = path.parent().unwrap_or(Path::new(""));
+            create_renamed(output_dir, &file_stem_lossy(path), "zip", options.rename_suffix)
+        }
+        _ => create_partial(path),
+    })
+}
+
+/// Crée le fichier partiel de la sortie en mode Renommer, pour `nom.ext`, sinon `nom-1.ext`, `nom-2.ext`...
+/// Avec `RenameSuffix::Timestamp`, le nom pris est suivi de la date et de l'heure (`nom_2024-05-03_1432.ext`),
+/// puis d'un numéro si plusieurs conversions ont lieu dans la même minute (`nom_2024-05-03_1432-1.ext`).
+/// Un nom est libre si ni la sortie ni son fichier partiel n'existent ; la création exclusive du fichier partiel
+/// réserve le nom de façon atomique, même si plusieurs threads visent le même nom.
+/// Retourne le fichier partiel ouvert, le chemin de la sortie et celui du fichier partiel.
+fn create_renamed(
+    output_dir: &Path,
+    output_stem: &str,
+    extension: &str,
+    suffix: RenameSuffix,
+) -> Result<(fs::File, PathBuf, PathBuf), ConverterError> {
+    let mut output_full_path = output_dir.join(format!("{}.{}", output_stem, extension));
+    let renamed_stem = match suffix {
+        RenameSuffix::Counter => output_stem.to_string(),
+        RenameSuffix::Timestamp => format!("{}_{}", output_stem, chrono::Local::now().format(RENAME_TIMESTAMP_FORMAT)),
+    };
+    let mut counter = 0;
+    loop {
+        let partial = partial_path(&output_full_path);
+        let created = match output_full_path.exists() {
+            true => Err(std::io::Error::from(std::io::ErrorKind::AlreadyExists)),
+            false => fs::File::create_new(&partial),
+        };
+        match created {
+            Ok(file) => return Ok((file, output_full_path, partial)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
                 counter += 1;
+                output_full_path = match (suffix, counter) {
+                    (RenameSuffix::Timestamp, 1) => output_dir.join(format!("{}.{}", renamed_stem, extension)),
+                    (RenameSuffix::Timestamp, _) => output_dir.join(format!("{}-{}.{}", renamed_stem, counter - 1, extension)),
+                    (RenameSuffix::Counter, _) => output_dir.join(format!("{}-{}.{}", renamed_stem, counter, extension)),
+                };
+            }
+            Err(e) => {
+                return Err(ConverterError::io(tr!("Impossible de créer le fichier de sortie {}", output_full_path.display()), e));
+            }
+        }
+    }
+}
+
+/// Décode l'image source. Avec `options.auto_orient`, l'orientation EXIF (photos de téléphone...)
+/// est appliquée aux pixels, la sortie ne conservant pas la balise d'orientation.
+/// Le profil ICC de la source est traité selon `options.color_profile` : retourné s'il doit être
+/// intégré à la sortie, sinon les pixels sont convertis en sRGB (ou laissés tels quels en mode Ignorer).
+/// Les fichiers RAW sont développés par `raw::decode`, déjà redressés et en sRGB. D'un document PDF, seule la première
+/// page sélectionnée est rendue ici (aperçu, estimation, favicon) : la conversion rend chaque page (voir `Batch::convert_pdf`).
+fn decode_source(input_path: &Path, options: &ConversionOptions) -> Result<(DynamicImage, Option<Vec<u8>>), ConverterError> {
+    if raw::is_raw(input_path) {
+        return Ok((raw::decode(input_path)?, None));
+    }
+    if pdf::is_pdf(input_path) {
+        return Ok((pdf::render_first_page(input_path, &options.pdf)?, None));
+    }
+    let mut decoder = ImageReader::open(input_path)
+        .map_err(|e| ConverterError::io(tr!("Impossible d'ouvrir l'image {}", input_path.display()), e))?
+        .into_decoder()
+        .map_err(|e| ConverterError::decode(input_path, e))?;
+    // Une balise d'orientation ou un profil illisible ne doit pas empêcher la conversion.
+    let orientation = match options.auto_orient {
+        true => decoder.orientation().unwrap_or(Orientation::NoTransforms),
+        false => Orientation::NoTransforms,
+    };
+    let icc_profile = match options.color_profile {
+        ColorProfileMode::Ignore => None,
+        _ => decoder.icc_profile().ok().flatten(),
+    };
+    let mut img = DynamicImage::from_decoder(decoder).map_err(|e| ConverterError::decode(input_path, e))?;
+    img.apply_orientation(orientation);
+
+    match icc_profile {
+        Some(icc_profile) if embeds_icc(options) => Ok((img, Some(icc_profile))),
+        Some(icc_profile) => Ok((color_profile::convert_to_srgb(img, &icc_profile), None)),
+        None => Ok((img, None)),
+    }
+}
+
+/// Indique si le profil ICC de la source est conservé pour être intégré à la sortie. Les formats qui ne peuvent pas
+/// le porter reçoivent des pixels convertis en sRGB (en mode « format le plus léger », lors de l'encodage de chaque format candidat).
+fn embeds_icc(options: &ConversionOptions) -> bool {
+    options.color_profile == ColorProfileMode::Embed && (options.smallest_format || metadata::supports_icc(options.output_format))
+}
+
+/// Encode `img` au format `format` (options de ce format), puis retire les métadonnées ou intègre le profil ICC.
+/// Un profil que le format ne peut pas porter est appliqué aux pixels (conversion en sRGB).
+fn encode_output(
+    img: &DynamicImage,
+    icc_profile: Option<&[u8]>,
+    options: &ConversionOptions,
+    format: OutputFormat,
+) -> Result<(Vec<u8>, encoder::EncodeInfo), ConverterError> {
+    let format_options;
+    let options = if format == options.output_format {
+        options
+    } else {
+        format_options = ConversionOptions { output_format: format, ..options.clone() };
+        &format_options
+    };
+    let converted;
+    let (img, icc_profile) = match icc_profile {
+        Some(icc_profile) if !metadata::supports_icc(format) => {
+            converted = color_profile::convert_to_srgb(img.clone(), icc_profile);
+            (&converted, None)
+        }
+        _ => (img, icc_profile),
+    };
+
+    let mut buffer = Cursor::new(Vec::new());
+    let encode_info = encoder::encode(img, &mut buffer, options)?;
+    Ok((finish_metadata(buffer.into_inner(), icc_profile, options, format)?, encode_info))
+}
+
+/// Retire les métadonnées du fichier encodé `bytes`, ou y intègre le profil ICC. La suppression des métadonnées
+/// l'emporte sur l'intégration du profil.
+fn finish_metadata(
+    bytes: Vec<u8>,
+    icc_profile: Option<&[u8]>,
+    options: &ConversionOptions,
+    format: OutputFormat,
+) -> Result<Vec<u8>, ConverterError> {
+    match icc_profile {
+        _ if options.strip_metadata => metadata::strip(bytes, format),
+        Some(icc_profile) => metadata::embed_icc(bytes, format, icc_profile),
+        None => Ok(bytes),
+    }
+}
+
+/// Applique à une image d'animation les traitements de la première, dont les dimensions produites sont `dimensions` :
+/// une image rognée différemment est mise à ces dimensions, toutes les images d'une animation ayant la même taille.
+fn process_frame(frame: &DynamicImage, dimensions: (u32, u32), options: &ConversionOptions) -> DynamicImage {
+    let frame = transform::process(frame.clone(), options);
+    match (frame.width(), frame.height()) == dimensions {
+        true => frame,
+        false => frame.resize_exact(dimensions.0, dimensions.1, image::imageops::FilterType::Lanczos3),
+    }
+}
+
+/// Indique si le modèle de nom contient `{width}` ou `{height}`, connues seulement après le décodage.
+fn name_template_uses_dimensions(template: &str) -> bool {
+    template.contains("{width}") || template.contains("{height}")
+}
+
+/// Calcule le nom (sans extension) d'un fichier produit à partir du modèle `template` :
+/// `{stem}` (nom de la source, après départage des doublons), `{width}` et `{height}` (dimensions produites),
+/// `{quality}` (qualité d'encodage ou `lossless`) et `{format}` (extension). Les caractères interdits dans un
+/// nom de fichier sont remplacés par `_` ; un modèle vide (ou un résultat vide) donne le nom de la source.
+pub fn render_name_template(
+    template: &str,
+    stem: &str,
+    format: OutputFormat,
+    dimensions: (u32, u32),
+    options: &ConversionOptions,
+) -> String {
+    let template = template.trim();
+    if template.is_empty() || template == DEFAULT_NAME_TEMPLATE {
+        return stem.to_string();
+    }
+
+    let quality = match format {
+        OutputFormat::WebP if options.webp.lossless => "lossless".to_string(),
+        OutputFormat::WebP => options.webp.quality.to_string(),
+        OutputFormat::Jpeg => options.jpeg.quality.to_string(),
+        OutputFormat::Avif => options.avif.quality.to_string(),
+        OutputFormat::Png if options.png.quantize => format!("{}c", options.png.max_colors),
+        OutputFormat::Png | OutputFormat::Tiff => "lossless".to_string(),
+    };
+    let name: String = template
+        .replace("{stem}", stem)
+        .replace("{width}", &dimensions.0.to_string())
+        .replace("{height}", &dimensions.1.to_string())
+        .replace("{quality}", &quality)
+        .replace("{format}", format.extension())
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect();
+    // Un nom sans extension ne doit pas se terminer par un point ou un espace (refusé par Windows).
+    let name = name.trim_end_matches(['.', ' ']);
+    if name.is_empty() { stem.to_string() } else { name.to_string() }
+}
+
+/// Fichier encodé en mémoire, prêt à être écrit.
+struct EncodedOutput {
+    format: OutputFormat,                      // Format retenu.
+    bytes: Vec<u8>,                            // Contenu du fichier.
+    encode_info: encoder::EncodeInfo,          // Informations de l'encodeur (palette PNG).
+    candidate_sizes: Vec<(OutputFormat, u64)>, // Tailles des formats essayés (mode « format le plus léger »).
+}
+
+/// Encode `img` dans chacun des formats candidats (`OutputFormat::SMALLEST_CANDIDATES`, sans le JPEG pour
+/// une image transparente) et retourne le plus léger, avec la taille obtenue pour chaque candidat.
+/// Un format en échec est écarté ; l'erreur n'est retournée que si aucun n'a abouti.
+fn encode_smallest(
+    img: &DynamicImage,
+    icc_profile: Option<&[u8]>,
+    options: &ConversionOptions,
+) -> Result<EncodedOutput, ConverterError> {
+    let mut best: Option<EncodedOutput> = None;
+    let mut candidate_sizes = Vec::new();
+    let mut last_error = None;
+    for format in OutputFormat::SMALLEST_CANDIDATES {
+        if format == OutputFormat::Jpeg && img.color().has_alpha() {
+            continue;
+        }
+        match encode_output(img, icc_profile, options, format) {
+            Ok((bytes, encode_info)) => {
+                candidate_sizes.push((format, bytes.len() as u64));
+                if best.as_ref().is_none_or(|best| bytes.len() < best.bytes.len()) {
+                    best = Some(EncodedOutput { format, bytes, encode_info, candidate_sizes: Vec::new() });
+                }
             }
+            Err(error) => last_error = Some(error),
         }
-        OverwriteMode::Overwrite => {
-            // Pas d'action spécifique, le fichier sera écrasé par défaut
+    }
+    match (best, last_error) {
+        (Some(best), _) => Ok(EncodedOutput { candidate_sizes, ..best }),
+        (None, Some(error)) => Err(error),
+        (None, None) => Err(ConverterError::encode(tr!("format le plus léger"), tr!("aucun format candidat"))),
+    }
+}
+
+/// Prévoit, sans décoder ni écrire, la sortie de `input_path` et ce qu'en ferait une conversion réelle
+/// selon le mode d'écrasement (simulation). Les dimensions d'un modèle de nom sont lues dans l'en-tête.
+/// Le traitement des sorties plus lourdes n'est pas prévu : il dépend du résultat de l'encodage.
+fn plan_output(input_path: &Path, output_dir: &Path, output_stem: &str, options: &ConversionOptions) -> (PathBuf, PlannedAction) {
+    let possible_formats: &[OutputFormat] = match options.smallest_format {
+        true => &OutputFormat::SMALLEST_CANDIDATES,
+        false => std::slice::from_ref(&options.output_format),
+    };
+    let dimensions = match name_template_uses_dimensions(&options.name_template) {
+        true => image::image_dimensions(input_path).map_or((0, 0), |(width, height)| transform::output_size(options, width, height)),
+        false => (0, 0),
+    };
+    let outputs: Vec<PathBuf> = possible_formats
+        .iter()
+        .map(|format| {
+            let name = render_name_template(&options.name_template, output_stem, *format, dimensions, options);
+            output_dir.join(format!("{}.{}", name, format.extension()))
+        })
+        .collect();
+    let Some(existing) = outputs.iter().find(|path| path.exists()) else {
+        return (outputs[0].clone(), PlannedAction::Convert);
+    };
+
+    let source_modified = fs::metadata(input_path).and_then(|metadata| metadata.modified()).ok();
+    (existing.clone(), planned_action(existing, source_modified, options.overwrite_mode))
+}
+
+/// Action prévue (simulation) pour la sortie existante `existing` d'une source modifiée à `source_modified`.
+fn planned_action(existing: &Path, source_modified: Option<SystemTime>, overwrite_mode: OverwriteMode) -> PlannedAction {
+    match overwrite_mode {
+        mode if mode.keeps_existing(existing, source_modified) => match mode {
+            OverwriteMode::IfNewer => PlannedAction::Skip(tr!("sortie plus récente que la source").to_string()),
+            _ => PlannedAction::Skip(tr!("sortie existante").to_string()),
+        },
+        OverwriteMode::Rename => PlannedAction::Rename,
+        OverwriteMode::Ask => PlannedAction::Ask,
+        _ => PlannedAction::Overwrite,
+    }
+}
+
+/// Nom du fichier d'icônes produit en mode favicon.
+const FAVICON_ICO_NAME: &str = "favicon.ico";
+
+/// Côtés (pixels) des images du `favicon.ico`.
+const FAVICON_ICO_SIZES: [u32; 4] = [16, 32, 48, 256];
+
+/// PNG produits à côté du `favicon.ico` : nom et côté (pixels), pour les navigateurs, iOS et Android.
+const FAVICON_PNG_FILES: [(&str, u32); 5] = [
+    ("favicon-16x16.png", 16),
+    ("favicon-32x32.png", 32),
+    ("apple-touch-icon.png", 180),
+    ("android-chrome-192x192.png", 192),
+    ("android-chrome-512x512.png", 512),
+];
+
+/// Écrit dans `output_dir` le `favicon.ico` (16, 32, 48 et 256 px) et les PNG usuels de `img`, chacun carré :
+/// une image non carrée est centrée sur un fond transparent. Retourne chaque fichier écrit, l'icône en premier,
+/// avec sa taille et s'il en a remplacé un existant.
+fn write_favicon(img: &DynamicImage, output_dir: &Path) -> Result<Vec<(PathBuf, u64, bool)>, ConverterError> {
+    let mut outputs = Vec::with_capacity(1 + FAVICON_PNG_FILES.len());
+
+    let icons: Vec<image::RgbaImage> = FAVICON_ICO_SIZES.iter().map(|&size| favicon_square(img, size)).collect();
+    let frames = icons
+        .iter()
+        .map(|icon| image::codecs::ico::IcoFrame::as_png(icon.as_raw(), icon.width(), icon.height(), image::ExtendedColorType::Rgba8))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ConverterError::encode("ICO", e))?;
+    let mut ico = Vec::new();
+    image::codecs::ico::IcoEncoder::new(&mut ico)
+        .encode_images(&frames)
+        .map_err(|e| ConverterError::encode("ICO", e))?;
+    outputs.push((output_dir.join(FAVICON_ICO_NAME), ico));
+
+    for (name, size) in FAVICON_PNG_FILES {
+        let mut png = Cursor::new(Vec::new());
+        favicon_square(img, size)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .map_err(|e| ConverterError::encode("PNG", e))?;
+        outputs.push((output_dir.join(name), png.into_inner()));
+    }
+
+    let mut written = Vec::with_capacity(outputs.len());
+    for (path, bytes) in outputs {
+        let (file, path, partial) = create_partial(&path)?;
+        let replaced = path.exists();
+        commit_output(file, &partial, &path, &bytes)?;
+        written.push((path, bytes.len() as u64, replaced));
+    }
+    Ok(written)
+}
+
+/// Réduit `img` pour tenir dans un carré de `size` pixels, centrée sur un fond transparent.
+fn favicon_square(img: &DynamicImage, size: u32) -> image::RgbaImage {
+    let resized = img.resize(size, size, image::imageops::FilterType::Lanczos3).to_rgba8();
+    let mut square = image::RgbaImage::new(size, size);
+    let x = (size - resized.width()) / 2;
+    let y = (size - resized.height()) / 2;
+    image::imageops::overlay(&mut square, &resized, x as i64, y as i64);
+    square
+}
+
+/// Issue de la conversion d'un fichier.
+enum Outcome {
+    Written(FileReport), // Fichier écrit (sortie convertie ou copie de la source).
+    SkippedExisting,     // Ignoré car la sortie existe déjà (mode Ignorer, ou réponse Ignorer en mode Demander).
+    SkippedLarger,       // Ignoré car la sortie aurait été plus lourde que la source.
+}
+
+/// Image source décodée, avec le traitement de son profil ICC.
+#[derive(Clone)]
+struct DecodedSource {
+    embeds_icc: bool,              // Vrai si le profil est conservé pour être intégré à la sortie.
+    img: DynamicImage,             // Image décodée (et redressée), avant le redimensionnement.
+    icc_profile: Option<Vec<u8>>, // Profil à intégrer à la sortie.
+}
+
+/// Partage le décodage d'une source entre ses destinations : chaque destination en reçoit une copie,
+/// la dernière l'original. La source n'est décodée à nouveau que si le traitement du profil diffère.
+#[derive(Default)]
+struct SourceCache {
+    cached: Option<DecodedSource>, // Dernière image décodée.
+    remaining: usize,              // Destinations restant à servir.
+    frames: Option<Option<Vec<AnimatedFrame>>>, // Images de l'animation (`Some(None)` : source fixe ; `None` : pas encore lue).
+}
+
+impl SourceCache {
+    /// Cache déjà rempli avec `img`, image fixe sans profil ICC (ex: page d'un document PDF rendue).
+    fn with_image(img: DynamicImage) -> Self {
+        Self { cached: Some(DecodedSource { embeds_icc: true, img, icc_profile: None }), remaining: 0, frames: Some(None) }
+    }
+
+    /// Images de l'animation `input_path`, décodées au premier appel et conservées pour les destinations suivantes.
+    /// `None` si la source est une image fixe.
+    fn frames(&mut self, input_path: &Path) -> Result<Option<&[AnimatedFrame]>, ConverterError> {
+        if self.frames.is_none() {
+            let frames = match animation::is_animated(input_path) {
+                true => Some(animation::decode_frames(input_path)?),
+                false => None,
+            };
+            self.frames = Some(frames);
+        }
+        Ok(self.frames.as_ref().and_then(|frames| frames.as_deref()))
+    }
+
+    /// Prévoit le nombre d'appels à `get` : le dernier reçoit l'image sans copie.
+    fn reserve(&mut self, destinations: usize) {
+        self.remaining = destinations;
+    }
+
+    /// Dimensions de l'image décodée (et redressée) de `input_path`, conservée pour les appels suivants.
+    fn dimensions(&mut self, input_path: &Path, options: &ConversionOptions) -> Result<(u32, u32), ConverterError> {
+        let decoded = self.decode(input_path, options)?;
+        let dimensions = (decoded.img.width(), decoded.img.height());
+        self.cached = Some(decoded);
+        Ok(dimensions)
+    }
+
+    /// Image décodée de `input_path` pour une conversion avec `options`.
+    fn get(&mut self, input_path: &Path, options: &ConversionOptions) -> Result<(DynamicImage, Option<Vec<u8>>), ConverterError> {
+        self.remaining = self.remaining.saturating_sub(1);
+        let decoded = self.decode(input_path, options)?;
+        if self.remaining > 0 {
+            self.cached = Some(decoded.clone());
+        }
+        Ok((decoded.img, decoded.icc_profile))
+    }
+
+    /// Image en cache si son traitement du profil convient à `options`, sinon nouveau décodage.
+    /// Une image décodée en conservant son profil et qui n'en a pas convient à tous les traitements.
+    fn decode(&mut self, input_path: &Path, options: &ConversionOptions) -> Result<DecodedSource, ConverterError> {
+        let embeds_icc = embeds_icc(options);
+        match self.cached.take() {
+            Some(cached) if cached.embeds_icc == embeds_icc || (cached.embeds_icc && cached.icc_profile.is_none()) => Ok(cached),
+            _ => {
+                let (img, icc_profile) = decode_source(input_path, options)?;
+                Ok(DecodedSource { embeds_icc, img, icc_profile })
+            }
         }
     }
+}
 
-    let img = ImageReader::open(input_path)
-        .map_err(|e| format!("Impossible d'ouvrir l'image {}: {}", input_path.display(), e))?
-        .decode()
-        .map_err(|e| format!("Impossible de décoder l'image {}: {}", input_path.display(), e))?;
+/// Fonction interne pour la logique de conversion unique, incluant la gestion du mode d'écrasement.
+/// `output_stem` est le nom (sans extension) du fichier à produire.
+/// Une sortie plus lourde que la source est traitée selon `options.larger_output`.
+/// En mode Demander, une sortie existante est soumise à l'utilisateur via `control`, dont la réponse fixe le mode du fichier.
+fn convert_image_internal(
+    input_path: &Path,
+    output_dir: &Path,
+    output_stem: &str,
+    options: &ConversionOptions,
+    contact_sheet: Option<&Mutex<ContactSheetBuilder>>,
+    control: &BatchControl,
+    source: &mut SourceCache,
+) -> Result<Outcome, ConverterError> {
+    // En mode « format le plus léger », une sortie existante dans l'un des formats candidats suffit à ignorer la source.
+    let possible_formats: &[OutputFormat] = match options.smallest_format {
+        true => &OutputFormat::SMALLEST_CANDIDATES,
+        false => std::slice::from_ref(&options.output_format),
+    };
+    let source_modified = fs::metadata(input_path).and_then(|metadata| metadata.modified()).ok();
+    let mut overwrite_mode = options.overwrite_mode;
+    let mut keeps_existing = |output: &Path| {
+        if overwrite_mode == OverwriteMode::Ask && output.exists() {
+            overwrite_mode = control.ask_conflict(output).overwrite_mode();
+        }
+        overwrite_mode.keeps_existing(output, source_modified)
+    };
+    let mut skip_existing = |dimensions: (u32, u32)| {
+        let existing = possible_formats
+            .iter()
+            .map(|format| {
+                let name = render_name_template(&options.name_template, output_stem, *format, dimensions, options);
+                output_dir.join(format!("{}.{}", name, format.extension()))
+            })
+            .find(|path| keeps_existing(path));
+        if let Some(existing) = &existing {
+            tracing::info!(existing = %existing.display(), "sortie existante conservée");
+        }
+        existing.is_some()
+    };
+    // Si le nom dépend des dimensions produites, la vérification attend le décodage de l'image.
+    let needs_dimensions = name_template_uses_dimensions(&options.name_template);
+    if !needs_dimensions && skip_existing((0, 0)) {
+        return Ok(Outcome::SkippedExisting); // Ne rien faire si le fichier existe déjà
+    }
 
-    let file = fs::File::create(&output_full_path)
-        .map_err(|e| format!("Impossible de créer le fichier de sortie {}: {}", output_full_path.display(), e))?;
-    let mut writer = BufWriter::new(file);
+    let (img, icc_profile) = source.get(input_path, options)?;
+    let img = transform::process(img, options);
+    let dimensions = (img.width(), img.height());
+    if needs_dimensions && skip_existing(dimensions) {
+        return Ok(Outcome::SkippedExisting);
+    }
+    // Une animation conservée l'est en WebP ou en PNG animé : chacune de ses images suit le même traitement que la première.
+    let animated_format = matches!(options.output_format, OutputFormat::WebP | OutputFormat::Png);
+    let frames = match options.animation.keep && !options.smallest_format && animated_format {
+        true => source.frames(input_path)?,
+        false => None,
+    };
+
+    // L'image est encodée en mémoire : le fichier de sortie n'est créé qu'une fois l'encodage réussi.
+    let EncodedOutput { format, bytes, encode_info, candidate_sizes } = if let Some(frames) = frames {
+        let frames: Vec<AnimatedFrame> = frames
+            .iter()
+            .map(|frame| AnimatedFrame { image: process_frame(&frame.image, dimensions, options), delay_ms: frame.delay_ms })
+            .collect();
+        let format = options.output_format;
+        let bytes = match format {
+            OutputFormat::Png => animation::encode_apng(&frames, &options.animation)?,
+            _ => animation::encode_webp(&frames, &options.animation, options.webp.lossless)?,
+        };
+        let bytes = finish_metadata(bytes, icc_profile.as_deref(), options, format)?;
+        EncodedOutput { format, bytes, encode_info: Default::default(), candidate_sizes: Vec::new() }
+    } else if options.smallest_format {
+        encode_smallest(&img, icc_profile.as_deref(), options)?
+    } else {
+        let format = options.output_format;
+        let (bytes, encode_info) = encode_output(&img, icc_profile.as_deref(), options, format)?;
+        EncodedOutput { format, bytes, encode_info, candidate_sizes: Vec::new() }
+    };
+    let extension = format.extension();
+    let output_name = render_name_template(&options.name_template, output_stem, format, dimensions, options);
+    let output_full_path = output_dir.join(format!("{}.{}", output_name, extension));
 
-    img.write_to(&mut writer, ImageFormat::WebP)
-        .map_err(|e| format!("Impossible d'écrire l'image WebP dans {}: {}", output_full_path.display(), e))?;
+    let input_size = fs::metadata(input_path).map_or(0, |metadata| metadata.len());
+    let is_larger = input_size > 0 && bytes.len() as u64 > input_size;
+    let (bytes, extension, output_full_path, kept_original) = match options.larger_output {
+        LargerOutputMode::Skip if is_larger => return Ok(Outcome::SkippedLarger),
+        LargerOutputMode::CopyOriginal if is_larger => {
+            let original = fs::read(input_path)
+                .map_err(|e| ConverterError::io(tr!("Impossible de lire l'image {}", input_path.display()), e))?;
+            let source_extension = input_path.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+            let original_path = output_dir.join(format!("{}.{}", output_name, source_extension));
+            if keeps_existing(&original_path) {
+                return Ok(Outcome::SkippedExisting);
+            }
+            (original, source_extension, original_path, true)
+        }
+        _ => (bytes, extension.to_string(), output_full_path, false),
+    };
+
+    // La sortie est écrite dans un fichier partiel, renommé une fois complet
+    let (file, output_full_path, partial) = match overwrite_mode {
+        OverwriteMode::Rename => create_renamed(output_dir, &output_name, &extension, options.rename_suffix)?,
+        // Pas d'action spécifique, le fichier sera écrasé par défaut
+        _ => create_partial(&output_full_path)?,
+    };
+    let replaced = output_full_path.exists();
+    commit_output(file, &partial, &output_full_path, &bytes)?;
+    let verification = verify::verify_output(
+        &output_full_path,
+        (!kept_original).then_some(&img),
+        &options.verify,
+        options.background_color,
+    );
+
+    // Réutilise l'image déjà décodée pour la planche contact.
+    if let Some(contact_sheet) = contact_sheet {
+        let caption = input_path.file_name().unwrap_or_default().to_string_lossy();
+        contact_sheet.lock().unwrap().add(&img, &caption);
+    }
 
-    Ok(())
+    Ok(Outcome::Written(FileReport {
+        source: input_path.to_path_buf(),
+        output: output_full_path,
+        input_size,
+        output_size: bytes.len() as u64,
+        kept_original,
+        format,
+        candidate_sizes,
+        palette_size: if kept_original { None } else { encode_info.palette_size },
+        original: OriginalOutcome::Kept,
+        verification,
+        replaced,
+    }))
 }
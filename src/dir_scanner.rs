@@ -0,0 +1,142 @@
+/// Ce module compte en arrière-plan les images convertibles d'un répertoire sélectionné.
+/// Les résultats partiels sont transmis à l'UI au fil du parcours, pour que les très grandes
+/// arborescences (partages réseau...) ne figent pas l'interface.
+use eframe::egui;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::converter;
+
+/// Intervalle minimal entre deux publications de résultats partiels.
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Avancement (ou résultat final) de l'analyse d'un répertoire.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ScanProgress {
+    pub files: usize, // Images convertibles trouvées jusqu'ici.
+    pub bytes: u64,   // Taille cumulée de ces images.
+    pub done: bool,   // Vrai une fois le parcours terminé.
+}
+
+/// Répertoire à analyser et options déterminant quels fichiers sont convertibles.
+#[derive(Debug, Clone)]
+pub struct ScanRequest {
+    pub path: PathBuf,
+    pub options: converter::ConversionOptions,
+}
+
+impl ScanRequest {
+    /// Clé de cache : le répertoire et la signature des options qui influencent le parcours.
+    fn key(&self) -> (PathBuf, String) {
+        (self.path.clone(), self.options.scan_signature())
+    }
+}
+
+/// Gère le thread d'analyse en cours, son annulation et le cache des analyses terminées.
+#[derive(Default)]
+pub struct DirectoryScanner {
+    current: Option<(PathBuf, String)>,           // Clé de l'analyse affichée.
+    progress: Arc<Mutex<ScanProgress>>,           // Résultats partiels publiés par le thread.
+    cancel: Arc<AtomicBool>,                      // Demande d'arrêt du thread en cours.
+    cancelled: bool,                              // Vrai si l'analyse courante a été abandonnée.
+    cache: HashMap<(PathBuf, String), ScanProgress>, // Analyses terminées.
+}
+
+impl DirectoryScanner {
+    /// À appeler à chaque frame avec le répertoire sélectionné (ou `None`).
+    /// Lance une nouvelle analyse quand la sélection change (en annulant la précédente),
+    /// ou quand l'analyse de la sélection courante avait été abandonnée.
+    pub fn update(&mut self, ctx: &egui::Context, request: Option<ScanRequest>) {
+        let key = request.as_ref().map(ScanRequest::key);
+        if key == self.current && !self.cancelled {
+            self.store_if_done();
+            return;
+        }
+
+        self.stop();
+        self.current = key.clone();
+        self.cancelled = false;
+
+        let (Some(request), Some(key)) = (request, key) else {
+            return;
+        };
+
+        if let Some(cached) = self.cache.get(&key) {
+            // Même répertoire et mêmes options qu'une analyse terminée : résultat instantané.
+            self.progress = Arc::new(Mutex::new(*cached));
+            return;
+        }
+
+        let progress = Arc::new(Mutex::new(ScanProgress::default()));
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.progress = Arc::clone(&progress);
+        self.cancel = Arc::clone(&cancel);
+        let ctx_clone = ctx.clone();
+
+        std::thread::spawn(move || {
+            let mut local = ScanProgress::default();
+            // Des motifs invalides n'écartent rien ici : la conversion signalera l'erreur.
+            let path_filter = request.options.path_filter.compile().ok();
+            let file_filter = request.options.file_filter.compile().ok();
+            let mut last_publish = Instant::now();
+
+            for entry in request.options.traversal.walk(&request.path).filter_map(Result::ok) {
+                if cancel.load(Ordering::Relaxed) {
+                    return;
+                }
+                if !entry.file_type().is_file()
+                    || !converter::is_supported_image(entry.path())
+                    || !path_filter.as_ref().is_none_or(|filter| {
+                        filter.accepts(entry.path().strip_prefix(&request.path).unwrap_or(entry.path()))
+                    })
+                    || !file_filter.as_ref().is_none_or(|filter| {
+                        !filter.is_active() || entry.metadata().is_ok_and(|metadata| filter.accepts(&metadata))
+                    })
+                    || !request.options.dimension_filter.accepts(entry.path())
+                {
+                    continue;
+                }
+
+                local.files += 1;
+                local.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+                if last_publish.elapsed() >= PUBLISH_INTERVAL {
+                    *progress.lock().unwrap() = local;
+                    ctx_clone.request_repaint();
+                    last_publish = Instant::now();
+                }
+            }
+
+            local.done = true;
+            *progress.lock().unwrap() = local;
+            ctx_clone.request_repaint();
+        });
+    }
+
+    /// Abandonne l'analyse en cours (ex: au lancement de la conversion, dont le parcours prend le relais).
+    pub fn stop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        if !self.progress.lock().unwrap().done {
+            self.cancelled = true;
+        }
+    }
+
+    /// Avancement de l'analyse courante, `None` s'il n'y en a pas ou si elle a été abandonnée.
+    pub fn progress(&self) -> Option<ScanProgress> {
+        if self.current.is_none() || self.cancelled {
+            return None;
+        }
+        Some(*self.progress.lock().unwrap())
+    }
+
+    /// Met en cache le résultat de l'analyse courante une fois terminée.
+    fn store_if_done(&mut self) {
+        let progress = *self.progress.lock().unwrap();
+        if let (Some(key), true) = (&self.current, progress.done) {
+            self.cache.entry(key.clone()).or_insert(progress);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+/// Ce module repère les images en double (ou presque) parmi les sources d'un répertoire, avant leur conversion,
+/// grâce à leur empreinte perceptuelle (`image_hasher`, successeur de `img_hash`) : deux images dont les empreintes
+/// diffèrent d'au plus `threshold` bits sont considérées comme la même image, quels que soient leur format,
+/// leurs dimensions ou leur compression. De chaque groupe, la source la plus grande est gardée.
+use image_hasher::{HashAlg, HasherConfig, ImageHash};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::converter::{self, ConversionOptions};
+use crate::tr;
+
+/// Taille (pixels) de l'image réduite décodée pour calculer l'empreinte.
+const HASH_SOURCE_SIZE: u32 = 256;
+
+/// Écart maximal (bits, sur 64) proposé dans l'interface.
+pub const MAX_THRESHOLD: u32 = 16;
+
+/// Réglages de la détection des doublons.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuplicateOptions {
+    pub enabled: bool,  // Repérer les doublons parmi les sources d'un répertoire avant la conversion.
+    pub exclude: bool,  // Ne pas convertir les doublons (sinon ils sont seulement signalés dans le bilan).
+    pub threshold: u32, // Écart maximal entre deux empreintes (bits) : 0 pour des images identiques uniquement.
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self { enabled: false, exclude: true, threshold: 4 }
+    }
+}
+
+/// Source en double d'une autre.
+#[derive(Debug, Clone)]
+pub struct Duplicate {
+    pub source: PathBuf,   // Source en double.
+    pub original: PathBuf, // Source gardée, dont `source` est le double.
+    pub distance: u32,     // Écart entre leurs empreintes (bits, 0 = identiques).
+}
+
+/// Source dont l'empreinte a été calculée.
+struct HashedSource {
+    path: PathBuf,
+    hash: ImageHash,
+    area: u64, // Surface (pixels), pour garder la plus grande image d'un groupe.
+    size: u64, // Taille du fichier (octets), à surface égale.
+}
+
+/// Doublons parmi `sources`, décodées avec `options` (orientation, profil de couleur). Les images sont comparées
+/// de la plus grande à la plus petite : chacune est le double de la première image gardée dont l'empreinte est assez
+/// proche (`options.duplicates.threshold`), sinon elle est gardée. Les sources illisibles sont ignorées et signalées
+/// dans les avertissements retournés ; `cancelled` interrompt le calcul des empreintes.
+pub fn find(
+    sources: &[PathBuf],
+    options: &ConversionOptions,
+    cancelled: impl Fn() -> bool + Sync,
+) -> (Vec<Duplicate>, Vec<String>) {
+    let threshold = options.duplicates.threshold;
+    let hasher = HasherConfig::new().hash_alg(HashAlg::Gradient).hash_size(8, 8).to_hasher();
+    let hashed: Vec<Result<HashedSource, String>> = sources
+        .par_iter()
+        .filter(|_| !cancelled())
+        .map(|path| {
+            let img = converter::decode_thumbnail(path, options, HASH_SOURCE_SIZE)
+                .map_err(|e| tr!("Doublons : {} ignoré ({})", path.display(), e))?;
+            let (width, height) = image::image_dimensions(path).unwrap_or((img.width(), img.height()));
+            Ok(HashedSource {
+                path: path.clone(),
+                hash: hasher.hash_image(&img),
+                area: width as u64 * height as u64,
+                size: file_size(path),
+            })
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    let mut hashed: Vec<HashedSource> = hashed
+        .into_iter()
+        .filter_map(|source| source.map_err(|warning| warnings.push(warning)).ok())
+        .collect();
+    hashed.sort_by(|a, b| (Reverse(a.area), Reverse(a.size), &a.path).cmp(&(Reverse(b.area), Reverse(b.size), &b.path)));
+
+    let mut kept: Vec<HashedSource> = Vec::new();
+    let mut duplicates = Vec::new();
+    for source in hashed {
+        let original = kept
+            .iter()
+            .map(|original| (original, original.hash.dist(&source.hash)))
+            .find(|&(_, distance)| distance <= threshold);
+        match original {
+            Some((original, distance)) => {
+                duplicates.push(Duplicate { source: source.path, original: original.path.clone(), distance })
+            }
+            None => kept.push(source),
+        }
+    }
+    duplicates.sort_by(|a, b| a.source.cmp(&b.source));
+    (duplicates, warnings)
+}
+
+/// Taille du fichier `path` en octets, 0 s'il est illisible.
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map_or(0, |metadata| metadata.len())
+}
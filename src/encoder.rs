@@ -0,0 +1,337 @@
+/// Ce module encode les images décodées dans le format de sortie choisi.
+use color_quant::NeuQuant;
+use image::codecs::avif::AvifEncoder;
+use image::{DynamicImage, ImageFormat, RgbImage, RgbaImage};
+use std::collections::HashMap;
+use std::io::{Cursor, Seek, Write};
+
+use crate::converter::{AvifOptions, ChromaSubsampling, ConversionOptions, JpegOptions, OutputFormat, PngOptions, WebpOptions};
+use crate::error::ConverterError;
+use crate::tr;
+
+/// Facteur d'échantillonnage de NeuQuant (1 = meilleure qualité, 30 = plus rapide).
+const QUANTIZER_SAMPLE_FACTOR: i32 = 10;
+
+/// Informations sur l'encodage d'une image, reprises dans le bilan de conversion.
+#[derive(Debug, Default, Clone)]
+pub struct EncodeInfo {
+    pub palette_size: Option<usize>, // Nombre de couleurs de la palette (PNG quantifié uniquement).
+}
+
+/// Encode `img` dans `writer` selon le format et les options de sortie.
+pub fn encode<W: Write + Seek>(img: &DynamicImage, writer: &mut W, options: &ConversionOptions) -> Result<EncodeInfo, ConverterError> {
+    match options.output_format {
+        OutputFormat::WebP => encode_webp(img, writer, &options.webp),
+        OutputFormat::Png if options.png.optimize => {
+            let mut buffer = Cursor::new(Vec::new());
+            let info = encode_png(img, &mut buffer, &options.png)?;
+            let optimized = optimize_png(buffer.get_ref(), options.png.optimization_level)?;
+            writer.write_all(&optimized)
+                .map_err(|e| ConverterError::io(tr!("Échec de l'écriture PNG"), e))?;
+            Ok(info)
+        }
+        OutputFormat::Png => encode_png(img, writer, &options.png),
+        OutputFormat::Jpeg => encode_jpeg(img, writer, &options.jpeg, options.background_color),
+        OutputFormat::Avif => encode_avif(img, writer, &options.avif),
+        OutputFormat::Tiff => {
+            img.write_to(writer, ImageFormat::Tiff)
+                .map_err(|e| ConverterError::encode("TIFF", e))?;
+            Ok(EncodeInfo::default())
+        }
+    }
+}
+
+/// Encode en WebP avec libwebp, avec perte (qualité réglable) ou sans perte.
+fn encode_webp<W: Write>(img: &DynamicImage, writer: &mut W, webp_options: &WebpOptions) -> Result<EncodeInfo, ConverterError> {
+    // libwebp n'accepte que le RGB et le RGBA 8 bits : la transparence est conservée si la source en a.
+    let (pixels, width, height, has_alpha) = if img.color().has_alpha() {
+        let rgba = img.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        (rgba.into_raw(), width, height, true)
+    } else {
+        let rgb = img.to_rgb8();
+        let (width, height) = rgb.dimensions();
+        (rgb.into_raw(), width, height, false)
+    };
+
+    let encoder = if has_alpha {
+        webp::Encoder::from_rgba(&pixels, width, height)
+    } else {
+        webp::Encoder::from_rgb(&pixels, width, height)
+    };
+    let encoded = encoder
+        .encode_simple(webp_options.lossless, webp_options.quality.min(100) as f32)
+        .map_err(|e| ConverterError::encode("WebP", format!("{:?}", e)))?;
+
+    writer.write_all(&encoded)
+        .map_err(|e| ConverterError::io(tr!("Échec de l'écriture WebP"), e))?;
+    Ok(EncodeInfo::default())
+}
+
+/// Encode en AVIF (ravif, via `image`) avec la qualité et la vitesse demandées.
+fn encode_avif<W: Write>(img: &DynamicImage, writer: &mut W, avif_options: &AvifOptions) -> Result<EncodeInfo, ConverterError> {
+    let encoder = AvifEncoder::new_with_speed_quality(
+        writer,
+        avif_options.speed.clamp(1, 10),
+        avif_options.quality.clamp(1, 100),
+    );
+    // L'encodeur travaille en 8 bits : les images 16 bits ou en niveaux de gris sont converties en RGB(A).
+    let result = if img.color().has_alpha() {
+        DynamicImage::ImageRgba8(img.to_rgba8()).write_with_encoder(encoder)
+    } else {
+        DynamicImage::ImageRgb8(img.to_rgb8()).write_with_encoder(encoder)
+    };
+    result.map_err(|e| ConverterError::encode("AVIF", e))?;
+    Ok(EncodeInfo::default())
+}
+
+/// Encode en JPEG avec la qualité, le mode progressif et le sous-échantillonnage demandés, avec mozjpeg
+/// ou l'encodeur standard. Le JPEG ne gérant pas la transparence, l'image est d'abord aplatie sur la couleur de fond.
+fn encode_jpeg<W: Write>(
+    img: &DynamicImage,
+    writer: &mut W,
+    jpeg_options: &JpegOptions,
+    background_color: [u8; 3],
+) -> Result<EncodeInfo, ConverterError> {
+    let rgb = flatten_alpha(img, background_color);
+    let (width, height) = (
+        u16::try_from(rgb.width()).map_err(|_| ConverterError::encode("JPEG", tr!("image trop large (65 535 px max.)")))?,
+        u16::try_from(rgb.height()).map_err(|_| ConverterError::encode("JPEG", tr!("image trop haute (65 535 px max.)")))?,
+    );
+    if jpeg_options.mozjpeg {
+        return encode_mozjpeg(&rgb, writer, jpeg_options);
+    }
+
+    let mut encoder = jpeg_encoder::Encoder::new(writer, jpeg_options.quality.clamp(1, 100));
+    encoder.set_progressive(jpeg_options.progressive);
+    encoder.set_sampling_factor(match jpeg_options.subsampling {
+        ChromaSubsampling::S444 => jpeg_encoder::SamplingFactor::R_4_4_4,
+        ChromaSubsampling::S422 => jpeg_encoder::SamplingFactor::R_4_2_2,
+        ChromaSubsampling::S420 => jpeg_encoder::SamplingFactor::R_4_2_0,
+    });
+    encoder.encode(rgb.as_raw(), width, height, jpeg_encoder::ColorType::Rgb)
+        .map_err(|e| ConverterError::encode("JPEG", e))?;
+
+    Ok(EncodeInfo::default())
+}
+
+/// Encode en JPEG avec mozjpeg : à qualité égale, les fichiers sont nettement plus légers qu'avec l'encodeur standard.
+/// libjpeg signale ses erreurs en interrompant le thread (panique), d'où le `catch_unwind`.
+fn encode_mozjpeg<W: Write>(rgb: &RgbImage, writer: &mut W, jpeg_options: &JpegOptions) -> Result<EncodeInfo, ConverterError> {
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| -> std::io::Result<()> {
+        let mut compress = mozjpeg::Compress::new(mozjpeg::ColorSpace::JCS_RGB);
+        compress.set_size(rgb.width() as usize, rgb.height() as usize);
+        compress.set_quality(jpeg_options.quality.clamp(1, 100) as f32);
+        // Le profil par défaut de mozjpeg est progressif : sans passes, le fichier est séquentiel.
+        match jpeg_options.progressive {
+            true => compress.set_progressive_mode(),
+            false => compress.set_optimize_scans(false),
+        }
+        let pixel_size = match jpeg_options.subsampling {
+            ChromaSubsampling::S444 => (1, 1),
+            ChromaSubsampling::S422 => (2, 1),
+            ChromaSubsampling::S420 => (2, 2),
+        };
+        compress.set_chroma_sampling_pixel_sizes(pixel_size, pixel_size);
+
+        let mut started = compress.start_compress(&mut *writer)?;
+        started.write_scanlines(rgb.as_raw())?;
+        started.finish()?;
+        Ok(())
+    }));
+    match result {
+        Ok(Ok(())) => Ok(EncodeInfo::default()),
+        Ok(Err(e)) => Err(ConverterError::io(tr!("Échec de l'écriture JPEG"), e)),
+        Err(_) => Err(ConverterError::encode("JPEG", tr!("erreur de mozjpeg"))),
+    }
+}
+
+/// Compose l'image sur un fond uni de couleur `background_color`, supprimant la transparence.
+pub fn flatten_alpha(img: &DynamicImage, background_color: [u8; 3]) -> RgbImage {
+    if !img.color().has_alpha() {
+        return img.to_rgb8();
+    }
+
+    let rgba = img.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let alpha = a as f32 / 255.0;
+        let blend = |foreground: u8, background: u8| {
+            (foreground as f32 * alpha + background as f32 * (1.0 - alpha)).round() as u8
+        };
+        image::Rgb([
+            blend(r, background_color[0]),
+            blend(g, background_color[1]),
+            blend(b, background_color[2]),
+        ])
+    })
+}
+
+/// Encode en PNG, en réduisant l'image à une palette indexée si l'option est active. Une image qui compte déjà
+/// au plus le nombre de couleurs demandé (source indexée, aplats...) est indexée avec ses couleurs exactes, sans perte.
+fn encode_png<W: Write + Seek>(img: &DynamicImage, writer: &mut W, png_options: &PngOptions) -> Result<EncodeInfo, ConverterError> {
+    if !png_options.quantize {
+        img.write_to(writer, ImageFormat::Png)
+            .map_err(|e| ConverterError::encode("PNG", e))?;
+        return Ok(EncodeInfo::default());
+    }
+
+    let max_colors = png_options.max_colors.clamp(2, 256) as usize;
+    let rgba = img.to_rgba8();
+    let (palette, indices) = exact_palette(&rgba, max_colors)
+        .unwrap_or_else(|| quantize(&rgba, max_colors, png_options.dithering));
+    let palette_size = palette.len() / 4;
+
+    let mut encoder = png::Encoder::new(writer, rgba.width(), rgba.height());
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette.chunks_exact(4).flat_map(|c| [c[0], c[1], c[2]]).collect::<Vec<u8>>());
+    if palette.chunks_exact(4).any(|c| c[3] < 255) {
+        encoder.set_trns(palette.chunks_exact(4).map(|c| c[3]).collect::<Vec<u8>>());
+    }
+
+    let mut png_writer = encoder.write_header()
+        .map_err(|e| ConverterError::encode("PNG", e))?;
+    png_writer.write_image_data(&indices)
+        .map_err(|e| ConverterError::encode("PNG", e))?;
+
+    Ok(EncodeInfo { palette_size: Some(palette_size) })
+}
+
+/// Palette (RGBA) des couleurs distinctes de l'image et index de chaque pixel, si elle en compte au plus `max_colors`.
+fn exact_palette(rgba: &RgbaImage, max_colors: usize) -> Option<(Vec<u8>, Vec<u8>)> {
+    let mut colors: HashMap<[u8; 4], u8> = HashMap::new();
+    let mut palette = Vec::new();
+    let mut indices = Vec::with_capacity(rgba.as_raw().len() / 4);
+    for pixel in rgba.pixels() {
+        let index = match colors.get(&pixel.0) {
+            Some(&index) => index,
+            None if colors.len() < max_colors => {
+                let index = colors.len() as u8;
+                colors.insert(pixel.0, index);
+                palette.extend_from_slice(&pixel.0);
+                index
+            }
+            None => return None,
+        };
+        indices.push(index);
+    }
+    Some((palette, indices))
+}
+
+/// Optimise sans perte le PNG `data` avec oxipng (filtres, compression, réduction de la profondeur et de la palette),
+/// avec l'effort `level` (0 à 6). Les blocs auxiliaires (profil ICC, métadonnées) sont conservés.
+fn optimize_png(data: &[u8], level: u8) -> Result<Vec<u8>, ConverterError> {
+    let options = oxipng::Options::from_preset(level.min(6));
+    oxipng::optimize_from_memory(data, &options).map_err(|e| ConverterError::encode("PNG", e))
+}
+
+/// Réduit l'image à au plus `max_colors` couleurs. Retourne la palette (RGBA) et l'index de chaque pixel.
+/// Avec `dithering`, l'erreur de quantification est diffusée aux pixels voisins (Floyd-Steinberg).
+fn quantize(rgba: &RgbaImage, max_colors: usize, dithering: bool) -> (Vec<u8>, Vec<u8>) {
+    let quantizer = NeuQuant::new(QUANTIZER_SAMPLE_FACTOR, max_colors, rgba.as_raw());
+    let palette = quantizer.color_map_rgba();
+
+    if !dithering {
+        let indices = rgba.pixels().map(|p| quantizer.index_of(&p.0) as u8).collect();
+        return (palette, indices);
+    }
+
+    let (width, height) = (rgba.width() as usize, rgba.height() as usize);
+    let mut pixels: Vec<f32> = rgba.as_raw().iter().map(|&v| v as f32).collect();
+    let mut indices = Vec::with_capacity(width * height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let offset = (y * width + x) * 4;
+            let pixel: [u8; 4] = std::array::from_fn(|c| pixels[offset + c].round().clamp(0.0, 255.0) as u8);
+            let index = quantizer.index_of(&pixel);
+            indices.push(index as u8);
+
+            let chosen = &palette[index * 4..index * 4 + 4];
+            let error: [f32; 4] = std::array::from_fn(|c| pixels[offset + c] - chosen[c] as f32);
+
+            let mut diffuse = |dx: isize, dy: usize, weight: f32| {
+                let nx = x as isize + dx;
+                let ny = y + dy;
+                if nx < 0 || nx as usize >= width || ny >= height {
+                    return;
+                }
+                let neighbour = (ny * width + nx as usize) * 4;
+                for c in 0..4 {
+                    pixels[neighbour + c] += error[c] * weight;
+                }
+            };
+            diffuse(1, 0, 7.0 / 16.0);
+            diffuse(-1, 1, 3.0 / 16.0);
+            diffuse(0, 1, 5.0 / 16.0);
+            diffuse(1, 1, 1.0 / 16.0);
+        }
+    }
+
+    (palette, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode une petite image RGB en JPEG avec l'encodeur (`mozjpeg` ou `jpeg-encoder`) et le mode demandés.
+    fn encode_test_jpeg(mozjpeg: bool, progressive: bool) -> Vec<u8> {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_fn(32, 24, |x, y| image::Rgb([(x * 8) as u8, (y * 10) as u8, 128])));
+        let options = JpegOptions { progressive, mozjpeg, ..JpegOptions::default() };
+        let mut bytes = Vec::new();
+        encode_jpeg(&img, &mut bytes, &options, [255, 255, 255]).unwrap();
+        bytes
+    }
+
+    /// Indique si `bytes` contient le marqueur JPEG `0xFF marker` (les données compressées n'en contiennent pas).
+    fn has_marker(bytes: &[u8], marker: u8) -> bool {
+        bytes.windows(2).any(|pair| pair == [0xFF, marker])
+    }
+
+    #[test]
+    fn jpeg_encoder_progressive_writes_sof2() {
+        let bytes = encode_test_jpeg(false, true);
+        assert!(has_marker(&bytes, 0xC2));
+        assert!(!has_marker(&bytes, 0xC0));
+    }
+
+    #[test]
+    fn jpeg_encoder_baseline_writes_sof0() {
+        let bytes = encode_test_jpeg(false, false);
+        assert!(has_marker(&bytes, 0xC0));
+        assert!(!has_marker(&bytes, 0xC2));
+    }
+
+    #[test]
+    fn mozjpeg_progressive_writes_sof2() {
+        let bytes = encode_test_jpeg(true, true);
+        assert!(has_marker(&bytes, 0xC2));
+        assert!(!has_marker(&bytes, 0xC0));
+    }
+
+    #[test]
+    fn mozjpeg_baseline_writes_sof0() {
+        // Le profil par défaut de mozjpeg est progressif : `set_optimize_scans(false)` doit le rendre séquentiel.
+        let bytes = encode_test_jpeg(true, false);
+        assert!(has_marker(&bytes, 0xC0));
+        assert!(!has_marker(&bytes, 0xC2));
+    }
+
+    #[test]
+    fn png_with_few_colors_keeps_exact_palette() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_fn(16, 16, |x, _| match x < 8 {
+            true => image::Rgb([200, 30, 30]),
+            false => image::Rgb([20, 40, 220]),
+        }));
+        let options = PngOptions { quantize: true, ..PngOptions::default() };
+        let mut buffer = Cursor::new(Vec::new());
+        let info = encode_png(&img, &mut buffer, &options).unwrap();
+        assert_eq!(info.palette_size, Some(2));
+
+        let reader = png::Decoder::new(Cursor::new(buffer.into_inner())).read_info().unwrap();
+        assert_eq!(reader.info().color_type, png::ColorType::Indexed);
+        assert_eq!(reader.info().palette.as_deref(), Some(&[200, 30, 30, 20, 40, 220][..]));
+    }
+}
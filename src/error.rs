@@ -0,0 +1,69 @@
+/// Ce module définit les erreurs de la conversion. Chaque variante correspond à une catégorie
+/// (entrées/sorties, décodage, encodage, chemins...) et son affichage est le message présenté dans l'interface,
+/// dans la langue de celle-ci.
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::tr;
+
+/// Erreur d'une conversion ou d'une opération sur les fichiers.
+/// Les erreurs sources sont partagées (`Arc`) pour que les bilans de conversion restent clonables.
+#[derive(Debug, Clone, Error)]
+pub enum ConverterError {
+    /// Lecture, écriture ou création d'un fichier ou d'un répertoire impossible.
+    #[error("{context} : {source}")]
+    Io {
+        context: String,             // Opération et chemin concernés.
+        source: Arc<std::io::Error>, // Erreur du système.
+    },
+
+    /// Image source illisible : fichier corrompu ou format non reconnu.
+    #[error("{}", tr!("Impossible de décoder l'image {} : {}", .path.display(), .source))]
+    Decode {
+        path: PathBuf,                  // Image source.
+        source: Arc<image::ImageError>, // Erreur du décodeur.
+    },
+
+    /// Échec de l'encodeur du format de sortie.
+    #[error("{}", tr!("Échec de l'encodage {} : {}", .format, .message))]
+    Encode {
+        format: &'static str, // Format de sortie (ex: "WebP").
+        message: String,      // Message de l'encodeur.
+    },
+
+    /// Chemins d'entrée ou de sortie refusés (ex: sortie à l'intérieur de l'entrée, nom invalide).
+    #[error("{0}")]
+    PathValidation(String),
+
+    /// Configuration de l'application impossible à enregistrer.
+    #[error("{0}")]
+    Config(String),
+
+    /// Manifeste du mode incrémental impossible à sérialiser.
+    #[error("{}", tr!("Impossible de sérialiser le manifeste : {}", .0))]
+    Serialization(Arc<serde_json::Error>),
+}
+
+impl ConverterError {
+    /// Erreur d'entrées/sorties, `context` décrivant l'opération (ex: "Impossible de lire x.png").
+    pub fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        ConverterError::Io { context: context.into(), source: Arc::new(source) }
+    }
+
+    /// Erreur de décodage de l'image `path`.
+    pub fn decode(path: &Path, source: image::ImageError) -> Self {
+        ConverterError::Decode { path: path.to_path_buf(), source: Arc::new(source) }
+    }
+
+    /// Erreur de l'encodeur `format`.
+    pub fn encode(format: &'static str, message: impl std::fmt::Display) -> Self {
+        ConverterError::Encode { format, message: message.to_string() }
+    }
+}
+
+impl From<serde_json::Error> for ConverterError {
+    fn from(error: serde_json::Error) -> Self {
+        ConverterError::Serialization(Arc::new(error))
+    }
+}
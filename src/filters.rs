@@ -0,0 +1,251 @@
+/// Ce module filtre les sources d'un lot selon leurs dimensions et leur orientation, et les fichiers
+/// d'un répertoire selon des motifs glob d'inclusion et d'exclusion (`globset`), leur taille et leur date de modification.
+/// Il définit aussi le parcours des répertoires : profondeur maximale, liens symboliques et fichiers cachés.
+/// Les dimensions sont lues dans l'en-tête du fichier uniquement, sans décoder l'image.
+use chrono::{Local, NaiveDate};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs::Metadata;
+use std::path::Path;
+use std::time::SystemTime;
+use walkdir::{DirEntry, WalkDir};
+
+use crate::error::ConverterError;
+use crate::tr;
+
+/// Orientation des images à conserver.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Orientation {
+    All,       // Toutes les orientations
+    Landscape, // Paysage (plus large que haute)
+    Portrait,  // Portrait (plus haute que large)
+    Square,    // Carré (largeur = hauteur)
+}
+
+impl Orientation {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Orientation::All => tr!("Toutes"),
+            Orientation::Landscape => tr!("Paysage"),
+            Orientation::Portrait => tr!("Portrait"),
+            Orientation::Square => tr!("Carré"),
+        }
+    }
+
+    fn matches(&self, width: u32, height: u32) -> bool {
+        match self {
+            Orientation::All => true,
+            Orientation::Landscape => width > height,
+            Orientation::Portrait => height > width,
+            Orientation::Square => width == height,
+        }
+    }
+}
+
+/// Filtre de dimensions appliqué aux lots (répertoire ou plusieurs fichiers).
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DimensionFilter {
+    pub min_width: u32,           // Largeur minimale en pixels (0 = pas de minimum).
+    pub min_height: u32,          // Hauteur minimale en pixels (0 = pas de minimum).
+    pub orientation: Orientation, // Orientation à conserver.
+}
+
+impl Default for DimensionFilter {
+    fn default() -> Self {
+        Self {
+            min_width: 0,
+            min_height: 0,
+            orientation: Orientation::All,
+        }
+    }
+}
+
+impl DimensionFilter {
+    /// Vrai si le filtre peut exclure des fichiers : sinon, aucun en-tête n'est lu.
+    pub fn is_active(&self) -> bool {
+        self.min_width > 0 || self.min_height > 0 || self.orientation != Orientation::All
+    }
+
+    /// Signature du filtre, intégrée à la clé du cache de l'analyse des répertoires.
+    pub fn signature(&self) -> String {
+        format!("min={}x{};orientation={:?}", self.min_width, self.min_height, self.orientation)
+    }
+
+    /// Indique si `path` passe le filtre. Un fichier dont l'en-tête est illisible est conservé :
+    /// le décodage complet signalera l'erreur éventuelle au lieu de l'écarter silencieusement.
+    pub fn accepts(&self, path: &Path) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+        match image::image_dimensions(path) {
+            Ok((width, height)) => {
+                width >= self.min_width && height >= self.min_height && self.orientation.matches(width, height)
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+/// Motifs glob appliqués aux fichiers d'un répertoire (ex: inclure `*.png`, exclure `**/thumbnails/**`).
+/// Les motifs, séparés par des virgules, portent sur le chemin relatif au répertoire d'entrée, sans distinction de casse.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PathFilter {
+    pub include: String, // Motifs à inclure (vide = tous les fichiers).
+    pub exclude: String, // Motifs à exclure, prioritaires sur l'inclusion.
+}
+
+/// Motifs compilés d'un `PathFilter`.
+#[derive(Debug, Clone)]
+pub struct CompiledPathFilter {
+    include: Option<GlobSet>, // `None` : tous les fichiers sont inclus.
+    exclude: Option<GlobSet>, // `None` : aucun fichier n'est exclu.
+}
+
+impl PathFilter {
+    /// Signature du filtre, intégrée à la clé du cache de l'analyse des répertoires.
+    pub fn signature(&self) -> String {
+        format!("include={};exclude={}", self.include.trim(), self.exclude.trim())
+    }
+
+    /// Compile les motifs ; un motif invalide est signalé avec son texte.
+    pub fn compile(&self) -> Result<CompiledPathFilter, ConverterError> {
+        Ok(CompiledPathFilter {
+            include: compile_patterns(&self.include)?,
+            exclude: compile_patterns(&self.exclude)?,
+        })
+    }
+}
+
+impl CompiledPathFilter {
+    /// Indique si le fichier de chemin `relative_path` (relatif au répertoire d'entrée) passe le filtre.
+    pub fn accepts(&self, relative_path: &Path) -> bool {
+        self.include.as_ref().is_none_or(|include| include.is_match(relative_path))
+            && !self.exclude.as_ref().is_some_and(|exclude| exclude.is_match(relative_path))
+    }
+}
+
+/// Compile une liste de motifs séparés par des virgules, `None` si elle est vide.
+fn compile_patterns(patterns: &str) -> Result<Option<GlobSet>, ConverterError> {
+    let patterns: Vec<&str> = patterns.split(',').map(str::trim).filter(|pattern| !pattern.is_empty()).collect();
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| ConverterError::PathValidation(tr!("Motif invalide « {} » : {}", pattern, e.kind())))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| ConverterError::PathValidation(tr!("Motifs invalides : {}", e)))
+}
+
+/// Format des dates saisies pour le filtre de date de modification.
+pub const DATE_INPUT_FORMAT: &str = "%Y-%m-%d";
+
+/// Filtre de taille et de date de modification appliqué aux fichiers d'un répertoire.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileFilter {
+    pub min_size_kb: u64,       // Taille minimale en Ko (0 = pas de minimum).
+    pub modified_after: String, // Date « AAAA-MM-JJ » : seuls les fichiers modifiés ce jour-là ou après sont gardés (vide = aucune).
+}
+
+/// Critères compilés d'un `FileFilter`.
+#[derive(Debug, Clone, Copy)]
+pub struct CompiledFileFilter {
+    min_size: u64,                      // Taille minimale en octets.
+    modified_after: Option<SystemTime>, // Début (heure locale) du jour choisi.
+}
+
+impl FileFilter {
+    /// Signature du filtre, intégrée à la clé du cache de l'analyse des répertoires.
+    pub fn signature(&self) -> String {
+        format!("min_size={}k;after={}", self.min_size_kb, self.modified_after.trim())
+    }
+
+    /// Compile le filtre ; une date mal formée est signalée avec son texte.
+    pub fn compile(&self) -> Result<CompiledFileFilter, ConverterError> {
+        let date = self.modified_after.trim();
+        let modified_after = if date.is_empty() {
+            None
+        } else {
+            let day = NaiveDate::parse_from_str(date, DATE_INPUT_FORMAT)
+                .map_err(|_| ConverterError::PathValidation(tr!("Date invalide « {} » (format attendu : AAAA-MM-JJ)", date)))?;
+            day.and_hms_opt(0, 0, 0)
+                .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+                .map(SystemTime::from)
+        };
+        Ok(CompiledFileFilter {
+            min_size: self.min_size_kb.saturating_mul(1024),
+            modified_after,
+        })
+    }
+}
+
+impl CompiledFileFilter {
+    /// Vrai si le filtre peut exclure des fichiers.
+    pub fn is_active(&self) -> bool {
+        self.min_size > 0 || self.modified_after.is_some()
+    }
+
+    /// Indique si un fichier de métadonnées `metadata` passe le filtre. Une date de modification
+    /// illisible ne l'écarte pas.
+    pub fn accepts(&self, metadata: &Metadata) -> bool {
+        let recent_enough = match (self.modified_after, metadata.modified()) {
+            (Some(after), Ok(modified)) => modified >= after,
+            _ => true,
+        };
+        metadata.len() >= self.min_size && recent_enough
+    }
+}
+
+/// Options du parcours d'un répertoire.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TraversalOptions {
+    pub max_depth: usize,      // Profondeur maximale des sous-répertoires parcourus (0 = illimitée, 1 = racine seule).
+    pub follow_symlinks: bool, // Suivre les liens symboliques vers des fichiers et des répertoires.
+    pub skip_hidden: bool,     // Ignorer les fichiers et répertoires cachés (nom commençant par un point, attribut caché sous Windows).
+}
+
+impl TraversalOptions {
+    /// Signature du parcours, intégrée à la clé du cache de l'analyse des répertoires.
+    pub fn signature(&self) -> String {
+        format!("depth={};follow={};hidden={}", self.max_depth, self.follow_symlinks, !self.skip_hidden)
+    }
+
+    /// Parcourt `root` selon ces options. Les répertoires cachés ignorés ne sont pas explorés ;
+    /// la racine elle-même est toujours parcourue.
+    pub fn walk(&self, root: &Path) -> impl Iterator<Item = walkdir::Result<DirEntry>> {
+        let mut walker = WalkDir::new(root).follow_links(self.follow_symlinks);
+        if self.max_depth > 0 {
+            walker = walker.max_depth(self.max_depth);
+        }
+        let skip_hidden = self.skip_hidden;
+        walker.into_iter().filter_entry(move |entry| !skip_hidden || entry.depth() == 0 || !is_hidden(entry))
+    }
+}
+
+/// Vrai si l'entrée est cachée : nom commençant par un point ou, sous Windows, attribut « caché ».
+fn is_hidden(entry: &DirEntry) -> bool {
+    if entry.file_name().to_string_lossy().starts_with('.') {
+        return true;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if let Ok(metadata) = entry.metadata() {
+            return metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0;
+        }
+    }
+    false
+}
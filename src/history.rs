@@ -0,0 +1,220 @@
+/// Ce module conserve l'historique des lots terminés dans une base SQLite (`history.sqlite3`, dans le dossier
+/// de configuration) : date, sources, options utilisées, nombre de fichiers et gain obtenu. La fenêtre « Historique »
+/// permet de parcourir les lots passés, de relancer l'un d'eux avec les mêmes réglages ou d'ouvrir son dossier de sortie.
+use chrono::{DateTime, Local};
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::converter::{ConversionReport, OutputFormat};
+use crate::error::ConverterError;
+use crate::job::ConversionJob;
+use crate::tr;
+
+/// Nom de la base, dans le dossier de configuration de l'application.
+const HISTORY_FILE_NAME: &str = "history.sqlite3";
+
+/// Nombre maximal de lots affichés dans la fenêtre « Historique » (les plus récents).
+pub const MAX_LISTED_ENTRIES: usize = 200;
+
+/// Action choisie dans la fenêtre « Historique ».
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HistoryAction {
+    Rerun(i64),      // Relancer le lot avec les mêmes sources et réglages
+    OpenFolder(i64), // Ouvrir le dossier de sortie du lot
+    Remove(i64),     // Retirer le lot de l'historique
+    Clear,           // Vider l'historique
+}
+
+/// Lot terminé, tel qu'enregistré dans l'historique.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub id: i64,                      // Identifiant dans la base
+    pub finished_at: DateTime<Local>, // Date de fin du lot
+    pub label: String,                // Nom affiché (fichier, répertoire ou nombre de fichiers)
+    pub job: ConversionJob,           // Sources, sortie et options, pour relancer le lot
+    pub output_root: PathBuf,         // Dossier proposé à l'ouverture
+    pub converted: usize,             // Fichiers écrits
+    pub skipped: usize,               // Fichiers ignorés
+    pub failed: usize,                // Fichiers en échec
+    pub cancelled: bool,              // Lot annulé avant la fin
+    pub input_bytes: u64,             // Taille cumulée des sources converties
+    pub output_bytes: u64,            // Taille cumulée des fichiers produits
+}
+
+impl HistoryEntry {
+    /// Gain d'espace (en %), `None` si aucun fichier n'a été converti.
+    pub fn savings_percent(&self) -> Option<f64> {
+        (self.input_bytes > 0).then(|| (1.0 - self.output_bytes as f64 / self.input_bytes as f64) * 100.0)
+    }
+
+    /// Résumé des réglages du lot : format, qualité et redimensionnement (ex: « WebP q80, redimensionné »).
+    pub fn settings_summary(&self) -> String {
+        let options = &self.job.options;
+        let format = match (options.smallest_format, options.output_format) {
+            (true, _) => tr!("le plus léger").to_string(),
+            (false, OutputFormat::WebP) if options.webp.lossless => tr!("WebP sans perte").to_string(),
+            (false, OutputFormat::WebP) => format!("WebP q{}", options.webp.quality),
+            (false, OutputFormat::Jpeg) => format!("JPEG q{}", options.jpeg.quality),
+            (false, OutputFormat::Avif) => format!("AVIF q{}", options.avif.quality),
+            (false, format) => format.label().to_string(),
+        };
+        match options.resize.signature() {
+            Some(_) => tr!("{}, redimensionné", format),
+            None => format,
+        }
+    }
+}
+
+/// Historique des lots, ouvert pour la session. Sans base accessible, l'historique reste vide.
+pub struct History {
+    connection: Option<Connection>, // Connexion à la base, `None` si elle n'a pas pu être ouverte
+}
+
+impl History {
+    /// Ouvre la base (en la créant si besoin). Un échec est consigné dans le journal de l'application.
+    pub fn open() -> Self {
+        let connection = match open_connection() {
+            Ok(connection) => Some(connection),
+            Err(e) => {
+                tracing::warn!("{}", e);
+                None
+            }
+        };
+        Self { connection }
+    }
+
+    /// Enregistre le lot `job`, terminé avec le bilan `report`.
+    pub fn record(&self, label: &str, job: &ConversionJob, output_root: &Path, report: &ConversionReport) {
+        let Some(connection) = &self.connection else {
+            return;
+        };
+        let job = match serde_json::to_string(job) {
+            Ok(job) => job,
+            Err(e) => {
+                tracing::warn!("Lot non inscrit à l'historique : {}", e);
+                return;
+            }
+        };
+        let result = connection.execute(
+            "INSERT INTO jobs (finished_at, label, job, output_root, converted, skipped, failed, cancelled, input_bytes, output_bytes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                Local::now().to_rfc3339(),
+                label,
+                job,
+                output_root.to_string_lossy(),
+                report.converted() as i64,
+                report.skipped() as i64,
+                report.failures.len() as i64,
+                report.cancelled,
+                report.input_bytes() as i64,
+                report.output_bytes() as i64,
+            ],
+        );
+        if let Err(e) = result {
+            tracing::warn!("Lot non inscrit à l'historique : {}", e);
+        }
+    }
+
+    /// Lots les plus récents (au plus `MAX_LISTED_ENTRIES`), du plus récent au plus ancien.
+    /// Une ligne illisible (options d'une version incompatible...) est ignorée.
+    pub fn entries(&self) -> Result<Vec<HistoryEntry>, ConverterError> {
+        let Some(connection) = &self.connection else {
+            return Ok(Vec::new());
+        };
+        let mut statement = connection
+            .prepare(
+                "SELECT id, finished_at, label, job, output_root, converted, skipped, failed, cancelled, input_bytes, output_bytes
+                 FROM jobs ORDER BY id DESC LIMIT ?1",
+            )
+            .map_err(history_error)?;
+        let rows = statement
+            .query_map([MAX_LISTED_ENTRIES as i64], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    [row.get::<_, i64>(5)?, row.get::<_, i64>(6)?, row.get::<_, i64>(7)?],
+                    row.get::<_, bool>(8)?,
+                    [row.get::<_, i64>(9)?, row.get::<_, i64>(10)?],
+                ))
+            })
+            .map_err(history_error)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (id, finished_at, label, job, output_root, [converted, skipped, failed], cancelled, [input_bytes, output_bytes]) =
+                row.map_err(history_error)?;
+            let (Ok(finished_at), Ok(job)) = (DateTime::parse_from_rfc3339(&finished_at), serde_json::from_str(&job)) else {
+                continue;
+            };
+            entries.push(HistoryEntry {
+                id,
+                finished_at: finished_at.with_timezone(&Local),
+                label,
+                job,
+                output_root: PathBuf::from(output_root),
+                converted: converted as usize,
+                skipped: skipped as usize,
+                failed: failed as usize,
+                cancelled,
+                input_bytes: input_bytes as u64,
+                output_bytes: output_bytes as u64,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Retire le lot `id` de l'historique.
+    pub fn remove(&self, id: i64) -> Result<(), ConverterError> {
+        self.execute("DELETE FROM jobs WHERE id = ?1", [id])
+    }
+
+    /// Vide l'historique.
+    pub fn clear(&self) -> Result<(), ConverterError> {
+        self.execute("DELETE FROM jobs", [])
+    }
+
+    /// Exécute une modification de la base.
+    fn execute(&self, sql: &str, params: impl rusqlite::Params) -> Result<(), ConverterError> {
+        match &self.connection {
+            Some(connection) => connection.execute(sql, params).map(|_| ()).map_err(history_error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Ouvre la base dans le dossier de configuration, en créant le dossier et la table si besoin.
+fn open_connection() -> Result<Connection, ConverterError> {
+    let dir = config::app_config_dir()
+        .ok_or_else(|| ConverterError::Config(tr!("Dossier de configuration introuvable").to_string()))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| ConverterError::io(tr!("Impossible de créer le dossier de configuration {}", dir.display()), e))?;
+    let connection = Connection::open(dir.join(HISTORY_FILE_NAME)).map_err(history_error)?;
+    connection
+        .execute_batch(
+            "CREATE TABLE IF NOT EXISTS jobs (
+                id INTEGER PRIMARY KEY,
+                finished_at TEXT NOT NULL,
+                label TEXT NOT NULL,
+                job TEXT NOT NULL,
+                output_root TEXT NOT NULL,
+                converted INTEGER NOT NULL,
+                skipped INTEGER NOT NULL,
+                failed INTEGER NOT NULL,
+                cancelled INTEGER NOT NULL,
+                input_bytes INTEGER NOT NULL,
+                output_bytes INTEGER NOT NULL
+            )",
+        )
+        .map_err(history_error)?;
+    Ok(connection)
+}
+
+/// Erreur de la base de l'historique.
+fn history_error(e: rusqlite::Error) -> ConverterError {
+    ConverterError::Config(tr!("Historique des lots inaccessible : {}", e))
+}
@@ -0,0 +1,94 @@
+/// Ce module traduit les textes de l'interface. Le texte français sert de clé (à la manière de gettext) :
+/// il reste lisible dans le code, et le catalogue `locales/en.toml` associe à chaque clé sa traduction anglaise.
+/// Un texte absent du catalogue s'affiche en français.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::LazyLock;
+
+/// Catalogue anglais : texte français → traduction.
+const ENGLISH_CATALOG: &str = include_str!("../locales/en.toml");
+
+/// Marque de remplacement des valeurs dans les textes à compléter (ex: "{} fichier(s)").
+const PLACEHOLDER: &str = "{}";
+
+/// Langue de l'interface.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Language {
+    French,  // Français (langue des textes sources)
+    English, // Anglais
+}
+
+impl Language {
+    /// Langues proposées dans l'interface.
+    pub const ALL: [Language; 2] = [Language::French, Language::English];
+
+    /// Nom de la langue, dans cette langue.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::French => "Français",
+            Language::English => "English",
+        }
+    }
+}
+
+/// Langue actuelle de l'interface (index dans `Language::ALL`), partagée par tous les threads.
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Traductions anglaises, lues une seule fois. Un catalogue illisible laisse l'interface en français.
+static ENGLISH: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
+    toml::from_str(ENGLISH_CATALOG).unwrap_or_else(|e| {
+        tracing::warn!("Catalogue de traduction anglais illisible : {}", e);
+        HashMap::new()
+    })
+});
+
+/// Change la langue de l'interface.
+pub fn set_language(language: Language) {
+    let index = Language::ALL.iter().position(|&candidate| candidate == language).unwrap_or(0);
+    CURRENT.store(index as u8, Ordering::Relaxed);
+}
+
+/// Langue actuelle de l'interface.
+pub fn language() -> Language {
+    Language::ALL.get(CURRENT.load(Ordering::Relaxed) as usize).copied().unwrap_or(Language::French)
+}
+
+/// Traduction de `text` dans la langue actuelle, `text` lui-même en français ou sans traduction.
+pub fn tr(text: &'static str) -> &'static str {
+    match language() {
+        Language::French => text,
+        Language::English => ENGLISH.get(text).map_or(text, |translation| translation.as_str()),
+    }
+}
+
+/// Remplace, dans l'ordre, chaque `{}` de `template` par la valeur correspondante de `values`.
+pub fn fill(template: &str, values: &[&dyn Display]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut values = values.iter();
+    let mut rest = template;
+    while let Some(index) = rest.find(PLACEHOLDER) {
+        filled.push_str(&rest[..index]);
+        match values.next() {
+            Some(value) => filled.push_str(&value.to_string()),
+            None => filled.push_str(PLACEHOLDER),
+        }
+        rest = &rest[index + PLACEHOLDER.len()..];
+    }
+    filled.push_str(rest);
+    filled
+}
+
+/// Texte traduit, complété par les valeurs données (chaque `{}` est remplacé dans l'ordre) :
+/// `tr!("Annuler")` donne un `&'static str`, `tr!("{} fichier(s)", count)` une `String`.
+/// Les valeurs à mettre en forme (précision, `Debug`...) sont formatées au préalable.
+#[macro_export]
+macro_rules! tr {
+    ($text:literal) => {
+        $crate::i18n::tr($text)
+    };
+    ($text:literal, $($value:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::tr($text), &[$(&$value),+])
+    };
+}
@@ -0,0 +1,164 @@
+/// Ce module fournit le point d'entrée de la bibliothèque : une conversion décrite par un `ConversionJob`
+/// (sources, répertoire de sortie, options) et exécutée par `run_job`, sans interface graphique.
+/// L'application et la ligne de commande passent par lui ; les fonctions de `converter` restent disponibles
+/// pour les usages plus fins (un fichier à la fois, relance des échecs).
+/// Une conversion lancée dans un thread rend compte par un canal d'évènements typés (`JobEvent`).
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+
+use crate::converter::{
+    self, BatchControl, CollisionPreflight, ConversionOptions, ConversionReport, FileStatus, OverwriteMode, Progress, ProgressFn,
+};
+use crate::error::ConverterError;
+use crate::platform_utils;
+use crate::run_folder;
+use crate::throughput::Throughput;
+use crate::tr;
+
+/// Sources d'une conversion.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum JobInput {
+    File(PathBuf),       // Une seule image
+    Files(Vec<PathBuf>), // Plusieurs images indépendantes, converties à la racine de la sortie
+    Directory(PathBuf),  // Toutes les images d'un répertoire et de ses sous-répertoires, arborescence reproduite
+}
+
+impl JobInput {
+    /// Sources correspondant à `path` : un répertoire ou un fichier. Erreur si le chemin n'existe pas.
+    pub fn from_path(path: PathBuf) -> Result<JobInput, ConverterError> {
+        if path.is_dir() {
+            Ok(JobInput::Directory(path))
+        } else if path.is_file() {
+            Ok(JobInput::File(path))
+        } else {
+            Err(ConverterError::PathValidation(tr!("Entrée introuvable : {}", path.display())))
+        }
+    }
+
+    /// Description courte des sources pour le journal (chemin, ou nombre de fichiers).
+    fn description(&self) -> String {
+        match self {
+            JobInput::File(path) | JobInput::Directory(path) => path.display().to_string(),
+            JobInput::Files(paths) => tr!("{} fichier(s)", paths.len()),
+        }
+    }
+}
+
+/// Conversion à exécuter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversionJob {
+    pub input: JobInput,            // Sources à convertir.
+    pub output_dir: PathBuf,        // Répertoire de sortie (créé si besoin).
+    pub options: ConversionOptions, // Format, qualité, traitements et gestion des fichiers existants.
+    #[serde(default)]
+    pub run_folder: Option<PathBuf>, // Sous-dossier de l'exécution déjà créé dans `output_dir`, où les sorties sont écrites.
+}
+
+impl ConversionJob {
+    /// Le lot avec le sous-dossier de l'exécution (`options.run_folder`) créé dans `output_dir` ; `preset` remplace
+    /// `{preset}` dans son nom. Le lot est inchangé sans l'option ou si ce dossier est déjà créé (reprise d'un lot
+    /// interrompu). `run_job` s'en charge : l'appeler avant permet de connaître le dossier dès le lancement.
+    pub fn with_run_folder(&self, preset: Option<&str>) -> Result<ConversionJob, ConverterError> {
+        if self.run_folder.is_some() {
+            return Ok(self.clone());
+        }
+        let run_folder = run_folder::prepare(&self.output_dir, &self.options, preset)?;
+        Ok(ConversionJob { run_folder, ..self.clone() })
+    }
+
+    /// Répertoire où les sorties sont écrites : le sous-dossier de l'exécution s'il est créé, sinon `output_dir`.
+    pub fn target_dir(&self) -> &Path {
+        self.run_folder.as_deref().unwrap_or(&self.output_dir)
+    }
+}
+
+/// Évènement d'une conversion en cours, envoyé par les threads de conversion sur un canal.
+#[derive(Debug)]
+pub enum JobEvent {
+    FileStarted(PathBuf),                             // Conversion d'un fichier commencée
+    FileDone { source: PathBuf, status: FileStatus }, // Fichier terminé : écrit, ignoré ou prévu (simulation)
+    FileFailed { source: PathBuf, error: String },    // Conversion d'un fichier en échec, avec son message
+    Progress { completed: usize, total: usize, throughput: Throughput }, // Avancement du lot
+    BatchDone(Box<Result<ConversionReport, ConverterError>>), // Lot terminé, avec son bilan (volumineux, d'où la boîte)
+}
+
+/// Rappel de progression qui traduit chaque avancement en évènements envoyés sur `events`, puis appelle `wake`
+/// (ex: rafraîchir l'interface). Un récepteur disparu n'interrompt pas la conversion.
+pub fn event_progress(events: Sender<JobEvent>, wake: impl Fn() + Sync + Send) -> impl Fn(Progress) + Sync + Send {
+    move |progress: Progress| {
+        if let Some((source, status)) = progress.file {
+            let source = source.to_path_buf();
+            let event = match status {
+                FileStatus::Running => JobEvent::FileStarted(source),
+                FileStatus::Failed(error) => JobEvent::FileFailed { source, error },
+                status => JobEvent::FileDone { source, status },
+            };
+            let _ = events.send(event);
+        }
+        let _ = events.send(JobEvent::Progress {
+            completed: progress.completed,
+            total: progress.total,
+            throughput: progress.throughput,
+        });
+        wake();
+    }
+}
+
+/// Exécute `job` dans le thread appelant et retourne son bilan. `progress` est appelé après chaque fichier
+/// (depuis les threads de conversion) ; `control` permet de suspendre ou d'annuler depuis un autre thread.
+/// Les chemins sont d'abord validés (voir `validate_paths`) ; le sous-dossier de l'exécution est créé si l'option
+/// est active, et retiré à la fin s'il est resté vide.
+pub fn run_job(job: &ConversionJob, progress: &ProgressFn, control: &BatchControl) -> Result<ConversionReport, ConverterError> {
+    let job = job.with_run_folder(None)?;
+    tracing::info!(input = %job.input.description(), output = %job.target_dir().display(), "lot lancé");
+    let result = validate_paths(&job).and_then(|()| convert(&job, &job.options, progress, control));
+    let result = run_folder::attach(result, job.run_folder.as_deref());
+    match &result {
+        Ok(report) => tracing::info!(
+            converted = report.converted(),
+            skipped = report.skipped(),
+            failed = report.failures.len(),
+            cancelled = report.cancelled,
+            "lot terminé"
+        ),
+        Err(e) => tracing::error!(error = %e, "lot interrompu"),
+    }
+    result
+}
+
+/// Relève, sans rien écrire, les sorties de `job` déjà présentes dans son répertoire de sortie (simulation en mode
+/// Écraser, voir `CollisionPreflight`). `control` permet d'abandonner le relevé depuis un autre thread.
+pub fn preflight_collisions(job: &ConversionJob, control: &BatchControl) -> Result<CollisionPreflight, ConverterError> {
+    let options = ConversionOptions {
+        dry_run: true,
+        overwrite_mode: OverwriteMode::Overwrite,
+        ..job.options.clone()
+    };
+    let report = convert(job, &options, &|_| {}, control)?;
+    Ok(CollisionPreflight::from_report(&report))
+}
+
+/// Vérifie que la sortie de `job` n'est ni son répertoire source ni l'un de ses sous-répertoires : les formats
+/// de sortie étant aussi des formats d'entrée, une nouvelle exécution reconvertirait sinon ses propres sorties.
+fn validate_paths(job: &ConversionJob) -> Result<(), ConverterError> {
+    match &job.input {
+        JobInput::Directory(dir) => platform_utils::validate_paths(dir, &job.output_dir),
+        JobInput::File(_) | JobInput::Files(_) => Ok(()),
+    }
+}
+
+/// Convertit les sources de `job` avec `options` (celles du lot, ou leur variante pour une simulation).
+fn convert(
+    job: &ConversionJob,
+    options: &ConversionOptions,
+    progress: &ProgressFn,
+    control: &BatchControl,
+) -> Result<ConversionReport, ConverterError> {
+    let output_dir = job.target_dir();
+    match &job.input {
+        JobInput::File(path) => converter::convert_single_image(path, output_dir, options, progress, control),
+        JobInput::Files(paths) => converter::convert_multiple_files(paths, output_dir, options, progress, control),
+        JobInput::Directory(dir) => converter::convert_images_in_directory(dir, output_dir, dir, options, progress, control),
+    }
+}
@@ -0,0 +1,169 @@
+/// Ce module conserve sur disque les lots lancés depuis l'interface (`interrupted_jobs.json`, dans le dossier
+/// de configuration) : la conversion à exécuter et l'état de chaque fichier déjà traité. Si l'application ou la machine
+/// s'arrête en cours de lot, le journal permet de proposer au lancement suivant de reprendre le travail interrompu,
+/// sans reconvertir les fichiers déjà traités. Un lot terminé ou annulé est retiré du journal.
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::config;
+use crate::error::ConverterError;
+use crate::job::ConversionJob;
+use crate::job_manager::JobId;
+use crate::tr;
+
+/// Nom du fichier du journal, dans le dossier de configuration de l'application.
+const JOURNAL_FILE_NAME: &str = "interrupted_jobs.json";
+
+/// Délai minimal entre deux enregistrements pendant une conversion : un lot de milliers de petits fichiers
+/// n'écrit pas le journal après chacun (au pire, les derniers fichiers sont reconvertis à la reprise).
+const SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// État d'un fichier traité, tel qu'enregistré dans le journal.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum SavedStatus {
+    Done,   // Écrit ou ignoré : il n'est pas reconverti à la reprise
+    Failed, // En échec : il est retenté à la reprise
+}
+
+/// Fichier traité d'un lot enregistré.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedFile {
+    pub source: PathBuf,     // Image source
+    pub status: SavedStatus, // État du fichier
+}
+
+/// Lot enregistré dans le journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedJob {
+    pub label: String,         // Nom affiché du lot
+    pub job: ConversionJob,    // Sources, sortie et options de la conversion (sous-dossier d'exécution compris)
+    pub output_root: PathBuf,  // Dossier proposé à l'ouverture une fois le lot terminé
+    pub files: Vec<SavedFile>, // Fichiers déjà traités, dans l'ordre
+}
+
+impl SavedJob {
+    /// Sources à ne pas reconvertir à la reprise.
+    pub fn completed(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.files.iter().filter(|file| file.status == SavedStatus::Done).map(|file| file.source.clone())
+    }
+}
+
+/// Lot de la session inscrit au journal.
+#[derive(Debug)]
+struct TrackedJob {
+    id: JobId,              // Identifiant du lot dans le gestionnaire
+    saved: SavedJob,        // Contenu enregistré
+    done: HashSet<PathBuf>, // Sources déjà consignées comme traitées (une reprise les signale à nouveau)
+}
+
+/// Journal des lots de la session et des lots interrompus d'une session précédente, en attente d'une décision.
+#[derive(Debug, Default)]
+pub struct JobJournal {
+    interrupted: Vec<SavedJob>,   // Lots interrompus lors d'une session précédente
+    running: Vec<TrackedJob>,     // Lots de la session, jusqu'à leur fin
+    dirty_since: Option<Instant>, // Date du premier changement non enregistré
+}
+
+impl JobJournal {
+    /// Charge le journal : les lots qu'il contient ont été interrompus lors d'une session précédente.
+    /// Un journal absent ou illisible ne contient aucun lot.
+    pub fn load() -> Self {
+        let interrupted = journal_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { interrupted, ..Default::default() }
+    }
+
+    /// Lots interrompus lors d'une session précédente.
+    pub fn interrupted(&self) -> &[SavedJob] {
+        &self.interrupted
+    }
+
+    /// Retire les lots interrompus du journal pour les reprendre (ils y reviennent avec `track`).
+    pub fn take_interrupted(&mut self) -> Vec<SavedJob> {
+        self.dirty_since.get_or_insert_with(Instant::now);
+        std::mem::take(&mut self.interrupted)
+    }
+
+    /// Abandonne les lots interrompus et enregistre le journal.
+    pub fn discard_interrupted(&mut self) {
+        self.interrupted.clear();
+        self.save();
+    }
+
+    /// Ajoute le lot `id` au journal et l'enregistre aussitôt.
+    pub fn track(&mut self, id: JobId, saved: SavedJob) {
+        let done = saved.completed().collect();
+        self.running.push(TrackedJob { id, saved, done });
+        self.save();
+    }
+
+    /// Consigne un fichier traité par le lot `id` ; le journal est enregistré au plus tard après `SAVE_INTERVAL`.
+    pub fn record(&mut self, id: JobId, source: &Path, status: SavedStatus) {
+        let Some(tracked) = self.running.iter_mut().find(|tracked| tracked.id == id) else {
+            return;
+        };
+        if status == SavedStatus::Done && !tracked.done.insert(source.to_path_buf()) {
+            return;
+        }
+        tracked.saved.files.push(SavedFile { source: source.to_path_buf(), status });
+        self.dirty_since.get_or_insert_with(Instant::now);
+    }
+
+    /// Retire du journal le lot `id`, terminé ou annulé, et enregistre le journal.
+    pub fn finish(&mut self, id: JobId) {
+        let count = self.running.len();
+        self.running.retain(|tracked| tracked.id != id);
+        if self.running.len() != count {
+            self.save();
+        }
+    }
+
+    /// Enregistre le journal si des changements attendent depuis `SAVE_INTERVAL`.
+    pub fn save_if_due(&mut self) {
+        if self.dirty_since.is_some_and(|since| since.elapsed() >= SAVE_INTERVAL) {
+            self.save();
+        }
+    }
+
+    /// Enregistre le journal ; sans lot en attente ni en cours, le fichier est supprimé.
+    /// Un échec est consigné dans le journal de l'application sans interrompre les conversions.
+    pub fn save(&mut self) {
+        self.dirty_since = None;
+        if let Err(e) = self.write() {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    /// Écrit le journal (lots interrompus puis lots de la session), en créant son dossier si besoin.
+    fn write(&self) -> Result<(), ConverterError> {
+        let path = journal_path().ok_or_else(|| ConverterError::Config(tr!("Dossier de configuration introuvable").to_string()))?;
+        let jobs: Vec<&SavedJob> = self.interrupted.iter().chain(self.running.iter().map(|tracked| &tracked.saved)).collect();
+        if jobs.is_empty() {
+            return match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(ConverterError::io(tr!("Impossible de supprimer le journal des lots {}", path.display()), e))
+                }
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                ConverterError::io(tr!("Impossible de créer le dossier de configuration {}", parent.display()), e)
+            })?;
+        }
+        let content = serde_json::to_string(&jobs)
+            .map_err(|e| ConverterError::Config(tr!("Impossible de sérialiser le journal des lots : {}", e)))?;
+        fs::write(&path, content)
+            .map_err(|e| ConverterError::io(tr!("Impossible d'écrire le journal des lots {}", path.display()), e))
+    }
+}
+
+/// Emplacement du journal, `None` si le système n'a pas de dossier de configuration.
+fn journal_path() -> Option<PathBuf> {
+    config::app_config_dir().map(|dir| dir.join(JOURNAL_FILE_NAME))
+}
@@ -0,0 +1,217 @@
+/// Ce module gère les lots de conversion lancés depuis l'interface : plusieurs lots peuvent être lancés
+/// sans attendre la fin des précédents, chacun avec son avancement, sa pause, son annulation et son bilan.
+/// Au-delà de `MAX_RUNNING_JOBS` lots simultanés, les suivants attendent et démarrent dès qu'un lot se termine.
+/// Chaque lot s'exécute dans son propre thread et rend compte par son canal d'évènements (`JobEvent`).
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+
+use crate::clipboard::CopyContent;
+use crate::converter::{BatchControl, ConversionReport, ProgressFn};
+use crate::error::ConverterError;
+use crate::job::{self, ConversionJob, JobEvent};
+use crate::throughput::Throughput;
+use crate::InputType;
+
+/// Nombre maximal de lots convertis en même temps : chacun répartit déjà ses fichiers sur plusieurs threads.
+pub const MAX_RUNNING_JOBS: usize = 2;
+
+/// Identifiant d'un lot, unique pendant la session.
+pub type JobId = u64;
+
+/// Travail d'un lot, exécuté dans son thread avec le rappel de progression et les commandes du lot.
+pub type JobWork = Box<dyn FnOnce(&ProgressFn, &BatchControl) -> Result<ConversionReport, ConverterError> + Send>;
+
+/// État d'un lot.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JobState {
+    Queued,   // En attente d'une place parmi les lots simultanés
+    Running,  // Conversion en cours
+    Finished, // Terminé (bilan disponible), jusqu'à ce que l'utilisateur le retire de la liste
+}
+
+/// Description d'un lot, conservée pour présenter son bilan.
+pub struct JobSpec {
+    pub label: String,                           // Nom affiché (fichier, répertoire ou nombre de fichiers)
+    pub input: Option<InputType>,                // Sélection convertie, `None` pour une relance des échecs
+    pub output_root: PathBuf,                    // Dossier proposé à l'ouverture une fois le lot terminé
+    pub single_file: bool,                       // Lot d'un fichier unique (ou d'une image collée)
+    pub retry_of: Option<Box<ConversionReport>>, // Bilan du lot dont les échecs sont relancés
+    pub job: Option<ConversionJob>,              // Conversion du lot, inscrite à l'historique (sauf image collée ou relance)
+}
+
+/// Modification de la liste des lots demandée depuis l'interface.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JobAction {
+    ShowReport(JobId),              // Afficher le bilan d'un lot terminé
+    CopyOutput(JobId, CopyContent), // Copier le fichier produit par un lot d'une image unique
+    Remove(JobId),                  // Retirer un lot terminé de la liste, ou abandonner un lot en attente
+}
+
+/// Lot suivi par le gestionnaire.
+pub struct ManagedJob {
+    pub id: JobId,                        // Identifiant du lot
+    pub spec: JobSpec,                    // Description du lot
+    pub state: JobState,                  // En attente, en cours ou terminé
+    pub control: Arc<BatchControl>,       // Pause, annulation et conflits, partagés avec le thread du lot
+    pub counts: (usize, usize),           // Fichiers traités / à traiter
+    pub throughput: Throughput,           // Débit et temps restant
+    pub current_file: Option<PathBuf>,    // Dernier fichier dont la conversion a commencé
+    pub outcome: Option<(String, bool)>,  // Bilan affiché une fois le lot terminé (message, erreur)
+    pub report: Option<ConversionReport>, // Bilan détaillé du lot terminé
+    events: Option<Receiver<JobEvent>>,   // Évènements envoyés par le thread du lot en cours
+    work: Option<JobWork>,                // Travail à lancer, tant que le lot est en attente
+}
+
+impl ManagedJob {
+    /// Avancement du lot, de 0.0 à 1.0.
+    pub fn progress(&self) -> f32 {
+        match self.counts {
+            (_, 0) => 0.0,
+            (completed, total) => completed as f32 / total as f32,
+        }
+    }
+
+    /// Fichier produit par le lot terminé, s'il portait sur une image unique (ou collée).
+    pub fn single_output(&self) -> Option<&Path> {
+        match self.report.as_ref()?.files.as_slice() {
+            [file] if self.spec.single_file => Some(&file.output),
+            _ => None,
+        }
+    }
+
+    /// Vrai si le lot est en attente ou en cours.
+    pub fn is_active(&self) -> bool {
+        self.state != JobState::Finished
+    }
+}
+
+/// Lots de la session, dans l'ordre de lancement.
+#[derive(Default)]
+pub struct JobManager {
+    jobs: Vec<ManagedJob>, // Lots en attente, en cours ou terminés
+    next_id: JobId,        // Identifiant du prochain lot
+}
+
+impl JobManager {
+    /// Ajoute un lot et le lance si une place est libre. Retourne son identifiant.
+    pub fn submit(&mut self, ctx: &egui::Context, spec: JobSpec, work: JobWork) -> JobId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.push(ManagedJob {
+            id,
+            spec,
+            state: JobState::Queued,
+            control: Arc::new(BatchControl::default()),
+            counts: (0, 0),
+            throughput: Throughput::default(),
+            current_file: None,
+            outcome: None,
+            report: None,
+            events: None,
+            work: Some(work),
+        });
+        self.start_queued(ctx);
+        id
+    }
+
+    /// Applique les évènements reçus des lots en cours (avancement, fichier en cours) et lance les lots
+    /// en attente quand une place se libère. Retourne, dans l'ordre, les évènements des fichiers
+    /// et les bilans (`BatchDone`) que l'interface doit encore traiter.
+    pub fn poll(&mut self, ctx: &egui::Context) -> Vec<(JobId, JobEvent)> {
+        let mut forwarded = Vec::new();
+        for managed in self.jobs.iter_mut().filter(|managed| managed.state == JobState::Running) {
+            let Some(events) = &managed.events else {
+                continue;
+            };
+            for event in events.try_iter().collect::<Vec<_>>() {
+                match event {
+                    JobEvent::Progress { completed, total, throughput } => {
+                        managed.counts = (completed, total);
+                        managed.throughput = throughput;
+                    }
+                    JobEvent::FileStarted(source) => {
+                        managed.current_file = Some(source.clone());
+                        forwarded.push((managed.id, JobEvent::FileStarted(source)));
+                    }
+                    JobEvent::BatchDone(result) => {
+                        managed.state = JobState::Finished;
+                        managed.events = None;
+                        managed.current_file = None;
+                        forwarded.push((managed.id, JobEvent::BatchDone(result)));
+                    }
+                    event => forwarded.push((managed.id, event)),
+                }
+            }
+        }
+        self.start_queued(ctx);
+        forwarded
+    }
+
+    /// Lance les lots en attente, dans l'ordre, tant qu'il reste des places.
+    fn start_queued(&mut self, ctx: &egui::Context) {
+        let mut running = self.jobs.iter().filter(|managed| managed.state == JobState::Running).count();
+        for managed in self.jobs.iter_mut().filter(|managed| managed.state == JobState::Queued) {
+            if running >= MAX_RUNNING_JOBS {
+                break;
+            }
+            let Some(work) = managed.work.take() else {
+                continue;
+            };
+            let (sender, receiver) = mpsc::channel();
+            managed.events = Some(receiver);
+            managed.state = JobState::Running;
+            running += 1;
+
+            let control = Arc::clone(&managed.control);
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let wake_ctx = ctx.clone();
+                let progress = job::event_progress(sender.clone(), move || wake_ctx.request_repaint());
+                let result = work(&progress, &control);
+                // Une erreur signifie que l'application se ferme : le bilan est sans objet.
+                let _ = sender.send(JobEvent::BatchDone(Box::new(result)));
+                ctx.request_repaint();
+            });
+        }
+    }
+
+    /// Lots de la session, dans l'ordre de lancement.
+    pub fn jobs(&self) -> &[ManagedJob] {
+        &self.jobs
+    }
+
+    /// Lot `id`, s'il est encore dans la liste.
+    pub fn job_mut(&mut self, id: JobId) -> Option<&mut ManagedJob> {
+        self.jobs.iter_mut().find(|managed| managed.id == id)
+    }
+
+    /// Lots en attente ou en cours.
+    pub fn active(&self) -> impl Iterator<Item = &ManagedJob> {
+        self.jobs.iter().filter(|managed| managed.is_active())
+    }
+
+    /// Vrai si un lot est en attente ou en cours.
+    pub fn is_busy(&self) -> bool {
+        self.active().next().is_some()
+    }
+
+    /// Vrai si la sélection `input` est en cours de conversion (ou en attente).
+    pub fn is_converting(&self, input: &InputType) -> bool {
+        self.active().any(|managed| managed.spec.input.as_ref() == Some(input))
+    }
+
+    /// Annule tous les lots : ceux en cours s'arrêtent après leurs fichiers en cours, ceux en attente sont abandonnés.
+    pub fn cancel_all(&mut self) {
+        self.jobs.retain(|managed| managed.state != JobState::Queued);
+        for managed in self.active() {
+            managed.control.cancel();
+        }
+    }
+
+    /// Retire le lot `id` de la liste s'il est terminé ou en attente ; un lot en cours doit d'abord être annulé.
+    pub fn remove(&mut self, id: JobId) {
+        self.jobs.retain(|managed| managed.id != id || managed.state == JobState::Running);
+    }
+}
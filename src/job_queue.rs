@@ -0,0 +1,128 @@
+/// Ce module gère la file d'attente affichée dans l'interface : chaque fichier de la sélection,
+/// sa taille, son état de conversion (en attente, en cours, converti, ignoré, en échec) et la taille produite.
+/// Elle est mise à jour depuis les threads de conversion via le rappel de progression.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::converter::FileStatus;
+use crate::InputType;
+use crate::tr;
+
+/// État d'un fichier de la file.
+#[derive(Debug, PartialEq, Clone)]
+pub enum QueueStatus {
+    Pending,        // En attente de conversion.
+    Running,        // Conversion en cours.
+    Done,           // Converti.
+    Skipped,        // Ignoré (sortie existante, source inchangée ou filtrée).
+    Failed(String), // En échec, avec son message.
+    Planned,        // Serait converti (simulation).
+}
+
+impl QueueStatus {
+    /// Libellé affiché dans la file.
+    pub fn label(&self) -> &'static str {
+        match self {
+            QueueStatus::Pending => tr!("En attente"),
+            QueueStatus::Running => tr!("En cours"),
+            QueueStatus::Done => tr!("Converti"),
+            QueueStatus::Skipped => tr!("Ignoré"),
+            QueueStatus::Failed(_) => tr!("Échec"),
+            QueueStatus::Planned => tr!("Prévu"),
+        }
+    }
+}
+
+/// Fichier de la file.
+#[derive(Debug, Clone)]
+pub struct QueueEntry {
+    pub source: PathBuf,          // Image source.
+    pub size: Option<u64>,        // Taille de la source (octets), `None` si illisible.
+    pub status: QueueStatus,      // État de conversion.
+    pub output_size: Option<u64>, // Taille du fichier produit, une fois converti.
+}
+
+/// File des fichiers de la sélection, dans l'ordre de la sélection (ou de découverte pour un répertoire).
+#[derive(Debug, Default)]
+pub struct JobQueue {
+    entries: Vec<QueueEntry>,           // Fichiers de la file.
+    positions: HashMap<PathBuf, usize>, // Position de chaque source dans `entries`.
+}
+
+impl JobQueue {
+    /// Construit la file d'une sélection. Les fichiers d'un répertoire n'y figurent qu'une fois
+    /// découverts par la conversion ; un fichier unique ou plusieurs fichiers y sont listés d'emblée.
+    pub fn from_input(input: Option<&InputType>) -> Self {
+        let mut queue = Self::default();
+        match input {
+            Some(InputType::SingleFile(path)) => queue.push(path),
+            Some(InputType::MultipleFiles(paths)) => paths.iter().for_each(|path| queue.push(path)),
+            Some(InputType::Directory(_) | InputType::Clipboard(_)) | None => {}
+        }
+        queue
+    }
+
+    /// Fichiers de la file.
+    pub fn entries(&self) -> &[QueueEntry] {
+        &self.entries
+    }
+
+    /// Nombre de fichiers dans chaque état : (en attente ou en cours, convertis ou prévus, ignorés, en échec).
+    pub fn counts(&self) -> (usize, usize, usize, usize) {
+        self.entries.iter().fold((0, 0, 0, 0), |(pending, done, skipped, failed), entry| match entry.status {
+            QueueStatus::Pending | QueueStatus::Running => (pending + 1, done, skipped, failed),
+            QueueStatus::Done | QueueStatus::Planned => (pending, done + 1, skipped, failed),
+            QueueStatus::Skipped => (pending, done, skipped + 1, failed),
+            QueueStatus::Failed(_) => (pending, done, skipped, failed + 1),
+        })
+    }
+
+    /// Remet en attente les fichiers des sources indiquées (ex: relance des échecs).
+    pub fn reset(&mut self, sources: &[PathBuf]) {
+        for source in sources {
+            if let Some(&position) = self.positions.get(source) {
+                let entry = &mut self.entries[position];
+                entry.status = QueueStatus::Pending;
+                entry.output_size = None;
+            }
+        }
+    }
+
+    /// Applique un changement d'état signalé par la conversion ; une source inconnue (fichier
+    /// découvert dans un répertoire) est ajoutée à la fin de la file.
+    pub fn update(&mut self, source: &Path, status: FileStatus) {
+        let position = match self.positions.get(source) {
+            Some(&position) => position,
+            None => {
+                self.push(source);
+                self.entries.len() - 1
+            }
+        };
+        let entry = &mut self.entries[position];
+        match status {
+            FileStatus::Running => entry.status = QueueStatus::Running,
+            FileStatus::Done(output_size) => {
+                entry.status = QueueStatus::Done;
+                entry.output_size = Some(output_size);
+            }
+            FileStatus::Skipped => entry.status = QueueStatus::Skipped,
+            FileStatus::Failed(error) => entry.status = QueueStatus::Failed(error),
+            FileStatus::Planned => entry.status = QueueStatus::Planned,
+        }
+    }
+
+    /// Ajoute une source en attente à la fin de la file (ignorée si elle y figure déjà).
+    fn push(&mut self, source: &Path) {
+        if self.positions.contains_key(source) {
+            return;
+        }
+        self.positions.insert(source.to_path_buf(), self.entries.len());
+        self.entries.push(QueueEntry {
+            source: source.to_path_buf(),
+            size: fs::metadata(source).ok().map(|metadata| metadata.len()),
+            status: QueueStatus::Pending,
+            output_size: None,
+        });
+    }
+}
@@ -0,0 +1,40 @@
+//! Bibliothèque de conversion du Convertisseur d'Images, utilisable sans l'interface graphique.
+//! Une conversion est décrite par un `ConversionJob` (sources, répertoire de sortie, `ConversionOptions`)
+//! et exécutée par `run_job`, qui retourne un `ConversionReport`. `BatchControl` suspend ou annule
+//! un lot depuis un autre thread ; `ProgressFn` reçoit l'avancement de chaque fichier.
+//! Les modules exposent aussi les étapes de la conversion : décodage (RAW compris), traitements,
+//! encodage, métadonnées, vérification des sorties, manifeste du mode incrémental, annulation d'une conversion
+//! et validation des chemins.
+
+pub mod animation; // Contient la lecture des images des animations (GIF, PNG et WebP animés)
+pub mod archive; // Contient le regroupement des fichiers produits dans une archive ZIP
+pub mod capture_date; // Contient la lecture de la date de prise de vue (EXIF) des photos
+pub mod color_profile; // Contient le traitement des profils de couleur ICC
+pub mod contact_sheet; // Contient la composition des planches contact
+pub mod converter; // Contient la logique de conversion d'images et l'enum OverwriteMode
+pub mod duplicates; // Contient la détection des images en double par empreinte perceptuelle
+pub mod encoder; // Contient l'encodage dans les différents formats de sortie
+pub mod error; // Contient le type d'erreur ConverterError et ses messages
+pub mod filters; // Contient les filtres de sélection des sources (dimensions, orientation)
+pub mod i18n; // Contient la traduction de l'interface (français, anglais)
+pub mod job; // Contient la description et l'exécution d'une conversion (point d'entrée de la bibliothèque)
+pub mod manifest; // Contient le manifeste des conversions utilisé par le mode incrémental
+pub mod metadata; // Contient la suppression des métadonnées des fichiers produits
+pub mod originals; // Contient le traitement des sources après leur conversion (corbeille, sauvegarde)
+pub mod pdf; // Contient le rendu des pages des documents PDF en images
+pub mod pdf_bundle; // Contient la réunion des images produites en un document PDF
+pub mod platform_utils; // Contient des utilitaires spécifiques à la plateforme et de validation de chemin
+pub mod raw; // Contient le décodage des fichiers RAW des appareils photo
+pub mod report_export; // Contient l'export du rapport de conversion en CSV ou JSON
+pub mod responsive; // Contient la génération des jeux d'images responsives (plusieurs largeurs, srcset)
+pub mod run_folder; // Contient la création du sous-dossier horodaté de chaque exécution
+pub mod sprite_sheet; // Contient l'assemblage des images produites en planche de sprites avec son atlas JSON
+pub mod targets; // Contient les destinations multiples d'une conversion (format et taille par destination)
+pub mod throughput; // Contient la mesure du débit et du temps restant d'une conversion en cours
+pub mod transform; // Contient les traitements d'image appliqués avant l'encodage
+pub mod undo; // Contient l'annulation d'une conversion terminée (suppression des fichiers créés)
+pub mod verify; // Contient la vérification des fichiers produits (relecture, PSNR et SSIM)
+
+pub use converter::{BatchControl, ConversionOptions, ConversionReport, Progress, ProgressFn};
+pub use error::ConverterError;
+pub use job::{run_job, ConversionJob, JobEvent, JobInput};
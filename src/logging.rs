@@ -0,0 +1,144 @@
+/// Ce module met en place la journalisation (`tracing`) : les évènements de la conversion (fichier converti, ignoré
+/// ou en échec, avertissements, début et fin des lots) sont écrits dans un fichier du dossier `logs` de la configuration,
+/// renouvelé chaque jour (les `MAX_LOG_FILES` derniers jours sont conservés), et gardés en mémoire pour le panneau
+/// « Journal » de la fenêtre, qui permet de les copier pour un rapport de bogue.
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Local};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+use crate::config;
+
+/// Préfixe des fichiers du journal (ex: `image_converter.2026-10-14.log`).
+const LOG_FILE_PREFIX: &str = "image_converter";
+
+/// Nombre de fichiers du journal conservés (un par jour).
+const MAX_LOG_FILES: usize = 7;
+
+/// Nombre d'évènements gardés en mémoire pour le panneau « Journal » : au-delà, les plus anciens sont oubliés.
+const MAX_ENTRIES: usize = 2000;
+
+/// Évènement du journal, tel qu'affiché dans le panneau.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: DateTime<Local>, // Date de l'évènement
+    pub level: Level,          // Gravité (information, avertissement, erreur...)
+    pub message: String,       // Message, suivi de ses champs (ex: `source=photo.jpg`)
+}
+
+impl LogEntry {
+    /// Ligne de texte de l'évènement, pour la copie du journal.
+    pub fn to_line(&self) -> String {
+        format!("{} {:>5} {}", self.time.format("%Y-%m-%d %H:%M:%S"), self.level, self.message)
+    }
+}
+
+/// Évènements récents de la session, partagés entre la couche de journalisation et l'interface.
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>, // Évènements, du plus ancien au plus récent
+}
+
+impl LogBuffer {
+    /// Évènements récents, du plus ancien au plus récent ; avec `warnings_only`, seulement les avertissements et erreurs.
+    pub fn entries(&self, warnings_only: bool) -> Vec<LogEntry> {
+        let entries = self.entries.lock().unwrap();
+        entries.iter().filter(|entry| !warnings_only || entry.level <= Level::WARN).cloned().collect()
+    }
+
+    /// Évènements récents sous forme de texte, une ligne par évènement.
+    pub fn text(&self) -> String {
+        let entries = self.entries.lock().unwrap();
+        entries.iter().map(|entry| entry.to_line() + "\n").collect()
+    }
+
+    /// Ajoute un évènement en oubliant le plus ancien si le tampon est plein.
+    fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+/// Couche qui copie les évènements dans le tampon du panneau « Journal ».
+struct BufferLayer(LogBuffer);
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        self.0.push(LogEntry { time: Local::now(), level: *event.metadata().level(), message: visitor.message });
+    }
+}
+
+/// Assemble le message d'un évènement et ses champs.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String, // Message suivi des champs `nom=valeur`
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{}", value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => {
+                let fields = std::mem::take(&mut self.message);
+                let _ = write!(self.message, "{:?}{}", value, fields);
+            }
+            name => {
+                let _ = write!(self.message, " {}={:?}", name, value);
+            }
+        }
+    }
+}
+
+/// Installe la journalisation de l'application et retourne le tampon du panneau « Journal ».
+/// Les évènements de l'application sont retenus à partir du niveau information, ceux des bibliothèques à partir
+/// des avertissements. Avec `console`, les avertissements et erreurs sont aussi écrits sur la sortie d'erreur.
+/// Sans dossier de configuration (ou s'il n'est pas accessible en écriture), le journal reste en mémoire.
+pub fn init(console: bool) -> LogBuffer {
+    let buffer = LogBuffer::default();
+    let file_layer = log_dir()
+        .and_then(|dir| {
+            RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix(LOG_FILE_PREFIX)
+                .filename_suffix("log")
+                .max_log_files(MAX_LOG_FILES)
+                .build(dir)
+                .ok()
+        })
+        .map(|appender| tracing_subscriber::fmt::layer().with_writer(appender).with_ansi(false));
+    let console_layer = console.then(|| {
+        tracing_subscriber::fmt::layer().with_writer(std::io::stderr).with_filter(LevelFilter::WARN)
+    });
+    let targets = Targets::new().with_target(env!("CARGO_CRATE_NAME"), Level::INFO).with_default(Level::WARN);
+
+    // Un second appel (déjà installée) est sans effet.
+    let _ = tracing_subscriber::registry()
+        .with(targets)
+        .with(file_layer)
+        .with(console_layer)
+        .with(BufferLayer(buffer.clone()))
+        .try_init();
+    buffer
+}
+
+/// Dossier des fichiers du journal, `None` si le système n'a pas de dossier de configuration.
+pub fn log_dir() -> Option<PathBuf> {
+    config::app_config_dir().map(|dir| dir.join("logs"))
+}
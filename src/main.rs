@@ -1,62 +1,180 @@
-/// Application graphique pour convertir des images (PNG, JPG, JPEG, BMP) en WebP.
-/// Permet de convertir une seule image, plusieurs images indépendantes ou toutes les images d'un répertoire et ses sous-répertoires.
-/// Offre une interface moderne avec messages de confirmation, gestion d'erreurs, et tooltips pour l'accessibilité.
-/// Les blocs de l'interface sont centrés horizontalement et verticalement dans la fenêtre.
-/// Utilise `eframe` pour l'UI, `image` pour la conversion, et `rfd` pour les dialogues de fichiers.
-/// Supporte Windows, macOS et Linux pour l'ouverture du dossier de sortie.
+//! Application graphique pour convertir des images (PNG, JPG, JPEG, BMP, TIFF, GIF, WebP, RAW, PDF) en WebP, PNG, JPEG, AVIF ou TIFF.
+//! Permet de convertir une seule image, plusieurs images indépendantes ou toutes les images d'un répertoire et ses sous-répertoires.
+//! Offre une interface moderne avec messages de confirmation, gestion d'erreurs, et tooltips pour l'accessibilité.
+//! Les blocs de l'interface sont centrés horizontalement et verticalement dans la fenêtre.
+//! Utilise `eframe` pour l'UI, `image` pour la conversion, et `rfd` pour les dialogues de fichiers.
+//! Supporte Windows, macOS et Linux pour l'ouverture du dossier de sortie.
+//! Lancée avec des arguments (`--input`, `--output`...), l'application convertit en ligne de commande, sans fenêtre.
 
 // Importe les modules externes nécessaires
 use eframe::{egui, App, CreationContext, Frame, NativeOptions};
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex}; // Pour la communication inter-threads
+use std::time::{Duration, Instant};
 
 // Importe nos modules locaux
-mod converter; // Contient la logique de conversion d'images et l'enum OverwriteMode
+mod cli; // Contient le mode ligne de commande (sans fenêtre)
+mod clipboard; // Contient la lecture et la conversion des images collées depuis le presse-papiers
+mod collision_check; // Contient le relevé en arrière-plan des sorties déjà présentes (contrôle préalable des collisions)
+mod config; // Contient la configuration conservée entre deux sessions
+mod dir_scanner; // Contient l'analyse en arrière-plan des répertoires sélectionnés
+mod job_journal; // Contient le journal des lots en cours, pour reprendre le travail interrompu au lancement suivant
+mod job_manager; // Contient le suivi des lots de conversion lancés depuis l'interface (plusieurs à la fois)
+mod history; // Contient l'historique des lots terminés (base SQLite)
+mod job_queue; // Contient la file d'attente des fichiers et leur état de conversion
+mod logging; // Contient la journalisation dans un fichier et le tampon du panneau « Journal »
 mod ui_helpers; // Contient des fonctions d'aide pour l'UI
-mod platform_utils; // Contient des utilitaires spécifiques à la plateforme et de validation de chemin
+mod presets; // Contient les préréglages nommés de conversion
+mod preview; // Contient le chargement en arrière-plan de l'aperçu de l'image sélectionnée
+mod tray; // Contient l'icône de la zone de notification et son menu
+mod watch_folder; // Contient le mode surveillance d'un dossier
+mod size_estimator; // Contient l'estimation en arrière-plan de la taille de sortie
 
-/// Type d'entrée sélectionné par l'utilisateur : fichier unique, répertoire, ou plusieurs fichiers.
+// Importe les modules de la bibliothèque de conversion (src/lib.rs), partagée avec la ligne de commande
+use image_converter::{
+    animation, archive, color_profile, contact_sheet, converter, duplicates, error, filters, i18n, job, originals, pdf, pdf_bundle,
+    platform_utils, report_export, responsive, run_folder, sprite_sheet, targets, throughput, tr, transform, undo, verify,
+};
+use job::{ConversionJob, JobEvent, JobInput};
+use history::HistoryAction;
+use job_manager::{JobAction, JobId, JobSpec};
+
+/// Délai sans nouveau changement avant l'enregistrement des réglages.
+const CONFIG_SAVE_DELAY: Duration = Duration::from_millis(1000);
+
+/// Type d'entrée sélectionné par l'utilisateur : fichier unique, répertoire, plusieurs fichiers ou image collée.
 #[derive(Debug, PartialEq, Clone)]
 pub enum InputType {
     SingleFile(PathBuf),
     Directory(PathBuf),
     MultipleFiles(Vec<PathBuf>), // Nouveau: pour la sélection de plusieurs fichiers indépendants
+    Clipboard(clipboard::PastedImage), // Image collée depuis le presse-papiers (Ctrl+V), gardée en mémoire
+}
+
+/// Cible du bouton proposé dans le toast de succès.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ToastAction {
+    OpenFile(PathBuf),   // Ouvrir l'image produite (conversion d'un fichier unique).
+    OpenFolder(PathBuf), // Ouvrir le dossier de sortie (conversion par lot).
 }
 
 /// Structure principale de l'application, gérant l'entrée, le répertoire de sortie et l'état de l'UI.
 pub struct ImageConverterApp {
     pub input: Option<InputType>,       // Fichier, répertoire ou liste de fichiers sélectionné.
+    pub forwarded_paths: Arc<Mutex<Vec<PathBuf>>>, // Fichiers transmis par un second lancement, à ajouter à la sélection
     pub output_dir: PathBuf,            // Répertoire de sortie pour les images converties.
+    pub last_input_dir: Option<PathBuf>, // Dossier de la dernière sélection, où s'ouvrent les boîtes de dialogue.
     pub dialog_message: Option<String>, // Message à afficher dans la fenêtre modale (succès ou erreur).
     pub show_dialog: bool,              // Contrôle l'affichage de la fenêtre modale.
-    pub is_converting: bool,            // Indique si une conversion est en cours.
-    pub conversion_progress: f32,       // Progrès de la conversion (0.0 à 1.0)
-    pub overwrite_mode: converter::OverwriteMode, // Mode de gestion des fichiers existants.
+    pub jobs: job_manager::JobManager,  // Lots de conversion en attente, en cours ou terminés
+    pub journal: job_journal::JobJournal, // Lots enregistrés sur disque jusqu'à leur fin (reprise après un arrêt)
+    pub job_queue: job_queue::JobQueue, // File des fichiers de la sélection et leur état, mis à jour par les évènements de sa conversion
+    pub queue_input: Option<InputType>, // Sélection dont la file a été construite
+    pub conversion_options: converter::ConversionOptions, // Options de conversion (écrasement, doublons, incrémental).
     pub is_file_hovered: bool,          // Indique si un fichier est survolé pour le drag and drop
     pub show_toast: bool,               // Contrôle l'affichage du "toast" de notification.
     pub toast_message: String,          // Message du "toast".
     pub toast_is_error: bool,           // Vrai si le toast est un message d'erreur.
-    // Nouveau: Pour la communication du résultat de la conversion depuis un thread secondaire
-    pub conversion_result: Arc<Mutex<Option<Result<(), String>>>>,
+    pub toast_action: Option<ToastAction>, // Bouton proposé dans le toast (ouvrir l'image ou le dossier).
+    pub size_estimator: size_estimator::SizeEstimator, // Estimation de la taille de sortie de l'image sélectionnée
+    pub image_preview: preview::ImagePreview, // Miniature de l'image sélectionnée (fichier unique)
+    pub compare_preview: bool, // Afficher la comparaison avant/après de l'aperçu
+    pub comparison_split: f32, // Position de la séparation de la comparaison (0.0 à 1.0)
+    pub dir_scanner: dir_scanner::DirectoryScanner, // Décompte en arrière-plan des images du répertoire sélectionné
+    pub collision_checker: collision_check::CollisionChecker, // Relevé des sorties déjà présentes pour la sélection
+    pub pending_collisions: Option<Vec<String>>, // Noms en double à résoudre avant de lancer la conversion
+    pub pending_space_warning: Option<(u64, u64)>, // Espace estimé et espace libre, si le volume de sortie semble trop petit
+    pub pending_undo: Option<undo::UndoPlan>, // Annulation de la dernière conversion, en attente de confirmation
+    pub retry_selection: HashSet<PathBuf>, // Sources en échec cochées dans le bilan, à relancer
+    pub retry_with_current_settings: bool, // Relancer la sélection avec les réglages actuels plutôt que ceux du lot
+    pub dry_run: bool, // Simulation : le prochain lot prévoit chaque fichier sans rien écrire
+    pub conflict_apply_to_all: bool, // Case « Appliquer à tous » de la fenêtre de conflit (mode Demander)
+    pub last_report: Option<converter::ConversionReport>, // Bilan du dernier lot (fusionné avec ses relances)
+    pub show_report: bool, // Contrôle l'affichage de la fenêtre du bilan du dernier lot
+    pub preview_dimensions: Option<(PathBuf, Option<(u32, u32)>)>, // Dimensions de l'image servant à l'aperçu du nom
+    pub folder_watcher: watch_folder::FolderWatcher, // Mode surveillance et journal des conversions automatiques
+    pub watch_dir: Option<PathBuf>, // Dossier choisi pour le mode surveillance
+    pub saved_config: config::AppConfig, // Réglages tels qu'enregistrés (ou à enregistrer) sur le disque
+    pub pending_config_since: Option<Instant>, // Date du premier changement non encore enregistré
+    pub presets: Vec<presets::Preset>, // Préréglages nommés
+    pub show_presets_editor: bool,     // Contrôle l'affichage de la fenêtre de gestion des préréglages
+    pub theme: config::AppTheme,       // Thème de l'interface (système, clair ou sombre)
+    pub language: i18n::Language,      // Langue de l'interface
+    pub show_settings: bool,           // Contrôle l'affichage de la fenêtre des paramètres
+    pub settings_tab: ui_helpers::SettingsTab, // Onglet affiché dans la fenêtre des paramètres
+    pub context_menu_silent: bool,     // Entrée du menu contextuel de l'Explorateur en conversion silencieuse
+    pub tray: Option<tray::SystemTray>, // Icône de la zone de notification, si le système la prend en charge
+    pub close_to_tray: bool,           // Fermer la fenêtre la cache dans la zone de notification
+    pub desktop_notifications: bool,   // Annoncer la fin d'un lot par une notification du système (fenêtre en arrière-plan)
+    pub quit_requested: bool,          // « Quitter » choisi dans le menu de l'icône : la fermeture n'est plus interceptée
+    pub new_preset_name: String,       // Nom saisi dans la fenêtre de gestion des préréglages
+    pub logs: logging::LogBuffer,      // Évènements récents affichés dans le panneau « Journal »
+    pub history: history::History,     // Historique des lots terminés
+    pub history_entries: Option<Vec<history::HistoryEntry>>, // Lots affichés dans la fenêtre « Historique », lus à son ouverture
+    pub show_history: bool,            // Contrôle l'affichage de la fenêtre « Historique »
+    pub log_warnings_only: bool,       // N'afficher que les avertissements et erreurs dans le panneau « Journal »
 }
 
-/// Définit les valeurs par défaut pour `ImageConverterApp`, avec le dossier de sortie sur le bureau.
+/// Définit les valeurs par défaut pour `ImageConverterApp`, à partir de la configuration enregistrée
+/// (à défaut, dossier de sortie sur le bureau et options par défaut).
 impl Default for ImageConverterApp {
     fn default() -> Self {
-        let desktop_dir = dirs::desktop_dir().unwrap_or(PathBuf::from("."));
+        let config = config::AppConfig::load();
         Self {
             input: None,
-            output_dir: desktop_dir.join("webp_converted"),
+            forwarded_paths: Arc::new(Mutex::new(Vec::new())),
+            output_dir: config.output_dir.clone(),
+            last_input_dir: config.last_input_dir.clone(),
             dialog_message: None,
             show_dialog: false,
-            is_converting: false,
-            conversion_progress: 0.0,
-            overwrite_mode: converter::OverwriteMode::Skip, // Par défaut, ignorer les fichiers existants
+            jobs: job_manager::JobManager::default(),
+            journal: job_journal::JobJournal::load(),
+            job_queue: job_queue::JobQueue::default(),
+            queue_input: None,
+            conversion_options: config.conversion_options.clone(),
             is_file_hovered: false,
             show_toast: false,
             toast_message: String::new(),
             toast_is_error: false,
-            conversion_result: Arc::new(Mutex::new(None)),
+            toast_action: None,
+            size_estimator: size_estimator::SizeEstimator::default(),
+            image_preview: preview::ImagePreview::default(),
+            compare_preview: false,
+            comparison_split: 0.5,
+            dir_scanner: dir_scanner::DirectoryScanner::default(),
+            collision_checker: collision_check::CollisionChecker::default(),
+            pending_collisions: None,
+            pending_space_warning: None,
+            pending_undo: None,
+            retry_selection: HashSet::new(),
+            retry_with_current_settings: false,
+            dry_run: false,
+            conflict_apply_to_all: false,
+            last_report: None,
+            show_report: false,
+            preview_dimensions: None,
+            folder_watcher: watch_folder::FolderWatcher::default(),
+            watch_dir: None,
+            presets: config.presets.clone(),
+            show_presets_editor: false,
+            theme: config.theme,
+            language: config.language,
+            show_settings: false,
+            settings_tab: ui_helpers::SettingsTab::default(),
+            context_menu_silent: config.context_menu_silent,
+            tray: None,
+            close_to_tray: config.close_to_tray,
+            desktop_notifications: config.desktop_notifications,
+            quit_requested: false,
+            new_preset_name: String::new(),
+            logs: logging::LogBuffer::default(),
+            history: history::History::open(),
+            history_entries: None,
+            show_history: false,
+            log_warnings_only: false,
+            saved_config: config,
+            pending_config_since: None,
         }
     }
 }
@@ -65,7 +183,8 @@ impl Default for ImageConverterApp {
 impl App for ImageConverterApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
         // Personnalise le style visuel de l'application.
-        ui_helpers::set_custom_style(ctx);
+        ui_helpers::set_custom_style(ctx, self.theme);
+        i18n::set_language(self.language);
 
         // Gère le glisser-déposer de fichiers
         self.is_file_hovered = !ctx.input(|i| i.raw.hovered_files.is_empty());
@@ -75,17 +194,53 @@ impl App for ImageConverterApp {
                 .filter_map(|file| file.path)
                 .collect();
 
-            if dropped_paths.len() == 1 {
-                if dropped_paths[0].is_file() {
-                    self.input = Some(InputType::SingleFile(dropped_paths[0].clone()));
-                } else if dropped_paths[0].is_dir() {
-                    self.input = Some(InputType::Directory(dropped_paths[0].clone()));
-                }
-            } else if dropped_paths.len() > 1 {
-                self.input = Some(InputType::MultipleFiles(dropped_paths));
+            if let Some(input) = InputType::from_paths(dropped_paths) {
+                self.input = Some(input);
             }
         }
 
+        // Ajoute les fichiers ouverts depuis un second lancement
+        let forwarded = std::mem::take(&mut *self.forwarded_paths.lock().unwrap());
+        if !forwarded.is_empty() {
+            self.add_opened_paths(forwarded);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
+
+        // Commandes du menu de l'icône de la zone de notification, et fermeture de la fenêtre qui s'y cache
+        self.handle_tray(ctx);
+
+        // Raccourcis clavier des actions principales
+        self.handle_shortcuts(ctx);
+
+        // Met à jour l'estimation de la taille de sortie selon la sélection courante
+        let estimate_request = self.estimate_request();
+        self.size_estimator.update(ctx, estimate_request);
+
+        // Charge l'aperçu du fichier unique sélectionné
+        let preview_path = match &self.input {
+            Some(InputType::SingleFile(path)) => Some(path.clone()),
+            _ => None,
+        };
+        self.image_preview.update(ctx, preview_path, &self.conversion_options, self.compare_preview);
+
+        // Analyse le répertoire sélectionné ; pendant sa conversion, le parcours du lot prend le relais
+        if !self.input.as_ref().is_some_and(|input| self.jobs.is_converting(input)) {
+            let scan_request = self.scan_request();
+            self.dir_scanner.update(ctx, scan_request);
+        }
+
+        // Applique les évènements des lots en cours (avancement, état des fichiers, bilans)
+        self.process_job_events(ctx);
+
+        // Reconstruit la file quand la sélection change
+        if self.queue_input != self.input {
+            // Mémorise le dossier de la sélection, où s'ouvriront les prochaines boîtes de dialogue
+            if let Some(dir) = self.input.as_ref().and_then(InputType::browse_dir) {
+                self.last_input_dir = Some(dir);
+            }
+            self.job_queue = job_queue::JobQueue::from_input(self.input.as_ref());
+            self.queue_input = self.input.clone();
+        }
 
         egui::CentralPanel::default().show(ctx, |ui| {
             // Centre verticalement et horizontalement le contenu.
@@ -94,6 +249,22 @@ impl App for ImageConverterApp {
 
                 // Titre
                 ui_helpers::render_title(ui);
+                if ui.small_button(tr!("⚙ Paramètres"))
+                    .on_hover_text(ui_helpers::with_shortcut(
+                        ctx,
+                        tr!("Thème, langue, répertoire de sortie et réglages par défaut de la conversion"),
+                        &ui_helpers::SETTINGS_SHORTCUT,
+                    ))
+                    .clicked()
+                {
+                    self.show_settings = true;
+                }
+                if ui.small_button(tr!("🕘 Historique"))
+                    .on_hover_text(tr!("Lots passés : relancer avec les mêmes réglages ou ouvrir le dossier de sortie"))
+                    .clicked()
+                {
+                    self.show_history = true;
+                }
                 ui.add_space(20.0);
 
                 // Zone principale centrée
@@ -106,95 +277,139 @@ impl App for ImageConverterApp {
                         ui.add_space(10.0);
 
                         // Boutons de sélection de fichiers/dossiers
-                        ui_helpers::render_file_selection_buttons(ui, &mut self.input);
+                        if ui_helpers::render_file_selection_buttons(ui, &mut self.input, self.last_input_dir.as_deref()) {
+                            self.paste_clipboard_image();
+                        }
                         ui.add_space(20.0);
 
                         // Affichage du chemin sélectionné
-                        ui_helpers::render_selected_input_display(ui, &self.input);
+                        ui_helpers::render_selected_input_display(
+                            ui,
+                            &self.input,
+                            self.size_estimator.estimate(),
+                            self.size_estimator.is_estimating(),
+                            self.dir_scanner.progress(),
+                        );
+                        // Aperçu du fichier unique sélectionné
+                        if let Some(preview) = self.image_preview.state() {
+                            let format_label = match self.conversion_options.smallest_format {
+                                true => tr!("le plus léger"),
+                                false => self.conversion_options.output_format.label(),
+                            };
+                            ui_helpers::render_image_preview(
+                                ui,
+                                preview,
+                                self.image_preview.comparison(),
+                                &mut self.compare_preview,
+                                &mut self.comparison_split,
+                                format_label,
+                            );
+                        }
+                        // File d'attente des lots (fichiers multiples, ou répertoire une fois la conversion lancée)
+                        let batch_input = matches!(self.input, Some(InputType::Directory(_) | InputType::MultipleFiles(_)));
+                        if batch_input && !self.job_queue.entries().is_empty() {
+                            ui_helpers::render_job_queue(ui, &self.job_queue);
+                        }
                         ui.add_space(10.0);
 
-                        // Section Répertoire de sortie
-                        ui_helpers::render_output_section(ui, &mut self.output_dir);
+                        // Section Préréglages
+                        let active_preset = self.active_preset().map(|preset| preset.name.clone());
+                        if let Some(index) = ui_helpers::render_preset_selector(
+                            ui,
+                            &self.presets,
+                            active_preset.as_deref(),
+                            &mut self.show_presets_editor,
+                        ) {
+                            self.apply_preset(index);
+                        }
                         ui.add_space(10.0);
 
-                        // Section Overwrite Mode
-                        ui_helpers::render_overwrite_options(ui, &mut self.overwrite_mode);
+                        // Section Nom des fichiers (le répertoire de sortie se règle dans les paramètres)
+                        let name_preview = self.name_preview();
+                        ui_helpers::render_name_template_option(ui, &mut self.conversion_options.name_template, &name_preview);
+                        ui.add_space(10.0);
+
+                        // Section Format de sortie
+                        ui_helpers::render_format_options(ui, &mut self.conversion_options);
+                        ui_helpers::render_rotation_options(ui, &mut self.conversion_options.rotation);
+                        ui_helpers::render_trim_options(ui, &mut self.conversion_options.trim);
+                        ui_helpers::render_crop_options(ui, &mut self.conversion_options.crop);
+                        ui_helpers::render_resize_options(ui, &mut self.conversion_options.resize);
+                        ui_helpers::render_pad_options(ui, &mut self.conversion_options.pad);
+                        ui_helpers::render_targets_options(ui, &mut self.conversion_options.targets);
+                        ui_helpers::render_responsive_options(ui, &mut self.conversion_options.responsive);
+                        ui_helpers::render_favicon_option(ui, &mut self.conversion_options.favicon);
+                        ui_helpers::render_pdf_options(ui, &mut self.conversion_options.pdf);
+                        ui_helpers::render_animation_options(
+                            ui,
+                            &mut self.conversion_options.frames,
+                            &mut self.conversion_options.animation,
+                        );
+                        ui_helpers::render_adjustment_options(ui, &mut self.conversion_options.adjustments);
+
+                        // Section Filtre de dimensions (uniquement pour les lots)
+                        if matches!(self.input, Some(InputType::Directory(_) | InputType::MultipleFiles(_))) {
+                            ui_helpers::render_dimension_filter_options(ui, &mut self.conversion_options.dimension_filter);
+                        }
+
+                        // Sections Organisation des sorties et Sélection des fichiers (uniquement pour un répertoire)
+                        if matches!(self.input, Some(InputType::Directory(_))) {
+                            ui_helpers::render_output_structure_options(ui, &mut self.conversion_options.output_structure);
+                            ui_helpers::render_path_filter_options(ui, &mut self.conversion_options.path_filter);
+                            ui_helpers::render_file_filter_options(ui, &mut self.conversion_options.file_filter);
+                            ui_helpers::render_duplicate_options(ui, &mut self.conversion_options.duplicates);
+                            ui_helpers::render_traversal_options(ui, &mut self.conversion_options.traversal);
+                        }
+
+                        // Section Noms en double (uniquement pour plusieurs fichiers)
+                        if matches!(self.input, Some(InputType::MultipleFiles(_))) {
+                            ui_helpers::render_collision_options(ui, &mut self.conversion_options.collision_strategy);
+                        }
                         ui.add_space(20.0);
 
-                        // Bouton de Conversion
-                        let convert_button_enabled = self.input.is_some() && !self.is_converting;
-                        if ui_helpers::render_convert_button(ui, convert_button_enabled).clicked() {
-                            if let Some(input) = &self.input {
-                                // Validation du chemin de sortie
-                                if let Some(input_path) = input.get_path_for_validation() {
-                                    if let Err(e) = platform_utils::validate_paths(input_path, &self.output_dir) {
-                                        self.dialog_message = Some(format!("Erreur de validation du chemin : {}", e));
-                                        self.show_dialog = true; // Afficher la modale pour l'erreur critique
-                                        return;
-                                    }
-                                }
+                        // Section Mode surveillance
+                        if ui_helpers::render_watch_folder_section(ui, &self.folder_watcher, &mut self.watch_dir) {
+                            self.toggle_folder_watch(ctx);
+                        }
+                        ui.add_space(10.0);
 
-                                self.is_converting = true;
-                                self.conversion_progress = 0.0; // Réinitialiser la progression
-
-                                // Lancer la conversion dans un thread séparé
-                                let input_clone = input.clone();
-                                let output_dir_clone = self.output_dir.clone();
-                                let overwrite_mode_clone = self.overwrite_mode.clone();
-                                let ctx_clone = ctx.clone();
-                                let conversion_result_clone = Arc::clone(&self.conversion_result);
-
-                                std::thread::spawn(move || {
-                                    let thread_result = match input_clone {
-                                        InputType::SingleFile(file_path) => {
-                                            converter::convert_single_image(&file_path, &output_dir_clone, &overwrite_mode_clone)
-                                        }
-                                        InputType::MultipleFiles(file_paths) => {
-                                            // TODO: Pour la barre de progression, il faudrait modifier convert_multiple_files
-                                            // pour qu'il prenne un callback de progression. Pour l'instant, la barre progressera après la fin.
-                                            converter::convert_multiple_files(&file_paths, &output_dir_clone, &overwrite_mode_clone)
-                                        }
-                                        InputType::Directory(dir_path) => {
-                                            let final_output_dir = output_dir_clone.join(
-                                                dir_path.file_name().unwrap_or_default()
-                                            );
-                                            converter::convert_images_in_directory(&dir_path, &final_output_dir, &dir_path, &overwrite_mode_clone)
-                                        }
-                                    };
-
-                                    // Envoyer le résultat au thread UI
-                                    *conversion_result_clone.lock().unwrap() = Some(thread_result);
-                                    ctx_clone.request_repaint(); // Demander au thread UI de se rafraîchir
-                                });
+                        // Sorties déjà présentes : sort selon chaque mode d'écrasement, relevé à la demande (lots)
+                        if matches!(self.input, Some(InputType::Directory(_) | InputType::MultipleFiles(_))) {
+                            let state = self.preflight_job().and_then(|job| self.collision_checker.state(&job));
+                            if ui_helpers::render_collision_check(
+                                ui,
+                                state.as_ref(),
+                                &mut self.conversion_options.overwrite_mode,
+                                self.conversion_options.run_folder.enabled,
+                            ) {
+                                if let Some(job) = self.preflight_job() {
+                                    self.collision_checker.start(ctx, job);
+                                }
                             }
+                            ui.add_space(10.0);
                         }
 
-                        // Vérifier le résultat de la conversion une once qu'elle est terminée
-                        if let Some(result) = self.conversion_result.lock().unwrap().take() {
-                            self.is_converting = false;
-                            self.conversion_progress = 1.0; // Marquer comme terminé
+                        // Bouton de Conversion, précédé de la case Simulation
+                        ui_helpers::render_dry_run_option(ui, &mut self.dry_run);
+                        let convert_button_enabled = self.can_convert();
+                        if ui_helpers::render_convert_button(ui, convert_button_enabled).clicked() {
+                            self.request_conversion(ctx);
+                        }
 
-                            match result {
-                                Ok(()) => {
-                                    self.toast_message = "Conversion terminée avec succès !".to_string();
-                                    self.show_toast = true;
-                                    self.toast_is_error = false;
-                                }
-                                Err(e) => {
-                                    self.dialog_message = Some(format!("Erreur lors de la conversion : {}", e)); // Wrap in Some
-                                    self.show_dialog = true; // Afficher la modale pour les erreurs de conversion
-                                    self.toast_message = "Erreur lors de la conversion !".to_string();
-                                    self.show_toast = true;
-                                    self.toast_is_error = true;
-                                }
+                        // Une ligne par lot : avancement des lots en cours, bilan des lots terminés
+                        if let Some(action) = ui_helpers::render_jobs(ui, ctx, self.jobs.jobs()) {
+                            self.apply_job_action(action);
+                        }
+                        // Annulation de la dernière conversion : ses fichiers sont listés avant d'être supprimés
+                        if let Some(report) = self.last_report.as_ref().filter(|report| undo::can_undo(report)) {
+                            if ui_helpers::render_undo_button(ui).clicked() {
+                                self.pending_undo = Some(undo::UndoPlan::from_report(report));
                             }
                         }
 
-                        // Afficher un indicateur de chargement si une conversion est en cours
-                        if self.is_converting {
-                            ui.add_space(10.0);
-                            ui.add(egui::ProgressBar::new(self.conversion_progress).show_percentage());
-                            ui.label("Conversion en cours...");
+                        // Panneau Journal : évènements récents de la conversion, à copier pour un rapport de bogue
+                        ui_helpers::render_log_panel(ui, &self.logs, &mut self.log_warnings_only);
+                        if self.jobs.is_busy() {
                             ctx.request_repaint(); // Demander un rafraîchissement continu pendant la conversion
                         }
                     },
@@ -202,42 +417,1186 @@ impl App for ImageConverterApp {
             }); // Fin vertical_centered
         }); // Fin CentralPanel
 
+        // Fenêtre du bilan du dernier lot
+        if self.show_report {
+            if let Some(report) = &self.last_report {
+                match ui_helpers::render_report_window(
+                    ctx,
+                    &mut self.show_report,
+                    report,
+                    &mut self.retry_selection,
+                    &mut self.retry_with_current_settings,
+                ) {
+                    Some(ui_helpers::ReportAction::Export) => self.export_report(),
+                    Some(ui_helpers::ReportAction::RetrySelected) => {
+                        let selection = std::mem::take(&mut self.retry_selection);
+                        self.start_retry(ctx, Some(&selection), self.retry_with_current_settings);
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        // Fenêtre de l'historique des lots
+        if self.show_history {
+            let entries = self.history_entries.get_or_insert_with(|| {
+                self.history.entries().unwrap_or_else(|e| {
+                    tracing::warn!("{}", e);
+                    Vec::new()
+                })
+            });
+            if let Some(action) = ui_helpers::render_history_window(ctx, &mut self.show_history, entries) {
+                self.apply_history_action(ctx, action);
+            }
+        }
+
+        // Fenêtre des paramètres
+        if self.show_settings {
+            let context_menu_action = ui_helpers::render_settings_window(
+                ctx,
+                &mut self.show_settings,
+                &mut self.settings_tab,
+                ui_helpers::SettingsValues {
+                    theme: &mut self.theme,
+                    language: &mut self.language,
+                    output_dir: &mut self.output_dir,
+                    options: &mut self.conversion_options,
+                    context_menu_silent: &mut self.context_menu_silent,
+                    close_to_tray: &mut self.close_to_tray,
+                    desktop_notifications: &mut self.desktop_notifications,
+                },
+            );
+            if let Some(action) = context_menu_action {
+                self.apply_context_menu_action(action);
+            }
+        }
+
+        // Fenêtre de gestion des préréglages
+        if self.show_presets_editor {
+            let active_preset = self.active_preset().map(|preset| preset.name.clone());
+            if let Some(action) = ui_helpers::render_presets_window(
+                ctx,
+                &mut self.show_presets_editor,
+                &self.presets,
+                active_preset.as_deref(),
+                &mut self.new_preset_name,
+            ) {
+                self.handle_preset_action(action);
+            }
+        }
+
+        // Fenêtre de résolution des noms en double
+        if let Some(collisions) = &self.pending_collisions {
+            if let Some(choice) = ui_helpers::render_collision_dialog(ctx, collisions, self.conversion_options.output_format.extension()) {
+                self.pending_collisions = None;
+                if let Some(strategy) = choice {
+                    self.start_conversion(ctx, strategy);
+                }
+            }
+        }
+
+        // Fenêtre d'avertissement : l'espace libre du volume de sortie semble insuffisant pour le répertoire
+        if let Some((estimated, available)) = self.pending_space_warning {
+            if let Some(proceed) = ui_helpers::render_free_space_dialog(ctx, estimated, available, &self.output_dir) {
+                self.pending_space_warning = None;
+                if proceed {
+                    self.start_conversion(ctx, self.conversion_options.collision_strategy);
+                }
+            }
+        }
+
+        // Fenêtre de confirmation de l'annulation de la dernière conversion
+        if let Some(plan) = &self.pending_undo {
+            if let Some(confirmed) = ui_helpers::render_undo_dialog(ctx, plan) {
+                if let (true, Some(plan)) = (confirmed, self.pending_undo.take()) {
+                    self.undo_last_conversion(&plan);
+                }
+            }
+        }
+
+        // Fenêtre de reprise des lots interrompus lors d'une session précédente
+        if !self.journal.interrupted().is_empty() {
+            match ui_helpers::render_interrupted_jobs_dialog(ctx, self.journal.interrupted()) {
+                Some(true) => self.resume_interrupted(ctx),
+                Some(false) => self.journal.discard_interrupted(),
+                None => {}
+            }
+        }
+
+        // Fenêtre de conflit : un lot attend la décision sur une sortie existante (mode Demander), un lot à la fois
+        let conflict = self.jobs.active().find_map(|managed| {
+            managed.control.pending_conflict().map(|output| (Arc::clone(&managed.control), output))
+        });
+        if let Some((control, output)) = conflict {
+            if let Some(choice) = ui_helpers::render_conflict_dialog(ctx, &output, &mut self.conflict_apply_to_all) {
+                control.resolve_conflict(choice, self.conflict_apply_to_all);
+                self.conflict_apply_to_all = false;
+            }
+        }
+
         // Fenêtre modale pour les erreurs critiques ou l'ouverture du dossier
         if self.show_dialog {
-            ui_helpers::render_dialog_window(ctx, &mut self.show_dialog, &mut self.dialog_message, &self.output_dir);
+            let retry_count = self.last_report.as_ref().map_or(0, |report| report.failures.len());
+            // « Ouvrir le dossier » cible le dossier de la dernière exécution s'il y en a un
+            let folder_to_open = self.last_report
+                .as_ref()
+                .and_then(|report| report.run_folder.clone())
+                .unwrap_or_else(|| self.output_dir.clone());
+            let retry_clicked = ui_helpers::render_dialog_window(
+                ctx,
+                &mut self.show_dialog,
+                &mut self.dialog_message,
+                &folder_to_open,
+                retry_count,
+            );
+            if retry_clicked {
+                self.start_retry(ctx, None, false);
+            }
         }
 
         // Afficher le toast de notification
         if self.show_toast {
-            ui_helpers::render_toast(ctx, &mut self.show_toast, &self.toast_message, self.toast_is_error);
+            let action_label = self.toast_action.as_ref().map(|action| match action {
+                ToastAction::OpenFile(_) => tr!("Ouvrir l'image"),
+                ToastAction::OpenFolder(_) => tr!("Ouvrir le dossier"),
+            });
+            let action_clicked = ui_helpers::render_toast(
+                ctx,
+                &mut self.show_toast,
+                &self.toast_message,
+                self.toast_is_error,
+                action_label,
+            );
+            if action_clicked {
+                self.run_toast_action();
+            }
+        }
+
+        self.persist_config(ctx);
+    }
+
+    /// Enregistre les derniers réglages à la fermeture, même si le délai d'enregistrement n'est pas écoulé.
+    /// Les lots encore en cours restent au journal, pour être repris au lancement suivant.
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.journal.save();
+        if self.pending_config_since.is_some() {
+            // Rien à signaler à l'utilisateur : la fenêtre se ferme.
+            let _ = self.saved_config.save();
+        }
+    }
+}
+
+impl ImageConverterApp {
+    /// Applique les raccourcis clavier : sélection d'un fichier ou d'un répertoire, collage d'une image,
+    /// conversion, annulation de la conversion en cours et fenêtre des paramètres.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Ctrl+Maj+O est testé en premier : le raccourci Ctrl+O accepte aussi la touche Maj enfoncée.
+        if ctx.input_mut(|i| i.consume_shortcut(&ui_helpers::OPEN_FOLDER_SHORTCUT)) {
+            if let Some(path) = ui_helpers::pick_input_folder(self.last_input_dir.as_deref()) {
+                self.input = Some(InputType::Directory(path));
+            }
+        } else if ctx.input_mut(|i| i.consume_shortcut(&ui_helpers::OPEN_FILE_SHORTCUT)) {
+            if let Some(path) = ui_helpers::pick_image_file(self.last_input_dir.as_deref()) {
+                self.input = Some(InputType::SingleFile(path));
+            }
+        }
+        // egui n'émet aucun évènement « coller » quand le presse-papiers ne contient qu'une image :
+        // Ctrl+V est repéré au relâchement de la touche, hors saisie de texte.
+        let paste_released = ctx.input(|i| {
+            i.events.iter().any(|event| {
+                matches!(event, egui::Event::Key { key, pressed: false, modifiers, .. }
+                    if *key == ui_helpers::PASTE_SHORTCUT.logical_key && modifiers.matches_logically(ui_helpers::PASTE_SHORTCUT.modifiers))
+            })
+        });
+        if paste_released && !ctx.wants_keyboard_input() {
+            self.paste_clipboard_image();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&ui_helpers::CONVERT_SHORTCUT)) && self.can_convert() {
+            self.request_conversion(ctx);
+        }
+        if self.jobs.is_busy() && ctx.input_mut(|i| i.consume_shortcut(&ui_helpers::CANCEL_SHORTCUT)) {
+            self.jobs.cancel_all();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&ui_helpers::SETTINGS_SHORTCUT)) {
+            self.show_settings = !self.show_settings;
+        }
+    }
+
+    /// Copie le fichier produit `output` (ses pixels ou son chemin) et confirme la copie dans un toast.
+    fn copy_output(&mut self, output: &Path, content: clipboard::CopyContent) {
+        match clipboard::copy_output(output, content) {
+            Ok(()) => {
+                self.toast_message = match content {
+                    clipboard::CopyContent::Image => tr!("Image copiée dans le presse-papiers").to_string(),
+                    clipboard::CopyContent::Path => tr!("Chemin copié dans le presse-papiers").to_string(),
+                };
+                self.toast_is_error = false;
+            }
+            Err(e) => {
+                self.toast_message = e.to_string();
+                self.toast_is_error = true;
+            }
+        }
+        self.toast_action = None;
+        self.show_toast = true;
+    }
+
+    /// Sélectionne l'image du presse-papiers comme entrée, ou signale son absence dans un toast.
+    fn paste_clipboard_image(&mut self) {
+        match clipboard::read_image() {
+            Ok(pasted) => self.input = Some(InputType::Clipboard(pasted)),
+            Err(e) => {
+                self.toast_message = e.to_string();
+                self.toast_is_error = true;
+                self.toast_action = None;
+                self.show_toast = true;
+            }
+        }
+    }
+
+    /// Applique les commandes du menu de l'icône de la zone de notification (ouvrir, pause, quitter) et,
+    /// si l'option est choisie, cache la fenêtre au lieu de la fermer : la surveillance et la conversion continuent.
+    fn handle_tray(&mut self, ctx: &egui::Context) {
+        let Some(tray) = &mut self.tray else {
+            return;
+        };
+        // Le menu met en pause (ou reprend) tous les lots en cours
+        let mut running = self.jobs.jobs().iter().filter(|managed| managed.state == job_manager::JobState::Running);
+        let converting = running.clone().next().is_some();
+        let paused = converting && running.all(|managed| managed.control.is_paused());
+        tray.update_pause_item(converting, paused);
+        while let Some(command) = tray.poll() {
+            match command {
+                tray::TrayCommand::Open => {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                }
+                tray::TrayCommand::Pause => {
+                    for managed in self.jobs.jobs().iter().filter(|managed| managed.state == job_manager::JobState::Running) {
+                        managed.control.set_paused(!paused);
+                    }
+                }
+                tray::TrayCommand::Quit => {
+                    self.quit_requested = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+        if self.close_to_tray && !self.quit_requested && ctx.input(|i| i.viewport().close_requested()) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+        }
+    }
+
+    /// Ajoute ou retire l'entrée du menu contextuel de l'Explorateur et annonce le résultat dans un toast.
+    fn apply_context_menu_action(&mut self, action: platform_utils::ContextMenuAction) {
+        let (result, success) = match action {
+            platform_utils::ContextMenuAction::Register { silent_output } => (
+                platform_utils::register_context_menu(silent_output.as_deref()),
+                tr!("« Convertir en WebP » ajouté au menu contextuel."),
+            ),
+            platform_utils::ContextMenuAction::Unregister => {
+                (platform_utils::unregister_context_menu(), tr!("« Convertir en WebP » retiré du menu contextuel."))
+            }
+        };
+        self.toast_is_error = result.is_err();
+        self.toast_message = result.map_or_else(|e| e.to_string(), |_| success.to_string());
+        self.toast_action = None;
+        self.show_toast = true;
+    }
+
+    /// Ajoute des fichiers ouverts depuis un second lancement à la sélection : ils complètent le ou les fichiers
+    /// déjà choisis et remplacent sinon la sélection (répertoire, image collée).
+    fn add_opened_paths(&mut self, paths: Vec<PathBuf>) {
+        let mut all_paths = match &self.input {
+            Some(InputType::SingleFile(path)) => vec![path.clone()],
+            Some(InputType::MultipleFiles(files)) => files.clone(),
+            _ => Vec::new(),
+        };
+        for path in paths {
+            if !all_paths.contains(&path) {
+                all_paths.push(path);
+            }
+        }
+        if let Some(input) = InputType::from_paths(all_paths) {
+            self.input = Some(input);
+        }
+    }
+
+    /// Traite un clic sur le bouton de conversion : lance la conversion (`job::run_job` valide les chemins)
+    /// ou, en mode `Ask`, ouvre d'abord la fenêtre de résolution des noms en double.
+    /// Pour un répertoire, avertit d'abord si l'espace libre du volume de sortie semble insuffisant.
+    fn request_conversion(&mut self, ctx: &egui::Context) {
+        let Some(input) = &self.input else {
+            return;
+        };
+
+        if let (InputType::Directory(_), false) = (input, self.dry_run) {
+            if let Some(shortage) = self.free_space_shortage() {
+                self.pending_space_warning = Some(shortage);
+                return;
+            }
+        }
+
+        if let InputType::MultipleFiles(file_paths) = input {
+            if self.conversion_options.collision_strategy == converter::CollisionStrategy::Ask {
+                let collisions = converter::find_stem_collisions(file_paths);
+                if !collisions.is_empty() {
+                    self.pending_collisions = Some(collisions);
+                    return;
+                }
+            }
+        }
+
+        self.start_conversion(ctx, self.conversion_options.collision_strategy);
+    }
+
+    /// Compare l'espace nécessaire au répertoire sélectionné (taille de ses images, d'après l'analyse en arrière-plan,
+    /// × rapport habituel du format) à l'espace libre du volume de sortie. Retourne `(estimé, libre)` s'il manque de la place.
+    /// Pendant l'analyse, la taille déjà trouvée est un minimum : l'avertissement reste fondé.
+    fn free_space_shortage(&self) -> Option<(u64, u64)> {
+        let scan = self.dir_scanner.progress()?;
+        let estimated = converter::estimate_output_bytes(scan.bytes, &self.conversion_options);
+        let available = platform_utils::free_space(&self.output_dir)?;
+        (estimated > available).then_some((estimated, available))
+    }
+
+    /// Lance la conversion de l'entrée courante dans un thread séparé.
+    fn start_conversion(&mut self, ctx: &egui::Context, collision_strategy: converter::CollisionStrategy) {
+        let Some(input) = &self.input else {
+            return;
+        };
+
+        let options = converter::ConversionOptions {
+            collision_strategy,
+            dry_run: self.dry_run,
+            ..self.conversion_options.clone()
+        };
+
+        // Créer le sous-dossier de l'exécution dès le lancement (voir `ConversionJob::with_run_folder`), pour que
+        // le journal et « Ouvrir le dossier » le ciblent. En simulation, son nom est seulement calculé.
+        let preset = self.active_preset().map(|preset| preset.name.clone());
+        let job_input = input.to_job_input();
+        let prepared = match &job_input {
+            Some(job_input) => {
+                let job = ConversionJob {
+                    output_dir: job_output_dir(job_input, self.output_dir.clone()),
+                    input: job_input.clone(),
+                    options: options.clone(),
+                    run_folder: None,
+                };
+                job.with_run_folder(preset.as_deref()).map(|job| (job.run_folder.clone(), Some(job)))
+            }
+            None => run_folder::prepare(&self.output_dir, &options, preset.as_deref()).map(|run_dir| (run_dir, None)),
+        };
+        let (run_dir, job) = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                self.dialog_message = Some(e.to_string());
+                self.show_dialog = true;
+                return;
+            }
+        };
+
+        self.dir_scanner.stop(); // Inutile de poursuivre l'analyse : la conversion parcourt elle-même le répertoire
+        self.collision_checker.stop(); // Le relevé n'aurait plus de sens une fois des sorties écrites
+        let output_root = match (&run_dir, input) {
+            (Some(run_dir), _) => run_dir.clone(), // « Ouvrir le dossier » cible le dossier de l'exécution
+            (None, InputType::Directory(dir_path)) => self.output_dir.join(dir_path.file_name().unwrap_or_default()),
+            (None, _) => self.output_dir.clone(),
+        };
+        let spec = JobSpec {
+            label: input.label(),
+            input: Some(input.clone()),
+            output_root,
+            single_file: matches!(input, InputType::SingleFile(_) | InputType::Clipboard(_)),
+            retry_of: None,
+            job: None,
+        };
+        self.job_queue = job_queue::JobQueue::from_input(Some(input));
+        self.queue_input = Some(input.clone());
+
+        // Lancer la conversion dans le thread du lot
+        let Some(job) = job else {
+            if let InputType::Clipboard(pasted) = input {
+                let pasted = pasted.clone();
+                let output_dir = run_dir.clone().unwrap_or_else(|| self.output_dir.clone());
+                self.jobs.submit(ctx, spec, Box::new(move |progress, control| {
+                    let result = clipboard::convert_pasted_image(&pasted, &output_dir, &options, progress, control);
+                    run_folder::attach(result, run_dir.as_deref())
+                }));
+            }
+            return;
+        };
+        self.submit_job(ctx, spec, job, Vec::new());
+    }
+
+    /// Lance `job` dans le gestionnaire de lots et l'inscrit au journal des lots (hors simulation), pour pouvoir
+    /// le reprendre si l'application s'arrête avant sa fin. À la reprise, `files` liste les fichiers déjà traités.
+    fn submit_job(
+        &mut self,
+        ctx: &egui::Context,
+        mut spec: JobSpec,
+        job: ConversionJob,
+        files: Vec<job_journal::SavedFile>,
+    ) {
+        let saved = job_journal::SavedJob {
+            label: spec.label.clone(),
+            job: job.clone(),
+            output_root: spec.output_root.clone(),
+            files,
+        };
+        let skipped: Vec<PathBuf> = saved.completed().collect();
+        spec.job = Some(job.clone());
+        let id = self.jobs.submit(ctx, spec, Box::new(move |progress, control| {
+            control.skip_completed(skipped);
+            job::run_job(&job, progress, control)
+        }));
+        if !saved.job.options.dry_run {
+            self.journal.track(id, saved);
+        }
+    }
+
+    /// Supprime les fichiers créés par la dernière conversion et remet ses originaux en place, puis oublie son bilan.
+    fn undo_last_conversion(&mut self, plan: &undo::UndoPlan) {
+        let outcome = undo::undo(plan);
+        self.last_report = None;
+        self.show_report = false;
+        self.toast_message = match outcome.restored {
+            0 => tr!("Dernière conversion annulée : {} fichier(s) supprimé(s)", outcome.removed),
+            restored => tr!(
+                "Dernière conversion annulée : {} fichier(s) supprimé(s), {} original(aux) remis en place",
+                outcome.removed,
+                restored
+            ),
+        };
+        self.show_toast = true;
+        self.toast_is_error = !outcome.errors.is_empty();
+        self.toast_action = None;
+        if !outcome.errors.is_empty() {
+            self.dialog_message = Some(tr!("Annulation incomplète :\n{}", outcome.errors.join("\n")));
+            self.show_dialog = true;
+        }
+    }
+
+    /// Applique une action choisie dans la fenêtre « Historique ».
+    fn apply_history_action(&mut self, ctx: &egui::Context, action: HistoryAction) {
+        let entry = |id| self.history_entries.iter().flatten().find(|entry: &&history::HistoryEntry| entry.id == id).cloned();
+        let result = match action {
+            HistoryAction::Rerun(id) => match entry(id) {
+                Some(entry) => {
+                    self.rerun_history_entry(ctx, entry);
+                    Ok(())
+                }
+                None => Ok(()),
+            },
+            HistoryAction::OpenFolder(id) => match entry(id) {
+                Some(entry) => platform_utils::open_output_directory(&entry.output_root),
+                None => Ok(()),
+            },
+            HistoryAction::Remove(id) => self.history.remove(id),
+            HistoryAction::Clear => self.history.clear(),
+        };
+        if matches!(action, HistoryAction::Remove(_) | HistoryAction::Clear) {
+            self.history_entries = None;
+        }
+        if let Err(e) = result {
+            self.dialog_message = Some(e.to_string());
+            self.show_dialog = true;
+        }
+    }
+
+    /// Relance un lot de l'historique avec ses sources, sa sortie et ses options d'origine.
+    /// Les sources doivent encore exister ; le lot est inscrit à l'historique comme un nouveau lot.
+    fn rerun_history_entry(&mut self, ctx: &egui::Context, entry: history::HistoryEntry) {
+        let input = InputType::from_job_input(&entry.job.input);
+        let missing = match &entry.job.input {
+            JobInput::File(path) | JobInput::Directory(path) => !path.exists(),
+            JobInput::Files(paths) => !paths.iter().any(|path| path.exists()),
+        };
+        if missing {
+            self.dialog_message = Some(tr!("Les sources de « {} » sont introuvables.", entry.label));
+            self.show_dialog = true;
+            return;
+        }
+        if self.jobs.is_converting(&input) {
+            return;
+        }
+        let spec = JobSpec {
+            label: entry.label,
+            single_file: matches!(input, InputType::SingleFile(_)),
+            input: Some(input),
+            output_root: entry.output_root,
+            retry_of: None,
+            job: None,
+        };
+        // Un nouveau sous-dossier d'exécution est créé par `run_job` si l'option est active.
+        let job = ConversionJob {
+            options: converter::ConversionOptions { dry_run: false, ..entry.job.options },
+            run_folder: None,
+            ..entry.job
+        };
+        self.submit_job(ctx, spec, job, Vec::new());
+    }
+
+    /// Reprend les lots interrompus lors d'une session précédente, sans reconvertir leurs fichiers déjà traités.
+    fn resume_interrupted(&mut self, ctx: &egui::Context) {
+        for saved in self.journal.take_interrupted() {
+            let input = InputType::from_job_input(&saved.job.input);
+            let spec = JobSpec {
+                label: saved.label,
+                single_file: matches!(input, InputType::SingleFile(_)),
+                input: Some(input),
+                output_root: saved.output_root,
+                retry_of: None,
+                job: None,
+            };
+            self.submit_job(ctx, spec, saved.job, saved.files);
+        }
+    }
+
+    /// Vrai si la sélection peut être convertie : elle existe et n'est pas déjà en cours de conversion.
+    fn can_convert(&self) -> bool {
+        self.input.as_ref().is_some_and(|input| !self.jobs.is_converting(input))
+    }
+
+    /// Applique les évènements reçus des lots : état des fichiers de la file (pour le lot de la sélection affichée)
+    /// et, à la fin de chaque lot, son bilan.
+    fn process_job_events(&mut self, ctx: &egui::Context) {
+        for (id, event) in self.jobs.poll(ctx) {
+            let shows_queue = self.jobs.jobs().iter().any(|managed| {
+                managed.id == id && (managed.spec.input.is_none() || managed.spec.input == self.queue_input)
+            });
+            match event {
+                JobEvent::FileStarted(source) if shows_queue => self.job_queue.update(&source, converter::FileStatus::Running),
+                JobEvent::FileStarted(_) => {}
+                JobEvent::FileDone { source, status } => {
+                    self.journal.record(id, &source, job_journal::SavedStatus::Done);
+                    if shows_queue {
+                        self.job_queue.update(&source, status);
+                    }
+                }
+                JobEvent::FileFailed { source, error } => {
+                    self.journal.record(id, &source, job_journal::SavedStatus::Failed);
+                    if shows_queue {
+                        self.job_queue.update(&source, converter::FileStatus::Failed(error));
+                    }
+                }
+                JobEvent::Progress { .. } => {}
+                JobEvent::BatchDone(result) => {
+                    // Terminé ou annulé, le lot n'est plus à reprendre
+                    self.journal.finish(id);
+                    self.handle_conversion_result(ctx, id, *result);
+                }
+            }
+        }
+        self.journal.save_if_due();
+    }
+
+    /// Applique une action demandée depuis la liste des lots.
+    fn apply_job_action(&mut self, action: JobAction) {
+        match action {
+            JobAction::ShowReport(id) => {
+                if let Some(report) = self.jobs.job_mut(id).and_then(|managed| managed.report.clone()) {
+                    self.last_report = Some(report);
+                    self.show_report = true;
+                }
+            }
+            JobAction::CopyOutput(id, content) => {
+                let output = self.jobs.job_mut(id).and_then(|managed| managed.single_output().map(Path::to_path_buf));
+                if let Some(output) = output {
+                    self.copy_output(&output, content);
+                }
+            }
+            JobAction::Remove(id) => self.jobs.remove(id),
+        }
+    }
+
+    /// Demande l'emplacement du rapport du dernier lot, puis l'exporte en CSV ou JSON selon l'extension choisie.
+    fn export_report(&mut self) {
+        let Some(report) = &self.last_report else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .add_filter("JSON", &["json"])
+            .set_file_name("rapport_conversion.csv")
+            .save_file()
+        else {
+            return;
+        };
+
+        match report_export::export(report, &path) {
+            Ok(()) => {
+                self.toast_message = tr!("Rapport exporté : {}", path.display());
+                self.toast_is_error = false;
+            }
+            Err(e) => {
+                self.toast_message = tr!("Erreur lors de l'export du rapport : {}", e);
+                self.toast_is_error = true;
+            }
+        }
+        self.toast_action = None;
+        self.show_toast = true;
+    }
+
+    /// Aperçu du nom produit pour la première image de la sélection (ou une image d'exemple 1920x1080),
+    /// d'après le modèle de nom et le redimensionnement. Les dimensions de l'image sont lues une seule fois.
+    fn name_preview(&mut self) -> String {
+        let sample = match &self.input {
+            Some(InputType::SingleFile(path)) => Some(path.clone()),
+            Some(InputType::MultipleFiles(paths)) => paths.first().cloned(),
+            Some(InputType::Directory(_) | InputType::Clipboard(_)) | None => None,
+        };
+        let source_dimensions = match &sample {
+            Some(path) => {
+                if self.preview_dimensions.as_ref().is_none_or(|(cached, _)| cached != path) {
+                    self.preview_dimensions = Some((path.clone(), image::image_dimensions(path).ok()));
+                }
+                self.preview_dimensions.as_ref().and_then(|(_, dimensions)| *dimensions)
+            }
+            None => None,
+        };
+        let (width, height) = source_dimensions.unwrap_or((1920, 1080));
+        let stem = sample
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .map_or("photo".to_string(), |stem| stem.to_string_lossy().to_string());
+        let options = &self.conversion_options;
+        let name = converter::render_name_template(
+            &options.name_template,
+            &stem,
+            options.output_format,
+            transform::output_size(options, width, height),
+            options,
+        );
+        format!("{}.{}", name, options.output_format.extension())
+    }
+
+    /// Préréglage correspondant aux réglages actuels, s'il y en a un.
+    fn active_preset(&self) -> Option<&presets::Preset> {
+        presets::find_matching(&self.presets, &self.output_dir, &self.conversion_options)
+    }
+
+    /// Remplace les réglages actuels par ceux du préréglage `index`.
+    fn apply_preset(&mut self, index: usize) {
+        if let Some(preset) = self.presets.get(index) {
+            self.output_dir = preset.output_dir.clone();
+            self.conversion_options = preset.conversion_options.clone();
+        }
+    }
+
+    /// Applique une modification demandée depuis la fenêtre de gestion des préréglages.
+    fn handle_preset_action(&mut self, action: presets::PresetAction) {
+        let current = presets::Preset {
+            name: String::new(),
+            output_dir: self.output_dir.clone(),
+            conversion_options: self.conversion_options.clone(),
+        };
+        match action {
+            presets::PresetAction::Apply(index) => self.apply_preset(index),
+            presets::PresetAction::SaveCurrent(name) => {
+                self.presets.push(presets::Preset { name, ..current });
+                self.new_preset_name.clear();
+            }
+            presets::PresetAction::Update(index) => {
+                if let Some(preset) = self.presets.get_mut(index) {
+                    *preset = presets::Preset { name: std::mem::take(&mut preset.name), ..current };
+                }
+            }
+            presets::PresetAction::Rename(index, name) => {
+                if let Some(preset) = self.presets.get_mut(index) {
+                    preset.name = name;
+                    self.new_preset_name.clear();
+                }
+            }
+            presets::PresetAction::Delete(index) => {
+                if index < self.presets.len() {
+                    self.presets.remove(index);
+                }
+            }
+        }
+    }
+
+    /// Enregistre les réglages modifiés, une fois qu'ils n'ont plus changé depuis `CONFIG_SAVE_DELAY`
+    /// (un curseur déplacé n'écrit pas le fichier à chaque image).
+    fn persist_config(&mut self, ctx: &egui::Context) {
+        // Fenêtre agrandie : la taille et la position d'avant l'agrandissement sont conservées pour la restaurer.
+        let (inner_rect, outer_rect, maximized) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (viewport.inner_rect, viewport.outer_rect, viewport.maximized.unwrap_or(false))
+        });
+        let window_size = inner_rect.filter(|_| !maximized).map(|rect| [rect.width(), rect.height()]);
+        let window_position = outer_rect.filter(|_| !maximized).map(|rect| [rect.min.x, rect.min.y]);
+        let current = config::AppConfig {
+            output_dir: self.output_dir.clone(),
+            last_input_dir: self.last_input_dir.clone(),
+            conversion_options: self.conversion_options.clone(),
+            presets: self.presets.clone(),
+            theme: self.theme,
+            language: self.language,
+            window_size: window_size.or(self.saved_config.window_size),
+            window_position: window_position.or(self.saved_config.window_position),
+            window_maximized: maximized,
+            context_menu_silent: self.context_menu_silent,
+            close_to_tray: self.close_to_tray,
+            desktop_notifications: self.desktop_notifications,
+        };
+        if current != self.saved_config {
+            self.saved_config = current;
+            self.pending_config_since = Some(Instant::now());
+        }
+
+        let Some(since) = self.pending_config_since else {
+            return;
+        };
+        if since.elapsed() < CONFIG_SAVE_DELAY {
+            ctx.request_repaint_after(CONFIG_SAVE_DELAY - since.elapsed());
+            return;
+        }
+        self.pending_config_since = None;
+        if let Err(e) = self.saved_config.save() {
+            // Un échec d'enregistrement ne bloque pas l'application : les réglages restent valables pour la session.
+            tracing::warn!("Erreur lors de l'enregistrement de la configuration : {}", e);
+        }
+    }
+
+    /// Démarre ou arrête le mode surveillance sur le dossier choisi, avec les options actuelles.
+    fn toggle_folder_watch(&mut self, ctx: &egui::Context) {
+        if self.folder_watcher.is_running() {
+            self.folder_watcher.stop();
+            return;
+        }
+        let Some(watch_dir) = self.watch_dir.clone() else {
+            return;
+        };
+        if let Err(e) = self.folder_watcher.start(ctx, &watch_dir, &self.output_dir, &self.conversion_options) {
+            self.dialog_message = Some(tr!("Impossible de démarrer la surveillance : {}", e));
+            self.show_dialog = true;
+        }
+    }
+
+    /// Relance les fichiers en échec du dernier lot : tous, ou seulement ceux de `selection`. Avec
+    /// `use_current_settings`, les réglages actuels remplacent les options du lot (ex: un autre format après un échec
+    /// d'encodage) ; les fichiers gardent leur dossier et leur nom de sortie prévus.
+    fn start_retry(&mut self, ctx: &egui::Context, selection: Option<&HashSet<PathBuf>>, use_current_settings: bool) {
+        let Some(report) = &self.last_report else {
+            return;
+        };
+        let failures = report.failures_among(selection);
+        if failures.is_empty() {
+            return;
+        }
+
+        let sources: Vec<PathBuf> = failures.iter().map(|failure| failure.source.clone()).collect();
+        self.job_queue.reset(&sources);
+        let output_root = report.output_root.clone();
+        let options = match use_current_settings {
+            true => self.conversion_options.clone(),
+            false => report.options.clone(),
+        };
+        let spec = JobSpec {
+            label: tr!("Relance de {} fichier(s) en échec", failures.len()),
+            input: None,
+            output_root: output_root.clone(),
+            single_file: false,
+            retry_of: Some(Box::new(report.clone())),
+            job: None,
+        };
+        self.jobs.submit(ctx, spec, Box::new(move |progress, control| {
+            converter::retry_failures(&failures, &output_root, &options, progress, control)
+        }));
+    }
+
+    /// Met à jour l'état de l'UI (toast, fenêtre modale) une fois la conversion terminée.
+    /// Le bilan d'une relance est fusionné avec celui du lot d'origine pour refléter l'état final.
+    /// Fenêtre en arrière-plan, réduite ou cachée, le bilan est aussi annoncé par une notification du système.
+    /// Le bilan reste affiché dans la ligne du lot `id`.
+    fn handle_conversion_result(
+        &mut self,
+        ctx: &egui::Context,
+        id: JobId,
+        result: Result<converter::ConversionReport, error::ConverterError>,
+    ) {
+        let Some(managed) = self.jobs.job_mut(id) else {
+            return;
+        };
+        let single_file = managed.spec.single_file;
+        let output_root = managed.spec.output_root.clone();
+        let history_job = managed.spec.job.clone().map(|job| (managed.spec.label.clone(), job));
+        self.retry_selection.clear(); // Le bilan change : la sélection des échecs à relancer aussi
+        let result = result.map(|report| match managed.spec.retry_of.take() {
+            Some(mut previous) => {
+                previous.merge_retry(report);
+                *previous
+            }
+            None => report,
+        });
+
+        let succeeded = result.is_ok();
+        if let (Ok(report), Some((label, job))) = (&result, &history_job) {
+            if !report.options.dry_run {
+                self.history.record(label, job, &output_root, report);
+                self.history_entries = None; // Relu à la prochaine ouverture de la fenêtre
+            }
+        }
+        let window_in_background = ctx.input(|i| {
+            let viewport = i.viewport();
+            viewport.focused == Some(false) || viewport.minimized == Some(true)
+        });
+        if self.desktop_notifications && window_in_background {
+            let notice = completion_notice(&result);
+            std::thread::spawn(move || {
+                if let Err(e) = platform_utils::send_desktop_notification(&notice) {
+                    tracing::warn!("{}", e);
+                }
+            });
+        }
+
+        match result {
+            Ok(report) if report.options.dry_run => {
+                // Simulation : rien n'a été écrit, le bilan liste l'action prévue pour chaque fichier
+                let (written, overwritten) = report.planned_counts();
+                self.toast_message = tr!(
+                    "Simulation : {} fichier(s) à convertir, dont {} écrasement(s), {} ignoré(s)",
+                    written,
+                    overwritten,
+                    report.planned.len() - written
+                );
+                self.show_toast = true;
+                self.toast_is_error = false;
+                self.toast_action = None;
+                self.show_report = true;
+                self.last_report = Some(report);
+            }
+            Ok(report) if report.cancelled => {
+                self.toast_message = tr!("Conversion annulée : {} fichier(s) converti(s)", report.converted());
+                self.show_toast = true;
+                self.toast_is_error = true;
+                self.toast_action = match report.converted() {
+                    0 => None,
+                    _ => Some(ToastAction::OpenFolder(output_root.clone())),
+                };
+                self.show_report = !single_file;
+                self.last_report = Some(report);
+            }
+            Ok(report) if !report.failures.is_empty() => {
+                self.toast_message = tr!("{} fichier(s) en échec !", report.failures.len());
+                self.show_toast = true;
+                self.toast_is_error = true;
+                self.toast_action = None;
+
+                let failure_lines: Vec<String> = report.failures
+                    .iter()
+                    .map(|failure| format!("• {} : {}", failure.source.display(), failure.error))
+                    .collect();
+                self.dialog_message = Some(tr!(
+                    "{} fichier(s) converti(s), {} ignoré(s), {} en échec :\n{}",
+                    report.converted(),
+                    report.skipped(),
+                    report.failures.len(),
+                    failure_lines.join("\n")
+                ));
+                self.show_dialog = true;
+                self.last_report = Some(report);
+            }
+            Ok(report) => {
+                self.toast_message = report_summary(&report);
+                self.show_toast = true;
+                self.toast_is_error = false;
+                // Le bilan détaillé (tailles, gain) est présenté à la fin de chaque lot
+                self.show_report = !single_file;
+
+                // Après un fichier unique, proposer d'ouvrir l'image réellement produite (le mode
+                // Renommer peut avoir choisi un autre nom) ; sinon, proposer le dossier de sortie.
+                // Avec une archive, c'est elle qui est proposée : les fichiers produits n'y sont plus épars.
+                self.toast_action = match (&report.archive, report.files.as_slice()) {
+                    (Some(archive), _) => Some(ToastAction::OpenFile(archive.clone())),
+                    (None, [file]) if single_file => Some(ToastAction::OpenFile(file.output.clone())),
+                    _ => Some(ToastAction::OpenFolder(output_root)),
+                };
+
+                if !report.warnings.is_empty() {
+                    self.dialog_message = Some(tr!(
+                        "Conversion terminée avec des avertissements :\n{}",
+                        report.warnings.join("\n")
+                    ));
+                    self.show_dialog = true;
+                }
+                self.last_report = Some(report);
+            }
+            Err(e) => {
+                self.dialog_message = Some(tr!("Erreur lors de la conversion : {}", e)); // Wrap in Some
+                self.show_dialog = true; // Afficher la modale pour les erreurs de conversion
+                self.toast_message = tr!("Erreur lors de la conversion !").to_string();
+                self.show_toast = true;
+                self.toast_is_error = true;
+                self.toast_action = None;
+            }
+        }
+
+        if let Some(managed) = self.jobs.job_mut(id) {
+            managed.outcome = Some((self.toast_message.clone(), self.toast_is_error));
+            managed.report = self.last_report.clone().filter(|_| succeeded);
+        }
+    }
+
+    /// Exécute l'action du bouton du toast et signale un éventuel échec dans un nouveau toast.
+    fn run_toast_action(&mut self) {
+        let result = match self.toast_action.take() {
+            Some(ToastAction::OpenFile(path)) => platform_utils::open_file(&path),
+            Some(ToastAction::OpenFolder(path)) => platform_utils::open_output_directory(&path),
+            None => return,
+        };
+
+        if let Err(e) = result {
+            self.toast_message = e.to_string();
+            self.toast_is_error = true;
+            self.show_toast = true;
+        } else {
+            self.show_toast = false;
+        }
+    }
+
+    /// Construit la requête d'estimation de taille : le fichier unique ou le premier des fichiers sélectionnés.
+    fn estimate_request(&self) -> Option<size_estimator::EstimateRequest> {
+        let path = match self.input.as_ref()? {
+            InputType::SingleFile(path) => path,
+            InputType::MultipleFiles(paths) => paths.first()?,
+            InputType::Directory(_) | InputType::Clipboard(_) => return None,
+        };
+        Some(size_estimator::EstimateRequest {
+            path: path.clone(),
+            options: self.conversion_options.clone(),
+        })
+    }
+
+    /// Lot que lancerait la conversion de la sélection, pour le relevé des sorties déjà présentes.
+    /// `None` pour une image collée, ou avec un sous-dossier d'exécution : toujours nouveau, il ne contient
+    /// aucune sortie existante.
+    fn preflight_job(&self) -> Option<ConversionJob> {
+        if self.conversion_options.run_folder.enabled {
+            return None;
+        }
+        let input = self.input.as_ref()?.to_job_input()?;
+        Some(ConversionJob {
+            output_dir: job_output_dir(&input, self.output_dir.clone()),
+            input,
+            options: self.conversion_options.clone(),
+            run_folder: None,
+        })
+    }
+
+    /// Construit la requête d'analyse : uniquement lorsqu'un répertoire est sélectionné.
+    fn scan_request(&self) -> Option<dir_scanner::ScanRequest> {
+        match self.input.as_ref()? {
+            InputType::Directory(path) => Some(dir_scanner::ScanRequest {
+                path: path.clone(),
+                options: self.conversion_options.clone(),
+            }),
+            _ => None,
         }
     }
 }
 
 // Ajout d'une méthode utilitaire à InputType pour faciliter la validation de chemin
 impl InputType {
-    fn get_path_for_validation(&self) -> Option<&PathBuf> {
+    /// Entrée correspondant aux sources d'une conversion (reprise ou relance d'un lot).
+    fn from_job_input(input: &JobInput) -> InputType {
+        match input {
+            JobInput::File(path) => InputType::SingleFile(path.clone()),
+            JobInput::Files(paths) => InputType::MultipleFiles(paths.clone()),
+            JobInput::Directory(path) => InputType::Directory(path.clone()),
+        }
+    }
+
+    /// Sources de la conversion de l'entrée, `None` pour une image collée (convertie depuis la mémoire).
+    fn to_job_input(&self) -> Option<JobInput> {
         match self {
-            InputType::SingleFile(path) => Some(path),
-            InputType::Directory(path) => Some(path),
-            InputType::MultipleFiles(_) => None, // La validation pour plusieurs fichiers est plus complexe, à implémenter si nécessaire
+            InputType::SingleFile(path) => Some(JobInput::File(path.clone())),
+            InputType::MultipleFiles(paths) => Some(JobInput::Files(paths.clone())),
+            InputType::Directory(path) => Some(JobInput::Directory(path.clone())),
+            InputType::Clipboard(_) => None,
+        }
+    }
+
+    /// Entrée correspondant à des chemins déposés ou passés au lancement : un fichier unique, un répertoire,
+    /// ou plusieurs fichiers. `None` si aucun chemin n'est utilisable.
+    fn from_paths(mut paths: Vec<PathBuf>) -> Option<InputType> {
+        match paths.len() {
+            0 => None,
+            1 => {
+                let path = paths.remove(0);
+                if path.is_file() {
+                    Some(InputType::SingleFile(path))
+                } else if path.is_dir() {
+                    Some(InputType::Directory(path))
+                } else {
+                    None
+                }
+            }
+            _ => Some(InputType::MultipleFiles(paths)),
         }
     }
 }
 
+/// Répertoire de sortie du lot `input` dans `output_dir` : un répertoire est reproduit dans un sous-dossier
+/// de la sortie portant son nom.
+fn job_output_dir(input: &JobInput, output_dir: PathBuf) -> PathBuf {
+    match input {
+        JobInput::Directory(dir_path) => output_dir.join(dir_path.file_name().unwrap_or_default()),
+        _ => output_dir,
+    }
+}
+
+/// Construit le message de succès affiché dans le toast à partir du bilan de conversion.
+fn report_summary(report: &converter::ConversionReport) -> String {
+    let mut details = Vec::new();
+    if report.skipped_by_manifest > 0 {
+        details.push(tr!(
+            "{} converti(s), {} inchangé(s) ignoré(s)",
+            report.converted(),
+            report.skipped_by_manifest
+        ));
+    }
+    if report.skipped_resumed > 0 {
+        details.push(tr!("{} déjà traité(s) avant l'interruption", report.skipped_resumed));
+    }
+    if report.skipped_existing > 0 {
+        details.push(tr!("{} ignoré(s) car déjà présent(s)", report.skipped_existing));
+    }
+    if report.skipped_larger > 0 {
+        details.push(tr!("{} ignoré(s) car plus lourd(s)", report.skipped_larger));
+    }
+    if report.kept_originals() > 0 {
+        details.push(tr!("{} original(aux) conservé(s)", report.kept_originals()));
+    }
+    if report.removed_originals() > 0 {
+        let destination = match report.options.originals {
+            originals::OriginalsMode::Trash => tr!("à la corbeille"),
+            _ => tr!("dans originals/"),
+        };
+        details.push(tr!("{} original(aux) déplacé(s) {}", report.removed_originals(), destination));
+    }
+    if report.filtered_by_dimensions > 0 {
+        details.push(tr!("{} filtré(s) par dimensions", report.filtered_by_dimensions));
+    }
+    if report.filtered_by_file > 0 {
+        details.push(tr!("{} filtré(s) par taille ou date", report.filtered_by_file));
+    }
+    if !report.duplicates.is_empty() {
+        match report.options.duplicates.exclude {
+            true => details.push(tr!("{} doublon(s) écarté(s)", report.duplicates.len())),
+            false => details.push(tr!("{} doublon(s) repéré(s)", report.duplicates.len())),
+        }
+    }
+    if report.verification_failures() > 0 {
+        details.push(tr!("{} sortie(s) en échec de vérification", report.verification_failures()));
+    }
+    if !report.srcset_snippets.is_empty() {
+        details.push(tr!("{} extrait(s) srcset", report.srcset_snippets.len()));
+    }
+    if !report.contact_sheets.is_empty() {
+        details.push(tr!("planche contact : {} page(s)", report.contact_sheets.len()));
+    }
+    if let Some(document) = report.pdf_bundle.as_ref().and_then(|document| document.file_name()) {
+        details.push(tr!("document PDF : {}", document.to_string_lossy()));
+    }
+    if let Some(sheet) = report.sprite_sheet.first().and_then(|sheet| sheet.file_name()) {
+        details.push(tr!("planche de sprites : {}", sheet.to_string_lossy()));
+    }
+    if let Some(archive) = report.archive.as_ref().and_then(|archive| archive.file_name()) {
+        details.push(tr!("archivé(s) dans {}", archive.to_string_lossy()));
+    }
+    if let Some(percent) = report.savings_percent() {
+        details.push(tr!(
+            "{} → {}, {} % d'espace gagné",
+            ui_helpers::format_size(report.input_bytes()),
+            ui_helpers::format_size(report.output_bytes()),
+            format!("{:.1}", percent)
+        ));
+    }
+
+    if details.is_empty() {
+        tr!("Conversion terminée avec succès !").to_string()
+    } else {
+        tr!("Conversion terminée avec succès ! ({})", details.join(", "))
+    }
+}
+
+/// Texte court de la notification de fin de conversion (ex: « 42 image(s) convertie(s), 3 erreur(s) »).
+fn completion_notice(result: &Result<converter::ConversionReport, error::ConverterError>) -> String {
+    match result {
+        Ok(report) if report.options.dry_run => tr!("Simulation terminée : {} fichier(s) à convertir", report.planned_counts().0),
+        Ok(report) if report.cancelled => tr!("Conversion annulée : {} fichier(s) converti(s)", report.converted()),
+        Ok(report) if !report.failures.is_empty() => {
+            tr!("{} image(s) convertie(s), {} erreur(s)", report.converted(), report.failures.len())
+        }
+        Ok(report) => tr!("{} image(s) convertie(s)", report.converted()),
+        Err(e) => tr!("Échec de la conversion : {}", e),
+    }
+}
+
+/// Chemins passés au lancement par « Ouvrir avec… » ou une association de fichiers (ex: `image_converter a.png b.jpg`) :
+/// uniquement des fichiers ou dossiers existants, sans option. `None` pour des arguments de la ligne de commande.
+fn opened_paths() -> Option<Vec<PathBuf>> {
+    let paths: Vec<PathBuf> = std::env::args_os().skip(1).map(PathBuf::from).collect();
+    let only_paths = paths.iter().all(|path| !path.to_string_lossy().starts_with('-') && path.exists());
+    (!paths.is_empty() && only_paths).then_some(paths)
+}
+
 /// Point d'entrée du programme, configure et lance l'application graphique.
 fn main() {
-    // Configure les options de la fenêtre (taille 600x500 pour plus d'espace).
+    // Des fichiers passés seuls ouvrent la fenêtre avec cette sélection ; avec d'autres arguments,
+    // la conversion est lancée en ligne de commande, sans ouvrir de fenêtre.
+    let opened_paths = opened_paths();
+    if opened_paths.is_none() && std::env::args_os().len() > 1 {
+        logging::init(false); // La ligne de commande affiche elle-même les échecs
+        std::process::exit(cli::run());
+    }
+    // Une fenêtre déjà ouverte reçoit les fichiers à la place d'une nouvelle (instance unique)
+    if opened_paths.as_deref().is_some_and(platform_utils::send_to_running_instance) {
+        return;
+    }
+    let initial_input = opened_paths.and_then(InputType::from_paths);
+    let logs = logging::init(true);
+
+    // Configure les options de la fenêtre : taille, position et agrandissement de la session précédente,
+    // 600x500 à l'emplacement choisi par le système au premier lancement.
+    let config = config::AppConfig::load();
+    i18n::set_language(config.language);
+    let window_size = config.window_size.unwrap_or([600.0, 500.0]);
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(window_size)
+        .with_maximized(config.window_maximized);
+    if let Some(position) = config.window_position {
+        viewport = viewport.with_position(position);
+    }
     let native_options = NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([600.0, 500.0]),
+        viewport,
         ..Default::default()
     };
 
     // Lance l'application `eframe` avec `ImageConverterApp`.
     eframe::run_native(
-        "Convertisseur d'Images",
+        tr!("Convertisseur d'Images"),
         native_options,
-        Box::new(|_cc: &CreationContext| Ok(Box::new(ImageConverterApp::default()))),
+        Box::new(|cc: &CreationContext| {
+            let app = ImageConverterApp {
+                input: initial_input,
+                tray: tray::SystemTray::new(&cc.egui_ctx),
+                logs,
+                ..Default::default()
+            };
+            // Les fichiers des lancements suivants sont ajoutés à la sélection de cette fenêtre
+            let forwarded_paths = Arc::clone(&app.forwarded_paths);
+            let ctx = cc.egui_ctx.clone();
+            platform_utils::listen_for_other_instances(move |paths| {
+                forwarded_paths.lock().unwrap().extend(paths);
+                ctx.request_repaint();
+            });
+            Ok(Box::new(app))
+        }),
     )
         .unwrap();
 }
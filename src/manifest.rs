@@ -0,0 +1,113 @@
+/// Ce module gère le manifeste des conversions, utilisé par le mode incrémental.
+/// Le manifeste (`.image_converter_manifest.json`, à la racine de la sortie) associe chaque source
+/// convertie à l'empreinte de son contenu et aux options utilisées, indépendamment des dates de modification.
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::ConverterError;
+use crate::tr;
+
+/// Nom du fichier manifeste dans le répertoire de sortie.
+pub const MANIFEST_FILE_NAME: &str = ".image_converter_manifest.json";
+
+/// Nombre maximal d'entrées conservées : au-delà, les plus anciennes sont retirées.
+const MAX_ENTRIES: usize = 100_000;
+
+/// Informations enregistrées pour une source convertie.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub hash: String,      // Empreinte SHA-256 (hexadécimale) du contenu de la source.
+    pub options: String,   // Signature des options ayant produit la sortie.
+    pub output: PathBuf,   // Fichier produit lors de la conversion.
+    pub converted_at: u64, // Date de conversion (secondes depuis l'epoch Unix).
+}
+
+/// Manifeste des conversions d'un répertoire de sortie.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(skip)]
+    path: PathBuf, // Emplacement du fichier manifeste.
+    entries: HashMap<PathBuf, ManifestEntry>, // Entrées indexées par chemin canonique de la source.
+}
+
+impl Manifest {
+    /// Charge le manifeste de `output_root`. Un manifeste absent ou illisible donne un manifeste vide,
+    /// ce qui revient à une conversion complète.
+    pub fn load(output_root: &Path) -> Self {
+        let path = output_root.join(MANIFEST_FILE_NAME);
+        let mut manifest: Manifest = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        manifest.path = path;
+        manifest
+    }
+
+    /// Indique si `source` a déjà été convertie avec ce contenu et ces options, et que la sortie existe encore.
+    pub fn is_up_to_date(&self, source: &Path, hash: &str, options: &str) -> bool {
+        self.entries
+            .get(&source_key(source))
+            .is_some_and(|entry| entry.hash == hash && entry.options == options && entry.output.exists())
+    }
+
+    /// Enregistre (ou remplace) l'entrée de `source` après une conversion réussie.
+    pub fn record(&mut self, source: &Path, hash: String, options: String, output: PathBuf) {
+        let converted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.entries.insert(source_key(source), ManifestEntry { hash, options, output, converted_at });
+    }
+
+    /// Retire les sources disparues, limite la taille puis écrit le manifeste de manière atomique
+    /// (fichier temporaire puis renommage), afin qu'une interruption ne laisse jamais un manifeste tronqué.
+    pub fn save(&mut self) -> Result<(), ConverterError> {
+        self.entries.retain(|source, _| source.exists());
+
+        if self.entries.len() > MAX_ENTRIES {
+            // Tri puis troncature : des dates identiques ne font pas dépasser la limite.
+            let mut entries: Vec<(PathBuf, ManifestEntry)> = self.entries.drain().collect();
+            entries.sort_unstable_by_key(|(_, entry)| std::cmp::Reverse(entry.converted_at));
+            entries.truncate(MAX_ENTRIES);
+            self.entries = entries.into_iter().collect();
+        }
+
+        let content = serde_json::to_string(self)?;
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, content)
+            .map_err(|e| ConverterError::io(tr!("Impossible d'écrire le manifeste {}", temp_path.display()), e))?;
+        fs::rename(&temp_path, &self.path)
+            .map_err(|e| ConverterError::io(tr!("Impossible de remplacer le manifeste {}", self.path.display()), e))?;
+        Ok(())
+    }
+}
+
+/// Calcule l'empreinte SHA-256 (hexadécimale) du contenu d'un fichier, sans le décoder.
+pub fn hash_file(path: &Path) -> Result<String, ConverterError> {
+    let file = fs::File::open(path)
+        .map_err(|e| ConverterError::io(tr!("Impossible de lire {}", path.display()), e))?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = reader.read(&mut buffer)
+            .map_err(|e| ConverterError::io(tr!("Impossible de lire {}", path.display()), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Clé d'une source dans le manifeste : son chemin canonique, ou le chemin tel quel à défaut.
+fn source_key(source: &Path) -> PathBuf {
+    source.canonicalize().unwrap_or_else(|_| source.to_path_buf())
+}
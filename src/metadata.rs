@@ -0,0 +1,259 @@
+/// Ce module supprime les métadonnées (EXIF, XMP, profil ICC, commentaires) des fichiers encodés,
+/// ou y intègre un profil ICC. Le traitement porte sur les octets produits par l'encodeur, juste avant l'écriture :
+/// quelle que soit l'origine d'une métadonnée (copie depuis la source, ajout par un encodeur), elle ne peut pas atteindre le fichier.
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Crc};
+use std::io::Write;
+
+use crate::converter::OutputFormat;
+use crate::error::ConverterError;
+use crate::tr;
+
+/// Signature d'un fichier PNG.
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Chunks PNG retirés : EXIF, profil ICC, textes (dont XMP, stocké dans un `iTXt`) et date de modification.
+const PNG_METADATA_CHUNKS: [&[u8; 4]; 6] = [b"eXIf", b"iCCP", b"iTXt", b"tEXt", b"zTXt", b"tIME"];
+
+/// Segments JPEG retirés : APP1 (EXIF, XMP), APP2 (ICC), APP13 (IPTC) et commentaires.
+/// APP0 (JFIF) et APP14 (Adobe) sont conservés : ils décrivent l'espace colorimétrique des données.
+const JPEG_METADATA_MARKERS: [u8; 4] = [0xE1, 0xE2, 0xED, 0xFE];
+
+/// Chunks WebP retirés, et bits correspondants de l'en-tête étendu `VP8X`.
+const WEBP_METADATA_CHUNKS: [&[u8; 4]; 3] = [b"ICCP", b"EXIF", b"XMP "];
+const WEBP_METADATA_FLAGS: u8 = 0x20 | 0x08 | 0x04; // ICC, EXIF, XMP
+
+/// En-tête des segments APP2 portant un profil ICC dans un JPEG.
+const JPEG_ICC_HEADER: &[u8] = b"ICC_PROFILE\0";
+
+/// Taille maximale du profil dans un segment APP2 : 65 535 octets moins la longueur, l'en-tête et la numérotation.
+const JPEG_ICC_CHUNK_SIZE: usize = 65_535 - 2 - JPEG_ICC_HEADER.len() - 2;
+
+/// Bits de l'en-tête étendu `VP8X` signalant un profil ICC et la transparence.
+const WEBP_ICC_FLAG: u8 = 0x20;
+const WEBP_ALPHA_FLAG: u8 = 0x10;
+
+/// Retire les métadonnées du fichier encodé `bytes` au format `format`.
+/// L'AVIF et le TIFF produits par `image` ne contiennent que les données de l'image : ils sont retournés tels quels.
+pub fn strip(bytes: Vec<u8>, format: OutputFormat) -> Result<Vec<u8>, ConverterError> {
+    let stripped = match format {
+        OutputFormat::Png => strip_png(&bytes),
+        OutputFormat::Jpeg => strip_jpeg(&bytes),
+        OutputFormat::WebP => strip_webp(&bytes),
+        OutputFormat::Avif | OutputFormat::Tiff => return Ok(bytes),
+    };
+    stripped.ok_or_else(|| {
+        ConverterError::encode(format.label(), tr!("structure du fichier inattendue, métadonnées non supprimées"))
+    })
+}
+
+/// Recopie les chunks PNG, sauf ceux de métadonnées. `None` si le fichier est mal formé.
+fn strip_png(bytes: &[u8]) -> Option<Vec<u8>> {
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        return None;
+    }
+
+    let mut output = PNG_SIGNATURE.to_vec();
+    let mut offset = PNG_SIGNATURE.len();
+    while offset < bytes.len() {
+        let length = u32::from_be_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?) as usize;
+        let chunk_type = bytes.get(offset + 4..offset + 8)?;
+        // Longueur, type, données puis CRC.
+        let chunk = bytes.get(offset..offset + 12 + length)?;
+        if !PNG_METADATA_CHUNKS.iter().any(|metadata| chunk_type == *metadata) {
+            output.extend_from_slice(chunk);
+        }
+        offset += chunk.len();
+    }
+    Some(output)
+}
+
+/// Recopie les segments JPEG jusqu'au début des données (SOS), sauf ceux de métadonnées.
+/// `None` si le fichier est mal formé.
+fn strip_jpeg(bytes: &[u8]) -> Option<Vec<u8>> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let mut output = vec![0xFF, 0xD8];
+    let mut offset = 2;
+    loop {
+        if *bytes.get(offset)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(offset + 1)?;
+        let length = u16::from_be_bytes(bytes.get(offset + 2..offset + 4)?.try_into().ok()?) as usize;
+        let segment = bytes.get(offset..offset + 2 + length)?;
+        if marker == 0xDA {
+            // Début des données compressées : le reste du fichier est recopié tel quel.
+            output.extend_from_slice(&bytes[offset..]);
+            return Some(output);
+        }
+        if !JPEG_METADATA_MARKERS.contains(&marker) {
+            output.extend_from_slice(segment);
+        }
+        offset += segment.len();
+    }
+}
+
+/// Recopie les chunks WebP, sauf ceux de métadonnées, et met à jour l'en-tête `VP8X` et la taille RIFF.
+/// `None` si le fichier est mal formé.
+fn strip_webp(bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WEBP" {
+        return None;
+    }
+
+    let mut output = bytes[0..12].to_vec();
+    let mut offset = 12;
+    while offset < bytes.len() {
+        let fourcc = bytes.get(offset..offset + 4)?;
+        let size = u32::from_le_bytes(bytes.get(offset + 4..offset + 8)?.try_into().ok()?) as usize;
+        // Les chunks sont alignés sur deux octets.
+        let padded = size + (size & 1);
+        let chunk = bytes.get(offset..(offset + 8 + padded).min(bytes.len()))?;
+
+        if fourcc == b"VP8X" {
+            let start = output.len();
+            output.extend_from_slice(chunk);
+            *output.get_mut(start + 8)? &= !WEBP_METADATA_FLAGS;
+        } else if !WEBP_METADATA_CHUNKS.iter().any(|metadata| fourcc == *metadata) {
+            output.extend_from_slice(chunk);
+        }
+        offset += 8 + padded;
+    }
+
+    let riff_size = u32::try_from(output.len() - 8).ok()?;
+    output[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(output)
+}
+
+/// Indique si le profil ICC peut être intégré au format `format` (PNG, JPEG et WebP).
+pub fn supports_icc(format: OutputFormat) -> bool {
+    matches!(format, OutputFormat::Png | OutputFormat::Jpeg | OutputFormat::WebP)
+}
+
+/// Intègre le profil ICC `icc_profile` dans le fichier encodé `bytes` au format `format` (voir `supports_icc`).
+pub fn embed_icc(bytes: Vec<u8>, format: OutputFormat, icc_profile: &[u8]) -> Result<Vec<u8>, ConverterError> {
+    let embedded = match format {
+        OutputFormat::Png => embed_icc_png(&bytes, icc_profile),
+        OutputFormat::Jpeg => embed_icc_jpeg(&bytes, icc_profile),
+        OutputFormat::WebP => embed_icc_webp(&bytes, icc_profile),
+        OutputFormat::Avif | OutputFormat::Tiff => return Ok(bytes),
+    };
+    embedded.ok_or_else(|| ConverterError::encode(format.label(), tr!("structure du fichier inattendue, profil ICC non intégré")))
+}
+
+/// Ajoute un chunk `iCCP` (profil compressé avec zlib) juste après l'en-tête `IHDR`.
+fn embed_icc_png(bytes: &[u8], icc_profile: &[u8]) -> Option<Vec<u8>> {
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        return None;
+    }
+    let ihdr_length = u32::from_be_bytes(bytes.get(8..12)?.try_into().ok()?) as usize;
+    let ihdr_end = PNG_SIGNATURE.len() + 12 + ihdr_length;
+    if bytes.get(12..16)? != b"IHDR" || bytes.len() < ihdr_end {
+        return None;
+    }
+
+    // Nom du profil, séparateur, méthode de compression (0 = zlib) puis profil compressé.
+    let mut data = b"ICC Profile\0\0".to_vec();
+    let mut compressor = ZlibEncoder::new(&mut data, Compression::default());
+    compressor.write_all(icc_profile).ok()?;
+    compressor.finish().ok()?;
+
+    let mut crc = Crc::new();
+    crc.update(b"iCCP");
+    crc.update(&data);
+
+    let mut output = bytes[..ihdr_end].to_vec();
+    output.extend_from_slice(&u32::try_from(data.len()).ok()?.to_be_bytes());
+    output.extend_from_slice(b"iCCP");
+    output.extend_from_slice(&data);
+    output.extend_from_slice(&crc.sum().to_be_bytes());
+    output.extend_from_slice(&bytes[ihdr_end..]);
+    Some(output)
+}
+
+/// Ajoute le profil en segments APP2 numérotés, après l'en-tête JFIF (APP0) s'il est présent.
+fn embed_icc_jpeg(bytes: &[u8], icc_profile: &[u8]) -> Option<Vec<u8>> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut insert_at = 2;
+    if bytes.get(2..4)? == [0xFF, 0xE0] {
+        insert_at += 2 + u16::from_be_bytes(bytes.get(4..6)?.try_into().ok()?) as usize;
+    }
+
+    let chunks: Vec<&[u8]> = icc_profile.chunks(JPEG_ICC_CHUNK_SIZE).collect();
+    let count = u8::try_from(chunks.len()).ok()?;
+    let mut output = bytes.get(..insert_at)?.to_vec();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let length = u16::try_from(2 + JPEG_ICC_HEADER.len() + 2 + chunk.len()).ok()?;
+        output.extend_from_slice(&[0xFF, 0xE2]);
+        output.extend_from_slice(&length.to_be_bytes());
+        output.extend_from_slice(JPEG_ICC_HEADER);
+        output.extend_from_slice(&[index as u8 + 1, count]);
+        output.extend_from_slice(chunk);
+    }
+    output.extend_from_slice(&bytes[insert_at..]);
+    Some(output)
+}
+
+/// Ajoute un chunk `ICCP` après l'en-tête étendu `VP8X`, créé si le fichier est au format simple (`VP8 ` ou `VP8L`).
+fn embed_icc_webp(bytes: &[u8], icc_profile: &[u8]) -> Option<Vec<u8>> {
+    if bytes.len() < 20 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WEBP" {
+        return None;
+    }
+
+    let mut output = bytes[0..12].to_vec();
+    let rest = match &bytes[12..16] {
+        b"VP8X" => {
+            let vp8x = bytes.get(12..30)?;
+            output.extend_from_slice(vp8x);
+            output[20] |= WEBP_ICC_FLAG;
+            &bytes[30..]
+        }
+        fourcc @ (b"VP8 " | b"VP8L") => {
+            let (width, height, has_alpha) = simple_webp_info(fourcc, bytes.get(20..30)?)?;
+            let flags = WEBP_ICC_FLAG | if has_alpha { WEBP_ALPHA_FLAG } else { 0 };
+            output.extend_from_slice(b"VP8X");
+            output.extend_from_slice(&10u32.to_le_bytes());
+            output.extend_from_slice(&[flags, 0, 0, 0]);
+            output.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+            output.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+            &bytes[12..]
+        }
+        _ => return None,
+    };
+
+    output.extend_from_slice(b"ICCP");
+    output.extend_from_slice(&u32::try_from(icc_profile.len()).ok()?.to_le_bytes());
+    output.extend_from_slice(icc_profile);
+    if icc_profile.len() % 2 == 1 {
+        output.push(0);
+    }
+    output.extend_from_slice(rest);
+
+    let riff_size = u32::try_from(output.len() - 8).ok()?;
+    output[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(output)
+}
+
+/// Dimensions et présence de transparence d'un WebP simple, lues dans les premiers octets de ses données.
+fn simple_webp_info(fourcc: &[u8], data: &[u8]) -> Option<(u32, u32, bool)> {
+    if fourcc == b"VP8L" {
+        // Signature 0x2F, puis largeur - 1 et hauteur - 1 sur 14 bits chacune, puis le bit de transparence.
+        if data[0] != 0x2F {
+            return None;
+        }
+        let bits = u32::from_le_bytes(data.get(1..5)?.try_into().ok()?);
+        Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1, (bits >> 28) & 1 == 1))
+    } else {
+        // Image clé VP8 : 3 octets d'en-tête, code de démarrage 9D 01 2A, puis largeur et hauteur sur 14 bits.
+        if data.get(3..6)? != [0x9D, 0x01, 0x2A] {
+            return None;
+        }
+        let width = u16::from_le_bytes(data.get(6..8)?.try_into().ok()?) & 0x3FFF;
+        let height = u16::from_le_bytes(data.get(8..10)?.try_into().ok()?) & 0x3FFF;
+        Some((width as u32, height as u32, false))
+    }
+}
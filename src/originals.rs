@@ -0,0 +1,104 @@
+/// Ce module traite les images sources après une conversion réussie : conservées (par défaut), placées
+/// dans la corbeille du système (`trash`) ou déplacées dans le dossier de sauvegarde `originals/` de la sortie.
+/// Une source n'est retirée qu'une fois le fichier produit vérifié (présent et de la taille écrite).
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::tr;
+
+/// Nom du dossier de sauvegarde des originaux, à la racine de la sortie.
+pub const BACKUP_DIR_NAME: &str = "originals";
+
+/// Sort des images sources après leur conversion.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum OriginalsMode {
+    Keep,   // Laisser les sources en place
+    Trash,  // Placer les sources dans la corbeille
+    Backup, // Déplacer les sources dans le dossier `originals/` de la sortie
+}
+
+impl OriginalsMode {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            OriginalsMode::Keep => tr!("Conserver"),
+            OriginalsMode::Trash => tr!("Mettre à la corbeille"),
+            OriginalsMode::Backup => tr!("Déplacer dans originals/"),
+        }
+    }
+}
+
+/// Sort d'une image source convertie, consigné dans le bilan.
+#[derive(Debug, PartialEq, Clone)]
+pub enum OriginalOutcome {
+    Kept,             // Source laissée en place.
+    Trashed,          // Source placée dans la corbeille.
+    MovedTo(PathBuf), // Source déplacée dans le dossier de sauvegarde.
+    Failed(String),   // Source laissée en place : vérification ou déplacement impossible.
+}
+
+impl OriginalOutcome {
+    /// Description affichée dans le bilan et l'export du rapport.
+    pub fn description(&self) -> String {
+        match self {
+            OriginalOutcome::Kept => tr!("conservé").to_string(),
+            OriginalOutcome::Trashed => tr!("mis à la corbeille").to_string(),
+            OriginalOutcome::MovedTo(path) => tr!("déplacé vers {}", path.display()),
+            OriginalOutcome::Failed(message) => tr!("conservé ({})", message),
+        }
+    }
+}
+
+/// Applique `mode` à la source `source`, une fois vérifié que `output` existe et fait `output_size` octets.
+/// En mode sauvegarde, la source est déplacée dans `output_root/originals/`, sous `relative_dir`
+/// (l'arborescence de la sortie), avec un suffixe numérique si le nom est déjà pris.
+pub fn dispose(
+    mode: OriginalsMode,
+    source: &Path,
+    output: &Path,
+    output_size: u64,
+    output_root: &Path,
+    relative_dir: &Path,
+) -> OriginalOutcome {
+    if mode == OriginalsMode::Keep {
+        return OriginalOutcome::Kept;
+    }
+    match fs::metadata(output) {
+        Ok(metadata) if metadata.len() == output_size && output_size > 0 => {}
+        _ => return OriginalOutcome::Failed(tr!("fichier produit introuvable ou incomplet").to_string()),
+    }
+
+    match mode {
+        OriginalsMode::Keep => OriginalOutcome::Kept,
+        OriginalsMode::Trash => match trash::delete(source) {
+            Ok(()) => OriginalOutcome::Trashed,
+            Err(e) => OriginalOutcome::Failed(tr!("corbeille indisponible : {}", e)),
+        },
+        OriginalsMode::Backup => match move_to_backup(source, &output_root.join(BACKUP_DIR_NAME).join(relative_dir)) {
+            Ok(destination) => OriginalOutcome::MovedTo(destination),
+            Err(message) => OriginalOutcome::Failed(message),
+        },
+    }
+}
+
+/// Déplace `source` dans `backup_dir` (créé si besoin) et retourne son nouvel emplacement.
+/// Entre deux disques, le déplacement se fait par copie puis suppression de la source.
+fn move_to_backup(source: &Path, backup_dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(backup_dir).map_err(|e| tr!("impossible de créer {} : {}", backup_dir.display(), e))?;
+
+    let stem = source.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = source.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+    let mut destination = backup_dir.join(format!("{}{}", stem, extension));
+    let mut counter = 1;
+    while destination.exists() {
+        destination = backup_dir.join(format!("{}-{}{}", stem, counter, extension));
+        counter += 1;
+    }
+
+    if fs::rename(source, &destination).is_err() {
+        fs::copy(source, &destination).map_err(|e| tr!("impossible de copier vers {} : {}", destination.display(), e))?;
+        fs::remove_file(source).map_err(|e| tr!("copie faite, mais impossible de supprimer la source : {}", e))?;
+    }
+    Ok(destination)
+}
@@ -0,0 +1,158 @@
+/// Ce module rastérise les pages des documents PDF avec PDFium (`pdfium-render`) : chaque page sélectionnée
+/// (ex: « 1-3,5 », toutes par défaut) devient une image à la résolution choisie, qui suit ensuite le même traitement
+/// que les autres sources. La bibliothèque PDFium est chargée à l'exécution, depuis le dossier de l'application
+/// ou parmi les bibliothèques du système.
+use image::error::{DecodingError, ImageFormatHint};
+use image::{DynamicImage, ImageError};
+use pdfium_render::prelude::{PdfDocument as PdfiumDocument, PdfRenderConfig, Pdfium};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::error::ConverterError;
+use crate::tr;
+
+/// Résolution de rendu proposée par défaut (points par pouce).
+pub const DEFAULT_DPI: u32 = 150;
+
+/// Résolutions acceptées (points par pouce) : au-delà, une page A4 dépasserait 100 mégapixels.
+pub const DPI_RANGE: std::ops::RangeInclusive<u32> = 36..=1200;
+
+/// Points (unité des pages PDF) par pouce.
+const POINTS_PER_INCH: f32 = 72.0;
+
+/// Réglages du rendu des pages PDF.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PdfOptions {
+    pub dpi: u32,      // Résolution du rendu (points par pouce).
+    pub pages: String, // Pages à convertir (ex: « 1-3,5 »), vide pour toutes.
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self { dpi: DEFAULT_DPI, pages: String::new() }
+    }
+}
+
+impl PdfOptions {
+    /// Signature du rendu, intégrée à celle des options de sortie (manifeste). `None` avec les réglages par défaut.
+    pub fn signature(&self) -> Option<String> {
+        (*self != Self::default()).then(|| format!("pdf={}dpi;pages={}", self.dpi, self.pages.trim()))
+    }
+}
+
+/// Indique si le fichier porte l'extension d'un document PDF.
+pub fn is_pdf(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+}
+
+/// Suffixe du nom des images produites pour la page `page` (numérotée à partir de 1), ex: `-p3`.
+pub fn page_suffix(page: usize) -> String {
+    format!("-p{}", page)
+}
+
+/// Numéros (à partir de 1) des pages désignées par `spec` dans un document de `page_count` pages, dans l'ordre
+/// croissant et sans doublon. `spec` liste des pages et des plages séparées par des virgules (ex: « 1-3,5,8- ») ;
+/// vide, il désigne toutes les pages. Les pages au-delà de la fin du document sont ignorées.
+pub fn parse_page_range(spec: &str, page_count: usize) -> Result<Vec<usize>, ConverterError> {
+    if spec.trim().is_empty() {
+        return Ok((1..=page_count).collect());
+    }
+    let invalid = || ConverterError::PathValidation(tr!("Sélection invalide « {} » (ex: 1-3,5)", spec.trim()));
+    let parse = |number: &str, default: usize| match number.trim() {
+        "" => Ok(default),
+        number => number.parse::<usize>().ok().filter(|&page| page > 0).ok_or_else(invalid),
+    };
+
+    let mut pages = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (parse(first, 1)?, parse(last, page_count)?),
+            None => {
+                let page = parse(part, 0)?;
+                (page, page)
+            }
+        };
+        if first > last && last > 0 {
+            return Err(invalid());
+        }
+        pages.extend(first..=last.min(page_count));
+    }
+    pages.sort_unstable();
+    pages.dedup();
+    Ok(pages)
+}
+
+/// Document PDF ouvert, dont les pages sont rendues à la demande.
+pub struct PdfDocument {
+    path: PathBuf,                     // Chemin du document, pour les messages d'erreur.
+    document: PdfiumDocument<'static>, // Document chargé par PDFium.
+}
+
+impl PdfDocument {
+    /// Ouvre le document `path`. Échoue si la bibliothèque PDFium est introuvable ou le document illisible
+    /// (fichier corrompu, protégé par un mot de passe...).
+    pub fn open(path: &Path) -> Result<Self, ConverterError> {
+        let pdfium = pdfium().map_err(|message| decoding_error(path, message.clone()))?;
+        let document = pdfium.load_pdf_from_file(path, None).map_err(|e| decoding_error(path, e.to_string()))?;
+        Ok(Self { path: path.to_path_buf(), document })
+    }
+
+    /// Nombre de pages du document.
+    pub fn page_count(&self) -> usize {
+        self.document.pages().len() as usize
+    }
+
+    /// Pages du document désignées par `options.pages` (voir `parse_page_range`), au moins une.
+    pub fn selected_pages(&self, options: &PdfOptions) -> Result<Vec<usize>, ConverterError> {
+        let pages = parse_page_range(&options.pages, self.page_count())?;
+        match pages.is_empty() {
+            true => Err(decoding_error(&self.path, tr!("Aucune page sélectionnée dans ce document").to_string())),
+            false => Ok(pages),
+        }
+    }
+
+    /// Rend la page `page` (numérotée à partir de 1) à `dpi` points par pouce, sur fond blanc.
+    pub fn render_page(&self, page: usize, dpi: u32) -> Result<DynamicImage, ConverterError> {
+        let index = u16::try_from(page.saturating_sub(1)).map_err(|e| decoding_error(&self.path, e.to_string()))?;
+        let dpi = dpi.clamp(*DPI_RANGE.start(), *DPI_RANGE.end());
+        let config = PdfRenderConfig::new().scale_page_by_factor(dpi as f32 / POINTS_PER_INCH);
+        let page_error = |e: pdfium_render::prelude::PdfiumError| decoding_error(&self.path, tr!("page {} : {}", page, e));
+        let pdf_page = self.document.pages().get(index).map_err(page_error)?;
+        let bitmap = pdf_page.render_with_config(&config).map_err(page_error)?;
+        Ok(bitmap.as_image())
+    }
+}
+
+/// Première page sélectionnée de `path`, pour l'aperçu, l'estimation de la taille ou le mode favicon.
+pub fn render_first_page(path: &Path, options: &PdfOptions) -> Result<DynamicImage, ConverterError> {
+    let document = PdfDocument::open(path)?;
+    let pages = document.selected_pages(options)?;
+    document.render_page(pages[0], options.dpi)
+}
+
+/// Instance de PDFium partagée par l'application, chargée au premier document. Un échec du chargement est conservé :
+/// la bibliothèque n'est pas recherchée à nouveau pendant la session.
+fn pdfium() -> Result<&'static Pdfium, &'static String> {
+    static PDFIUM: OnceLock<Result<Pdfium, String>> = OnceLock::new();
+    PDFIUM
+        .get_or_init(|| {
+            let app_dir = std::env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf)).unwrap_or_default();
+            Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path(&app_dir))
+                .or_else(|_| Pdfium::bind_to_system_library())
+                .map(Pdfium::new)
+                .map_err(|e| {
+                    tracing::warn!("PDFium non chargé : {:?}", e);
+                    let library = Pdfium::pdfium_platform_library_name();
+                    tr!("Bibliothèque PDFium introuvable : placez {} à côté de l'application", library.to_string_lossy())
+                })
+        })
+        .as_ref()
+}
+
+/// Erreur de décodage du document `path`.
+fn decoding_error(path: &Path, message: String) -> ConverterError {
+    let error = ImageError::Decoding(DecodingError::new(ImageFormatHint::Name("PDF".to_string()), message));
+    ConverterError::decode(path, error)
+}
@@ -0,0 +1,158 @@
+/// Ce module réunit les images produites par une conversion en un seul document PDF (`printpdf`), une image par page,
+/// dans l'ordre des sources : pratique pour regrouper des numérisations une fois compressées. Les sorties JPEG sont
+/// intégrées telles quelles, sans nouvelle compression ; les autres formats sont intégrés en pixels, sur la couleur de fond.
+use image::codecs::jpeg::JpegDecoder;
+use image::{ColorType, ImageDecoder, ImageFormat};
+use printpdf::{ColorBits, ColorSpace, Image, ImageFilter, ImageTransform, ImageXObject, Mm, PdfDocument, Px};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use crate::encoder;
+use crate::tr;
+
+/// Millimètres par pouce.
+const MM_PER_INCH: f32 = 25.4;
+
+/// Réglages du document PDF d'une conversion.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PdfBundleOptions {
+    pub enabled: bool, // Réunir les images produites dans un document PDF à la fin de la conversion.
+    pub name: String,  // Nom du document (sans extension), écrit à la racine de la sortie.
+    pub dpi: u32,      // Résolution des images dans le document : fixe la taille des pages.
+}
+
+impl Default for PdfBundleOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            name: "images".to_string(),
+            dpi: 150,
+        }
+    }
+}
+
+/// Relève les images produites au fil de la conversion, puis écrit le document à la fin.
+pub struct PdfBundleBuilder {
+    options: PdfBundleOptions,
+    output_root: PathBuf,
+    background_color: [u8; 3],       // Fond des images transparentes.
+    images: Vec<(PathBuf, PathBuf)>, // Source et image produite de chaque page.
+}
+
+impl PdfBundleBuilder {
+    pub fn new(options: &PdfBundleOptions, output_root: &Path, background_color: [u8; 3]) -> Self {
+        Self {
+            options: PdfBundleOptions { dpi: options.dpi.max(1), ..options.clone() },
+            output_root: output_root.to_path_buf(),
+            background_color,
+            images: Vec::new(),
+        }
+    }
+
+    /// Ajoute l'image `output` produite pour `source`. Les images d'une même source (pages d'un document PDF...)
+    /// gardent leur ordre d'ajout.
+    pub fn add(&mut self, source: &Path, output: &Path) {
+        self.images.push((source.to_path_buf(), output.to_path_buf()));
+    }
+
+    /// Écrit le document `{nom}.pdf` à la racine de la sortie, les pages triées par source. Retourne son chemin
+    /// (`None` sans image produite) et les problèmes rencontrés : une image illisible est omise du document.
+    pub fn finish(mut self) -> (Option<PathBuf>, Vec<String>) {
+        self.images.sort_by(|(a, _), (b, _)| a.cmp(b)); // Tri stable : les pages d'une source restent dans l'ordre.
+        let mut warnings = Vec::new();
+        let mut document = None;
+        for (_, output) in &self.images {
+            let image = match self.page_image(output) {
+                Ok(image) => image,
+                Err(e) => {
+                    warnings.push(tr!("Document PDF : {} omis ({})", output.display(), e));
+                    continue;
+                }
+            };
+            let (width, height) = (self.to_mm(image.width.0), self.to_mm(image.height.0));
+            let layer = match &document {
+                None => {
+                    let (new_document, page, layer) = PdfDocument::new(self.options.name.as_str(), width, height, "image");
+                    let layer = new_document.get_page(page).get_layer(layer);
+                    document = Some(new_document);
+                    layer
+                }
+                Some(document) => {
+                    let (page, layer) = document.add_page(width, height, "image");
+                    document.get_page(page).get_layer(layer)
+                }
+            };
+            let transform = ImageTransform { dpi: Some(self.options.dpi as f32), ..Default::default() };
+            Image::from(image).add_to_layer(layer, transform);
+        }
+
+        let Some(document) = document else {
+            return (None, warnings);
+        };
+        let path = self.output_root.join(format!("{}.pdf", self.document_stem()));
+        let written = document
+            .save_to_bytes()
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| fs::write(&path, bytes).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => (Some(path), warnings),
+            Err(e) => {
+                warnings.push(tr!("Impossible d'écrire le document PDF {} : {}", path.display(), e));
+                (None, warnings)
+            }
+        }
+    }
+
+    /// Image de la page pour `output` : les données JPEG (niveaux de gris ou RVB) telles quelles, sinon les pixels
+    /// RVB de l'image décodée, aplatie sur la couleur de fond.
+    fn page_image(&self, output: &Path) -> Result<ImageXObject, String> {
+        let data = fs::read(output).map_err(|e| e.to_string())?;
+        if image::guess_format(&data).is_ok_and(|format| format == ImageFormat::Jpeg) {
+            let decoder = JpegDecoder::new(Cursor::new(&data)).map_err(|e| e.to_string())?;
+            let (width, height) = decoder.dimensions();
+            let color_space = match decoder.color_type() {
+                ColorType::L8 => Some(ColorSpace::Greyscale),
+                ColorType::Rgb8 => Some(ColorSpace::Rgb),
+                _ => None, // CMJN... : intégré en pixels.
+            };
+            if let Some(color_space) = color_space {
+                return Ok(image_object(width, height, color_space, data, Some(ImageFilter::DCT)));
+            }
+        }
+
+        let img = image::load_from_memory(&data).map_err(|e| e.to_string())?;
+        let rgb = encoder::flatten_alpha(&img, self.background_color);
+        Ok(image_object(rgb.width(), rgb.height(), ColorSpace::Rgb, rgb.into_raw(), None))
+    }
+
+    /// Taille en millimètres de `pixels` à la résolution du document.
+    fn to_mm(&self, pixels: usize) -> Mm {
+        Mm(pixels as f32 * MM_PER_INCH / self.options.dpi as f32)
+    }
+
+    /// Nom du document sans extension, `images` si le nom choisi est vide.
+    fn document_stem(&self) -> &str {
+        match self.options.name.trim() {
+            "" => "images",
+            name => name,
+        }
+    }
+}
+
+/// Image PDF de `width` x `height` pixels, 8 bits par composante.
+fn image_object(width: u32, height: u32, color_space: ColorSpace, image_data: Vec<u8>, image_filter: Option<ImageFilter>) -> ImageXObject {
+    ImageXObject {
+        width: Px(width as usize),
+        height: Px(height as usize),
+        color_space,
+        bits_per_component: ColorBits::Bit8,
+        interpolate: true,
+        image_data,
+        image_filter,
+        smask: None,
+        clipping_bbox: None,
+    }
+}
@@ -1,44 +1,220 @@
 /// Ce module contient des utilitaires spécifiques à la plateforme et des fonctions de validation de chemin.
+/// Il assure aussi l'instance unique : la fenêtre ouverte écoute sur un port de la boucle locale, et un second
+/// lancement avec des fichiers lui transmet leurs chemins (un par ligne) au lieu d'ouvrir une autre fenêtre.
+/// Il mesure l'espace libre du volume de sortie avant les gros lots.
+/// Sous Windows, il inscrit enfin (à la demande) l'entrée « Convertir en WebP » du menu contextuel de l'Explorateur.
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
+
+use crate::error::ConverterError;
+use crate::tr;
+
+/// Port de la boucle locale sur lequel l'instance ouverte attend les fichiers des lancements suivants.
+const INSTANCE_PORT: u16 = 47613;
+
+/// Première ligne d'une transmission, qui distingue un second lancement d'un autre programme utilisant le port.
+const INSTANCE_GREETING: &str = "image_converter:open";
+
+/// Délai de connexion à l'instance ouverte : au-delà, le lancement ouvre sa propre fenêtre.
+const INSTANCE_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Clés du registre (utilisateur courant, sans droits administrateur) de l'entrée du menu contextuel :
+/// sur les fichiers image, puis sur les dossiers.
+const CONTEXT_MENU_KEYS: [&str; 2] = [
+    r"HKCU\Software\Classes\SystemFileAssociations\image\shell\ImageConverter.WebP",
+    r"HKCU\Software\Classes\Directory\shell\ImageConverter.WebP",
+];
+
+/// Modification du menu contextuel de l'Explorateur demandée depuis les paramètres.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ContextMenuAction {
+    Register { silent_output: Option<PathBuf> }, // Ajouter l'entrée ; avec un dossier, convertir sans fenêtre vers celui-ci
+    Unregister,                                  // Retirer l'entrée
+}
+
+/// Adresse d'écoute de l'instance ouverte, accessible depuis la machine seulement.
+fn instance_address() -> SocketAddr {
+    SocketAddr::from((Ipv4Addr::LOCALHOST, INSTANCE_PORT))
+}
 
 /// Ouvre le répertoire de sortie en utilisant la commande appropriée pour le système d'exploitation.
 /// Supporte Windows, macOS et Linux.
-pub fn open_output_directory(path: &PathBuf) -> Result<(), String> {
-    let result = if cfg!(target_os = "windows") {
-        Command::new("explorer")
-            .arg(path.to_str().ok_or("Chemin invalide pour explorer")?)
-            .spawn()
+pub fn open_output_directory(path: &Path) -> Result<(), ConverterError> {
+    open_with_system(path).map_err(|e| ConverterError::io(tr!("Échec de l'ouverture du dossier"), e))
+}
+
+/// Ouvre un fichier avec l'application par défaut du système (ex: la visionneuse d'images).
+pub fn open_file(path: &Path) -> Result<(), ConverterError> {
+    open_with_system(path).map_err(|e| ConverterError::io(tr!("Échec de l'ouverture du fichier"), e))
+}
+
+/// Ouvre un chemin avec le gestionnaire du système : `start` sous Windows, `open` sous macOS,
+/// `xdg-open` (ou `gio open` à défaut) sous Linux et autres Unix-like.
+/// Le chemin est transmis tel quel, ce qui fonctionne aussi pour les noms non UTF-8.
+fn open_with_system(path: &Path) -> Result<(), std::io::Error> {
+    if cfg!(target_os = "windows") {
+        // `start` est une commande interne de cmd ; le premier argument entre guillemets est le titre.
+        Command::new("cmd").arg("/C").arg("start").arg("").arg(path).spawn()?;
     } else if cfg!(target_os = "macos") {
-        Command::new("open")
-            .arg(path.to_str().ok_or("Chemin invalide pour open")?)
-            .spawn()
-    } else {
-        // Pour Linux et autres Unix-like
-        Command::new("xdg-open")
-            .arg(path.to_str().ok_or("Chemin invalide pour xdg-open")?)
-            .spawn()
+        Command::new("open").arg(path).spawn()?;
+    } else if Command::new("xdg-open").arg(path).spawn().is_err() {
+        // Pour Linux et autres Unix-like, repli sur gio si xdg-open est absent
+        Command::new("gio").arg("open").arg(path).spawn()?;
+    }
+    Ok(())
+}
+
+/// Abaisse la priorité du thread courant (valeur « nice » maximale sous Linux et macOS, priorité la plus basse
+/// sous Windows), pour qu'une conversion en arrière-plan ne ralentisse pas le reste du système.
+/// Retourne `false` si le système refuse le changement ; le thread garde alors sa priorité.
+pub fn lower_current_thread_priority() -> bool {
+    thread_priority::set_current_thread_priority(thread_priority::ThreadPriority::Min).is_ok()
+}
+
+/// Transmet `paths` à l'instance déjà ouverte. Retourne `false` si aucune instance n'écoute
+/// (ou si la transmission échoue) : le lancement ouvre alors sa propre fenêtre.
+pub fn send_to_running_instance(paths: &[PathBuf]) -> bool {
+    let Ok(mut stream) = TcpStream::connect_timeout(&instance_address(), INSTANCE_CONNECT_TIMEOUT) else {
+        return false;
+    };
+    let mut message = format!("{}\n", INSTANCE_GREETING);
+    for path in paths {
+        // Les chemins sont transmis absolus : les deux processus n'ont pas forcément le même dossier courant.
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.clone());
+        message.push_str(&path.to_string_lossy());
+        message.push('\n');
+    }
+    stream.write_all(message.as_bytes()).and_then(|_| stream.flush()).is_ok()
+}
+
+/// Écoute les lancements suivants dans un thread dédié et appelle `on_paths` avec les chemins de chacun.
+/// Retourne `false` si le port est déjà pris (autre instance, autre programme) : l'instance reste alors autonome.
+pub fn listen_for_other_instances(on_paths: impl Fn(Vec<PathBuf>) + Send + 'static) -> bool {
+    let Ok(listener) = TcpListener::bind(instance_address()) else {
+        return false;
+    };
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // Un client muet ne doit pas bloquer les lancements suivants.
+            let _ = stream.set_read_timeout(Some(INSTANCE_CONNECT_TIMEOUT));
+            let mut lines = BufReader::new(stream).lines().map_while(Result::ok);
+            if lines.next().as_deref() != Some(INSTANCE_GREETING) {
+                continue;
+            }
+            let paths: Vec<PathBuf> = lines.filter(|line| !line.is_empty()).map(PathBuf::from).collect();
+            if !paths.is_empty() {
+                on_paths(paths);
+            }
+        }
+    });
+    true
+}
+
+/// Inscrit « Convertir en WebP » dans le menu contextuel de l'Explorateur, pour les images et les dossiers.
+/// L'entrée ouvre l'application avec les chemins sélectionnés ou, avec `silent_output`, les convertit
+/// en ligne de commande vers ce dossier, sans fenêtre. Windows uniquement.
+pub fn register_context_menu(silent_output: Option<&Path>) -> Result<(), ConverterError> {
+    ensure_windows()?;
+    let exe = std::env::current_exe().map_err(|e| ConverterError::io(tr!("Chemin de l'application introuvable"), e))?;
+    let command = match silent_output {
+        Some(output) => format!("\"{}\" --input \"%1\" --output \"{}\" --format webp", exe.display(), output.display()),
+        None => format!("\"{}\" \"%1\"", exe.display()),
     };
+    for key in CONTEXT_MENU_KEYS {
+        run_reg(&["add", key, "/ve", "/d", tr!("Convertir en WebP"), "/f"])?;
+        run_reg(&["add", key, "/v", "Icon", "/d", &exe.display().to_string(), "/f"])?;
+        run_reg(&["add", &format!(r"{}\command", key), "/ve", "/d", &command, "/f"])?;
+    }
+    Ok(())
+}
 
-    result.map_err(|e| format!("Échec de l'ouverture du dossier : {}", e))?;
+/// Retire l'entrée du menu contextuel de l'Explorateur. Windows uniquement.
+pub fn unregister_context_menu() -> Result<(), ConverterError> {
+    ensure_windows()?;
+    for key in CONTEXT_MENU_KEYS {
+        // Une clé déjà absente n'est pas une erreur : l'entrée est retirée dans tous les cas.
+        let _ = run_reg(&["delete", key, "/f"]);
+    }
     Ok(())
 }
 
+/// Erreur hors de Windows, seul système dont le menu contextuel est pris en charge.
+fn ensure_windows() -> Result<(), ConverterError> {
+    if cfg!(target_os = "windows") {
+        Ok(())
+    } else {
+        Err(ConverterError::PathValidation(tr!("Le menu contextuel n'est disponible que sous Windows.").to_string()))
+    }
+}
+
+/// Exécute `reg.exe` (outil du registre fourni avec Windows) avec `args`.
+fn run_reg(args: &[&str]) -> Result<(), ConverterError> {
+    let status = Command::new("reg")
+        .args(args)
+        .output()
+        .map_err(|e| ConverterError::io(tr!("Échec de l'écriture dans le registre"), e))?
+        .status;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(ConverterError::PathValidation(tr!("Le registre a refusé la modification ({})", status)))
+    }
+}
+
+/// Affiche une notification du système (centre de notifications sous Windows et macOS, serveur de notifications
+/// sous Linux) titrée du nom de l'application. L'appel peut attendre le système : il est fait hors du thread de l'interface.
+pub fn send_desktop_notification(body: &str) -> Result<(), ConverterError> {
+    notify_rust::Notification::new()
+        .summary(tr!("Convertisseur d'Images"))
+        .body(body)
+        .show()
+        .map(|_| ())
+        .map_err(|e| ConverterError::PathValidation(tr!("Notification impossible : {}", e)))
+}
+
+/// Espace libre (octets) du volume qui contient `path`. Le chemin n'a pas besoin d'exister encore : l'espace est
+/// mesuré sur son plus proche parent existant (ex: le dossier de sortie avant sa création).
+/// `None` si le système ne renseigne pas l'espace libre.
+pub fn free_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|ancestor| ancestor.exists())?;
+    fs4::available_space(existing).ok()
+}
+
 /// Valide que le répertoire de sortie n'est pas le même que le répertoire d'entrée,
-/// ni un sous-répertoire de celui-ci.
-pub fn validate_paths(input_path: &Path, output_path: &Path) -> Result<(), String> {
+/// ni un sous-répertoire de celui-ci. Le répertoire de sortie peut ne pas encore exister.
+pub fn validate_paths(input_path: &Path, output_path: &Path) -> Result<(), ConverterError> {
     // Résoudre les chemins pour obtenir les chemins absolus et canoniques
-    let canonical_input = input_path.canonicalize().map_err(|e| format!("Impossible de canoniser le chemin d'entrée : {}", e))?;
-    let canonical_output = output_path.canonicalize().map_err(|e| format!("Impossible de canoniser le chemin de sortie : {}", e))?;
+    let canonical_input = input_path.canonicalize()
+        .map_err(|e| ConverterError::io(tr!("Impossible de canoniser le chemin d'entrée"), e))?;
+    let canonical_output = canonicalize_missing(output_path)
+        .map_err(|e| ConverterError::io(tr!("Impossible de canoniser le chemin de sortie"), e))?;
 
     if canonical_input == canonical_output {
-        return Err("Le répertoire de sortie ne peut pas être le même que le répertoire d'entrée.".to_string());
+        return Err(ConverterError::PathValidation(
+            tr!("Le répertoire de sortie ne peut pas être le même que le répertoire d'entrée.").to_string(),
+        ));
     }
 
     // Vérifier si le répertoire de sortie est un sous-répertoire de l'entrée
     if canonical_output.starts_with(&canonical_input) {
-        return Err("Le répertoire de sortie ne peut pas être un sous-répertoire du répertoire d'entrée.".to_string());
+        return Err(ConverterError::PathValidation(
+            tr!("Le répertoire de sortie ne peut pas être un sous-répertoire du répertoire d'entrée.").to_string(),
+        ));
     }
 
     Ok(())
 }
+
+/// Chemin canonique de `path`, qui peut ne pas encore exister : son plus proche parent existant est canonisé,
+/// puis complété par le reste du chemin.
+fn canonicalize_missing(path: &Path) -> std::io::Result<PathBuf> {
+    let absolute = std::path::absolute(path)?;
+    let Some(existing) = absolute.ancestors().find(|ancestor| ancestor.exists()) else {
+        return Ok(absolute);
+    };
+    let missing = absolute.strip_prefix(existing).unwrap_or(Path::new(""));
+    Ok(existing.canonicalize()?.join(missing))
+}
@@ -0,0 +1,75 @@
+/// Ce module gère les préréglages nommés (ex: "Miniatures web", "Archive sans perte") : une combinaison
+/// d'options de conversion et de répertoire de sortie, enregistrée dans le fichier de configuration
+/// et appliquée en un clic.
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::converter::{ConversionOptions, OutputFormat};
+use crate::responsive::ResponsiveOptions;
+
+/// Nom du préréglage fourni pour les développeurs web.
+pub const RESPONSIVE_PRESET_NAME: &str = "Images responsives (web)";
+
+/// Nom du préréglage fourni pour les icônes de site.
+pub const FAVICON_PRESET_NAME: &str = "Favicon (site web)";
+
+/// Préréglage enregistré.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,                          // Nom affiché dans la liste (unique).
+    pub output_dir: PathBuf,                   // Répertoire de sortie.
+    pub conversion_options: ConversionOptions, // Format, qualité, redimensionnement, écrasement...
+}
+
+/// Modification demandée depuis la fenêtre de gestion des préréglages.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PresetAction {
+    Apply(usize),          // Appliquer le préréglage aux réglages actuels.
+    SaveCurrent(String),   // Enregistrer les réglages actuels sous un nouveau nom.
+    Update(usize),         // Remplacer le préréglage par les réglages actuels.
+    Rename(usize, String), // Renommer le préréglage.
+    Delete(usize),         // Supprimer le préréglage.
+}
+
+/// Préréglage fourni à la première utilisation : WebP en 480, 768, 1280 et 1920 px de large avec l'extrait
+/// `srcset` de chaque image, métadonnées retirées, dans le sous-dossier `responsive` de `output_dir`.
+pub fn responsive_web(output_dir: &Path) -> Preset {
+    Preset {
+        name: RESPONSIVE_PRESET_NAME.to_string(),
+        output_dir: output_dir.join("responsive"),
+        conversion_options: ConversionOptions {
+            output_format: OutputFormat::WebP,
+            smallest_format: false,
+            strip_metadata: true,
+            responsive: ResponsiveOptions { enabled: true, ..ResponsiveOptions::default() },
+            ..ConversionOptions::default()
+        },
+    }
+}
+
+/// Préréglage fourni à la première utilisation : mode favicon (`favicon.ico` multi-résolution et PNG usuels),
+/// métadonnées retirées, dans le sous-dossier `favicon` de `output_dir`.
+pub fn favicon(output_dir: &Path) -> Preset {
+    Preset {
+        name: FAVICON_PRESET_NAME.to_string(),
+        output_dir: output_dir.join("favicon"),
+        conversion_options: ConversionOptions {
+            strip_metadata: true,
+            favicon: true,
+            ..ConversionOptions::default()
+        },
+    }
+}
+
+/// Préréglage correspondant exactement aux réglages actuels, s'il y en a un.
+pub fn find_matching<'a>(presets: &'a [Preset], output_dir: &Path, options: &ConversionOptions) -> Option<&'a Preset> {
+    presets
+        .iter()
+        .find(|preset| preset.output_dir == output_dir && preset.conversion_options == *options)
+}
+
+/// Vrai si `name` peut nommer un nouveau préréglage : non vide et pas déjà utilisé.
+pub fn is_valid_name(presets: &[Preset], name: &str) -> bool {
+    let name = name.trim();
+    !name.is_empty() && !presets.iter().any(|preset| preset.name == name)
+}
@@ -0,0 +1,184 @@
+/// Ce module charge en arrière-plan la miniature de l'image sélectionnée, affichée avant la conversion,
+/// ainsi que sa version ré-encodée avec les réglages actuels pour la comparaison avant/après.
+/// Le décodage et l'encodage ont lieu dans des threads ; les textures egui sont créées sur le thread de l'UI à la réception.
+use eframe::egui;
+use image::DynamicImage;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::converter::{self, ConversionOptions};
+
+/// Côté maximal (en pixels) de la miniature décodée.
+const PREVIEW_MAX_SIZE: u32 = 512;
+
+/// Délai (en secondes) sans changement de réglage avant de ré-encoder la miniature.
+const COMPARISON_DEBOUNCE_DELAY: f64 = 0.3;
+
+/// Résultat d'un chargement : génération de la requête et miniature décodée (ou message d'erreur).
+type PreviewResult = (u64, Result<Arc<DynamicImage>, String>);
+
+/// Résultat d'un ré-encodage : génération de la requête, image relue et taille encodée (ou message d'erreur).
+type ComparisonResult = (u64, Result<(DynamicImage, u64), String>);
+
+/// État de l'aperçu de l'image sélectionnée.
+pub enum PreviewState {
+    Loading,                    // Décodage en cours.
+    Ready(egui::TextureHandle), // Miniature prête à afficher.
+    Failed(String),             // Image illisible, avec son message.
+}
+
+/// État de la version ré-encodée de la miniature (comparaison avant/après).
+pub enum ComparisonState {
+    Encoding,                        // Encodage en cours.
+    Ready(egui::TextureHandle, u64), // Miniature ré-encodée et sa taille encodée (octets).
+    Failed(String),                  // Encodage ou relecture impossible, avec son message.
+}
+
+/// Gère les threads de chargement et d'encodage, la miniature de l'image sélectionnée et sa version ré-encodée.
+#[derive(Default)]
+pub struct ImagePreview {
+    path: Option<PathBuf>,                     // Image dont l'aperçu est demandé.
+    state: Option<PreviewState>,               // État de l'aperçu, `None` sans image sélectionnée.
+    thumbnail: Option<Arc<DynamicImage>>,      // Miniature décodée, source de la comparaison.
+    generation: Arc<AtomicU64>,                // Incrémenté à chaque changement d'image : invalide les chargements obsolètes.
+    result: Arc<Mutex<Option<PreviewResult>>>, // Résultat envoyé par le thread de chargement.
+    comparison_signature: Option<String>,      // Signature des options de la comparaison demandée.
+    comparison_changed_at: f64,                // Instant du dernier changement de ces options.
+    comparison_launched: bool,                 // Vrai si l'encodage pour la signature courante a été lancé.
+    comparison: Option<ComparisonState>,       // État de la comparaison, `None` si elle n'est pas demandée.
+    comparison_generation: Arc<AtomicU64>,     // Incrémenté à chaque requête : invalide les encodages obsolètes.
+    comparison_result: Arc<Mutex<Option<ComparisonResult>>>, // Résultat envoyé par le thread d'encodage.
+}
+
+impl ImagePreview {
+    /// État de l'aperçu de l'image courante, `None` sans image sélectionnée.
+    pub fn state(&self) -> Option<&PreviewState> {
+        self.state.as_ref()
+    }
+
+    /// État de la version ré-encodée, `None` si la comparaison n'est pas demandée ou la miniature pas encore prête.
+    pub fn comparison(&self) -> Option<&ComparisonState> {
+        self.comparison.as_ref()
+    }
+
+    /// À appeler à chaque frame avec l'image à prévisualiser : lance son chargement quand elle change
+    /// et crée la texture une fois l'image décodée. Avec `compare`, la miniature est ré-encodée
+    /// avec `options` une fois le délai anti-rebond écoulé.
+    pub fn update(&mut self, ctx: &egui::Context, path: Option<PathBuf>, options: &ConversionOptions, compare: bool) {
+        if path != self.path {
+            let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+            self.path = path.clone();
+            self.state = path.as_ref().map(|_| PreviewState::Loading);
+            self.thumbnail = None;
+            self.reset_comparison();
+            if let Some(path) = path {
+                self.spawn_loader(ctx, path, options.clone(), generation);
+            }
+        }
+
+        if let Some((generation, thumbnail)) = self.result.lock().unwrap().take() {
+            if generation == self.generation.load(Ordering::SeqCst) {
+                self.state = Some(match thumbnail {
+                    Ok(thumbnail) => {
+                        let texture = ctx.load_texture("apercu_selection", color_image(&thumbnail), egui::TextureOptions::LINEAR);
+                        self.thumbnail = Some(thumbnail);
+                        PreviewState::Ready(texture)
+                    }
+                    Err(message) => PreviewState::Failed(message),
+                });
+            }
+        }
+
+        self.update_comparison(ctx, options, compare);
+    }
+
+    /// Ré-encode la miniature quand les options de sortie changent (après le délai anti-rebond)
+    /// et crée la texture de la version ré-encodée à la réception.
+    fn update_comparison(&mut self, ctx: &egui::Context, options: &ConversionOptions, compare: bool) {
+        let now = ctx.input(|i| i.time);
+        let signature = (compare && self.thumbnail.is_some()).then(|| options.output_signature());
+        if signature != self.comparison_signature {
+            self.reset_comparison();
+            self.comparison = signature.as_ref().map(|_| ComparisonState::Encoding);
+            self.comparison_signature = signature;
+            self.comparison_changed_at = now;
+        }
+
+        if let Some((generation, encoded)) = self.comparison_result.lock().unwrap().take() {
+            if generation == self.comparison_generation.load(Ordering::SeqCst) {
+                self.comparison = Some(match encoded {
+                    Ok((img, size)) => {
+                        let texture = ctx.load_texture("apercu_reencode", color_image(&img), egui::TextureOptions::LINEAR);
+                        ComparisonState::Ready(texture, size)
+                    }
+                    Err(message) => ComparisonState::Failed(message),
+                });
+            }
+        }
+
+        let (Some(thumbnail), Some(_)) = (&self.thumbnail, &self.comparison_signature) else {
+            return;
+        };
+        if self.comparison_launched {
+            return;
+        }
+        let elapsed = now - self.comparison_changed_at;
+        if elapsed < COMPARISON_DEBOUNCE_DELAY {
+            ctx.request_repaint_after(Duration::from_secs_f64(COMPARISON_DEBOUNCE_DELAY - elapsed));
+            return;
+        }
+
+        self.comparison_launched = true;
+        let generation = self.comparison_generation.load(Ordering::SeqCst);
+        let generation_clone = Arc::clone(&self.comparison_generation);
+        let result_clone = Arc::clone(&self.comparison_result);
+        let thumbnail = Arc::clone(thumbnail);
+        let options = options.clone();
+        let ctx_clone = ctx.clone();
+
+        std::thread::spawn(move || {
+            // Un encodage remplacé par une requête plus récente est abandonné sans être calculé.
+            if generation_clone.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let encoded = converter::encode_round_trip(&thumbnail, &options).map_err(|e| e.to_string());
+            if generation_clone.load(Ordering::SeqCst) == generation {
+                *result_clone.lock().unwrap() = Some((generation, encoded));
+                ctx_clone.request_repaint();
+            }
+        });
+    }
+
+    /// Abandonne la comparaison en cours : tout encodage lancé devient obsolète.
+    fn reset_comparison(&mut self) {
+        self.comparison_generation.fetch_add(1, Ordering::SeqCst);
+        self.comparison_signature = None;
+        self.comparison_launched = false;
+        self.comparison = None;
+    }
+
+    /// Décode la miniature de `path` dans un thread et transmet le résultat s'il n'est pas devenu obsolète.
+    fn spawn_loader(&self, ctx: &egui::Context, path: PathBuf, options: ConversionOptions, generation: u64) {
+        let generation_clone = Arc::clone(&self.generation);
+        let result_clone = Arc::clone(&self.result);
+        let ctx_clone = ctx.clone();
+
+        std::thread::spawn(move || {
+            let thumbnail = converter::decode_thumbnail(&path, &options, PREVIEW_MAX_SIZE)
+                .map(Arc::new)
+                .map_err(|e| e.to_string());
+            if generation_clone.load(Ordering::SeqCst) == generation {
+                *result_clone.lock().unwrap() = Some((generation, thumbnail));
+                ctx_clone.request_repaint();
+            }
+        });
+    }
+}
+
+/// Convertit une image en image egui (RGBA).
+fn color_image(img: &DynamicImage) -> egui::ColorImage {
+    let rgba = img.to_rgba8();
+    egui::ColorImage::from_rgba_unmultiplied([rgba.width() as usize, rgba.height() as usize], rgba.as_raw())
+}
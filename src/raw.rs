@@ -0,0 +1,34 @@
+/// Ce module décode les fichiers RAW des appareils photo (CR2, NEF, ARW, DNG) avec `imagepipe` :
+/// dématriçage, balance des blancs de l'appareil, rotation et conversion en sRGB 8 bits.
+/// L'image obtenue suit ensuite le même traitement que les autres sources.
+use image::error::{DecodingError, ImageFormatHint};
+use image::{DynamicImage, ImageError, RgbImage};
+use std::path::Path;
+
+use crate::error::ConverterError;
+
+/// Extensions (en minuscules) des fichiers RAW pris en charge.
+pub const RAW_EXTENSIONS: [&str; 4] = ["cr2", "nef", "arw", "dng"];
+
+/// Indique si le fichier porte l'extension d'un format RAW pris en charge.
+pub fn is_raw(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| RAW_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Décode un fichier RAW en image sRGB à sa taille d'origine.
+pub fn decode(path: &Path) -> Result<DynamicImage, ConverterError> {
+    let decoding_error = |message: String| {
+        let error = ImageError::Decoding(DecodingError::new(ImageFormatHint::Name("RAW".to_string()), message));
+        ConverterError::decode(path, error)
+    };
+
+    // 0 x 0 : pas de réduction, le redimensionnement éventuel est appliqué ensuite comme pour les autres sources.
+    let decoded = imagepipe::simple_decode_8bit(path, 0, 0).map_err(decoding_error)?;
+    let width = u32::try_from(decoded.width).map_err(|e| decoding_error(e.to_string()))?;
+    let height = u32::try_from(decoded.height).map_err(|e| decoding_error(e.to_string()))?;
+    RgbImage::from_raw(width, height, decoded.data)
+        .map(DynamicImage::ImageRgb8)
+        .ok_or_else(|| decoding_error("Données de l'image incomplètes".to_string()))
+}
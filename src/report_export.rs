@@ -0,0 +1,198 @@
+/// Ce module exporte le rapport d'une conversion, fichier par fichier, au format CSV ou JSON :
+/// source, sortie, tailles avant et après, état, réglages d'image appliqués et erreur éventuelle. Utile pour vérifier les gros lots.
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::converter::{ConversionReport, FileReport, PlannedAction};
+use crate::error::ConverterError;
+use crate::tr;
+use crate::verify::Verification;
+
+/// Format du fichier exporté.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ExportFormat {
+    Csv,  // Tableau, une ligne par fichier (séparateur `,`).
+    Json, // Liste d'objets.
+}
+
+impl ExportFormat {
+    /// Déduit le format de l'extension du fichier choisi (CSV par défaut).
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => ExportFormat::Json,
+            _ => ExportFormat::Csv,
+        }
+    }
+}
+
+/// Ligne du rapport exporté.
+#[derive(Debug, Serialize)]
+struct ReportRow<'a> {
+    input: &'a Path,              // Image source.
+    output: Option<&'a Path>,     // Fichier produit, s'il y en a un.
+    input_size: Option<u64>,      // Taille de la source (octets).
+    output_size: Option<u64>,     // Taille du fichier produit (octets).
+    status: &'static str,         // "converted", "original_kept", "skipped", "failed" ou, en simulation, "would_*".
+    candidates: String,           // Formats essayés et leur taille (ex: "webp=1200;avif=980"), mode « format le plus léger ».
+    palette_size: Option<usize>,  // Nombre de couleurs de la palette choisie (PNG indexé).
+    brightness: Option<i32>,      // Luminosité appliquée, si les réglages d'image modifient l'image.
+    contrast: Option<i32>,        // Contraste appliqué, idem.
+    saturation: Option<i32>,      // Saturation appliquée, idem.
+    sharpen_amount: Option<f32>,  // Intensité de l'accentuation appliquée (0 sans accentuation), idem.
+    original: Option<String>,     // Sort de la source après la conversion (conservée, corbeille, sauvegarde).
+    verification: Option<String>, // Relecture du fichier produit (option de vérification).
+    psnr: Option<f64>,            // PSNR de la sortie (dB), si sa fidélité a été mesurée.
+    ssim: Option<f64>,            // SSIM de la sortie (0 à 1), si sa fidélité a été mesurée.
+    error: Option<String>,        // Message d'erreur, pour un échec.
+}
+
+/// Écrit le rapport de `report` dans `path`, au format déduit de son extension.
+pub fn export(report: &ConversionReport, path: &Path) -> Result<(), ConverterError> {
+    let rows = rows(report);
+    let content = match ExportFormat::from_path(path) {
+        ExportFormat::Json => serde_json::to_string_pretty(&rows)?,
+        ExportFormat::Csv => to_csv(&rows),
+    };
+    fs::write(path, content)
+        .map_err(|e| ConverterError::io(tr!("Impossible d'écrire le rapport {}", path.display()), e))
+}
+
+/// Lignes du rapport : fichiers convertis, puis ignorés, puis en échec.
+fn rows(report: &ConversionReport) -> Vec<ReportRow<'_>> {
+    let source_size = |path: &PathBuf| fs::metadata(path).ok().map(|metadata| metadata.len());
+    let quality = |file: &FileReport| match file.verification {
+        Verification::Valid(score) => score,
+        _ => None,
+    };
+    let adjustments = Some(&report.options.adjustments).filter(|adjustments| !adjustments.is_identity());
+
+    let converted = report.files.iter().map(|file| ReportRow {
+        input: &file.source,
+        output: Some(&file.output),
+        input_size: Some(file.input_size),
+        output_size: Some(file.output_size),
+        status: if file.kept_original { "original_kept" } else { "converted" },
+        candidates: file.candidate_sizes
+            .iter()
+            .map(|(format, size)| format!("{}={}", format.extension(), size))
+            .collect::<Vec<String>>()
+            .join(";"),
+        palette_size: file.palette_size,
+        brightness: adjustments.map(|adjustments| adjustments.brightness),
+        contrast: adjustments.map(|adjustments| adjustments.contrast),
+        saturation: adjustments.map(|adjustments| adjustments.saturation),
+        sharpen_amount: adjustments.map(|adjustments| adjustments.effective_sharpen_amount()),
+        original: Some(file.original.description()),
+        verification: Some(file.verification.description()),
+        psnr: quality(file).map(|score| score.psnr),
+        ssim: quality(file).map(|score| score.ssim),
+        error: None,
+    });
+    let skipped = report.skipped_sources.iter().map(|source| ReportRow {
+        input: source,
+        output: None,
+        input_size: source_size(source),
+        output_size: None,
+        status: "skipped",
+        candidates: String::new(),
+        palette_size: None,
+        brightness: None,
+        contrast: None,
+        saturation: None,
+        sharpen_amount: None,
+        original: None,
+        verification: None,
+        psnr: None,
+        ssim: None,
+        error: None,
+    });
+    let failed = report.failures.iter().map(|failure| ReportRow {
+        input: &failure.source,
+        output: None,
+        input_size: source_size(&failure.source),
+        output_size: None,
+        status: "failed",
+        candidates: String::new(),
+        palette_size: None,
+        brightness: None,
+        contrast: None,
+        saturation: None,
+        sharpen_amount: None,
+        original: None,
+        verification: None,
+        psnr: None,
+        ssim: None,
+        error: Some(failure.error.to_string()),
+    });
+    // Une simulation n'écrit rien : ses lignes décrivent l'action prévue pour chaque source.
+    if report.options.dry_run {
+        let planned = report.planned.iter().map(|file| ReportRow {
+            input: &file.source,
+            output: file.output.as_deref(),
+            input_size: source_size(&file.source),
+            output_size: None,
+            status: match file.action {
+                PlannedAction::Convert => "would_convert",
+                PlannedAction::Overwrite => "would_overwrite",
+                PlannedAction::Rename => "would_rename",
+                PlannedAction::Ask => "would_ask",
+                PlannedAction::Skip(_) => "would_skip",
+            },
+            candidates: String::new(),
+            palette_size: None,
+            brightness: None,
+            contrast: None,
+            saturation: None,
+            sharpen_amount: None,
+            original: None,
+            verification: None,
+            psnr: None,
+            ssim: None,
+            error: match &file.action {
+                PlannedAction::Skip(reason) => Some(reason.clone()),
+                _ => None,
+            },
+        });
+        return planned.chain(failed).collect();
+    }
+    converted.chain(skipped).chain(failed).collect()
+}
+
+/// Met les lignes au format CSV, avec une ligne d'en-tête.
+fn to_csv(rows: &[ReportRow]) -> String {
+    let mut csv = String::from("input,output,input_size,output_size,status,candidates,palette_size,brightness,contrast,saturation,sharpen_amount,original,verification,psnr,ssim,error\n");
+    for row in rows {
+        let fields = [
+            row.input.display().to_string(),
+            row.output.map(|path| path.display().to_string()).unwrap_or_default(),
+            row.input_size.map(|size| size.to_string()).unwrap_or_default(),
+            row.output_size.map(|size| size.to_string()).unwrap_or_default(),
+            row.status.to_string(),
+            row.candidates.clone(),
+            row.palette_size.map(|size| size.to_string()).unwrap_or_default(),
+            row.brightness.map(|value| value.to_string()).unwrap_or_default(),
+            row.contrast.map(|value| value.to_string()).unwrap_or_default(),
+            row.saturation.map(|value| value.to_string()).unwrap_or_default(),
+            row.sharpen_amount.map(|amount| format!("{:.2}", amount)).unwrap_or_default(),
+            row.original.clone().unwrap_or_default(),
+            row.verification.clone().unwrap_or_default(),
+            row.psnr.map(|psnr| format!("{:.2}", psnr)).unwrap_or_default(),
+            row.ssim.map(|ssim| format!("{:.4}", ssim)).unwrap_or_default(),
+            row.error.clone().unwrap_or_default(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Entoure un champ de guillemets s'il contient un séparateur, un guillemet ou un saut de ligne.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
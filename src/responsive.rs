@@ -0,0 +1,115 @@
+/// Ce module génère des jeux d'images responsives : chaque source est produite en plusieurs largeurs
+/// (ex: 480, 768, 1280 et 1920 px) avec le suffixe `-{largeur}w`, et un extrait HTML `srcset` peut être écrit à côté.
+/// Une largeur supérieure à celle de la source n'est pas produite : les images ne sont jamais agrandies.
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::ConverterError;
+use crate::targets::Destination;
+use crate::transform::{ResizeMode, ResizeOptions};
+use crate::tr;
+
+/// Largeurs proposées par défaut (pixels).
+pub const DEFAULT_WIDTHS: [u32; 4] = [480, 768, 1280, 1920];
+
+/// Extension des extraits HTML écrits à côté des images (ex: `photo.srcset.html`).
+const SRCSET_EXTENSION: &str = "srcset.html";
+
+/// Réglages du jeu d'images responsives.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponsiveOptions {
+    pub enabled: bool,      // Produire chaque image en plusieurs largeurs.
+    pub widths: Vec<u32>,   // Largeurs demandées (pixels).
+    pub write_srcset: bool, // Écrire l'extrait HTML `<img srcset>` de chaque image.
+}
+
+impl Default for ResponsiveOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            widths: DEFAULT_WIDTHS.to_vec(),
+            write_srcset: true,
+        }
+    }
+}
+
+impl ResponsiveOptions {
+    /// Vrai si les images sont produites en plusieurs largeurs.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.widths.iter().any(|&width| width > 0)
+    }
+
+    /// Signature du jeu d'images, intégrée à celle des options de sortie (manifeste). `None` si inactif.
+    pub fn signature(&self) -> Option<String> {
+        self.is_active().then(|| {
+            let widths: Vec<String> = self.widths.iter().map(u32::to_string).collect();
+            format!("responsive=[{}];srcset={}", widths.join(","), self.write_srcset)
+        })
+    }
+
+    /// Largeurs produites pour une source de `source_width` pixels, dans l'ordre croissant : celles qui ne dépassent
+    /// pas la source, ou la largeur de la source seule si toutes la dépassent.
+    pub fn widths_for(&self, source_width: u32) -> Vec<u32> {
+        let mut widths: Vec<u32> = self.widths.iter().copied().filter(|&width| width > 0 && width <= source_width).collect();
+        widths.sort_unstable();
+        widths.dedup();
+        if widths.is_empty() {
+            widths.push(source_width);
+        }
+        widths
+    }
+}
+
+/// Décline chaque destination en une destination par largeur, suffixée `-{largeur}w`.
+pub fn expand(destinations: &[Destination], widths: &[u32]) -> Vec<Destination> {
+    destinations
+        .iter()
+        .flat_map(|destination| {
+            widths.iter().map(move |&width| {
+                let mut options = destination.options.clone();
+                options.resize = ResizeOptions {
+                    mode: ResizeMode::Dimensions,
+                    max_width: width,
+                    max_height: 0,
+                    filter: options.resize.filter,
+                    ..ResizeOptions::default()
+                };
+                Destination {
+                    subfolder: destination.subfolder.clone(),
+                    name_suffix: format!("{}-{}w", destination.name_suffix, width),
+                    width: Some(width),
+                    options,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Écrit l'extrait `<img srcset>` des images `images` (chemin et largeur), toutes dans le même dossier,
+/// sous le nom `{output_stem}.srcset.html`. La plus grande largeur sert d'image par défaut.
+pub fn write_srcset(output_stem: &str, images: &[(PathBuf, u32)]) -> Result<PathBuf, ConverterError> {
+    let Some((largest, _)) = images.iter().max_by_key(|(_, width)| *width) else {
+        return Err(ConverterError::PathValidation(tr!("Aucune image pour l'extrait srcset").to_string()));
+    };
+    let directory = largest.parent().unwrap_or(Path::new(""));
+    let file_name = |path: &Path| escape_html(&path.file_name().unwrap_or_default().to_string_lossy());
+
+    let srcset: Vec<String> = images.iter().map(|(path, width)| format!("{} {}w", file_name(path), width)).collect();
+    let snippet = format!(
+        "<img src=\"{}\"\n     srcset=\"{}\"\n     sizes=\"100vw\"\n     alt=\"{}\">\n",
+        file_name(largest),
+        srcset.join(", "),
+        escape_html(output_stem)
+    );
+    let path = directory.join(format!("{}.{}", output_stem, SRCSET_EXTENSION));
+    fs::write(&path, snippet)
+        .map_err(|e| ConverterError::io(tr!("Impossible d'écrire l'extrait srcset {}", path.display()), e))?;
+    Ok(path)
+}
+
+/// Échappe les caractères spéciaux du HTML dans un attribut.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
+}
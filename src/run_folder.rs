@@ -0,0 +1,107 @@
+/// Ce module gère le sous-dossier horodaté créé pour chaque exécution (ex: `2024-06-03_14-32-05/`).
+/// Chaque lot écrit alors dans son propre dossier : les sorties de réglages différents ne se mélangent pas.
+/// `job::run_job` le crée au lancement du lot et le retire s'il est resté vide ; un dossier neuf n'ayant pas
+/// de manifeste, le mode incrémental y reconvertit tout.
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::converter::{ConversionOptions, ConversionReport};
+use crate::error::ConverterError;
+use crate::tr;
+
+/// Modèle utilisé si le modèle saisi est vide.
+const DEFAULT_TEMPLATE: &str = "{date}";
+
+/// Format de la date insérée à la place de `{date}`.
+const DATE_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// Réglages du sous-dossier par exécution.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunFolderOptions {
+    pub enabled: bool,    // Créer un sous-dossier par exécution.
+    pub template: String, // Modèle du nom : `{date}` (date et heure) et `{preset}` (préréglage, à défaut format de sortie).
+}
+
+impl Default for RunFolderOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            template: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+/// Calcule le nom du sous-dossier à partir du modèle, en remplaçant les caractères interdits dans un nom de fichier.
+/// `{preset}` est remplacé par le nom du préréglage appliqué, ou par l'extension du format de sortie sans préréglage.
+pub fn folder_name(options: &ConversionOptions, preset: Option<&str>) -> String {
+    let template = options.run_folder.template.trim();
+    let template = if template.is_empty() { DEFAULT_TEMPLATE } else { template };
+
+    let name = template
+        .replace("{date}", &chrono::Local::now().format(DATE_FORMAT).to_string())
+        .replace("{preset}", preset.unwrap_or(options.output_format.extension()));
+
+    name.chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect()
+}
+
+/// Crée le sous-dossier de l'exécution dans `output_dir` et retourne son chemin.
+/// Si un dossier du même nom existe déjà (deux lancements dans la même seconde), un suffixe est ajouté.
+pub fn create(output_dir: &Path, options: &ConversionOptions, preset: Option<&str>) -> Result<PathBuf, ConverterError> {
+    let name = folder_name(options, preset);
+    let mut run_dir = output_dir.join(&name);
+    let mut counter = 1;
+    while run_dir.exists() {
+        run_dir = output_dir.join(format!("{}-{}", name, counter));
+        counter += 1;
+    }
+
+    fs::create_dir_all(&run_dir)
+        .map_err(|e| ConverterError::io(tr!("Impossible de créer le dossier d'exécution {}", run_dir.display()), e))?;
+    Ok(run_dir)
+}
+
+/// Sous-dossier de l'exécution d'un lot écrivant dans `output_dir`, si l'option est active : créé, ou seulement
+/// nommé en simulation (rien n'est écrit).
+pub fn prepare(output_dir: &Path, options: &ConversionOptions, preset: Option<&str>) -> Result<Option<PathBuf>, ConverterError> {
+    match (options.run_folder.enabled, options.dry_run) {
+        (false, _) => Ok(None),
+        (true, true) => Ok(Some(output_dir.join(folder_name(options, preset)))),
+        (true, false) => create(output_dir, options, preset).map(Some),
+    }
+}
+
+/// Rattache le dossier de l'exécution `run_dir` au bilan `result`, et le supprime s'il est resté vide.
+/// Sans effet en simulation : le dossier n'a pas été créé.
+pub fn attach(
+    result: Result<ConversionReport, ConverterError>,
+    run_dir: Option<&Path>,
+) -> Result<ConversionReport, ConverterError> {
+    match (result, run_dir) {
+        (Ok(report), _) if report.options.dry_run => Ok(report),
+        (Ok(mut report), Some(run_dir)) => {
+            if !remove_if_empty(run_dir) {
+                report.run_folder = Some(run_dir.to_path_buf());
+            }
+            Ok(report)
+        }
+        (Err(e), Some(run_dir)) => {
+            remove_if_empty(run_dir);
+            Err(e)
+        }
+        (result, None) => result,
+    }
+}
+
+/// Supprime le dossier de l'exécution s'il ne contient aucun fichier (lot interrompu ou sans sortie).
+/// Retourne `true` si le dossier a été supprimé.
+pub fn remove_if_empty(run_dir: &Path) -> bool {
+    let has_files = WalkDir::new(run_dir)
+        .into_iter()
+        .any(|entry| entry.map_or(true, |entry| !entry.file_type().is_dir()));
+    !has_files && fs::remove_dir_all(run_dir).is_ok()
+}
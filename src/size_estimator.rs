@@ -0,0 +1,121 @@
+/// Ce module calcule en arrière-plan une estimation de la taille de sortie de l'image sélectionnée.
+use eframe::egui;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::converter;
+
+/// Délai (en secondes) sans changement de réglage avant de lancer un nouvel essai d'encodage.
+const DEBOUNCE_DELAY: f64 = 0.3;
+
+/// Résultat d'un essai : génération de la requête et taille estimée.
+type EstimateResult = (u64, Option<u64>);
+
+/// Paramètres dont dépend l'estimation : tout changement relance un essai.
+#[derive(Debug, Clone)]
+pub struct EstimateRequest {
+    pub path: PathBuf,
+    pub options: converter::ConversionOptions,
+}
+
+/// Deux requêtes sont équivalentes si elles portent sur le même fichier et produiraient la même sortie :
+/// les options sans effet sur le contenu (écrasement, doublons...) ne relancent pas d'essai.
+impl PartialEq for EstimateRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.options.output_signature() == other.options.output_signature()
+    }
+}
+
+/// Gère le minuteur anti-rebond, le thread d'essai et le dernier résultat obtenu.
+pub struct SizeEstimator {
+    request: Option<EstimateRequest>, // Dernière requête demandée par l'UI.
+    changed_at: f64,                  // Instant du dernier changement de requête.
+    launched: bool,                   // Vrai si l'essai pour la requête courante a été lancé.
+    estimate: Option<u64>,            // Dernière estimation valide (en octets).
+    finished: bool,                   // Vrai une fois le résultat de la requête courante reçu (même sans estimation).
+    generation: Arc<AtomicU64>,       // Incrémenté à chaque requête : invalide les essais obsolètes.
+    result: Arc<Mutex<Option<EstimateResult>>>, // Résultat envoyé par le thread d'essai.
+}
+
+impl Default for SizeEstimator {
+    fn default() -> Self {
+        Self {
+            request: None,
+            changed_at: 0.0,
+            launched: false,
+            estimate: None,
+            finished: false,
+            generation: Arc::new(AtomicU64::new(0)),
+            result: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl SizeEstimator {
+    /// Dernière estimation disponible pour la requête courante.
+    pub fn estimate(&self) -> Option<u64> {
+        self.estimate
+    }
+
+    /// Vrai tant que l'estimation de la requête courante est attendue (délai anti-rebond ou essai en cours).
+    pub fn is_estimating(&self) -> bool {
+        self.request.is_some() && !self.finished
+    }
+
+    /// À appeler à chaque frame avec la requête correspondant aux réglages actuels.
+    /// Lance l'essai d'encodage une fois le délai anti-rebond écoulé et récupère son résultat.
+    pub fn update(&mut self, ctx: &egui::Context, request: Option<EstimateRequest>) {
+        let now = ctx.input(|i| i.time);
+
+        if request != self.request {
+            // Les réglages ont changé : l'ancienne estimation et tout essai en cours sont obsolètes.
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            self.request = request;
+            self.changed_at = now;
+            self.launched = false;
+            self.estimate = None;
+            self.finished = false;
+        }
+
+        if let Some((generation, estimate)) = self.result.lock().unwrap().take() {
+            if generation == self.generation.load(Ordering::SeqCst) {
+                self.estimate = estimate;
+                self.finished = true;
+            }
+        }
+
+        let Some(request) = &self.request else {
+            return;
+        };
+        if self.launched {
+            return;
+        }
+
+        let elapsed = now - self.changed_at;
+        if elapsed < DEBOUNCE_DELAY {
+            ctx.request_repaint_after(Duration::from_secs_f64(DEBOUNCE_DELAY - elapsed));
+            return;
+        }
+
+        self.launched = true;
+        let generation = self.generation.load(Ordering::SeqCst);
+        let generation_clone = Arc::clone(&self.generation);
+        let result_clone = Arc::clone(&self.result);
+        let request_clone = request.clone();
+        let ctx_clone = ctx.clone();
+
+        std::thread::spawn(move || {
+            // Un essai remplacé par une requête plus récente est abandonné sans être calculé.
+            if generation_clone.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let estimate = converter::estimate_output_size(&request_clone.path, &request_clone.options);
+            if generation_clone.load(Ordering::SeqCst) == generation {
+                *result_clone.lock().unwrap() = Some((generation, estimate));
+                ctx_clone.request_repaint();
+            }
+        });
+    }
+}
@@ -0,0 +1,188 @@
+/// Ce module assemble les images produites par une conversion en une planche de sprites (`sprites.png`), accompagnée
+/// d'un atlas JSON des coordonnées de chaque image (format « hash » de TexturePacker, lu par Phaser, PixiJS...).
+/// Les images sont rangées par étagères, des plus hautes aux plus basses, sans dépasser la largeur maximale choisie.
+use image::{imageops, ImageFormat, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::tr;
+
+/// Réglages de la planche de sprites.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpriteSheetOptions {
+    pub enabled: bool,  // Assembler les images produites en planche de sprites à la fin de la conversion.
+    pub name: String,   // Nom de la planche et de son atlas (sans extension), écrits à la racine de la sortie.
+    pub padding: u32,   // Espace (pixels) entre deux sprites, contre les débordements du filtrage.
+    pub max_width: u32, // Largeur maximale de la planche (pixels), élargie si un sprite la dépasse.
+}
+
+impl Default for SpriteSheetOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            name: "sprites".to_string(),
+            padding: 2,
+            max_width: 2048,
+        }
+    }
+}
+
+/// Atlas JSON de la planche.
+#[derive(Serialize)]
+struct Atlas {
+    frames: BTreeMap<String, AtlasFrame>, // Sprites par nom (chemin de la sortie relatif à la racine, sans extension).
+    meta: AtlasMeta,
+}
+
+#[derive(Serialize)]
+struct AtlasFrame {
+    frame: AtlasRect,
+    rotated: bool,
+    trimmed: bool,
+    #[serde(rename = "spriteSourceSize")]
+    sprite_source_size: AtlasRect,
+    #[serde(rename = "sourceSize")]
+    source_size: AtlasSize,
+}
+
+#[derive(Serialize)]
+struct AtlasMeta {
+    app: &'static str,
+    image: String, // Nom du fichier de la planche, à côté de l'atlas.
+    format: &'static str,
+    size: AtlasSize,
+    scale: &'static str,
+}
+
+#[derive(Serialize)]
+struct AtlasRect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Serialize)]
+struct AtlasSize {
+    w: u32,
+    h: u32,
+}
+
+/// Relève les images produites au fil de la conversion, puis assemble la planche à la fin.
+pub struct SpriteSheetBuilder {
+    options: SpriteSheetOptions,
+    output_root: PathBuf,
+    sprites: Vec<PathBuf>, // Images produites, à assembler.
+}
+
+impl SpriteSheetBuilder {
+    pub fn new(options: &SpriteSheetOptions, output_root: &Path) -> Self {
+        Self { options: options.clone(), output_root: output_root.to_path_buf(), sprites: Vec::new() }
+    }
+
+    /// Ajoute l'image produite `output`.
+    pub fn add(&mut self, output: &Path) {
+        self.sprites.push(output.to_path_buf());
+    }
+
+    /// Écrit la planche `{nom}.png` et son atlas `{nom}.json` à la racine de la sortie. Retourne les fichiers écrits
+    /// (aucun sans image produite) et les problèmes rencontrés : une image illisible est omise de la planche.
+    pub fn finish(mut self) -> (Vec<PathBuf>, Vec<String>) {
+        self.sprites.sort(); // Les conversions parallèles se terminent dans un ordre quelconque.
+        let mut warnings = Vec::new();
+        let mut images = Vec::new();
+        for output in &self.sprites {
+            match image::open(output) {
+                Ok(img) => images.push((self.frame_name(output), img.to_rgba8())),
+                Err(e) => warnings.push(tr!("Planche de sprites : {} omis ({})", output.display(), e)),
+            }
+        }
+        if images.is_empty() {
+            return (Vec::new(), warnings);
+        }
+
+        let sizes: Vec<(u32, u32)> = images.iter().map(|(_, img)| img.dimensions()).collect();
+        let (positions, (width, height)) = pack(&sizes, self.options.max_width, self.options.padding);
+        let mut sheet = RgbaImage::new(width, height);
+        let mut frames = BTreeMap::new();
+        for ((name, img), (x, y)) in images.iter().zip(positions) {
+            imageops::replace(&mut sheet, img, x as i64, y as i64);
+            let (w, h) = img.dimensions();
+            frames.insert(
+                name.clone(),
+                AtlasFrame {
+                    frame: AtlasRect { x, y, w, h },
+                    rotated: false,
+                    trimmed: false,
+                    sprite_source_size: AtlasRect { x: 0, y: 0, w, h },
+                    source_size: AtlasSize { w, h },
+                },
+            );
+        }
+
+        let stem = match self.options.name.trim() {
+            "" => "sprites",
+            name => name,
+        };
+        let image_path = self.output_root.join(format!("{}.png", stem));
+        let atlas_path = self.output_root.join(format!("{}.json", stem));
+        let atlas = Atlas {
+            frames,
+            meta: AtlasMeta {
+                app: "image_converter",
+                image: format!("{}.png", stem),
+                format: "RGBA8888",
+                size: AtlasSize { w: width, h: height },
+                scale: "1",
+            },
+        };
+        let mut written = Vec::new();
+        match sheet.save_with_format(&image_path, ImageFormat::Png) {
+            Ok(()) => written.push(image_path),
+            Err(e) => {
+                warnings.push(tr!("Impossible d'écrire la planche de sprites {} : {}", image_path.display(), e));
+                return (written, warnings);
+            }
+        }
+        let atlas = serde_json::to_string_pretty(&atlas).map_err(|e| e.to_string());
+        match atlas.and_then(|atlas| fs::write(&atlas_path, atlas).map_err(|e| e.to_string())) {
+            Ok(()) => written.push(atlas_path),
+            Err(e) => warnings.push(tr!("Impossible d'écrire l'atlas des sprites {} : {}", atlas_path.display(), e)),
+        }
+        (written, warnings)
+    }
+
+    /// Nom du sprite dans l'atlas : chemin de la sortie relatif à la racine, sans extension (ex: `icones/fleche`).
+    fn frame_name(&self, output: &Path) -> String {
+        let relative = output.strip_prefix(&self.output_root).unwrap_or(output).with_extension("");
+        relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+    }
+}
+
+/// Range des images de dimensions `sizes` par étagères, des plus hautes aux plus basses, espacées de `padding` pixels.
+/// Retourne la position de chaque image (dans l'ordre de `sizes`) et les dimensions de la planche, dont la largeur
+/// ne dépasse `max_width` que si une image est plus large.
+fn pack(sizes: &[(u32, u32)], max_width: u32, padding: u32) -> (Vec<(u32, u32)>, (u32, u32)) {
+    let max_width = sizes.iter().map(|&(width, _)| width).max().unwrap_or(0).max(max_width);
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&index| std::cmp::Reverse(sizes[index].1));
+
+    let mut positions = vec![(0, 0); sizes.len()];
+    let (mut x, mut y, mut shelf_height, mut sheet_width) = (0, 0, 0, 0);
+    for index in order {
+        let (width, height) = sizes[index];
+        if x > 0 && x + width > max_width {
+            y += shelf_height + padding;
+            x = 0;
+            shelf_height = 0;
+        }
+        positions[index] = (x, y);
+        sheet_width = sheet_width.max(x + width);
+        shelf_height = shelf_height.max(height);
+        x += width + padding;
+    }
+    (positions, (sheet_width.max(1), (y + shelf_height).max(1)))
+}
@@ -0,0 +1,131 @@
+/// Ce module décrit les destinations multiples d'une conversion : chaque image est produite une fois par destination
+/// (ex: WebP pleine taille dans `web/`, miniature de 300 px dans `thumbs/`), chacune dans son sous-dossier de la sortie
+/// avec son format et son redimensionnement. Les autres réglages (qualité, nom, écrasement...) sont communs.
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+use crate::converter::{ConversionOptions, OutputFormat};
+use crate::transform::{ResizeMode, ResizeOptions};
+
+/// Destination des fichiers produits.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputTarget {
+    pub subfolder: String,           // Sous-dossier de la sortie (vide = racine de la sortie).
+    pub output_format: OutputFormat, // Format des fichiers produits pour cette destination.
+    pub resize: ResizeOptions,       // Redimensionnement propre à cette destination.
+}
+
+impl Default for OutputTarget {
+    fn default() -> Self {
+        Self {
+            subfolder: String::new(),
+            output_format: OutputFormat::WebP,
+            resize: ResizeOptions::default(),
+        }
+    }
+}
+
+/// Configuration des destinations d'un lot. Inactive, chaque image n'est produite qu'une fois,
+/// avec le format et le redimensionnement des options principales.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputTargets {
+    pub enabled: bool,              // Produire chaque image pour chacune des destinations.
+    pub targets: Vec<OutputTarget>, // Destinations, dans l'ordre de production.
+}
+
+impl Default for OutputTargets {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            targets: vec![
+                OutputTarget { subfolder: "web".to_string(), ..OutputTarget::default() },
+                OutputTarget {
+                    subfolder: "thumbs".to_string(),
+                    resize: ResizeOptions { mode: ResizeMode::Dimensions, max_width: 300, max_height: 300, ..ResizeOptions::default() },
+                    ..OutputTarget::default()
+                },
+            ],
+        }
+    }
+}
+
+/// Destination résolue : sous-dossier relatif à la racine de la sortie et options complètes de la conversion.
+#[derive(Debug, Clone)]
+pub struct Destination {
+    pub subfolder: Option<PathBuf>, // `None` : dossier de sortie du fichier, sans sous-dossier ajouté.
+    pub name_suffix: String,        // Suffixe ajouté au nom des fichiers produits (ex: `-480w`, jeu d'images responsives).
+    pub width: Option<u32>,         // Largeur visée (jeu d'images responsives).
+    pub options: ConversionOptions, // Options principales, avec le format et le redimensionnement de la destination.
+}
+
+impl Destination {
+    /// Dossier de sortie d'un fichier dont la destination principale est `output_dir`, sous `output_root`.
+    /// Le sous-dossier de la destination est inséré entre la racine et l'arborescence reproduite.
+    pub fn output_dir(&self, output_root: &Path, output_dir: &Path) -> PathBuf {
+        match &self.subfolder {
+            Some(subfolder) => {
+                output_root.join(subfolder).join(output_dir.strip_prefix(output_root).unwrap_or(Path::new("")))
+            }
+            None => output_dir.to_path_buf(),
+        }
+    }
+}
+
+impl OutputTargets {
+    /// Vrai si les images sont produites pour plusieurs destinations.
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.targets.is_empty()
+    }
+
+    /// Signature des destinations, intégrée à celle des options de sortie (manifeste). `None` si inactive.
+    pub fn signature(&self) -> Option<String> {
+        self.is_active().then(|| {
+            let targets: Vec<String> = self
+                .targets
+                .iter()
+                .map(|target| {
+                    let resize = target.resize.signature().unwrap_or_default();
+                    format!("{}:{}:{}", target.subfolder.trim(), target.output_format.extension(), resize)
+                })
+                .collect();
+            format!("targets=[{}]", targets.join("|"))
+        })
+    }
+
+    /// Destinations de la conversion avec `options` : une seule, les options elles-mêmes, si la configuration est inactive.
+    /// Une destination n'essaie qu'un format : le mode « format le plus léger » y est désactivé.
+    /// Le filtre de rééchantillonnage reste celui des options principales.
+    pub fn resolve(&self, options: &ConversionOptions) -> Vec<Destination> {
+        if !self.is_active() {
+            return vec![Destination { subfolder: None, name_suffix: String::new(), width: None, options: options.clone() }];
+        }
+        self.targets
+            .iter()
+            .map(|target| {
+                let mut target_options = options.clone();
+                target_options.targets.enabled = false;
+                target_options.smallest_format = false;
+                target_options.output_format = target.output_format;
+                target_options.resize = ResizeOptions { filter: options.resize.filter, ..target.resize.clone() };
+                Destination {
+                    subfolder: safe_subfolder(&target.subfolder),
+                    name_suffix: String::new(),
+                    width: None,
+                    options: target_options,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Sous-dossier relatif tiré du texte saisi : les composants absolus ou remontants (`..`) sont ignorés,
+/// et une destination ne peut pas écrire hors de la sortie. `None` si rien ne subsiste.
+fn safe_subfolder(subfolder: &str) -> Option<PathBuf> {
+    let path: PathBuf = Path::new(subfolder.trim())
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+    (!path.as_os_str().is_empty()).then_some(path)
+}
@@ -0,0 +1,65 @@
+/// Ce module mesure le débit d'une conversion en cours (images par seconde, octets lus par seconde)
+/// sur une moyenne glissante des dernières secondes, et en déduit le temps restant estimé.
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Durée de la moyenne glissante : le débit suit les changements de rythme (pause, gros fichiers) sans trop varier.
+const ROLLING_WINDOW: Duration = Duration::from_secs(10);
+
+/// Durée minimale de mesure avant d'annoncer un débit, pour éviter des valeurs aberrantes au démarrage.
+const MIN_MEASURE: Duration = Duration::from_millis(500);
+
+/// Débit instantané d'une conversion, transmis à l'interface avec l'avancement.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Throughput {
+    pub files_per_second: f64, // Fichiers traités par seconde (moyenne glissante).
+    pub bytes_per_second: f64, // Octets de sources traités par seconde (moyenne glissante).
+    pub eta: Option<Duration>, // Temps restant estimé, `None` tant que le débit est inconnu.
+}
+
+/// Mesure du débit : date de chaque fichier terminé et taille de sa source, sur la fenêtre glissante.
+/// Partagée par les threads de conversion.
+pub struct ThroughputMeter {
+    started: Instant,                         // Début de la conversion.
+    samples: Mutex<VecDeque<(Instant, u64)>>, // Fichiers terminés dans la fenêtre : date et taille de la source.
+}
+
+impl Default for ThroughputMeter {
+    fn default() -> Self {
+        Self { started: Instant::now(), samples: Mutex::new(VecDeque::new()) }
+    }
+}
+
+impl ThroughputMeter {
+    /// Enregistre un fichier terminé dont la source pèse `bytes` octets.
+    pub fn record(&self, bytes: u64) {
+        let now = Instant::now();
+        let mut samples = self.samples.lock().unwrap();
+        samples.push_back((now, bytes));
+        while samples.front().is_some_and(|(date, _)| now.duration_since(*date) > ROLLING_WINDOW) {
+            samples.pop_front();
+        }
+    }
+
+    /// Débit actuel, et temps restant pour les `remaining` fichiers qui restent à traiter.
+    pub fn snapshot(&self, remaining: usize) -> Throughput {
+        let now = Instant::now();
+        let window_start = now.checked_sub(ROLLING_WINDOW).map_or(self.started, |start| start.max(self.started));
+        let elapsed = now.duration_since(window_start);
+        if elapsed < MIN_MEASURE {
+            return Throughput::default();
+        }
+
+        let samples = self.samples.lock().unwrap();
+        let recent = samples.iter().filter(|(date, _)| *date >= window_start);
+        let (files, bytes) = recent.fold((0usize, 0u64), |(files, bytes), (_, size)| (files + 1, bytes + size));
+        let seconds = elapsed.as_secs_f64();
+        let files_per_second = files as f64 / seconds;
+        Throughput {
+            files_per_second,
+            bytes_per_second: bytes as f64 / seconds,
+            eta: (files_per_second > 0.0).then(|| Duration::from_secs_f64(remaining as f64 / files_per_second)),
+        }
+    }
+}
@@ -0,0 +1,626 @@
+/// Ce module applique les traitements d'image (rotation, rognage des bords, recadrage, redimensionnement, marges, réglages, ...) entre le décodage et l'encodage.
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use crate::converter::ConversionOptions;
+use crate::encoder;
+use crate::tr;
+
+/// Rayon (écart-type du flou gaussien) utilisé par le masque flou.
+const UNSHARP_SIGMA: f32 = 1.0;
+
+/// Plus grande dimension de l'image réduite sur laquelle le recadrage intelligent cherche la zone la plus détaillée.
+const SMART_CROP_SAMPLE_SIZE: u32 = 256;
+
+/// Rotation ou symétrie appliquée à toutes les images du lot, après le redressement EXIF.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Rotation {
+    None,           // Aucune
+    Rotate90,       // Rotation de 90° dans le sens horaire
+    Rotate180,      // Rotation de 180°
+    Rotate270,      // Rotation de 270° dans le sens horaire (90° antihoraire)
+    FlipHorizontal, // Symétrie horizontale (miroir gauche-droite)
+    FlipVertical,   // Symétrie verticale (haut-bas)
+}
+
+impl Rotation {
+    /// Transformations proposées dans l'interface.
+    pub const ALL: [Rotation; 6] = [
+        Rotation::None,
+        Rotation::Rotate90,
+        Rotation::Rotate180,
+        Rotation::Rotate270,
+        Rotation::FlipHorizontal,
+        Rotation::FlipVertical,
+    ];
+
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Rotation::None => tr!("Aucune"),
+            Rotation::Rotate90 => tr!("Rotation 90°"),
+            Rotation::Rotate180 => tr!("Rotation 180°"),
+            Rotation::Rotate270 => tr!("Rotation 270°"),
+            Rotation::FlipHorizontal => tr!("Miroir horizontal"),
+            Rotation::FlipVertical => tr!("Miroir vertical"),
+        }
+    }
+
+    /// Dimensions d'une image de `width` × `height` pixels après la transformation.
+    pub fn rotated_size(&self, width: u32, height: u32) -> (u32, u32) {
+        match self {
+            Rotation::Rotate90 | Rotation::Rotate270 => (height, width),
+            _ => (width, height),
+        }
+    }
+
+    /// Signature de la transformation pour le manifeste, `None` si les images ne sont pas transformées.
+    pub fn signature(&self) -> Option<String> {
+        let name = match self {
+            Rotation::None => return None,
+            Rotation::Rotate90 => "90",
+            Rotation::Rotate180 => "180",
+            Rotation::Rotate270 => "270",
+            Rotation::FlipHorizontal => "flip-h",
+            Rotation::FlipVertical => "flip-v",
+        };
+        Some(format!("rotate={}", name))
+    }
+}
+
+/// Applique la rotation ou la symétrie à l'image. Retourne l'image telle quelle sans transformation.
+pub fn apply_rotation(img: DynamicImage, rotation: Rotation) -> DynamicImage {
+    match rotation {
+        Rotation::None => img,
+        Rotation::Rotate90 => img.rotate90(),
+        Rotation::Rotate180 => img.rotate180(),
+        Rotation::Rotate270 => img.rotate270(),
+        Rotation::FlipHorizontal => img.fliph(),
+        Rotation::FlipVertical => img.flipv(),
+    }
+}
+
+/// Réglages du rognage automatique des bords uniformes (pixels transparents ou couleur de fond unie).
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrimOptions {
+    pub enabled: bool, // Rogner les bords uniformes avant le recadrage.
+    pub tolerance: u8, // Écart maximal (par canal) avec la couleur du coin pour qu'un pixel compte comme bord.
+}
+
+impl Default for TrimOptions {
+    fn default() -> Self {
+        Self { enabled: false, tolerance: 10 }
+    }
+}
+
+impl TrimOptions {
+    /// Signature du rognage pour le manifeste, `None` si les bords sont conservés.
+    pub fn signature(&self) -> Option<String> {
+        self.enabled.then(|| format!("trim={}", self.tolerance))
+    }
+}
+
+/// Rogne les bords uniformes de l'image : la couleur de référence est celle du coin supérieur gauche,
+/// et un coin transparent rogne tous les pixels transparents quelle que soit leur couleur.
+/// Retourne l'image telle quelle si aucun bord n'est trouvé, ou si l'image entière est uniforme.
+pub fn apply_trim(img: DynamicImage, trim: &TrimOptions) -> DynamicImage {
+    if !trim.enabled || img.width() == 0 || img.height() == 0 {
+        return img;
+    }
+    let rgba = img.to_rgba8();
+    let tolerance = trim.tolerance as i16;
+    let corner = *rgba.get_pixel(0, 0);
+    let transparent_corner = (corner[3] as i16) <= tolerance;
+    let is_border = |x: u32, y: u32| {
+        let pixel = rgba.get_pixel(x, y);
+        match transparent_corner {
+            true => (pixel[3] as i16) <= tolerance,
+            false => (0..4).all(|channel| (pixel[channel] as i16 - corner[channel] as i16).abs() <= tolerance),
+        }
+    };
+    let (width, height) = rgba.dimensions();
+    let row_is_border = |y: u32| (0..width).all(|x| is_border(x, y));
+    let column_is_border = |x: u32, top: u32, bottom: u32| (top..bottom).all(|y| is_border(x, y));
+
+    let Some(top) = (0..height).find(|&y| !row_is_border(y)) else {
+        return img; // Image entièrement uniforme : rien à garder en particulier.
+    };
+    let bottom = (top..height).rev().find(|&y| !row_is_border(y)).map_or(height, |y| y + 1);
+    let left = (0..width).find(|&x| !column_is_border(x, top, bottom)).unwrap_or(0);
+    let right = (left..width).rev().find(|&x| !column_is_border(x, top, bottom)).map_or(width, |x| x + 1);
+
+    if (left, top, right, bottom) == (0, 0, width, height) {
+        return img;
+    }
+    img.crop_imm(left, top, right - left, bottom - top)
+}
+
+/// Position du cadre lors du recadrage.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum CropAnchor {
+    Center, // Cadre centré
+    Smart,  // Cadre placé sur la zone la plus détaillée (contours les plus marqués)
+}
+
+impl CropAnchor {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            CropAnchor::Center => tr!("Centré"),
+            CropAnchor::Smart => tr!("Intelligent"),
+        }
+    }
+}
+
+/// Réglages du recadrage à des proportions fixes (ex: 16:9, 1:1), appliqué avant le redimensionnement.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CropOptions {
+    pub enabled: bool,      // Recadrer les images aux proportions demandées.
+    pub ratio_width: u32,   // Proportion horizontale (ex: 16 pour 16:9).
+    pub ratio_height: u32,  // Proportion verticale (ex: 9 pour 16:9).
+    pub anchor: CropAnchor, // Position du cadre dans l'image.
+}
+
+impl Default for CropOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ratio_width: 16,
+            ratio_height: 9,
+            anchor: CropAnchor::Center,
+        }
+    }
+}
+
+impl CropOptions {
+    /// Proportions courantes proposées dans l'interface.
+    pub const PRESETS: [(u32, u32); 5] = [(1, 1), (4, 3), (3, 2), (16, 9), (9, 16)];
+
+    /// Vrai si les images sont recadrées.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.ratio_width > 0 && self.ratio_height > 0
+    }
+
+    /// Dimensions du cadre pour une image de `width` × `height` pixels : la plus grande zone aux proportions demandées.
+    pub fn cropped_size(&self, width: u32, height: u32) -> (u32, u32) {
+        if !self.is_active() {
+            return (width, height);
+        }
+        let (ratio_width, ratio_height) = (self.ratio_width as u64, self.ratio_height as u64);
+        if width as u64 * ratio_height > height as u64 * ratio_width {
+            (((height as u64 * ratio_width / ratio_height) as u32).max(1), height)
+        } else {
+            (width, ((width as u64 * ratio_height / ratio_width) as u32).max(1))
+        }
+    }
+
+    /// Signature du recadrage pour le manifeste, `None` si les images ne sont pas recadrées.
+    pub fn signature(&self) -> Option<String> {
+        self.is_active().then(|| {
+            let anchor = match self.anchor {
+                CropAnchor::Center => "center",
+                CropAnchor::Smart => "smart",
+            };
+            format!("crop={}:{}:{}", self.ratio_width, self.ratio_height, anchor)
+        })
+    }
+}
+
+/// Dimensions produites pour une source de `width` × `height` pixels : rotation, recadrage, redimensionnement
+/// puis canevas. Le rognage des bords, qui dépend du contenu de l'image, n'est pas pris en compte.
+pub fn output_size(options: &ConversionOptions, width: u32, height: u32) -> (u32, u32) {
+    if options.pad.is_active() {
+        return (options.pad.width, options.pad.height);
+    }
+    let (width, height) = options.rotation.rotated_size(width, height);
+    let (width, height) = options.crop.cropped_size(width, height);
+    options.resize.target_size(width, height)
+}
+
+/// Recadre l'image selon les réglages. Retourne l'image telle quelle si elle a déjà les proportions demandées.
+pub fn apply_crop(img: DynamicImage, crop: &CropOptions) -> DynamicImage {
+    let (width, height) = crop.cropped_size(img.width(), img.height());
+    if (width, height) == (img.width(), img.height()) {
+        return img;
+    }
+    let (x, y) = match crop.anchor {
+        CropAnchor::Center => ((img.width() - width) / 2, (img.height() - height) / 2),
+        CropAnchor::Smart => smart_crop_origin(&img, width, height),
+    };
+    img.crop_imm(x, y, width, height)
+}
+
+/// Origine du cadre de `width` × `height` pixels qui couvre le plus de détails (somme des gradients de luminance),
+/// cherchée sur une version réduite de l'image. Le cadre ne se déplace que dans le sens où l'image dépasse.
+fn smart_crop_origin(img: &DynamicImage, width: u32, height: u32) -> (u32, u32) {
+    let sample = img.thumbnail(SMART_CROP_SAMPLE_SIZE, SMART_CROP_SAMPLE_SIZE).to_luma8();
+    let (sample_width, sample_height) = sample.dimensions();
+    let horizontal = width < img.width();
+    let scale = match horizontal {
+        true => sample_width as f64 / img.width() as f64,
+        false => sample_height as f64 / img.height() as f64,
+    };
+
+    // Énergie de chaque colonne (ou ligne) : somme des écarts de luminance avec les voisins de droite et du dessous.
+    let mut energy = vec![0u64; if horizontal { sample_width } else { sample_height } as usize];
+    for (x, y, pixel) in sample.enumerate_pixels() {
+        let value = pixel[0] as i32;
+        let right = if x + 1 < sample_width { sample.get_pixel(x + 1, y)[0] as i32 } else { value };
+        let below = if y + 1 < sample_height { sample.get_pixel(x, y + 1)[0] as i32 } else { value };
+        let gradient = ((value - right).abs() + (value - below).abs()) as u64;
+        energy[if horizontal { x } else { y } as usize] += gradient;
+    }
+
+    // Fenêtre glissante de la taille du cadre réduit ; à énergie égale, la position la plus centrale l'emporte.
+    let window = (((if horizontal { width } else { height }) as f64 * scale).round() as usize).clamp(1, energy.len());
+    let positions = energy.len() - window;
+    let center = positions / 2;
+    let mut sum: u64 = energy[..window].iter().sum();
+    let (mut best_start, mut best_sum) = (0usize, sum);
+    for start in 1..=positions {
+        sum = sum + energy[start + window - 1] - energy[start - 1];
+        if sum > best_sum || (sum == best_sum && start.abs_diff(center) < best_start.abs_diff(center)) {
+            (best_start, best_sum) = (start, sum);
+        }
+    }
+
+    let offset = (best_start as f64 / scale).round() as u32;
+    match horizontal {
+        true => (offset.min(img.width() - width), 0),
+        false => (0, offset.min(img.height() - height)),
+    }
+}
+
+/// Mode de redimensionnement des images.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ResizeMode {
+    None,       // Dimensions d'origine
+    Dimensions, // Tenir dans une largeur et une hauteur maximales (proportions conservées)
+    Percentage, // Pourcentage des dimensions d'origine
+}
+
+impl ResizeMode {
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResizeMode::None => tr!("Aucun"),
+            ResizeMode::Dimensions => tr!("Dimensions"),
+            ResizeMode::Percentage => tr!("Pourcentage"),
+        }
+    }
+}
+
+/// Filtre de rééchantillonnage du redimensionnement, du plus rapide au plus fin.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ResizeFilter {
+    Nearest,    // Plus proche voisin : très rapide, crénelé (pixel art)
+    Triangle,   // Bilinéaire : rapide, légèrement flou
+    CatmullRom, // Bicubique : bon compromis
+    Lanczos3,   // Lanczos : le plus net, le plus lent
+}
+
+impl ResizeFilter {
+    /// Filtres proposés dans l'interface.
+    pub const ALL: [ResizeFilter; 4] = [ResizeFilter::Nearest, ResizeFilter::Triangle, ResizeFilter::CatmullRom, ResizeFilter::Lanczos3];
+
+    /// Libellé affiché dans l'interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResizeFilter::Nearest => tr!("Plus proche voisin (rapide)"),
+            ResizeFilter::Triangle => tr!("Bilinéaire"),
+            ResizeFilter::CatmullRom => tr!("Bicubique (Catmull-Rom)"),
+            ResizeFilter::Lanczos3 => tr!("Lanczos3 (net)"),
+        }
+    }
+
+    /// Filtre correspondant de la bibliothèque `image`.
+    fn filter_type(self) -> FilterType {
+        match self {
+            ResizeFilter::Nearest => FilterType::Nearest,
+            ResizeFilter::Triangle => FilterType::Triangle,
+            ResizeFilter::CatmullRom => FilterType::CatmullRom,
+            ResizeFilter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+/// Réglages du redimensionnement, appliqué à chaque image avant les autres traitements.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResizeOptions {
+    pub mode: ResizeMode,
+    pub max_width: u32,       // Largeur maximale en pixels (mode Dimensions, 0 = pas de limite).
+    pub max_height: u32,      // Hauteur maximale en pixels (mode Dimensions, 0 = pas de limite).
+    pub percent: u32,         // Échelle en % des dimensions d'origine (mode Pourcentage, 1 à 400).
+    pub filter: ResizeFilter, // Filtre de rééchantillonnage (vitesse ou finesse).
+}
+
+impl Default for ResizeOptions {
+    fn default() -> Self {
+        Self {
+            mode: ResizeMode::None,
+            max_width: 1920,
+            max_height: 1080,
+            percent: 50,
+            filter: ResizeFilter::Lanczos3,
+        }
+    }
+}
+
+impl ResizeOptions {
+    /// Dimensions de sortie d'une image de `width` × `height` pixels.
+    /// En mode Dimensions, une image qui tient déjà dans le cadre n'est pas agrandie.
+    pub fn target_size(&self, width: u32, height: u32) -> (u32, u32) {
+        match self.mode {
+            ResizeMode::None => (width, height),
+            ResizeMode::Dimensions => {
+                let limit = |max: u32, size: u32| if max == 0 { 1.0 } else { max as f64 / size.max(1) as f64 };
+                let scale = limit(self.max_width, width).min(limit(self.max_height, height)).min(1.0);
+                scale_size(width, height, scale)
+            }
+            ResizeMode::Percentage => scale_size(width, height, self.percent.clamp(1, 400) as f64 / 100.0),
+        }
+    }
+
+    /// Signature du redimensionnement pour le manifeste, `None` si les images gardent leurs dimensions.
+    /// Le filtre n'y figure que s'il diffère du filtre par défaut (Lanczos3).
+    pub fn signature(&self) -> Option<String> {
+        let size = match self.mode {
+            ResizeMode::None => return None,
+            ResizeMode::Dimensions => format!("resize={}x{}", self.max_width, self.max_height),
+            ResizeMode::Percentage => format!("resize={}%", self.percent.clamp(1, 400)),
+        };
+        Some(match self.filter {
+            ResizeFilter::Lanczos3 => size,
+            filter => format!("{};filter={:?}", size, filter),
+        })
+    }
+}
+
+/// Applique l'échelle `scale` aux dimensions, sans descendre sous 1 pixel.
+fn scale_size(width: u32, height: u32, scale: f64) -> (u32, u32) {
+    let scaled = |size: u32| ((size as f64 * scale).round() as u32).max(1);
+    (scaled(width), scaled(height))
+}
+
+/// Redimensionne l'image selon les réglages. Retourne l'image telle quelle si ses dimensions ne changent pas.
+fn apply_resize(img: DynamicImage, resize: &ResizeOptions) -> DynamicImage {
+    let (width, height) = resize.target_size(img.width(), img.height());
+    if (width, height) == (img.width(), img.height()) {
+        return img;
+    }
+    img.resize_exact(width, height, resize.filter.filter_type())
+}
+
+/// Réglages de la mise sur canevas : chaque image est centrée sur un fond de dimensions fixes, sans déformation.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PadOptions {
+    pub enabled: bool,     // Placer les images sur un canevas de dimensions fixes.
+    pub width: u32,        // Largeur du canevas en pixels.
+    pub height: u32,       // Hauteur du canevas en pixels.
+    pub color: [u8; 3],    // Couleur du fond (RGB).
+    pub transparent: bool, // Fond transparent plutôt que coloré (formats gérant la transparence).
+}
+
+impl Default for PadOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            width: 800,
+            height: 800,
+            color: [255, 255, 255],
+            transparent: false,
+        }
+    }
+}
+
+impl PadOptions {
+    /// Vrai si les images sont placées sur un canevas.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.width > 0 && self.height > 0
+    }
+
+    /// Signature du canevas pour le manifeste, `None` si les images gardent leurs dimensions.
+    pub fn signature(&self) -> Option<String> {
+        self.is_active().then(|| match self.transparent {
+            true => format!("pad={}x{}:transparent", self.width, self.height),
+            false => format!("pad={}x{}:{:?}", self.width, self.height, self.color),
+        })
+    }
+}
+
+/// Centre l'image sur le canevas, après l'avoir réduite (proportions conservées, filtre `filter`) si elle le dépasse.
+/// Retourne l'image telle quelle si elle a déjà les dimensions du canevas.
+pub fn apply_pad(img: DynamicImage, pad: &PadOptions, filter: ResizeFilter) -> DynamicImage {
+    if !pad.is_active() || (img.width(), img.height()) == (pad.width, pad.height) {
+        return img;
+    }
+    let fit = ResizeOptions { mode: ResizeMode::Dimensions, max_width: pad.width, max_height: pad.height, percent: 100, filter };
+    let img = apply_resize(img, &fit);
+
+    let background = match pad.transparent {
+        true => Rgba([0, 0, 0, 0]),
+        false => Rgba([pad.color[0], pad.color[1], pad.color[2], 255]),
+    };
+    let mut canvas = RgbaImage::from_pixel(pad.width, pad.height, background);
+    let x = (pad.width - img.width()) / 2;
+    let y = (pad.height - img.height()) / 2;
+    imageops::overlay(&mut canvas, &img.to_rgba8(), x as i64, y as i64);
+
+    // Conserve un type sans alpha si ni la source ni le fond n'en ont, pour ne pas alourdir la sortie.
+    if pad.transparent || img.color().has_alpha() {
+        DynamicImage::ImageRgba8(canvas)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+    }
+}
+
+/// Réglages de l'image : luminosité, contraste, saturation et accentuation (masque flou).
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Adjustments {
+    pub brightness: i32,     // Luminosité, de -100 à 100 (0 = inchangée).
+    pub contrast: i32,       // Contraste, de -100 à 100 (0 = inchangé).
+    pub saturation: i32,     // Saturation, de -100 (niveaux de gris) à 100 (0 = inchangée).
+    pub sharpen: bool,       // Appliquer un masque flou (accentuation).
+    pub sharpen_amount: f32, // Intensité de l'accentuation (0.0 à 2.0).
+}
+
+impl Default for Adjustments {
+    fn default() -> Self {
+        Self {
+            brightness: 0,
+            contrast: 0,
+            saturation: 0,
+            sharpen: false,
+            sharpen_amount: 0.5,
+        }
+    }
+}
+
+impl Adjustments {
+    /// Vrai si aucun réglage ne modifie l'image : le traitement est alors entièrement évité.
+    pub fn is_identity(&self) -> bool {
+        self.brightness == 0
+            && self.contrast == 0
+            && self.saturation == 0
+            && (!self.sharpen || self.sharpen_amount <= 0.0)
+    }
+
+    /// Intensité de l'accentuation réellement appliquée (0 sans accentuation).
+    pub fn effective_sharpen_amount(&self) -> f32 {
+        if self.sharpen { self.sharpen_amount } else { 0.0 }
+    }
+
+    /// Résumé des réglages actifs pour le bilan (ex: « luminosité +10, contraste -5 »), `None` s'ils sont neutres.
+    pub fn description(&self) -> Option<String> {
+        if self.is_identity() {
+            return None;
+        }
+        let mut parts: Vec<String> = [(tr!("luminosité"), self.brightness), (tr!("contraste"), self.contrast), (tr!("saturation"), self.saturation)]
+            .into_iter()
+            .filter(|(_, value)| *value != 0)
+            .map(|(label, value)| format!("{} {:+}", label, value))
+            .collect();
+        if self.effective_sharpen_amount() > 0.0 {
+            parts.push(format!("{} {:.2}", tr!("accentuation"), self.sharpen_amount));
+        }
+        Some(parts.join(", "))
+    }
+}
+
+/// Applique les réglages à l'image décodée. Retourne l'image telle quelle si les réglages sont neutres.
+pub fn apply_adjustments(img: DynamicImage, adjustments: &Adjustments) -> DynamicImage {
+    if adjustments.is_identity() {
+        return img;
+    }
+
+    let mut img = img;
+    if adjustments.brightness != 0 {
+        // -100..100 correspond à un décalage de -128..128 sur chaque canal.
+        img = img.brighten(adjustments.brightness.clamp(-100, 100) * 128 / 100);
+    }
+    if adjustments.contrast != 0 {
+        img = img.adjust_contrast(adjustments.contrast.clamp(-100, 100) as f32);
+    }
+    if adjustments.saturation != 0 {
+        img = adjust_saturation(&img, adjustments.saturation.clamp(-100, 100));
+    }
+    if adjustments.sharpen && adjustments.sharpen_amount > 0.0 {
+        img = unsharp_mask(&img, adjustments.sharpen_amount.clamp(0.0, 2.0));
+    }
+    img
+}
+
+/// Accentue l'image : `original + amount × (original - flou)`, canal par canal (alpha préservé).
+fn unsharp_mask(img: &DynamicImage, amount: f32) -> DynamicImage {
+    let blurred = img.blur(UNSHARP_SIGMA).to_rgba8();
+    let mut sharpened = img.to_rgba8();
+
+    for (x, y, pixel) in sharpened.enumerate_pixels_mut() {
+        let blurred_pixel = blurred.get_pixel(x, y);
+        for channel in 0..3 {
+            let original = pixel[channel] as f32;
+            let detail = original - blurred_pixel[channel] as f32;
+            pixel[channel] = (original + amount * detail).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    // Conserve un type sans alpha si la source n'en avait pas, pour ne pas alourdir la sortie.
+    if img.color().has_alpha() {
+        DynamicImage::ImageRgba8(sharpened)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(sharpened).to_rgb8())
+    }
+}
+
+/// Modifie la saturation en TSL : `saturation` de -100 (niveaux de gris) à 100 (saturation doublée),
+/// teinte et luminance conservées (alpha préservé).
+fn adjust_saturation(img: &DynamicImage, saturation: i32) -> DynamicImage {
+    let factor = 1.0 + saturation as f32 / 100.0;
+    let mut adjusted = img.to_rgba8();
+
+    for pixel in adjusted.pixels_mut() {
+        let [r, g, b] = [pixel[0], pixel[1], pixel[2]].map(|channel| channel as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let chroma = max - min;
+        if chroma <= f32::EPSILON {
+            continue; // Gris : aucune saturation à modifier.
+        }
+
+        let hsl_saturation = chroma / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+
+        let hsl_saturation = (hsl_saturation * factor).clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * hsl_saturation;
+        let second = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u32 {
+            0 => (chroma, second, 0.0),
+            1 => (second, chroma, 0.0),
+            2 => (0.0, chroma, second),
+            3 => (0.0, second, chroma),
+            4 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+        let offset = lightness - chroma / 2.0;
+        for (channel, value) in [r, g, b].into_iter().enumerate() {
+            pixel[channel] = ((value + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    // Conserve un type sans alpha si la source n'en avait pas, pour ne pas alourdir la sortie.
+    if img.color().has_alpha() {
+        DynamicImage::ImageRgba8(adjusted)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(adjusted).to_rgb8())
+    }
+}
+
+/// Applique toute la chaîne de traitements configurée à l'image décodée, avant l'encodage.
+/// L'image est tournée, rognée, recadrée, redimensionnée puis placée sur son canevas en premier : les réglages (accentuation...) portent sur l'image finale.
+/// La transparence éventuellement aplatie l'est avant les réglages, qui s'appliquent alors aussi au fond.
+pub fn process(img: DynamicImage, options: &ConversionOptions) -> DynamicImage {
+    let img = apply_rotation(img, options.rotation);
+    let img = apply_trim(img, &options.trim);
+    let img = apply_crop(img, &options.crop);
+    let img = apply_resize(img, &options.resize);
+    let img = apply_pad(img, &options.pad, options.resize.filter);
+    let img = match options.flatten_alpha && img.color().has_alpha() {
+        true => DynamicImage::ImageRgb8(encoder::flatten_alpha(&img, options.background_color)),
+        false => img,
+    };
+    // Après le redimensionnement : l'accentuation est calibrée sur les pixels finaux, et les réglages coûtent moins sur l'image réduite.
+    apply_adjustments(img, &options.adjustments)
+}
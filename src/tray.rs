@@ -0,0 +1,114 @@
+/// Ce module gère l'icône de la zone de notification (`tray-icon`, sous Windows et macOS) : la fenêtre fermée
+/// s'y cache pendant que la surveillance d'un dossier ou un long lot se poursuit, et son menu propose
+/// « Ouvrir », « Pause » et « Quitter ». Sous Linux, `tray-icon` exige sa propre boucle GTK : l'icône n'y est pas proposée.
+use eframe::egui;
+use std::sync::mpsc::Receiver;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use crate::tr;
+
+/// Vrai si l'icône de la zone de notification est prise en charge sur ce système.
+pub const SUPPORTED: bool = cfg!(any(target_os = "windows", target_os = "macos"));
+
+/// Côté de l'icône, en pixels.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+const ICON_SIZE: u32 = 32;
+
+/// Commande choisie dans le menu de l'icône.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TrayCommand {
+    Open,  // Réafficher la fenêtre
+    Pause, // Suspendre ou reprendre la conversion en cours
+    Quit,  // Fermer l'application, conversion en cours comprise
+}
+
+/// Icône de la zone de notification et commandes reçues de son menu.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+pub struct SystemTray {
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    _icon: tray_icon::TrayIcon, // Icône affichée, retirée de la zone de notification à sa suppression
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    pause_item: tray_icon::menu::MenuItem, // Entrée « Pause » / « Reprendre » du menu
+    pause_state: Option<(bool, bool)>, // Dernier état affiché par l'entrée de pause : (conversion en cours, en pause)
+    commands: Receiver<TrayCommand>,   // Commandes choisies dans le menu, dans l'ordre
+}
+
+impl SystemTray {
+    /// Affiche l'icône. `ctx` est réveillé à chaque commande du menu, même fenêtre cachée.
+    /// `None` si l'icône n'est pas disponible sur ce système ou n'a pas pu être créée.
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    pub fn new(ctx: &egui::Context) -> Option<SystemTray> {
+        use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+
+        let open_item = MenuItem::new(tr!("Ouvrir"), true, None);
+        let pause_item = MenuItem::new(tr!("Pause"), false, None);
+        let quit_item = MenuItem::new(tr!("Quitter"), true, None);
+        let menu = Menu::new();
+        menu.append_items(&[&open_item, &pause_item, &PredefinedMenuItem::separator(), &quit_item]).ok()?;
+
+        let (sender, commands) = std::sync::mpsc::channel();
+        let items = [
+            (open_item.id().clone(), TrayCommand::Open),
+            (pause_item.id().clone(), TrayCommand::Pause),
+            (quit_item.id().clone(), TrayCommand::Quit),
+        ];
+        let ctx = ctx.clone();
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            if let Some((_, command)) = items.iter().find(|(id, _)| *id == event.id) {
+                // Une erreur signifie que l'application se ferme : la commande est sans objet.
+                let _ = sender.send(*command);
+                ctx.request_repaint();
+            }
+        }));
+
+        let icon = tray_icon::Icon::from_rgba(icon_pixels(), ICON_SIZE, ICON_SIZE).ok()?;
+        let tray_icon = tray_icon::TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip(tr!("Convertisseur d'Images"))
+            .with_icon(icon)
+            .build()
+            .ok()?;
+        Some(SystemTray { _icon: tray_icon, pause_item, pause_state: None, commands })
+    }
+
+    /// Icône non prise en charge sur ce système.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    pub fn new(_ctx: &egui::Context) -> Option<SystemTray> {
+        None
+    }
+
+    /// Prochaine commande choisie dans le menu, s'il y en a une.
+    pub fn poll(&self) -> Option<TrayCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    /// Met à jour l'entrée de pause : active pendant une conversion, « Reprendre » si elle est en pause.
+    pub fn update_pause_item(&mut self, converting: bool, paused: bool) {
+        if self.pause_state == Some((converting, paused)) {
+            return;
+        }
+        self.pause_state = Some((converting, paused));
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        {
+            self.pause_item.set_enabled(converting);
+            self.pause_item.set_text(if paused { tr!("Reprendre") } else { tr!("Pause") });
+        }
+    }
+}
+
+/// Pixels de l'icône : un disque bleu sur fond transparent.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn icon_pixels() -> Vec<u8> {
+    let center = (ICON_SIZE as f32 - 1.0) / 2.0;
+    let radius = ICON_SIZE as f32 / 2.0 - 1.0;
+    let mut pixels = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+            let alpha = if distance <= radius { 255 } else { 0 };
+            pixels.extend_from_slice(&[66, 133, 244, alpha]);
+        }
+    }
+    pixels
+}
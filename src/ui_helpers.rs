@@ -1,38 +1,121 @@
 /// Ce module contient des fonctions d'aide pour la construction de l'interface utilisateur.
 use eframe::egui;
 use rfd::FileDialog;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 // use std::process::exit; // Déplacé à l'intérieur de render_dialog_window
 use std::time::Duration;
 
 // Importe les enums InputType et OverwriteMode du module parent (main.rs et converter.rs)
 use super::InputType;
-use crate::converter::OverwriteMode;
-use crate::platform_utils; // Importe le module platform_utils
+use crate::animation::{AnimationOptions, FrameOptions};
+use crate::archive::ArchiveOptions;
+use crate::clipboard::CopyContent;
+use crate::collision_check::CollisionCheck;
+use crate::color_profile::ColorProfileMode;
+use crate::config::AppTheme;
+use crate::contact_sheet::{ContactSheetFormat, ContactSheetOptions};
+use crate::converter::{
+    self, ChromaSubsampling, CollisionStrategy, ConflictChoice, ConversionOptions, ConversionReport, LargerOutputMode, OutputFormat, OutputStructure, OverwriteMode,
+    CollisionOutcome, PlannedAction, RenameSuffix,
+};
+use crate::dir_scanner::ScanProgress;
+use crate::duplicates::{self, DuplicateOptions};
+use crate::filters::{DimensionFilter, FileFilter, Orientation, PathFilter, TraversalOptions};
+use crate::i18n::Language;
+use crate::history::{HistoryAction, HistoryEntry};
+use crate::job_journal::SavedJob;
+use crate::job_manager::{JobAction, JobState, ManagedJob};
+use crate::job_queue::{JobQueue, QueueStatus};
+use crate::logging::{self, LogBuffer};
+use crate::platform_utils::{self, ContextMenuAction}; // Importe le module platform_utils
+use crate::originals::{OriginalOutcome, OriginalsMode};
+use crate::pdf::{self, PdfOptions};
+use crate::pdf_bundle::PdfBundleOptions;
+use crate::presets::{self, Preset, PresetAction};
+use crate::preview::{ComparisonState, PreviewState};
+use crate::responsive::{self, ResponsiveOptions};
+use crate::run_folder::RunFolderOptions;
+use crate::sprite_sheet::SpriteSheetOptions;
+use crate::targets::{OutputTarget, OutputTargets};
+use crate::throughput::Throughput;
+use crate::tr;
+use crate::tray;
+use crate::undo::UndoPlan;
+use crate::verify::VerifyOptions;
+use crate::transform::{Adjustments, CropAnchor, CropOptions, PadOptions, ResizeFilter, ResizeMode, ResizeOptions, Rotation, TrimOptions};
+use crate::watch_folder::FolderWatcher;
 
-/// Applique un style personnalisé à l'interface utilisateur.
-pub fn set_custom_style(ctx: &egui::Context) {
-    let mut style = (*ctx.style()).clone();
+/// Applique le style personnalisé de l'application, avec une palette claire et une palette sombre complètes,
+/// et choisit celle du thème demandé (en mode Système, egui suit le thème clair ou sombre du système).
+pub fn set_custom_style(ctx: &egui::Context, theme: AppTheme) {
+    ctx.set_style_of(egui::Theme::Light, custom_style(ctx.style_of(egui::Theme::Light).as_ref().clone(), false));
+    ctx.set_style_of(egui::Theme::Dark, custom_style(ctx.style_of(egui::Theme::Dark).as_ref().clone(), true));
+    ctx.set_theme(match theme {
+        AppTheme::System => egui::ThemePreference::System,
+        AppTheme::Light => egui::ThemePreference::Light,
+        AppTheme::Dark => egui::ThemePreference::Dark,
+    });
+}
+
+/// Personnalise le style `style` avec la palette claire ou sombre (`dark`).
+fn custom_style(mut style: egui::Style, dark: bool) -> egui::Style {
     style.spacing.item_spacing = egui::vec2(10.0, 10.0);
     style.spacing.button_padding = egui::vec2(15.0, 8.0); // Plus de padding pour les boutons
 
-    // Couleurs de fond des boutons (bleu)
-    style.visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(50, 150, 250); // Bleu vif
-    style.visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(70, 170, 255); // Bleu plus clair au survol
-    style.visuals.widgets.active.bg_fill = egui::Color32::from_rgb(30, 130, 230);  // Bleu plus foncé à l'activation
+    if dark {
+        // Couleurs de fond des boutons (bleu, un peu moins lumineux que sur fond clair)
+        style.visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(40, 120, 220);
+        style.visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(60, 140, 235);
+        style.visuals.widgets.active.bg_fill = egui::Color32::from_rgb(25, 100, 200);
+        style.visuals.widgets.inactive.weak_bg_fill = egui::Color32::from_rgb(40, 120, 220);
+        style.visuals.widgets.hovered.weak_bg_fill = egui::Color32::from_rgb(60, 140, 235);
+        style.visuals.widgets.active.weak_bg_fill = egui::Color32::from_rgb(25, 100, 200);
+
+        // Texte des libellés et champs (gris très clair) et des boutons (blanc)
+        style.visuals.widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(220, 220, 220));
+        style.visuals.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, egui::Color32::WHITE);
+        style.visuals.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, egui::Color32::WHITE);
+        style.visuals.widgets.active.fg_stroke = egui::Stroke::new(1.0, egui::Color32::WHITE);
 
-    // Couleur du texte des boutons (blanc)
-    style.visuals.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 255, 255));
-    style.visuals.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 255, 255));
-    style.visuals.widgets.active.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 255, 255));
+        // Fonds de l'application, des fenêtres et des champs de saisie
+        style.visuals.panel_fill = egui::Color32::from_rgb(28, 29, 32);
+        style.visuals.window_fill = egui::Color32::from_rgb(36, 37, 41);
+        style.visuals.extreme_bg_color = egui::Color32::from_rgb(18, 18, 20);
+        style.visuals.faint_bg_color = egui::Color32::from_rgb(42, 43, 47);
+        style.visuals.widgets.noninteractive.bg_fill = egui::Color32::from_rgb(36, 37, 41);
 
-    // Couleur de fond générale de l'application
-    style.visuals.window_fill = egui::Color32::from_rgb(245, 245, 245); // Un gris très clair pour l'arrière-plan
+        // Bordures des fenêtres et des cadres
+        style.visuals.window_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(70, 70, 75));
+        style.visuals.widgets.noninteractive.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 60, 65));
+        style.visuals.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(80, 80, 85));
+        style.visuals.widgets.hovered.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(130, 130, 140));
+        // Anneau de focus bien visible sur fond sombre (navigation au clavier).
+        style.visuals.widgets.active.bg_stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(150, 200, 255));
+        style.visuals.selection.bg_fill = egui::Color32::from_rgb(30, 90, 170);
+        style.visuals.hyperlink_color = egui::Color32::from_rgb(120, 180, 255);
+    } else {
+        // Couleurs de fond des boutons (bleu)
+        style.visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(50, 150, 250); // Bleu vif
+        style.visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(70, 170, 255); // Bleu plus clair au survol
+        style.visuals.widgets.active.bg_fill = egui::Color32::from_rgb(30, 130, 230);  // Bleu plus foncé à l'activation
+
+        // Couleur du texte des boutons (blanc)
+        style.visuals.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 255, 255));
+        style.visuals.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 255, 255));
+        style.visuals.widgets.active.fg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 255, 255));
 
-    // Bordures des fenêtres et des cadres
-    style.visuals.window_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(180, 180, 180));
-    style.visuals.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(200, 200, 200)); // Bordure des éléments inactifs
-    style.visuals.widgets.hovered.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(150, 150, 150)); // Bordure des éléments survolés
+        // Couleur de fond générale de l'application
+        style.visuals.window_fill = egui::Color32::from_rgb(245, 245, 245); // Un gris très clair pour l'arrière-plan
+
+        // Bordures des fenêtres et des cadres
+        style.visuals.window_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(180, 180, 180));
+        style.visuals.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(200, 200, 200)); // Bordure des éléments inactifs
+        style.visuals.widgets.hovered.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(150, 150, 150)); // Bordure des éléments survolés
+        // Anneau de focus bien visible pour la navigation au clavier (Tab / Maj+Tab) : egui dessine
+        // l'élément qui a le focus avec le style `active`.
+        style.visuals.widgets.active.bg_stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(20, 60, 140));
+    }
 
     // Coins arrondis (utilisation de CornerRadius et f32/u8 selon le champ)
     style.visuals.window_corner_radius = egui::CornerRadius::same(8); // u8
@@ -44,15 +127,14 @@ pub fn set_custom_style(ctx: &egui::Context) {
     style.visuals.widgets.active.corner_radius = egui::CornerRadius::same(6); // u8
     style.visuals.widgets.open.corner_radius = egui::CornerRadius::same(6); // u8
 
-
-    ctx.set_style(style);
+    style
 }
 
 /// Rend le titre de l'application.
 pub fn render_title(ui: &mut egui::Ui) {
     ui.style_mut().text_styles.get_mut(&egui::TextStyle::Heading).unwrap().size = 28.0; // Titre plus grand
-    ui.heading(egui::RichText::new("Convertisseur WebP").strong()) // Titre en gras
-        .on_hover_text("Convertissez vos images en WebP rapidement !");
+    ui.heading(egui::RichText::new(tr!("Convertisseur d'Images")).strong()) // Titre en gras
+        .on_hover_text(tr!("Convertissez vos images en WebP, PNG, JPEG, AVIF ou TIFF rapidement !"));
 }
 
 /// Rend la zone de Drag & Drop pour la sélection des fichiers.
@@ -63,16 +145,18 @@ pub fn render_drag_drop_area(ui: &mut egui::Ui, _input: &mut Option<InputType>,
     let (response, painter) = ui.allocate_painter(rect.size(), egui::Sense::hover()); // Sense::hover() car le clic est géré par les boutons
     let is_hovering_files = ui.input(|i| !i.raw.hovered_files.is_empty());
 
-    let fill_color = if is_file_hovered || is_hovering_files {
-        egui::Color32::from_rgb(200, 230, 255) // Couleur de survol pour feedback
-    } else {
-        egui::Color32::from_rgb(230, 230, 230) // Couleur de fond normale
+    let dark = ui.visuals().dark_mode;
+    let fill_color = match (is_file_hovered || is_hovering_files, dark) {
+        (true, false) => egui::Color32::from_rgb(200, 230, 255), // Couleur de survol pour feedback
+        (true, true) => egui::Color32::from_rgb(30, 60, 100),
+        (false, false) => egui::Color32::from_rgb(230, 230, 230), // Couleur de fond normale
+        (false, true) => egui::Color32::from_rgb(45, 46, 50),
     };
 
-    let stroke_color = if is_file_hovered || is_hovering_files {
-        egui::Color32::from_rgb(50, 150, 250)
-    } else {
-        egui::Color32::from_rgb(180, 180, 180)
+    let stroke_color = match (is_file_hovered || is_hovering_files, dark) {
+        (true, _) => egui::Color32::from_rgb(50, 150, 250),
+        (false, false) => egui::Color32::from_rgb(180, 180, 180),
+        (false, true) => egui::Color32::from_rgb(90, 90, 95),
     };
 
     painter.rect(
@@ -84,12 +168,16 @@ pub fn render_drag_drop_area(ui: &mut egui::Ui, _input: &mut Option<InputType>,
     );
 
     let text = if is_hovering_files {
-        "Relâchez les fichiers ici !"
+        tr!("Relâchez les fichiers ici !")
     } else {
-        "Déposez vos fichiers ou dossiers ici (PNG, JPG, BMP)"
+        tr!("Déposez vos fichiers ou dossiers ici (PNG, JPG, BMP, TIFF, GIF, WebP, RAW, PDF)")
     };
 
-    let text_color = egui::Color32::from_rgb(80, 80, 80); // Texte gris foncé
+    let text_color = if dark {
+        egui::Color32::from_rgb(200, 200, 200) // Texte gris clair sur fond sombre
+    } else {
+        egui::Color32::from_rgb(80, 80, 80) // Texte gris foncé
+    };
 
     let font_size = if is_hovering_files { 20.0 } else { 16.0 };
     painter.text(
@@ -100,35 +188,89 @@ pub fn render_drag_drop_area(ui: &mut egui::Ui, _input: &mut Option<InputType>,
         text_color,
     );
 
+    // La zone étant dessinée à la main, son nom accessible est fourni explicitement aux lecteurs d'écran.
+    response.widget_info(|| {
+        egui::WidgetInfo::labeled(
+            egui::WidgetType::Label,
+            true,
+            tr!("Zone de dépôt : déposez vos fichiers ou dossiers ici (PNG, JPG, BMP, TIFF, GIF, WebP, RAW, PDF)"),
+        )
+    });
+
     // Correction: Chaîner on_hover_text directement à la valeur de retour
-    response.on_hover_text("Déposez des images/dossiers ici")
+    response.on_hover_text(tr!("Déposez des images/dossiers ici"))
+}
+
+/// Raccourci clavier de sélection d'un fichier unique.
+pub const OPEN_FILE_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::O);
+
+/// Raccourci clavier de sélection d'un répertoire.
+pub const OPEN_FOLDER_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT), egui::Key::O);
+
+/// Raccourci clavier de lancement de la conversion.
+pub const CONVERT_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Enter);
+
+/// Raccourci clavier d'annulation de la conversion en cours.
+pub const CANCEL_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Escape);
+
+/// Raccourci clavier d'ouverture (ou de fermeture) de la fenêtre des paramètres.
+pub const SETTINGS_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Comma);
+
+/// Raccourci clavier de collage d'une image du presse-papiers.
+pub const PASTE_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::V);
+
+/// Infobulle `text` suivie du raccourci clavier correspondant, dans la notation de la plateforme (ex: "Ctrl+O", "⌘O").
+pub fn with_shortcut(ctx: &egui::Context, text: &str, shortcut: &egui::KeyboardShortcut) -> String {
+    format!("{} ({})", text, ctx.format_shortcut(shortcut))
+}
+
+/// Boîte de dialogue ouverte dans `start_dir` s'il existe encore, à défaut dans le dossier proposé par le système.
+fn file_dialog(start_dir: Option<&Path>) -> FileDialog {
+    match start_dir.filter(|dir| dir.is_dir()) {
+        Some(dir) => FileDialog::new().set_directory(dir),
+        None => FileDialog::new(),
+    }
+}
+
+/// Ouvre la boîte de dialogue de sélection d'une image, dans `start_dir` (dossier de la dernière sélection).
+pub fn pick_image_file(start_dir: Option<&Path>) -> Option<PathBuf> {
+    file_dialog(start_dir).add_filter("Images", &converter::SUPPORTED_EXTENSIONS).pick_file()
 }
 
-/// Rend les boutons de sélection de fichiers/dossiers.
-pub fn render_file_selection_buttons(ui: &mut egui::Ui, input: &mut Option<InputType>) {
+/// Ouvre la boîte de dialogue de sélection d'un répertoire d'images, dans `start_dir` (dossier de la dernière sélection).
+pub fn pick_input_folder(start_dir: Option<&Path>) -> Option<PathBuf> {
+    file_dialog(start_dir).pick_folder()
+}
+
+/// Rend les boutons de sélection de fichiers/dossiers. Les boîtes de dialogue s'ouvrent dans `last_input_dir`,
+/// le dossier de la dernière sélection. Retourne `true` si le bouton « Coller » a été cliqué.
+pub fn render_file_selection_buttons(ui: &mut egui::Ui, input: &mut Option<InputType>, last_input_dir: Option<&Path>) -> bool {
+    let mut paste = false;
     ui.horizontal(|ui| {
         // Bouton pour sélectionner un fichier unique.
         if ui
-            .button("📄 Fichier Unique")
-            .on_hover_text("Sélectionner une seule image (PNG, JPG, JPEG, BMP)")
+            .button(tr!("📄 Fichier Unique"))
+            .on_hover_text(with_shortcut(
+                ui.ctx(),
+                tr!("Sélectionner une seule image (PNG, JPG, JPEG, BMP, TIFF, GIF, WebP, RAW, PDF)"),
+                &OPEN_FILE_SHORTCUT,
+            ))
             .clicked()
         {
-            if let Some(path) = FileDialog::new()
-                .add_filter("Images", &["png", "jpg", "jpeg", "bmp"])
-                .pick_file()
-            {
+            if let Some(path) = pick_image_file(last_input_dir) {
                 *input = Some(InputType::SingleFile(path));
             }
         }
 
         // Bouton pour sélectionner plusieurs fichiers.
         if ui
-            .button("📂 Plusieurs Fichiers")
-            .on_hover_text("Sélectionner plusieurs images (PNG, JPG, JPEG, BMP)")
+            .button(tr!("📂 Plusieurs Fichiers"))
+            .on_hover_text(tr!("Sélectionner plusieurs images (PNG, JPG, JPEG, BMP, TIFF, GIF, WebP, RAW, PDF)"))
             .clicked()
         {
-            if let Some(paths) = FileDialog::new()
-                .add_filter("Images", &["png", "jpg", "jpeg", "bmp"])
+            if let Some(paths) = file_dialog(last_input_dir)
+                .add_filter("Images", &converter::SUPPORTED_EXTENSIONS)
                 .pick_files()
             {
                 *input = Some(InputType::MultipleFiles(paths));
@@ -137,29 +279,76 @@ pub fn render_file_selection_buttons(ui: &mut egui::Ui, input: &mut Option<Input
 
         // Bouton pour sélectionner un répertoire.
         if ui
-            .button("📁 Répertoire")
-            .on_hover_text("Sélectionner un dossier contenant des images")
+            .button(tr!("📁 Répertoire"))
+            .on_hover_text(with_shortcut(ui.ctx(), tr!("Sélectionner un dossier contenant des images"), &OPEN_FOLDER_SHORTCUT))
             .clicked()
         {
-            if let Some(path) = FileDialog::new().pick_folder() {
+            if let Some(path) = pick_input_folder(last_input_dir) {
                 *input = Some(InputType::Directory(path));
             }
         }
+
+        // Bouton pour coller une image du presse-papiers.
+        paste = ui
+            .button(tr!("📋 Coller"))
+            .on_hover_text(with_shortcut(
+                ui.ctx(),
+                tr!("Convertir l'image du presse-papiers (capture d'écran...)"),
+                &PASTE_SHORTCUT,
+            ))
+            .clicked();
     });
+    paste
 }
 
 
-/// Rend l'affichage du chemin d'entrée sélectionné.
-pub fn render_selected_input_display(ui: &mut egui::Ui, input: &Option<InputType>) {
+/// Rend l'affichage du chemin d'entrée sélectionné, suivi de l'estimation de taille de sortie si disponible
+/// (ou d'un indicateur pendant son calcul).
+pub fn render_selected_input_display(
+    ui: &mut egui::Ui,
+    input: &Option<InputType>,
+    size_estimate: Option<u64>,
+    estimating: bool,
+    dir_scan: Option<ScanProgress>,
+) {
     ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Entrée sélectionnée:").strong()); // Utilisation de RichText
+        ui.label(egui::RichText::new(tr!("Entrée sélectionnée:")).strong()); // Utilisation de RichText
         if let Some(selected_input) = input {
             let display_text = match selected_input {
-                InputType::SingleFile(path) => format!("Fichier: {}", path.file_name().unwrap_or_default().to_string_lossy()),
-                InputType::Directory(path) => format!("Répertoire: {}", path.file_name().unwrap_or_default().to_string_lossy()),
-                InputType::MultipleFiles(paths) => format!("{} fichiers", paths.len()),
+                InputType::SingleFile(path) => tr!("Fichier: {}", path.file_name().unwrap_or_default().to_string_lossy()),
+                InputType::Directory(path) => tr!("Répertoire: {}", path.file_name().unwrap_or_default().to_string_lossy()),
+                InputType::MultipleFiles(_) => tr!("Liste de fichiers (voir la file ci-dessous)").to_string(),
+                InputType::Clipboard(pasted) => {
+                    tr!("Image collée ({} × {} px)", pasted.image.width(), pasted.image.height())
+                }
             };
-            ui.label(display_text).on_hover_text(format!("Chemin complet: {}", selected_input.get_display_path()));
+            ui.label(display_text).on_hover_text(tr!("Chemin complet: {}", selected_input.get_display_path()));
+
+            if let Some(estimate) = size_estimate {
+                ui.label(egui::RichText::new(format!("≈ {}", format_size(estimate))).weak())
+                    .on_hover_text(tr!("Taille estimée du fichier produit"));
+            } else if estimating {
+                // Pendant le réglage (curseur de qualité...), l'estimation suit après un court délai.
+                ui.spinner().on_hover_text(tr!("Estimation de la taille du fichier produit…"));
+            }
+
+            // Décompte des images du répertoire, mis à jour au fil de l'analyse
+            if let Some(scan) = dir_scan {
+                if scan.done {
+                    ui.label(egui::RichText::new(tr!(
+                        "{} image(s), {}",
+                        format_count(scan.files),
+                        format_size(scan.bytes)
+                    )).weak());
+                } else {
+                    ui.spinner();
+                    ui.label(egui::RichText::new(tr!(
+                        "analyse en cours… {} fichiers trouvés",
+                        format_count(scan.files)
+                    )).weak())
+                        .on_hover_text(tr!("{} trouvés jusqu'ici", format_size(scan.bytes)));
+                }
+            }
 
             // Bouton pour effacer la sélection (si implémenté, sinon masqué ou désactivé)
             // ui.add_enabled_ui(input.is_some(), |ui| {
@@ -168,11 +357,236 @@ pub fn render_selected_input_display(ui: &mut egui::Ui, input: &Option<InputType
             //     // }
             // });
         } else {
-            ui.label(egui::RichText::new("Aucune").weak()); // Utilisation de RichText
+            ui.label(egui::RichText::new(tr!("Aucune")).weak()); // Utilisation de RichText
+        }
+    });
+}
+
+/// Côté maximal (en points) de l'aperçu de l'image sélectionnée.
+const PREVIEW_DISPLAY_SIZE: f32 = 160.0;
+
+/// Côté maximal (en points) de la comparaison avant/après.
+const COMPARISON_DISPLAY_SIZE: f32 = 320.0;
+
+/// Rend l'aperçu de l'image sélectionnée : sa miniature, réduite pour tenir dans `PREVIEW_DISPLAY_SIZE`,
+/// ou la comparaison avant/après si elle est demandée (`compare`) et disponible.
+/// `split` est la position de la séparation (0.0 à 1.0), `format_label` le format de sortie affiché.
+pub fn render_image_preview(
+    ui: &mut egui::Ui,
+    state: &PreviewState,
+    comparison: Option<&ComparisonState>,
+    compare: &mut bool,
+    split: &mut f32,
+    format_label: &str,
+) {
+    match (state, comparison) {
+        (PreviewState::Loading, _) => {
+            ui.add_sized([PREVIEW_DISPLAY_SIZE, PREVIEW_DISPLAY_SIZE / 2.0], egui::Spinner::new());
+        }
+        (PreviewState::Ready(original), Some(comparison)) => {
+            render_comparison_view(ui, original, comparison, split, format_label);
+        }
+        (PreviewState::Ready(texture), None) => {
+            let size = texture.size_vec2();
+            let scale = (PREVIEW_DISPLAY_SIZE / size.x.max(size.y)).min(1.0);
+            ui.add(egui::Image::new((texture.id(), size * scale)))
+                .on_hover_text(tr!("Aperçu ({} × {} px)", size.x as u32, size.y as u32));
+        }
+        (PreviewState::Failed(message), _) => {
+            ui.label(egui::RichText::new(tr!("Aperçu indisponible")).weak()).on_hover_text(message);
+        }
+    }
+    if matches!(state, PreviewState::Ready(_)) {
+        ui.checkbox(compare, tr!("Comparer avant/après"))
+            .on_hover_text(tr!("Comparer l'original à l'image ré-encodée avec la qualité et le format choisis"));
+    }
+}
+
+/// Rend la comparaison avant/après : l'original à gauche de la séparation, la version ré-encodée à droite.
+/// La séparation se déplace en faisant glisser (ou en cliquant) sur l'image.
+fn render_comparison_view(
+    ui: &mut egui::Ui,
+    original: &egui::TextureHandle,
+    comparison: &ComparisonState,
+    split: &mut f32,
+    format_label: &str,
+) {
+    let encoded = match comparison {
+        ComparisonState::Ready(encoded, encoded_size) => {
+            ui.label(egui::RichText::new(tr!("Original  |  {} ({} pour l'aperçu)", format_label, format_size(*encoded_size))).weak());
+            encoded
+        }
+        ComparisonState::Encoding => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(egui::RichText::new(tr!("Encodage de l'aperçu…")).weak());
+            });
+            original
+        }
+        ComparisonState::Failed(message) => {
+            ui.label(egui::RichText::new(tr!("Comparaison indisponible")).weak()).on_hover_text(message);
+            original
+        }
+    };
+
+    let size = original.size_vec2();
+    let scale = (COMPARISON_DISPLAY_SIZE / size.x.max(size.y)).min(1.0);
+    let (rect, response) = ui.allocate_exact_size(size * scale, egui::Sense::click_and_drag());
+    if let Some(pointer) = response.interact_pointer_pos() {
+        *split = ((pointer.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+    }
+
+    let divider_x = rect.left() + rect.width() * *split;
+    let painter = ui.painter_at(rect);
+    let left = egui::Rect::from_min_max(rect.min, egui::pos2(divider_x, rect.bottom()));
+    let right = egui::Rect::from_min_max(egui::pos2(divider_x, rect.top()), rect.max);
+    painter.image(
+        original.id(),
+        left,
+        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(*split, 1.0)),
+        egui::Color32::WHITE,
+    );
+    painter.image(
+        encoded.id(),
+        right,
+        egui::Rect::from_min_max(egui::pos2(*split, 0.0), egui::pos2(1.0, 1.0)),
+        egui::Color32::WHITE,
+    );
+    painter.vline(divider_x, rect.y_range(), egui::Stroke::new(2.0, egui::Color32::WHITE));
+
+    response
+        .on_hover_cursor(egui::CursorIcon::ResizeHorizontal)
+        .on_hover_text(tr!("Faire glisser pour déplacer la séparation : original à gauche, ré-encodé à droite"));
+}
+
+/// Rend la file d'attente : pour chaque fichier, son nom, sa taille, son état et la taille produite.
+pub fn render_job_queue(ui: &mut egui::Ui, queue: &JobQueue) {
+    let (pending, done, skipped, failed) = queue.counts();
+    ui.label(egui::RichText::new(tr!(
+        "File : {} fichier(s) — {} en attente, {} converti(s), {} ignoré(s), {} en échec",
+        format_count(queue.entries().len()),
+        format_count(pending),
+        format_count(done),
+        format_count(skipped),
+        format_count(failed)
+    )).strong());
+
+    egui::ScrollArea::vertical()
+        .id_salt("job_queue_scroll")
+        .max_height(150.0)
+        .show_rows(ui, ui.text_style_height(&egui::TextStyle::Body), queue.entries().len(), |ui, rows| {
+            egui::Grid::new("job_queue_grid").num_columns(4).striped(true).show(ui, |ui| {
+                for entry in &queue.entries()[rows] {
+                    ui.label(entry.source.file_name().unwrap_or_default().to_string_lossy())
+                        .on_hover_text(entry.source.display().to_string());
+                    ui.label(entry.size.map(format_size).unwrap_or_default());
+                    let status = match &entry.status {
+                        QueueStatus::Running => ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(entry.status.label());
+                        }).response,
+                        QueueStatus::Done => ui.colored_label(egui::Color32::from_rgb(40, 140, 40), entry.status.label()),
+                        QueueStatus::Failed(error) => ui.colored_label(egui::Color32::from_rgb(180, 40, 40), entry.status.label())
+                            .on_hover_text(error),
+                        _ => ui.weak(entry.status.label()),
+                    };
+                    status.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Label, true, entry.status.label()));
+                    ui.label(entry.output_size.map(|size| format!("→ {}", format_size(size))).unwrap_or_default());
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+/// Rend le panneau repliable « Journal » : évènements récents de la conversion (fichiers convertis, ignorés ou en échec,
+/// avertissements), avec un filtre sur les avertissements, la copie du journal pour un rapport de bogue
+/// et l'ouverture du dossier des fichiers du journal.
+pub fn render_log_panel(ui: &mut egui::Ui, logs: &LogBuffer, warnings_only: &mut bool) {
+    egui::CollapsingHeader::new(tr!("Journal")).id_salt("log_panel").show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.checkbox(warnings_only, tr!("Avertissements seulement"));
+            if ui.button(tr!("📋 Copier le journal"))
+                .on_hover_text(tr!("Copier les évènements récents dans le presse-papiers, pour les joindre à un rapport de bogue"))
+                .clicked()
+            {
+                ui.ctx().copy_text(logs.text());
+            }
+            if let Some(dir) = logging::log_dir().filter(|dir| dir.is_dir()) {
+                if ui.button(tr!("📂 Dossier des journaux"))
+                    .on_hover_text(dir.display().to_string())
+                    .clicked()
+                {
+                    if let Err(e) = platform_utils::open_output_directory(&dir) {
+                        tracing::warn!("{}", e);
+                    }
+                }
+            }
+        });
+
+        let entries = logs.entries(*warnings_only);
+        if entries.is_empty() {
+            ui.weak(tr!("Aucun évènement pour l'instant."));
+            return;
         }
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::vertical()
+            .id_salt("log_panel_scroll")
+            .max_height(200.0)
+            .stick_to_bottom(true)
+            .show_rows(ui, row_height, entries.len(), |ui, rows| {
+                for entry in &entries[rows] {
+                    let text = egui::RichText::new(entry.to_line()).monospace();
+                    match entry.level {
+                        tracing::Level::ERROR => ui.colored_label(egui::Color32::from_rgb(180, 40, 40), text),
+                        tracing::Level::WARN => ui.colored_label(egui::Color32::from_rgb(200, 120, 0), text),
+                        _ => ui.label(text),
+                    };
+                }
+            });
     });
 }
 
+/// Formate une taille en octets de manière lisible (ex: "240 KB", "1.3 MB").
+pub fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+
+    let bytes_f = bytes as f64;
+    if bytes_f >= GB {
+        format!("{:.1} GB", bytes_f / GB)
+    } else if bytes_f >= MB {
+        format!("{:.1} MB", bytes_f / MB)
+    } else if bytes_f >= KB {
+        format!("{:.0} KB", bytes_f / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Formate une durée de manière lisible, arrondie à la seconde (ex: "45 s", "3 min 05 s", "1 h 12 min").
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64().round() as u64;
+    match seconds {
+        0..=59 => format!("{} s", seconds),
+        60..=3599 => format!("{} min {:02} s", seconds / 60, seconds % 60),
+        _ => format!("{} h {:02} min", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+/// Formate un nombre avec une espace comme séparateur des milliers (ex: "23 400").
+pub fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(' ');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
 // Ajout d'une méthode utilitaire pour l'affichage des chemins
 impl InputType {
     fn get_display_path(&self) -> String {
@@ -185,6 +599,30 @@ impl InputType {
                     .collect::<Vec<String>>()
                     .join(", ")
             }
+            InputType::Clipboard(pasted) => tr!("{} (presse-papiers)", pasted.name),
+        }
+    }
+
+    /// Nom court de la sélection, affiché dans la liste des lots : le nom du fichier ou du répertoire,
+    /// le nombre de fichiers choisis, ou le nom de l'image collée.
+    pub fn label(&self) -> String {
+        match self {
+            InputType::SingleFile(path) | InputType::Directory(path) => {
+                path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().to_string())
+            }
+            InputType::MultipleFiles(paths) => tr!("{} fichier(s)", paths.len()),
+            InputType::Clipboard(pasted) => tr!("{} (presse-papiers)", pasted.name),
+        }
+    }
+
+    /// Dossier de la sélection, point de départ des prochaines boîtes de dialogue : le répertoire choisi,
+    /// ou le dossier parent du (premier) fichier choisi.
+    pub fn browse_dir(&self) -> Option<PathBuf> {
+        match self {
+            InputType::SingleFile(path) => path.parent().map(Path::to_path_buf),
+            InputType::Directory(path) => Some(path.clone()),
+            InputType::MultipleFiles(paths) => paths.first().and_then(|path| path.parent()).map(Path::to_path_buf),
+            InputType::Clipboard(_) => None,
         }
     }
 }
@@ -193,119 +631,1989 @@ impl InputType {
 /// Rend la section du répertoire de sortie.
 pub fn render_output_section(ui: &mut egui::Ui, output_dir: &mut PathBuf) {
     ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Répertoire de sortie:").strong()); // Utilisation de RichText
+        ui.label(egui::RichText::new(tr!("Répertoire de sortie:")).strong()); // Utilisation de RichText
         ui.label(output_dir.display().to_string())
-            .on_hover_text("Dossier où les images WebP seront sauvegardées");
+            .on_hover_text(tr!("Dossier où les images converties seront sauvegardées"));
         if ui
-            .button("📁 Changer") // Icône de dossier
-            .on_hover_text("Modifier le dossier de sortie")
+            .button(tr!("📁 Changer")) // Icône de dossier
+            .on_hover_text(tr!("Modifier le dossier de sortie"))
             .clicked()
         {
-            if let Some(path) = FileDialog::new().pick_folder() {
+            if let Some(path) = file_dialog(Some(output_dir)).pick_folder() {
                 *output_dir = path;
             }
         }
     });
 }
 
-/// Rend la section des options de gestion des fichiers existants.
-pub fn render_overwrite_options(ui: &mut egui::Ui, overwrite_mode: &mut OverwriteMode) {
+/// Rend la liste déroulante des préréglages et le bouton de leur fenêtre de gestion.
+/// `active` est le nom du préréglage correspondant aux réglages actuels.
+/// Retourne l'indice du préréglage choisi dans la liste, et passe `show_editor` à `true` si « Gérer » est cliqué.
+pub fn render_preset_selector(ui: &mut egui::Ui, presets: &[Preset], active: Option<&str>, show_editor: &mut bool) -> Option<usize> {
+    let mut selected = None;
     ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Si le fichier existe:").strong()); // Utilisation de RichText
-        ui.radio_value(overwrite_mode, OverwriteMode::Skip, "Ignorer")
-            .on_hover_text("Ne pas convertir si le fichier WebP existe déjà.");
-        ui.radio_value(overwrite_mode, OverwriteMode::Overwrite, "Écraser")
-            .on_hover_text("Écraser le fichier WebP existant.");
-        ui.radio_value(overwrite_mode, OverwriteMode::Rename, "Renommer")
-            .on_hover_text("Créer un nouveau fichier avec un suffixe (ex: image-1.webp).");
+        ui.label(egui::RichText::new(tr!("Préréglage:")).strong());
+        egui::ComboBox::from_id_salt("preset_combo")
+            .selected_text(active.unwrap_or(tr!("Personnalisé")))
+            .show_ui(ui, |ui| {
+                if presets.is_empty() {
+                    ui.weak(tr!("Aucun préréglage enregistré"));
+                }
+                for (index, preset) in presets.iter().enumerate() {
+                    if ui.selectable_label(active == Some(preset.name.as_str()), &preset.name).clicked() {
+                        selected = Some(index);
+                    }
+                }
+            })
+            .response
+            .on_hover_text(tr!("Appliquer un préréglage : format, qualité, redimensionnement, écrasement et répertoire de sortie."));
+        if ui.button(tr!("⚙ Gérer")).on_hover_text(tr!("Créer, mettre à jour, renommer ou supprimer des préréglages")).clicked() {
+            *show_editor = true;
+        }
     });
+    selected
 }
 
-/// Rend le bouton de conversion.
-pub fn render_convert_button(ui: &mut egui::Ui, enabled: bool) -> egui::Response {
-    ui.style_mut().text_styles.get_mut(&egui::TextStyle::Button).unwrap().size = 18.0; // Bouton plus grand
-    ui.add_enabled(
-        enabled,
-        egui::Button::new("🚀 Convertir les images")
-            .fill(egui::Color32::from_rgb(50, 150, 250)) // Couleur de fond du bouton (bleu vif)
-            .stroke(egui::Stroke::new(0.0, egui::Color32::TRANSPARENT)), // Pas de bordure visible
-    )
-        .on_hover_text("Lancer la conversion des images en WebP")
+/// Onglet de la fenêtre des paramètres.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum SettingsTab {
+    #[default]
+    General,    // Interface et répertoire de sortie
+    Conversion, // Traitement des fichiers existants, des originaux et des métadonnées
+    Advanced,   // Conversions simultanées, planche contact
 }
 
-/// Rend la fenêtre modale pour les messages critiques (erreurs graves ou demande d'ouvrir dossier).
-pub fn render_dialog_window(
-    ctx: &egui::Context,
-    show_dialog: &mut bool,
-    dialog_message: &mut Option<String>,
-    output_dir: &PathBuf,
-) {
-    use std::process::exit; // Déplacé ici
+impl SettingsTab {
+    /// Onglets, dans l'ordre d'affichage.
+    pub const ALL: [SettingsTab; 3] = [SettingsTab::General, SettingsTab::Conversion, SettingsTab::Advanced];
+
+    /// Libellé de l'onglet.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsTab::General => tr!("Général"),
+            SettingsTab::Conversion => tr!("Conversion"),
+            SettingsTab::Advanced => tr!("Avancé"),
+        }
+    }
+}
+
+/// Réglages modifiés dans la fenêtre des paramètres.
+pub struct SettingsValues<'a> {
+    pub theme: &'a mut AppTheme,             // Thème de l'interface
+    pub language: &'a mut Language,          // Langue de l'interface
+    pub output_dir: &'a mut PathBuf,         // Répertoire de sortie
+    pub options: &'a mut ConversionOptions,  // Réglages par défaut de la conversion
+    pub context_menu_silent: &'a mut bool,   // Menu contextuel de l'Explorateur en conversion silencieuse
+    pub close_to_tray: &'a mut bool,         // Fermer la fenêtre la cache dans la zone de notification
+    pub desktop_notifications: &'a mut bool, // Notification du système à la fin d'un lot
+}
+
+/// Hauteur maximale du contenu d'un onglet des paramètres, au-delà de laquelle il défile.
+const SETTINGS_MAX_HEIGHT: f32 = 400.0;
 
-    egui::Window::new("Information")
+/// Rend la fenêtre des paramètres de l'application, en onglets : Général (thème, langue, répertoire de sortie),
+/// Conversion (fichiers existants, originaux, métadonnées) et Avancé (conversions simultanées, planche contact,
+/// menu contextuel de l'Explorateur). Retourne la modification du menu contextuel demandée, s'il y en a une.
+pub fn render_settings_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    tab: &mut SettingsTab,
+    values: SettingsValues,
+) -> Option<ContextMenuAction> {
+    let SettingsValues {
+        theme,
+        language,
+        output_dir,
+        options,
+        context_menu_silent,
+        close_to_tray,
+        desktop_notifications,
+    } = values;
+    let mut action = None;
+    egui::Window::new(tr!("Paramètres"))
+        .id(egui::Id::new("settings_window"))
+        .open(open)
         .collapsible(false)
         .resizable(false)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
-            ui.label(dialog_message.as_ref().unwrap());
-            ui.add_space(15.0);
             ui.horizontal(|ui| {
-                if ui.button("Ouvrir le dossier").clicked() {
-                    let result = platform_utils::open_output_directory(output_dir);
-                    if let Err(e) = result {
-                        *dialog_message = Some(format!("Erreur lors de l'ouverture du dossier : {}", e));
-                        // show_dialog reste true pour afficher le nouveau message d'erreur
-                    } else {
-                        *show_dialog = false; // Ferme la modale si l'ouverture réussit
-                        exit(0); // Quitte l'application
-                    }
+                for candidate in SettingsTab::ALL {
+                    ui.selectable_value(tab, candidate, candidate.label());
                 }
-                if ui.button("Fermer").clicked() {
-                    *show_dialog = false;
-                    *dialog_message = None;
+            });
+            ui.separator();
+            egui::ScrollArea::vertical().max_height(SETTINGS_MAX_HEIGHT).show(ui, |ui| match tab {
+                SettingsTab::General => {
+                    render_general_settings(ui, theme, language, output_dir, options);
+                    render_close_to_tray_option(ui, close_to_tray);
+                    ui.checkbox(desktop_notifications, tr!("Notification à la fin d'un lot"))
+                        .on_hover_text(tr!("Annoncer le bilan par une notification du système quand la fenêtre est en arrière-plan, réduite ou cachée."));
+                }
+                SettingsTab::Conversion => render_conversion_settings(ui, options),
+                SettingsTab::Advanced => {
+                    render_advanced_settings(ui, options);
+                    action = render_context_menu_options(ui, context_menu_silent, output_dir);
                 }
             });
         });
+    action
 }
 
-/// Rend un "toast" de notification temporaire.
-pub fn render_toast(ctx: &egui::Context, show_toast: &mut bool, message: &str, is_error: bool) {
-    let toast_color = if is_error {
-        egui::Color32::from_rgb(255, 100, 100) // Rouge pour les erreurs
-    } else {
-        egui::Color32::from_rgb(100, 200, 100) // Vert pour les succès
-    };
+/// Rend l'onglet Général des paramètres : thème, langue, répertoire de sortie et sous-dossier par exécution.
+fn render_general_settings(
+    ui: &mut egui::Ui,
+    theme: &mut AppTheme,
+    language: &mut Language,
+    output_dir: &mut PathBuf,
+    options: &mut ConversionOptions,
+) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Thème:")).strong());
+        radio_group(ui, "theme_group", tr!("Thème"), |ui| {
+            ui.radio_value(theme, AppTheme::System, AppTheme::System.label())
+                .on_hover_text(tr!("Suivre le thème clair ou sombre du système d'exploitation."));
+            ui.radio_value(theme, AppTheme::Light, AppTheme::Light.label());
+            ui.radio_value(theme, AppTheme::Dark, AppTheme::Dark.label());
+        });
+    });
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Langue:")).strong());
+        egui::ComboBox::from_id_salt("language_combo")
+            .selected_text(language.label())
+            .show_ui(ui, |ui| {
+                for candidate in Language::ALL {
+                    ui.selectable_value(language, candidate, candidate.label());
+                }
+            })
+            .response
+            .on_hover_text(tr!("Langue de l'interface graphique (la ligne de commande reste en français)."));
+    });
+    ui.add_space(10.0);
+    render_output_section(ui, output_dir);
+    render_run_folder_option(ui, &mut options.run_folder);
+}
 
-    let text_color = egui::Color32::WHITE;
+/// Rend l'onglet Conversion des paramètres : fichiers existants, sorties plus lourdes, originaux, mode incrémental,
+/// métadonnées, orientation et profil couleur.
+fn render_conversion_settings(ui: &mut egui::Ui, options: &mut ConversionOptions) {
+    render_overwrite_options(ui, &mut options.overwrite_mode, &mut options.rename_suffix);
+    render_larger_output_options(ui, &mut options.larger_output);
+    render_originals_options(ui, &mut options.originals);
+    render_incremental_option(ui, &mut options.incremental, options.archive.enabled);
+    render_strip_metadata_option(ui, &mut options.strip_metadata);
+    render_auto_orient_option(ui, &mut options.auto_orient);
+    render_color_profile_options(ui, &mut options.color_profile);
+}
 
-    egui::Window::new("")
-        .id(egui::Id::new("toast_window"))
+/// Rend l'onglet Avancé des paramètres : conversions simultanées, priorité basse, vérification des sorties,
+/// planche contact, document PDF, planche de sprites et archive ZIP.
+fn render_advanced_settings(ui: &mut egui::Ui, options: &mut ConversionOptions) {
+    render_worker_option(ui, &mut options.workers, &mut options.low_priority);
+    render_verify_options(ui, &mut options.verify);
+    render_contact_sheet_options(ui, &mut options.contact_sheet);
+    render_pdf_bundle_options(ui, &mut options.pdf_bundle);
+    render_sprite_sheet_options(ui, &mut options.sprite_sheet);
+    render_archive_options(ui, &mut options.archive);
+}
+
+/// Rend la fenêtre de gestion des préréglages. `new_name` est le nom saisi pour un nouveau
+/// préréglage ou un renommage. Retourne la modification demandée par l'utilisateur, s'il y en a une.
+pub fn render_presets_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    presets: &[Preset],
+    active: Option<&str>,
+    new_name: &mut String,
+) -> Option<PresetAction> {
+    let mut action = None;
+    let name_is_valid = presets::is_valid_name(presets, new_name);
+
+    egui::Window::new(tr!("Préréglages"))
+        .open(open)
         .collapsible(false)
         .resizable(false)
-        .title_bar(false)
-        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-20.0, -20.0)) // Coin inférieur droit
-        .frame(egui::Frame::window(&ctx.style()).fill(toast_color).stroke(egui::Stroke::new(0.0, egui::Color32::TRANSPARENT)).corner_radius(8.0)) // Correction de Stroke::none() et rounding()
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
-            ui.add_space(5.0);
+            if presets.is_empty() {
+                ui.weak(tr!("Aucun préréglage enregistré."));
+            }
+            egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                for (index, preset) in presets.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if active == Some(preset.name.as_str()) {
+                            ui.label(egui::RichText::new(&preset.name).strong());
+                        } else {
+                            ui.label(&preset.name);
+                        }
+                        if ui.small_button(tr!("Appliquer")).clicked() {
+                            action = Some(PresetAction::Apply(index));
+                        }
+                        if ui.small_button(tr!("Mettre à jour"))
+                            .on_hover_text(tr!("Remplacer ce préréglage par les réglages actuels"))
+                            .clicked()
+                        {
+                            action = Some(PresetAction::Update(index));
+                        }
+                        if ui.add_enabled(name_is_valid, egui::Button::new(tr!("Renommer")).small())
+                            .on_hover_text(tr!("Donner à ce préréglage le nom saisi ci-dessous"))
+                            .clicked()
+                        {
+                            action = Some(PresetAction::Rename(index, new_name.trim().to_string()));
+                        }
+                        if ui.small_button("🗑").on_hover_text(tr!("Supprimer ce préréglage")).clicked() {
+                            action = Some(PresetAction::Delete(index));
+                        }
+                    });
+                }
+            });
+            ui.separator();
             ui.horizontal(|ui| {
-                ui.add_space(10.0);
-                ui.label(egui::RichText::new(message).color(text_color).strong());
-                ui.add_space(10.0);
+                ui.add(egui::TextEdit::singleline(new_name).hint_text(tr!("Nom du préréglage")).desired_width(180.0));
+                if ui.add_enabled(name_is_valid, egui::Button::new(tr!("💾 Enregistrer les réglages actuels")))
+                    .on_hover_text(tr!("Créer un préréglage avec les options et le répertoire de sortie actuels"))
+                    .clicked()
+                {
+                    action = Some(PresetAction::SaveCurrent(new_name.trim().to_string()));
+                }
             });
-            ui.add_space(5.0);
         });
 
-    // Optionnel: faire disparaître le toast après quelques secondes
-    if *show_toast {
-        let current_time = ctx.input(|i| i.time);
-        let start_time_id = egui::Id::new("toast_start_time");
-        let start_time: f64 = ctx.data_mut(|data| *data.get_temp_mut_or_insert_with(start_time_id, || current_time));
+    action
+}
 
-        if current_time - start_time > 3.0 { // Toast disparaît après 3 secondes
-            *show_toast = false;
-            ctx.data_mut(|data| data.remove::<f64>(start_time_id)); // Supprime le temps de début
-        } else {
-            ctx.request_repaint_after(Duration::from_millis(50)); // Redessine pour le timer
+/// Rend le champ du modèle de nom des fichiers produits, suivi de l'aperçu du nom obtenu (`preview`).
+pub fn render_name_template_option(ui: &mut egui::Ui, template: &mut String, preview: &str) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Nom des fichiers:")).strong());
+        ui.add(egui::TextEdit::singleline(template).hint_text("{stem}").desired_width(200.0))
+            .on_hover_text(tr!("Modèle du nom : {stem} (nom de la source), {width} et {height} (dimensions produites), {quality} (qualité), {format} (extension). Ex: {stem}_{width}x{height}_q{quality}"));
+        ui.label(egui::RichText::new(format!("→ {}", preview)).weak())
+            .on_hover_text(tr!("Aperçu du nom produit pour la première image sélectionnée"));
+    });
+}
+
+/// Rend l'option du sous-dossier par exécution et son modèle de nom.
+pub fn render_run_folder_option(ui: &mut egui::Ui, run_folder: &mut RunFolderOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut run_folder.enabled, tr!("Créer un sous-dossier par exécution"))
+            .on_hover_text(tr!("Placer les sorties de chaque lot dans un sous-dossier daté du répertoire de sortie (ex: 2024-06-03_14-32-05). Chaque dossier neuf a son propre manifeste, vide : le mode incrémental y reconvertit toutes les sources."));
+        ui.add_enabled_ui(run_folder.enabled, |ui| {
+            ui.add(egui::TextEdit::singleline(&mut run_folder.template).desired_width(150.0))
+                .on_hover_text(tr!("Modèle du nom : {date} (date et heure de lancement), {preset} (préréglage appliqué, à défaut format de sortie)."));
+        });
+    });
+}
+
+/// Rend la section du format de sortie et de ses options spécifiques.
+pub fn render_format_options(ui: &mut egui::Ui, options: &mut ConversionOptions) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Format de sortie:")).strong());
+        egui::ComboBox::from_id_salt("output_format_combo")
+            .selected_text(options.output_format.label())
+            .show_ui(ui, |ui| {
+                for format in OutputFormat::ALL {
+                    ui.selectable_value(&mut options.output_format, format, format.label());
+                }
+            })
+            .response
+            .on_hover_text(tr!("Format des fichiers produits (l'extension est choisie en conséquence)."));
+        ui.checkbox(&mut options.smallest_format, tr!("Le plus léger"))
+            .on_hover_text(tr!("Encoder chaque image en WebP, AVIF, PNG et JPEG (sans transparence) et ne garder que le fichier le plus léger. La liste sert alors à régler les options de chaque format."));
+    });
+
+    // Les options de qualité ne concernent que la sortie WebP.
+    if options.output_format == OutputFormat::WebP {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut options.webp.lossless, tr!("Sans perte"))
+                .on_hover_text(tr!("Encodage sans perte : image identique à la source, fichiers plus lourds."));
+            ui.add_enabled_ui(!options.webp.lossless, |ui| {
+                ui.add(egui::Slider::new(&mut options.webp.quality, 0..=100).text(tr!("qualité")))
+                    .on_hover_text(tr!("Qualité d'encodage WebP avec perte (100 = meilleure qualité, fichiers plus lourds)."));
+            });
+        });
+    }
+
+    // Les options de qualité et de vitesse ne concernent que la sortie AVIF.
+    if options.output_format == OutputFormat::Avif {
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(&mut options.avif.quality, 1..=100).text(tr!("qualité")))
+                .on_hover_text(tr!("Qualité d'encodage AVIF (100 = meilleure qualité, fichiers plus lourds)."));
+            ui.add(egui::Slider::new(&mut options.avif.speed, 1..=10).text(tr!("vitesse")))
+                .on_hover_text(tr!("Vitesse d'encodage : 1 = très lent mais plus compact, 10 = rapide."));
+        });
+    }
+
+    // Les options de palette ne concernent que la sortie PNG.
+    if options.output_format == OutputFormat::Png {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut options.png.quantize, tr!("Réduire à une palette"))
+                .on_hover_text(tr!("Quantifier l'image en couleurs indexées : fichiers bien plus légers pour les captures d'écran et schémas."));
+            ui.add_enabled_ui(options.png.quantize, |ui| {
+                ui.add(egui::Slider::new(&mut options.png.max_colors, 2..=256).text(tr!("couleurs")))
+                    .on_hover_text(tr!("Nombre maximal de couleurs de la palette."));
+                ui.checkbox(&mut options.png.dithering, tr!("Tramage"))
+                    .on_hover_text(tr!("Diffuser l'erreur de quantification pour adoucir les dégradés."));
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut options.png.optimize, tr!("Optimiser (oxipng)"))
+                .on_hover_text(tr!("Recompresser le PNG produit sans perte (filtres, compression, profondeur de couleur) : fichiers plus légers, pixels identiques."));
+            ui.add_enabled_ui(options.png.optimize, |ui| {
+                ui.add(egui::Slider::new(&mut options.png.optimization_level, 0..=6).text(tr!("effort")))
+                    .on_hover_text(tr!("Effort de l'optimisation : 0 = rapide, 6 = le plus compact mais très lent."));
+            });
+        });
+    }
+
+    // Les options JPEG ne concernent que la sortie JPEG.
+    if options.output_format == OutputFormat::Jpeg {
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(&mut options.jpeg.quality, 1..=100).text(tr!("qualité")))
+                .on_hover_text(tr!("Qualité d'encodage JPEG (100 = meilleure qualité, fichiers plus lourds)."));
+            ui.checkbox(&mut options.jpeg.progressive, tr!("Progressif"))
+                .on_hover_text(tr!("Encodage progressif : l'image s'affiche par passes successives lors du chargement."));
+            ui.checkbox(&mut options.jpeg.mozjpeg, tr!("mozjpeg"))
+                .on_hover_text(tr!("Encoder avec mozjpeg : fichiers nettement plus légers à qualité égale, encodage plus lent. Décoché, l'encodeur standard est utilisé."));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("Chrominance:"));
+            radio_group(ui, "chroma_subsampling_group", tr!("Chrominance"), |ui| {
+                for subsampling in [ChromaSubsampling::S444, ChromaSubsampling::S422, ChromaSubsampling::S420] {
+                    ui.radio_value(&mut options.jpeg.subsampling, subsampling, subsampling.label());
+                }
+            });
+        });
+    }
+
+    // Le JPEG ne gère pas la transparence : elle y est toujours aplatie sur la couleur de fond.
+    ui.horizontal(|ui| {
+        let is_jpeg = options.output_format == OutputFormat::Jpeg;
+        let mut flatten = options.flatten_alpha || is_jpeg;
+        ui.add_enabled(!is_jpeg, egui::Checkbox::new(&mut flatten, tr!("Aplatir la transparence")))
+            .on_hover_text(tr!("Remplacer la transparence par la couleur de fond (ex: fond blanc derrière un logo PNG)."))
+            .on_disabled_hover_text(tr!("Le JPEG ne gère pas la transparence : elle est toujours remplacée par la couleur de fond."));
+        if !is_jpeg {
+            options.flatten_alpha = flatten;
+        }
+        ui.label(tr!("Fond:"));
+        ui.add_enabled_ui(flatten, |ui| {
+            ui.color_edit_button_srgb(&mut options.background_color)
+                .on_hover_text(tr!("Couleur de fond utilisée pour remplacer la transparence."));
+        });
+    });
+}
+
+/// Rend la section du redimensionnement : dimensions maximales ou pourcentage des dimensions d'origine.
+pub fn render_resize_options(ui: &mut egui::Ui, resize: &mut ResizeOptions) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Redimensionner:")).strong());
+        radio_group(ui, "resize_mode_group", tr!("Redimensionner"), |ui| {
+            for mode in [ResizeMode::None, ResizeMode::Dimensions, ResizeMode::Percentage] {
+                ui.radio_value(&mut resize.mode, mode, mode.label());
+            }
+        });
+    });
+    match resize.mode {
+        ResizeMode::None => {}
+        ResizeMode::Dimensions => {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut resize.max_width).range(0..=20000).prefix(tr!("larg. ≤ ")).suffix(tr!(" px")))
+                    .on_hover_text(tr!("Largeur maximale (0 = aucune). Les proportions sont conservées."));
+                ui.add(egui::DragValue::new(&mut resize.max_height).range(0..=20000).prefix(tr!("haut. ≤ ")).suffix(tr!(" px")))
+                    .on_hover_text(tr!("Hauteur maximale (0 = aucune). Les images plus petites ne sont pas agrandies."));
+            });
         }
+        ResizeMode::Percentage => {
+            ui.add(egui::Slider::new(&mut resize.percent, 1..=400).suffix(" %").text(tr!("des dimensions d'origine")))
+                .on_hover_text(tr!("Échelle appliquée à chaque image (ex: 50 % divise la largeur et la hauteur par deux)."));
+        }
+    }
+    if resize.mode != ResizeMode::None {
+        ui.horizontal(|ui| {
+            ui.label(tr!("Filtre:"));
+            egui::ComboBox::from_id_salt("resize_filter_combo")
+                .selected_text(resize.filter.label())
+                .show_ui(ui, |ui| {
+                    for filter in ResizeFilter::ALL {
+                        ui.selectable_value(&mut resize.filter, filter, filter.label());
+                    }
+                })
+                .response
+                .on_hover_text(tr!("Filtre de rééchantillonnage : les plus rapides accélèrent les gros lots, Lanczos3 donne les images les plus nettes."));
+        });
+    }
+}
+
+/// Rend le sélecteur de rotation ou de symétrie appliquée à toutes les images.
+pub fn render_rotation_options(ui: &mut egui::Ui, rotation: &mut Rotation) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Transformation:")).strong());
+        egui::ComboBox::from_id_salt("rotation_combo")
+            .selected_text(rotation.label())
+            .show_ui(ui, |ui| {
+                for option in Rotation::ALL {
+                    ui.selectable_value(rotation, option, option.label());
+                }
+            })
+            .response
+            .on_hover_text(tr!("Tourner ou retourner toutes les images du lot, après le redressement EXIF."));
+    });
+}
+
+/// Rend les options de rognage automatique des bords uniformes.
+pub fn render_trim_options(ui: &mut egui::Ui, trim: &mut TrimOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut trim.enabled, tr!("Rogner les bords uniformes"))
+            .on_hover_text(tr!("Retirer les marges transparentes ou d'une couleur unie (celle du coin supérieur gauche), ex: sprites exportés, scans."));
+        ui.add_enabled(trim.enabled, egui::Slider::new(&mut trim.tolerance, 0..=64).text(tr!("tolérance")))
+            .on_hover_text(tr!("Écart de couleur toléré avec le fond (0 = couleur exacte ; à augmenter pour les scans et le JPEG)."));
+    });
+}
+
+/// Rend les options de recadrage : proportions (courantes ou libres) et position du cadre.
+pub fn render_crop_options(ui: &mut egui::Ui, crop: &mut CropOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut crop.enabled, egui::RichText::new(tr!("Recadrer:")).strong())
+            .on_hover_text(tr!("Recadrer chaque image aux proportions choisies avant le redimensionnement."));
+        ui.add_enabled_ui(crop.enabled, |ui| {
+            egui::ComboBox::from_id_salt("crop_ratio_combo")
+                .selected_text(format!("{}:{}", crop.ratio_width, crop.ratio_height))
+                .show_ui(ui, |ui| {
+                    for (ratio_width, ratio_height) in CropOptions::PRESETS {
+                        let selected = (crop.ratio_width, crop.ratio_height) == (ratio_width, ratio_height);
+                        if ui.selectable_label(selected, format!("{}:{}", ratio_width, ratio_height)).clicked() {
+                            (crop.ratio_width, crop.ratio_height) = (ratio_width, ratio_height);
+                        }
+                    }
+                });
+            ui.add(egui::DragValue::new(&mut crop.ratio_width).range(1..=100))
+                .on_hover_text(tr!("Proportion horizontale."));
+            ui.label(":");
+            ui.add(egui::DragValue::new(&mut crop.ratio_height).range(1..=100))
+                .on_hover_text(tr!("Proportion verticale."));
+            radio_group(ui, "crop_anchor_group", tr!("Position du cadre"), |ui| {
+                ui.radio_value(&mut crop.anchor, CropAnchor::Center, CropAnchor::Center.label())
+                    .on_hover_text(tr!("Garder le centre de l'image."));
+                ui.radio_value(&mut crop.anchor, CropAnchor::Smart, CropAnchor::Smart.label())
+                    .on_hover_text(tr!("Placer le cadre sur la zone la plus détaillée (sujet net sur fond uni...)."));
+            });
+        });
+    });
+}
+
+/// Rend les options du canevas : dimensions fixes et couleur (ou transparence) du fond.
+pub fn render_pad_options(ui: &mut egui::Ui, pad: &mut PadOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut pad.enabled, egui::RichText::new(tr!("Canevas fixe:")).strong())
+            .on_hover_text(tr!("Centrer chaque image sur un fond de dimensions fixes, sans la déformer (ex: miniatures de produits uniformes)."));
+        ui.add_enabled_ui(pad.enabled, |ui| {
+            ui.add(egui::DragValue::new(&mut pad.width).range(1..=20000).suffix(tr!(" px")))
+                .on_hover_text(tr!("Largeur du canevas."));
+            ui.label("×");
+            ui.add(egui::DragValue::new(&mut pad.height).range(1..=20000).suffix(tr!(" px")))
+                .on_hover_text(tr!("Hauteur du canevas. Les images plus grandes sont réduites pour y tenir."));
+            ui.checkbox(&mut pad.transparent, tr!("Fond transparent"))
+                .on_hover_text(tr!("Laisser le fond transparent (WebP, PNG, AVIF, TIFF) ; le JPEG utilise la couleur de fond générale."));
+            ui.add_enabled_ui(!pad.transparent, |ui| {
+                ui.color_edit_button_srgb(&mut pad.color).on_hover_text(tr!("Couleur du fond du canevas."));
+            });
+        });
+    });
+}
+
+/// Rend la section repliable des destinations multiples : sous-dossier, format et redimensionnement de chacune.
+pub fn render_targets_options(ui: &mut egui::Ui, targets: &mut OutputTargets) {
+    egui::CollapsingHeader::new(tr!("Destinations multiples"))
+        .default_open(targets.enabled)
+        .show(ui, |ui| {
+            ui.checkbox(&mut targets.enabled, tr!("Produire chaque image pour plusieurs destinations"))
+                .on_hover_text(tr!("Ex: WebP pleine taille dans web/ et miniature de 300 px dans thumbs/. Le format et le redimensionnement ci-dessus sont alors remplacés par ceux de chaque destination ; les autres réglages sont communs."));
+            ui.add_enabled_ui(targets.enabled, |ui| {
+                let mut removed = None;
+                for (index, target) in targets.targets.iter_mut().enumerate() {
+                    ui.push_id(index, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut target.subfolder).hint_text(tr!("sous-dossier")).desired_width(90.0))
+                                .on_hover_text(tr!("Sous-dossier de la sortie (vide = racine de la sortie)."));
+                            egui::ComboBox::from_id_salt("target_format_combo")
+                                .selected_text(target.output_format.label())
+                                .show_ui(ui, |ui| {
+                                    for format in OutputFormat::ALL {
+                                        ui.selectable_value(&mut target.output_format, format, format.label());
+                                    }
+                                });
+                            egui::ComboBox::from_id_salt("target_resize_combo")
+                                .selected_text(target.resize.mode.label())
+                                .show_ui(ui, |ui| {
+                                    for mode in [ResizeMode::None, ResizeMode::Dimensions, ResizeMode::Percentage] {
+                                        ui.selectable_value(&mut target.resize.mode, mode, mode.label());
+                                    }
+                                });
+                            match target.resize.mode {
+                                ResizeMode::None => {}
+                                ResizeMode::Dimensions => {
+                                    ui.add(egui::DragValue::new(&mut target.resize.max_width).range(0..=20000).prefix("≤ ").suffix(tr!(" px")))
+                                        .on_hover_text(tr!("Largeur maximale (0 = aucune)."));
+                                    ui.add(egui::DragValue::new(&mut target.resize.max_height).range(0..=20000).prefix("× ").suffix(tr!(" px")))
+                                        .on_hover_text(tr!("Hauteur maximale (0 = aucune)."));
+                                }
+                                ResizeMode::Percentage => {
+                                    ui.add(egui::DragValue::new(&mut target.resize.percent).range(1..=400).suffix(" %"));
+                                }
+                            }
+                            if ui.small_button("✖").on_hover_text(tr!("Retirer cette destination")).clicked() {
+                                removed = Some(index);
+                            }
+                        });
+                    });
+                }
+                if let Some(index) = removed {
+                    targets.targets.remove(index);
+                }
+                if ui.button(tr!("➕ Ajouter une destination")).clicked() {
+                    targets.targets.push(OutputTarget::default());
+                }
+            });
+        });
+}
+
+/// Rend la section repliable du jeu d'images responsives : largeurs produites et extrait `srcset`.
+pub fn render_responsive_options(ui: &mut egui::Ui, responsive: &mut ResponsiveOptions) {
+    egui::CollapsingHeader::new(tr!("Images responsives (srcset)"))
+        .default_open(responsive.enabled)
+        .show(ui, |ui| {
+            ui.checkbox(&mut responsive.enabled, tr!("Produire chaque image en plusieurs largeurs"))
+                .on_hover_text(tr!("Chaque image est produite dans chacune des largeurs (suffixe -480w, -768w...), sans agrandissement : les largeurs supérieures à celle de la source sont ignorées. Remplace le redimensionnement."));
+            ui.add_enabled_ui(responsive.enabled, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr!("Largeurs:"));
+                    let mut removed = None;
+                    for (index, width) in responsive.widths.iter_mut().enumerate() {
+                        ui.add(egui::DragValue::new(width).range(1..=20000).suffix(tr!(" px")));
+                        if ui.small_button("✖").on_hover_text(tr!("Retirer cette largeur")).clicked() {
+                            removed = Some(index);
+                        }
+                    }
+                    if let Some(index) = removed {
+                        responsive.widths.remove(index);
+                    }
+                    if ui.small_button("➕").on_hover_text(tr!("Ajouter une largeur")).clicked() {
+                        let next = responsive.widths.iter().max().map_or(responsive::DEFAULT_WIDTHS[0], |width| width * 2);
+                        responsive.widths.push(next);
+                    }
+                });
+                ui.checkbox(&mut responsive.write_srcset, tr!("Écrire l'extrait HTML srcset"))
+                    .on_hover_text(tr!("Écrire à côté des images un fichier nom.srcset.html contenant la balise <img srcset> prête à copier."));
+            });
+        });
+}
+
+/// Rend la case du mode favicon : chaque image produit un `favicon.ico` multi-résolution et les PNG usuels.
+pub fn render_favicon_option(ui: &mut egui::Ui, favicon: &mut bool) {
+    ui.checkbox(favicon, tr!("Favicon (.ico multi-résolution)"))
+        .on_hover_text(tr!("Au lieu de la sortie habituelle, produire dans un dossier nom-favicon un favicon.ico (16, 32, 48 et 256 px) et les PNG usuels : favicon-16x16, favicon-32x32, apple-touch-icon (180 px), android-chrome 192 et 512 px. Une image non carrée est centrée sur un fond transparent."));
+}
+
+/// Rend la section repliable du rendu des documents PDF : résolution et pages converties.
+/// Une sélection de pages invalide est signalée sous le champ.
+pub fn render_pdf_options(ui: &mut egui::Ui, options: &mut PdfOptions) {
+    egui::CollapsingHeader::new(tr!("Documents PDF"))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.add(egui::Slider::new(&mut options.dpi, pdf::DPI_RANGE).text(tr!("ppp")))
+                .on_hover_text(tr!("Résolution du rendu de chaque page (points par pouce) : 72 pour l'écran, 150 à 300 pour une lecture confortable ou l'impression."));
+            ui.horizontal(|ui| {
+                ui.label(tr!("Pages :"));
+                ui.add(egui::TextEdit::singleline(&mut options.pages).hint_text(tr!("toutes")).desired_width(120.0))
+                    .on_hover_text(tr!("Pages et plages séparées par des virgules (ex: 1-3,5,8-). Vide : toutes les pages. Chaque page produit une image nommée nom-p1, nom-p2..."));
+            });
+            if let Err(e) = pdf::parse_page_range(&options.pages, 0) {
+                ui.colored_label(egui::Color32::RED, e.to_string());
+            }
+        });
+}
+
+/// Rend la section repliable des animations : extraction de leurs images (sélection et pas) ou animation conservée
+/// en WebP ou PNG animé (durée des images, boucles, qualité).
+pub fn render_animation_options(ui: &mut egui::Ui, options: &mut FrameOptions, animation: &mut AnimationOptions) {
+    egui::CollapsingHeader::new(tr!("Images animées"))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.checkbox(&mut animation.keep, tr!("Conserver l'animation (WebP, PNG)"))
+                .on_hover_text(tr!("Réencoder les GIF, PNG et WebP animés en WebP animé ou en PNG animé (APNG, sans perte) selon le format de sortie, chaque image suivant le même traitement (redimensionnement...), au lieu de n'en garder que la première. La transparence est conservée. Pour les autres formats de sortie, seule la première image est convertie."));
+            ui.add_enabled_ui(animation.keep && !options.extract, |ui| {
+                ui.add(egui::Slider::new(&mut animation.delay_scale, 0.1..=4.0).step_by(0.05).text(tr!("× durée des images")))
+                    .on_hover_text(tr!("Facteur appliqué à la durée de chaque image : 0,5 joue l'animation deux fois plus vite, 2 deux fois plus lentement."));
+                ui.add(egui::DragValue::new(&mut animation.loop_count).range(0..=1000).prefix(tr!("lectures ")))
+                    .on_hover_text(tr!("Nombre de lectures de l'animation (0 = en boucle infinie)."));
+                ui.add(egui::Slider::new(&mut animation.quality, 0..=100).text(tr!("qualité des images")))
+                    .on_hover_text(tr!("Qualité de chaque image d'une animation WebP (avec perte) ; l'option « Sans perte » du WebP s'applique aussi aux animations. Les PNG animés sont toujours sans perte."));
+            });
+            ui.checkbox(&mut options.extract, tr!("Extraire les images"))
+                .on_hover_text(tr!("Convertir chaque image des GIF, PNG et WebP animés en image fixe nommée nom_0001, nom_0002... au lieu de la première seulement."));
+            ui.add_enabled_ui(options.extract, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("Images :"));
+                    ui.add(egui::TextEdit::singleline(&mut options.frames).hint_text(tr!("toutes")).desired_width(120.0))
+                        .on_hover_text(tr!("Images et plages séparées par des virgules (ex: 1-20,30-). Vide : toutes les images."));
+                    ui.add(egui::DragValue::new(&mut options.step).range(1..=100).prefix(tr!("une sur ")))
+                        .on_hover_text(tr!("Ne garder qu'une image sur N parmi celles sélectionnées (1 = toutes)."));
+                });
+            });
+            if let Err(e) = pdf::parse_page_range(&options.frames, 0) {
+                ui.colored_label(egui::Color32::RED, e.to_string());
+            }
+        });
+}
+
+/// Rend la section repliable des réglages d'image (luminosité, contraste, saturation, accentuation).
+pub fn render_adjustment_options(ui: &mut egui::Ui, adjustments: &mut Adjustments) {
+    egui::CollapsingHeader::new(tr!("Réglages de l'image"))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.add(egui::Slider::new(&mut adjustments.brightness, -100..=100).text(tr!("luminosité")))
+                .on_hover_text(tr!("Éclaircir ou assombrir l'image (0 = inchangée)."));
+            ui.add(egui::Slider::new(&mut adjustments.contrast, -100..=100).text(tr!("contraste")))
+                .on_hover_text(tr!("Augmenter ou réduire le contraste (0 = inchangé)."));
+            ui.add(egui::Slider::new(&mut adjustments.saturation, -100..=100).text(tr!("saturation")))
+                .on_hover_text(tr!("Raviver ou atténuer les couleurs (-100 = niveaux de gris, 0 = inchangée)."));
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut adjustments.sharpen, tr!("Accentuer"))
+                    .on_hover_text(tr!("Appliquer un masque flou pour redonner du piqué aux images douces (scans...)."));
+                ui.add_enabled(
+                    adjustments.sharpen,
+                    egui::Slider::new(&mut adjustments.sharpen_amount, 0.0..=2.0).text(tr!("intensité")),
+                );
+            });
+            if ui.add_enabled(!adjustments.is_identity(), egui::Button::new(tr!("Réinitialiser"))).clicked() {
+                *adjustments = Adjustments::default();
+            }
+        });
+}
+
+/// Rend la section des options de gestion des fichiers existants, et le suffixe des fichiers renommés
+/// quand le mode Renommer peut s'appliquer (Renommer, ou réponse Renommer en mode Demander).
+pub fn render_overwrite_options(ui: &mut egui::Ui, overwrite_mode: &mut OverwriteMode, rename_suffix: &mut RenameSuffix) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Si le fichier existe:")).strong()); // Utilisation de RichText
+        radio_group(ui, "overwrite_mode_group", tr!("Si le fichier existe"), |ui| {
+            ui.radio_value(overwrite_mode, OverwriteMode::Skip, OverwriteMode::Skip.label())
+                .on_hover_text(tr!("Ne pas convertir si le fichier de sortie existe déjà."));
+            ui.radio_value(overwrite_mode, OverwriteMode::Overwrite, OverwriteMode::Overwrite.label())
+                .on_hover_text(tr!("Écraser le fichier de sortie existant."));
+            ui.radio_value(overwrite_mode, OverwriteMode::Rename, OverwriteMode::Rename.label())
+                .on_hover_text(tr!("Créer un nouveau fichier avec un suffixe (ex: image-1)."));
+            ui.radio_value(overwrite_mode, OverwriteMode::IfNewer, OverwriteMode::IfNewer.label())
+                .on_hover_text(tr!("Reconvertir uniquement les sources modifiées depuis la création de leur sortie (date de modification) : relancer la conversion d'une arborescence ne traite que les fichiers nouveaux ou modifiés."));
+            ui.radio_value(overwrite_mode, OverwriteMode::Ask, OverwriteMode::Ask.label())
+                .on_hover_text(tr!("Suspendre la conversion à chaque fichier existant pour choisir : écraser, ignorer ou renommer."));
+        });
+    });
+    if matches!(overwrite_mode, OverwriteMode::Rename | OverwriteMode::Ask) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("Suffixe des fichiers renommés:"));
+            radio_group(ui, "rename_suffix_group", tr!("Suffixe des fichiers renommés"), |ui| {
+                ui.radio_value(rename_suffix, RenameSuffix::Counter, RenameSuffix::Counter.label())
+                    .on_hover_text(tr!("Ex: image-1.webp, image-2.webp"));
+                ui.radio_value(rename_suffix, RenameSuffix::Timestamp, RenameSuffix::Timestamp.label())
+                    .on_hover_text(tr!("Ex: image_2024-05-03_1432.webp : des exports répétés ne se confondent jamais."));
+            });
+        });
+    }
+}
+
+/// Rend les options du sort des sources après une conversion réussie.
+pub fn render_originals_options(ui: &mut egui::Ui, originals: &mut OriginalsMode) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Après conversion, les originaux:")).strong());
+        radio_group(ui, "originals_group", tr!("Après conversion, les originaux"), |ui| {
+            ui.radio_value(originals, OriginalsMode::Keep, OriginalsMode::Keep.label())
+                .on_hover_text(tr!("Laisser les images sources en place."));
+            ui.radio_value(originals, OriginalsMode::Trash, OriginalsMode::Trash.label())
+                .on_hover_text(tr!("Placer chaque source dans la corbeille du système une fois son fichier produit vérifié."));
+            ui.radio_value(originals, OriginalsMode::Backup, OriginalsMode::Backup.label())
+                .on_hover_text(tr!("Déplacer chaque source dans le dossier originals/ du dossier de sortie une fois son fichier produit vérifié."));
+        });
+    });
+}
+
+/// Rend les options de traitement des sorties plus lourdes que leur source.
+pub fn render_larger_output_options(ui: &mut egui::Ui, larger_output: &mut LargerOutputMode) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Si la sortie est plus lourde:")).strong());
+        radio_group(ui, "larger_output_group", tr!("Si la sortie est plus lourde"), |ui| {
+            ui.radio_value(larger_output, LargerOutputMode::Keep, LargerOutputMode::Keep.label())
+                .on_hover_text(tr!("Écrire le fichier converti même s'il est plus lourd que la source."));
+            ui.radio_value(larger_output, LargerOutputMode::CopyOriginal, LargerOutputMode::CopyOriginal.label())
+                .on_hover_text(tr!("Copier la source telle quelle (avec son extension) à la place du fichier converti."));
+            ui.radio_value(larger_output, LargerOutputMode::Skip, LargerOutputMode::Skip.label())
+                .on_hover_text(tr!("Ne rien écrire pour cette image."));
+        });
+    });
+}
+
+/// Regroupe des boutons radio sous un même groupe accessible (rôle « groupe radio » nommé `label`),
+/// pour que les lecteurs d'écran annoncent le groupe, l'option choisie et le nombre d'options.
+pub fn radio_group<R>(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    label: &str,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> R {
+    let group_id = ui.make_persistent_id(id_salt);
+    let ctx = ui.ctx().clone();
+    ctx.accesskit_node_builder(group_id, |node| {
+        node.set_role(egui::accesskit::Role::RadioGroup);
+        node.set_label(label);
+    });
+    ctx.with_accessibility_parent(group_id, || add_contents(ui))
+}
+
+/// Rend le débit de la conversion en cours (images et mégaoctets par seconde) et le temps restant estimé.
+/// Rien n'est affiché tant que le débit n'est pas encore mesuré.
+pub fn render_throughput(ui: &mut egui::Ui, throughput: &Throughput) {
+    if throughput.files_per_second <= 0.0 {
+        return;
+    }
+    let mut text = tr!(
+        "{} image(s)/s · {}/s",
+        format!("{:.1}", throughput.files_per_second),
+        format_size(throughput.bytes_per_second as u64)
+    );
+    if let Some(eta) = throughput.eta {
+        text.push_str(&tr!(" · temps restant ≈ {}", format_duration(eta)));
+    }
+    ui.label(egui::RichText::new(text).weak())
+        .on_hover_text(tr!("Moyenne sur les 10 dernières secondes."));
+}
+
+/// Rend la barre de progression de la conversion, avec un nom accessible et sa valeur en pourcentage.
+pub fn render_progress_bar(ui: &mut egui::Ui, progress: f32) {
+    let response = ui.add(egui::ProgressBar::new(progress).show_percentage());
+    response.widget_info(|| {
+        let mut info = egui::WidgetInfo::labeled(
+            egui::WidgetType::ProgressIndicator,
+            true,
+            tr!("Progression de la conversion"),
+        );
+        info.value = Some((progress as f64 * 100.0).floor());
+        info
+    });
+}
+
+/// Rend une ligne par lot de la session : avancement, pause et annulation d'un lot en cours,
+/// abandon d'un lot en attente, bilan d'un lot terminé. Retourne la modification demandée, s'il y en a une.
+pub fn render_jobs(ui: &mut egui::Ui, ctx: &egui::Context, jobs: &[ManagedJob]) -> Option<JobAction> {
+    let mut action = None;
+    for managed in jobs {
+        ui.add_space(10.0);
+        ui.group(|ui| {
+            ui.label(egui::RichText::new(&managed.spec.label).strong());
+            match managed.state {
+                JobState::Queued => {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("En attente de la fin d'un lot en cours..."));
+                        if ui.button(tr!("Annuler")).clicked() {
+                            action = Some(JobAction::Remove(managed.id));
+                        }
+                    });
+                }
+                JobState::Running => render_running_job(ui, ctx, managed),
+                JobState::Finished => {
+                    if let Some((message, is_error)) = &managed.outcome {
+                        render_status_announcement(ui, message, *is_error);
+                    }
+                    ui.horizontal(|ui| {
+                        if managed.report.is_some() && ui.small_button(tr!("📊 Voir le bilan")).clicked() {
+                            action = Some(JobAction::ShowReport(managed.id));
+                        }
+                        if managed.single_output().is_some() {
+                            if let Some(content) = render_copy_output_menu(ui) {
+                                action = Some(JobAction::CopyOutput(managed.id, content));
+                            }
+                        }
+                        if ui.small_button(tr!("✖ Retirer")).on_hover_text(tr!("Retirer ce lot de la liste")).clicked() {
+                            action = Some(JobAction::Remove(managed.id));
+                        }
+                    });
+                }
+            }
+        });
+    }
+    action
+}
+
+/// Rend l'avancement d'un lot en cours (barre, débit, fichier en cours) et ses boutons de pause et d'annulation.
+fn render_running_job(ui: &mut egui::Ui, ctx: &egui::Context, managed: &ManagedJob) {
+    let (completed, total) = managed.counts;
+    render_progress_bar(ui, managed.progress());
+    render_throughput(ui, &managed.throughput);
+    if let Some(name) = managed.current_file.as_deref().and_then(Path::file_name) {
+        ui.weak(tr!("En cours : {}", name.to_string_lossy()));
+    }
+    let control = &managed.control;
+    ui.horizontal(|ui| {
+        if control.is_cancelled() {
+            ui.label(tr!("Annulation en cours..."));
+            return;
+        }
+        if control.pending_conflict().is_some() {
+            ui.label(tr!("En attente de votre choix : {} / {} fichier(s)", completed, total));
+        } else if control.is_paused() {
+            ui.label(tr!("En pause : {} / {} fichier(s)", completed, total));
+            if ui.button(tr!("▶ Reprendre")).clicked() {
+                control.set_paused(false);
+            }
+        } else {
+            ui.label(tr!("Conversion en cours... {} / {} fichier(s)", completed, total));
+            if ui.button(tr!("⏸ Pause"))
+                .on_hover_text(tr!("Suspendre la conversion après les fichiers en cours, pour libérer le processeur"))
+                .clicked()
+            {
+                control.set_paused(true);
+            }
+        }
+        if ui.button(tr!("Annuler"))
+            .on_hover_text(with_shortcut(
+                ctx,
+                tr!("Arrêter la conversion après les fichiers en cours (les fichiers déjà convertis sont conservés)"),
+                &CANCEL_SHORTCUT,
+            ))
+            .clicked()
+        {
+            control.cancel();
+        }
+    });
+}
+
+/// Rend la ligne d'état du dernier lot. Contrairement au toast, elle reste affichée et est exposée
+/// comme région « live » : les lecteurs d'écran annoncent la fin du lot dès que le message change.
+pub fn render_status_announcement(ui: &mut egui::Ui, message: &str, is_error: bool) {
+    let color = if is_error {
+        egui::Color32::from_rgb(180, 40, 40)
+    } else {
+        egui::Color32::from_rgb(40, 120, 40)
+    };
+    let response = ui.label(egui::RichText::new(message).color(color));
+    ui.ctx().accesskit_node_builder(response.id, |node| {
+        node.set_live(if is_error {
+            egui::accesskit::Live::Assertive
+        } else {
+            egui::accesskit::Live::Polite
+        });
+    });
+}
+
+/// Rend l'option du mode incrémental (manifeste des conversions), désactivée quand l'archive ZIP est demandée :
+/// l'archive retire les fichiers produits, que le manifeste ne reconnaîtrait plus.
+pub fn render_incremental_option(ui: &mut egui::Ui, incremental: &mut bool, archive_enabled: bool) {
+    ui.add_enabled(!archive_enabled, egui::Checkbox::new(incremental, tr!("Mode incrémental")))
+        .on_disabled_hover_text(tr!("Indisponible avec l'archive ZIP : les fichiers archivés sont retirés de la sortie, le manifeste ne reconnaîtrait plus aucune source inchangée."))
+        .on_hover_text(tr!("Ignorer les images dont le contenu et les options n'ont pas changé depuis la dernière conversion (empreintes SHA-256 enregistrées dans le manifeste du dossier de sortie). Contrairement au mode « Si plus récent », une copie à l'identique reste reconnue même si sa date a changé."));
+}
+
+/// Rend la case de suppression des métadonnées.
+pub fn render_strip_metadata_option(ui: &mut egui::Ui, strip_metadata: &mut bool) {
+    ui.checkbox(strip_metadata, tr!("Supprimer toutes les métadonnées"))
+        .on_hover_text(tr!("Garantit que les fichiers produits ne contiennent ni EXIF (coordonnées GPS, numéro de série de l'appareil...), ni XMP, ni profil ICC."));
+}
+
+/// Rend la case de rotation automatique selon l'orientation EXIF.
+pub fn render_auto_orient_option(ui: &mut egui::Ui, auto_orient: &mut bool) {
+    ui.checkbox(auto_orient, tr!("Rotation automatique (EXIF)"))
+        .on_hover_text(tr!("Redresser les photos (téléphones, appareils photo) d'après leur balise d'orientation EXIF."));
+}
+
+/// Rend le choix du traitement des profils de couleur ICC (Adobe RGB, Display P3...).
+pub fn render_color_profile_options(ui: &mut egui::Ui, color_profile: &mut ColorProfileMode) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Profil couleur:")).strong());
+        radio_group(ui, "color_profile_group", tr!("Profil couleur"), |ui| {
+            ui.radio_value(color_profile, ColorProfileMode::ConvertToSrgb, ColorProfileMode::ConvertToSrgb.label())
+                .on_hover_text(tr!("Convertir les couleurs en sRGB : affichage fidèle dans tous les navigateurs et visionneuses."));
+            ui.radio_value(color_profile, ColorProfileMode::Embed, ColorProfileMode::Embed.label())
+                .on_hover_text(tr!("Garder les couleurs d'origine et intégrer le profil (PNG, JPEG, WebP ; conversion en sRGB pour l'AVIF et le TIFF)."));
+            ui.radio_value(color_profile, ColorProfileMode::Ignore, ColorProfileMode::Ignore.label())
+                .on_hover_text(tr!("Ignorer le profil : les images Adobe RGB ou Display P3 paraîtront délavées."));
+        });
+    });
+}
+
+/// Rend le réglage du nombre de conversions simultanées et l'option de priorité basse.
+pub fn render_worker_option(ui: &mut egui::Ui, workers: &mut usize, low_priority: &mut bool) {
+    ui.horizontal(|ui| {
+        ui.label(tr!("Conversions simultanées:"));
+        let max_workers = std::thread::available_parallelism().map_or(8, |n| n.get()) * 2;
+        ui.add(egui::Slider::new(workers, 0..=max_workers).custom_formatter(|value, _| {
+            if value == 0.0 { tr!("auto").to_string() } else { format!("{}", value) }
+        }))
+            .on_hover_text(tr!("Nombre d'images converties en parallèle (auto = un thread par cœur du processeur). Moins de threads laissent la machine plus réactive."));
+        ui.checkbox(low_priority, tr!("Priorité basse"))
+            .on_hover_text(tr!("Convertir avec une priorité réduite : un gros lot ne ralentit pas les autres applications."));
+    });
+}
+
+/// Rend les options de vérification des fichiers produits : relecture, mesure de la fidélité et seuil SSIM.
+pub fn render_verify_options(ui: &mut egui::Ui, verify: &mut VerifyOptions) {
+    ui.checkbox(&mut verify.enabled, tr!("Vérifier les fichiers produits"))
+        .on_hover_text(tr!("Relire et décoder chaque fichier écrit : une sortie illisible ou aux dimensions inattendues est signalée dans le bilan, et sa source n'est ni mise à la corbeille ni déplacée."));
+    ui.add_enabled_ui(verify.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut verify.measure_quality, tr!("Mesurer la fidélité (PSNR, SSIM)"))
+                .on_hover_text(tr!("Comparer chaque sortie à l'image avant encodage. Plus lent : l'image produite est entièrement décodée."));
+            ui.add_enabled_ui(verify.measure_quality, |ui| {
+                ui.label(tr!("SSIM minimal:"));
+                ui.add(egui::Slider::new(&mut verify.min_ssim, 0.0..=1.0).step_by(0.01).custom_formatter(|value, _| {
+                    if value == 0.0 { tr!("aucun").to_string() } else { format!("{:.2}", value) }
+                }))
+                    .on_hover_text(tr!("Signaler les sorties dont le SSIM est inférieur à ce seuil (1 = identique à l'image avant encodage)."));
+            });
+        });
+    });
+}
+
+/// Rend l'option qui cache la fenêtre dans la zone de notification à sa fermeture (Windows et macOS).
+pub fn render_close_to_tray_option(ui: &mut egui::Ui, close_to_tray: &mut bool) {
+    ui.add_enabled(tray::SUPPORTED, egui::Checkbox::new(close_to_tray, tr!("Réduire dans la zone de notification à la fermeture")))
+        .on_hover_text(tr!("La surveillance et la conversion en cours continuent ; l'icône de la zone de notification permet de rouvrir, suspendre ou quitter."))
+        .on_disabled_hover_text(tr!("Disponible sous Windows et macOS uniquement."));
+}
+
+/// Rend les boutons d'ajout et de retrait de l'entrée « Convertir en WebP » du menu contextuel de l'Explorateur
+/// (Windows uniquement). En conversion silencieuse, l'entrée convertit vers `output_dir` sans ouvrir de fenêtre.
+/// Retourne la modification demandée par l'utilisateur, s'il y en a une.
+pub fn render_context_menu_options(ui: &mut egui::Ui, silent: &mut bool, output_dir: &Path) -> Option<ContextMenuAction> {
+    let mut action = None;
+    ui.add_space(10.0);
+    ui.label(egui::RichText::new(tr!("Menu contextuel de l'Explorateur:")).strong());
+    ui.add_enabled_ui(cfg!(target_os = "windows"), |ui| {
+        ui.horizontal(|ui| {
+            if ui.button(tr!("Ajouter « Convertir en WebP »"))
+                .on_hover_text(tr!("Proposer la conversion dans le menu du clic droit sur les images et les dossiers."))
+                .clicked()
+            {
+                let silent_output = silent.then(|| output_dir.to_path_buf());
+                action = Some(ContextMenuAction::Register { silent_output });
+            }
+            if ui.button(tr!("Retirer")).clicked() {
+                action = Some(ContextMenuAction::Unregister);
+            }
+        });
+        ui.checkbox(silent, tr!("Conversion silencieuse"))
+            .on_hover_text(tr!("Convertir en WebP vers le répertoire de sortie actuel sans ouvrir la fenêtre (à appliquer avant l'ajout)."));
+    })
+        .response
+        .on_disabled_hover_text(tr!("Disponible sous Windows uniquement."));
+    action
+}
+
+/// Rend l'option de planche contact et ses réglages (colonnes, lignes par page, taille des vignettes, légendes, format).
+pub fn render_contact_sheet_options(ui: &mut egui::Ui, contact_sheet: &mut ContactSheetOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut contact_sheet.enabled, tr!("Générer une planche contact"))
+            .on_hover_text(tr!("Composer une grille de vignettes légendées (contact-sheet.webp) à la racine du dossier de sortie."));
+        ui.add_enabled_ui(contact_sheet.enabled, |ui| {
+            ui.add(egui::DragValue::new(&mut contact_sheet.columns).range(1..=20).suffix(tr!(" col.")))
+                .on_hover_text(tr!("Nombre de colonnes"));
+            ui.add(egui::DragValue::new(&mut contact_sheet.rows_per_page).range(0..=50).suffix(tr!(" lignes")))
+                .on_hover_text(tr!("Nombre de lignes par page (0 = toutes les images sur une seule planche)"));
+            ui.add(egui::DragValue::new(&mut contact_sheet.cell_size).range(32..=1024).suffix(tr!(" px")))
+                .on_hover_text(tr!("Taille maximale de chaque vignette"));
+        });
+    });
+    ui.add_enabled_ui(contact_sheet.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut contact_sheet.captions, tr!("Légendes"))
+                .on_hover_text(tr!("Afficher le nom de chaque image sous sa vignette"));
+            ui.radio_value(&mut contact_sheet.format, ContactSheetFormat::WebP, "WebP")
+                .on_hover_text(tr!("Sans perte et léger, mais limité à 16383 pixels de côté"));
+            ui.radio_value(&mut contact_sheet.format, ContactSheetFormat::Png, "PNG")
+                .on_hover_text(tr!("Lisible partout, sans limite de taille : pour une seule grande planche"));
+        });
+    });
+}
+
+/// Rend l'option du document PDF réunissant les images produites et ses réglages (nom, résolution).
+pub fn render_pdf_bundle_options(ui: &mut egui::Ui, pdf_bundle: &mut PdfBundleOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut pdf_bundle.enabled, tr!("Réunir dans un PDF"))
+            .on_hover_text(tr!("Écrire à la racine du dossier de sortie un document PDF contenant les images produites, une par page, dans l'ordre des sources. Les JPEG sont intégrés sans nouvelle compression."));
+        ui.add_enabled_ui(pdf_bundle.enabled, |ui| {
+            ui.add(egui::TextEdit::singleline(&mut pdf_bundle.name).hint_text("images").desired_width(120.0))
+                .on_hover_text(tr!("Nom du document (sans l'extension .pdf)"));
+            ui.add(egui::DragValue::new(&mut pdf_bundle.dpi).range(36..=1200).suffix(tr!(" ppp")))
+                .on_hover_text(tr!("Résolution des images dans le document : fixe la taille des pages (une image de 1240 px de large fait 21 cm à 150 ppp)."));
+        });
+    });
+}
+
+/// Rend l'option de la planche de sprites et ses réglages (nom, espacement, largeur maximale).
+pub fn render_sprite_sheet_options(ui: &mut egui::Ui, sprite_sheet: &mut SpriteSheetOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut sprite_sheet.enabled, tr!("Planche de sprites"))
+            .on_hover_text(tr!("Assembler les images produites en une planche PNG à la racine du dossier de sortie, accompagnée d'un atlas JSON des coordonnées de chaque image (format de TexturePacker, lu par Phaser, PixiJS...)."));
+        ui.add_enabled_ui(sprite_sheet.enabled, |ui| {
+            ui.add(egui::TextEdit::singleline(&mut sprite_sheet.name).hint_text("sprites").desired_width(120.0))
+                .on_hover_text(tr!("Nom de la planche et de l'atlas (sans les extensions .png et .json)"));
+            ui.add(egui::DragValue::new(&mut sprite_sheet.padding).range(0..=64).prefix(tr!("espace ")).suffix(tr!(" px")))
+                .on_hover_text(tr!("Espace entre deux sprites, pour éviter que le filtrage ne déborde sur les voisins."));
+            ui.add(egui::DragValue::new(&mut sprite_sheet.max_width).range(64..=16384).prefix(tr!("larg. ≤ ")).suffix(tr!(" px")))
+                .on_hover_text(tr!("Largeur maximale de la planche, élargie si une image la dépasse."));
+        });
+    });
+}
+
+/// Rend l'option de l'archive ZIP regroupant les fichiers produits et son nom.
+pub fn render_archive_options(ui: &mut egui::Ui, archive: &mut ArchiveOptions) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut archive.enabled, tr!("Archive ZIP"))
+            .on_hover_text(tr!("Regrouper les fichiers produits dans une archive .zip à la racine du dossier de sortie, au lieu de fichiers épars (arborescence conservée) : pratique pour un envoi par courriel ou un CMS."));
+        ui.add_enabled(
+            archive.enabled,
+            egui::TextEdit::singleline(&mut archive.name).hint_text("conversion").desired_width(120.0),
+        )
+        .on_hover_text(tr!("Nom de l'archive (sans l'extension .zip)"));
+    });
+}
+
+/// Rend les filtres de dimensions et d'orientation appliqués aux lots.
+pub fn render_dimension_filter_options(ui: &mut egui::Ui, filter: &mut DimensionFilter) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Filtrer:")).strong());
+        ui.add(egui::DragValue::new(&mut filter.min_width).range(0..=20000).prefix(tr!("larg. ≥ ")).suffix(tr!(" px")))
+            .on_hover_text(tr!("Largeur minimale (0 = aucune) : les images plus étroites sont ignorées."));
+        ui.add(egui::DragValue::new(&mut filter.min_height).range(0..=20000).prefix(tr!("haut. ≥ ")).suffix(tr!(" px")))
+            .on_hover_text(tr!("Hauteur minimale (0 = aucune) : les images moins hautes sont ignorées."));
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr!("Orientation:"));
+        radio_group(ui, "orientation_filter_group", tr!("Orientation"), |ui| {
+            for orientation in [Orientation::All, Orientation::Landscape, Orientation::Portrait, Orientation::Square] {
+                ui.radio_value(&mut filter.orientation, orientation, orientation.label());
+            }
+        });
+    });
+}
+
+/// Rend les motifs glob d'inclusion et d'exclusion appliqués aux fichiers d'un répertoire.
+/// Un motif invalide est signalé sous les champs.
+pub fn render_path_filter_options(ui: &mut egui::Ui, filter: &mut PathFilter) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Inclure:")).strong());
+        ui.add(egui::TextEdit::singleline(&mut filter.include).hint_text("*.png, photos/**").desired_width(160.0))
+            .on_hover_text(tr!("Motifs des fichiers à convertir, séparés par des virgules (vide = tous). Chemins relatifs au répertoire d'entrée."));
+        ui.label(egui::RichText::new(tr!("Exclure:")).strong());
+        ui.add(egui::TextEdit::singleline(&mut filter.exclude).hint_text("**/thumbnails/**").desired_width(160.0))
+            .on_hover_text(tr!("Motifs des fichiers à ignorer, séparés par des virgules. Prioritaires sur l'inclusion."));
+    });
+    if let Err(e) = filter.compile() {
+        ui.colored_label(egui::Color32::RED, e.to_string());
+    }
+}
+
+/// Rend les filtres de taille minimale et de date de modification appliqués aux fichiers d'un répertoire.
+/// Une date mal formée est signalée sous les champs.
+pub fn render_file_filter_options(ui: &mut egui::Ui, filter: &mut FileFilter) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Taille ≥")).strong());
+        ui.add(egui::DragValue::new(&mut filter.min_size_kb).range(0..=1_000_000).suffix(tr!(" Ko")))
+            .on_hover_text(tr!("Taille minimale (0 = aucune) : les fichiers plus petits sont ignorés."));
+        ui.label(egui::RichText::new(tr!("Modifiés depuis le")).strong());
+        ui.add(egui::TextEdit::singleline(&mut filter.modified_after).hint_text(tr!("AAAA-MM-JJ")).desired_width(90.0))
+            .on_hover_text(tr!("Seuls les fichiers modifiés ce jour-là ou après sont convertis (vide = tous)."));
+    });
+    if let Err(e) = filter.compile() {
+        ui.colored_label(egui::Color32::RED, e.to_string());
+    }
+}
+
+/// Rend la section repliable de la détection des doublons parmi les sources d'un répertoire.
+pub fn render_duplicate_options(ui: &mut egui::Ui, options: &mut DuplicateOptions) {
+    egui::CollapsingHeader::new(tr!("Doublons"))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.checkbox(&mut options.enabled, tr!("Repérer les doublons"))
+                .on_hover_text(tr!("Comparer l'empreinte perceptuelle des sources avant la conversion : les images identiques ou presque (autre format, autre taille, recompressées) sont signalées dans le bilan. La plus grande de chaque groupe est gardée."));
+            ui.add_enabled_ui(options.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut options.exclude, tr!("Ne pas les convertir"))
+                        .on_hover_text(tr!("Écarter les doublons de la conversion comme des fichiers filtrés (sinon ils sont seulement signalés)."));
+                    ui.add(egui::DragValue::new(&mut options.threshold).range(0..=duplicates::MAX_THRESHOLD).prefix(tr!("écart ≤ ")))
+                        .on_hover_text(tr!("Écart maximal entre deux empreintes, sur 64 bits : 0 pour les images identiques seulement, 4 à 8 pour tolérer recadrages légers et retouches."));
+                });
+            });
+        });
+}
+
+/// Rend la section repliable « Options avancées » du parcours d'un répertoire :
+/// profondeur maximale, liens symboliques et fichiers cachés.
+pub fn render_traversal_options(ui: &mut egui::Ui, traversal: &mut TraversalOptions) {
+    egui::CollapsingHeader::new(tr!("Options avancées"))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("Profondeur maximale:"));
+                ui.add(egui::DragValue::new(&mut traversal.max_depth).range(0..=100).custom_formatter(|depth, _| {
+                    match depth as usize {
+                        0 => tr!("illimitée").to_string(),
+                        1 => tr!("racine seule").to_string(),
+                        depth => tr!("{} niveaux", depth),
+                    }
+                }))
+                .on_hover_text(tr!("Nombre de niveaux parcourus, racine comprise (0 = tous les sous-répertoires)."));
+            });
+            ui.checkbox(&mut traversal.follow_symlinks, tr!("Suivre les liens symboliques"))
+                .on_hover_text(tr!("Convertir les images et parcourir les répertoires désignés par des liens symboliques."));
+            ui.checkbox(&mut traversal.skip_hidden, tr!("Ignorer les fichiers et dossiers cachés"))
+                .on_hover_text(tr!("Ignorer les éléments dont le nom commence par un point (ou marqués cachés sous Windows), et le contenu des dossiers cachés."));
+        });
+}
+
+/// Rend le choix de l'organisation des sorties d'un répertoire : arborescence reproduite ou à plat.
+pub fn render_output_structure_options(ui: &mut egui::Ui, output_structure: &mut OutputStructure) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Sorties:")).strong());
+        radio_group(ui, "output_structure_group", tr!("Sorties"), |ui| {
+            ui.radio_value(output_structure, OutputStructure::Mirror, OutputStructure::Mirror.label())
+                .on_hover_text(tr!("Recréer les sous-dossiers de la source dans le dossier de sortie."));
+            ui.radio_value(output_structure, OutputStructure::Flatten, OutputStructure::Flatten.label())
+                .on_hover_text(tr!("Placer tous les fichiers produits dans le dossier de sortie ; les noms en double sont préfixés par leur dossier parent (ex: drafts_banner), puis numérotés."));
+            ui.radio_value(output_structure, OutputStructure::ByDate, OutputStructure::ByDate.label())
+                .on_hover_text(tr!("Ranger les fichiers produits dans des sous-dossiers année/mois (ex: 2024/06/) d'après la date de prise de vue EXIF, à défaut la date de modification. Idéal pour archiver le contenu d'une carte mémoire."));
+        });
+    });
+}
+
+/// Rend la section du choix de départage des noms en double (sélection de plusieurs fichiers).
+pub fn render_collision_options(ui: &mut egui::Ui, collision_strategy: &mut CollisionStrategy) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Noms en double:")).strong());
+        radio_group(ui, "collision_strategy_group", tr!("Noms en double"), |ui| {
+            ui.radio_value(collision_strategy, CollisionStrategy::Ask, tr!("Demander"))
+                .on_hover_text(tr!("Demander avant la conversion si plusieurs fichiers portent le même nom."));
+            ui.radio_value(collision_strategy, CollisionStrategy::NumericSuffix, tr!("Suffixe numérique"))
+                .on_hover_text(tr!("Ajouter un suffixe aux doublons (ex: banner-1)."));
+            ui.radio_value(collision_strategy, CollisionStrategy::ParentPrefix, tr!("Préfixe du dossier"))
+                .on_hover_text(tr!("Préfixer les doublons par leur dossier parent (ex: drafts_banner)."));
+        });
+    });
+}
+
+/// Rend la fenêtre demandant comment départager les noms en double.
+/// Retourne `Some(Some(stratégie))` si l'utilisateur a choisi, `Some(None)` s'il a annulé,
+/// et `None` tant qu'aucun choix n'a été fait.
+pub fn render_collision_dialog(ctx: &egui::Context, collisions: &[String], extension: &str) -> Option<Option<CollisionStrategy>> {
+    let mut choice = None;
+
+    egui::Window::new(tr!("Noms en double"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(tr!("Plusieurs fichiers sélectionnés produiraient le même nom de sortie :"));
+            egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                for stem in collisions {
+                    ui.label(format!("• {}.{}", stem, extension));
+                }
+            });
+            ui.add_space(15.0);
+            ui.horizontal(|ui| {
+                if ui.button(tr!("Suffixe numérique"))
+                    .on_hover_text(tr!("Ex: banner.{}, banner-1.{}", extension, extension))
+                    .clicked()
+                {
+                    choice = Some(Some(CollisionStrategy::NumericSuffix));
+                }
+                if ui.button(tr!("Préfixe du dossier"))
+                    .on_hover_text(tr!("Ex: holiday_banner.{}, drafts_banner.{}", extension, extension))
+                    .clicked()
+                {
+                    choice = Some(Some(CollisionStrategy::ParentPrefix));
+                }
+                if ui.button(tr!("Annuler")).clicked() {
+                    choice = Some(None);
+                }
+            });
+        });
+
+    choice
+}
+
+/// Rend la fenêtre « Historique » : un lot passé par ligne (date, sources, réglages, fichiers et gain),
+/// avec ses actions. Retourne l'action choisie.
+pub fn render_history_window(ctx: &egui::Context, open: &mut bool, entries: &[HistoryEntry]) -> Option<HistoryAction> {
+    let mut action = None;
+
+    egui::Window::new(tr!("Historique"))
+        .open(open)
+        .collapsible(false)
+        .default_width(560.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            if entries.is_empty() {
+                ui.weak(tr!("Aucun lot terminé pour l'instant."));
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                egui::Grid::new("history_grid").num_columns(5).striped(true).show(ui, |ui| {
+                    for entry in entries {
+                        ui.label(entry.finished_at.format("%Y-%m-%d %H:%M").to_string());
+                        ui.label(&entry.label).on_hover_text(entry.output_root.display().to_string());
+                        ui.label(entry.settings_summary());
+                        let mut counts = tr!("{} converti(s)", format_count(entry.converted));
+                        if entry.failed > 0 {
+                            counts += &tr!(", {} en échec", format_count(entry.failed));
+                        }
+                        if let Some(percent) = entry.savings_percent() {
+                            counts += &format!(" · {:.1} %", percent);
+                        }
+                        if entry.cancelled {
+                            counts += tr!(" (annulé)");
+                        }
+                        ui.label(counts).on_hover_text(tr!(
+                            "{} → {}, {} ignoré(s)",
+                            format_size(entry.input_bytes),
+                            format_size(entry.output_bytes),
+                            format_count(entry.skipped)
+                        ));
+                        ui.horizontal(|ui| {
+                            if ui.small_button(tr!("↻ Relancer"))
+                                .on_hover_text(tr!("Convertir à nouveau les mêmes sources avec les mêmes réglages"))
+                                .clicked()
+                            {
+                                action = Some(HistoryAction::Rerun(entry.id));
+                            }
+                            if ui.small_button("📂").on_hover_text(tr!("Ouvrir le dossier de sortie")).clicked() {
+                                action = Some(HistoryAction::OpenFolder(entry.id));
+                            }
+                            if ui.small_button("🗑").on_hover_text(tr!("Retirer de l'historique")).clicked() {
+                                action = Some(HistoryAction::Remove(entry.id));
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            });
+            ui.add_space(10.0);
+            if ui.button(tr!("Vider l'historique")).clicked() {
+                action = Some(HistoryAction::Clear);
+            }
+        });
+
+    action
+}
+
+/// Rend le bouton « Annuler la dernière conversion ».
+pub fn render_undo_button(ui: &mut egui::Ui) -> egui::Response {
+    ui.small_button(tr!("↶ Annuler la dernière conversion"))
+        .on_hover_text(tr!("Supprimer les fichiers créés par le dernier lot terminé (la liste est affichée avant la suppression)"))
+}
+
+/// Rend la fenêtre de confirmation de l'annulation de la dernière conversion : fichiers à supprimer,
+/// originaux à remettre en place, et ce qui ne peut pas être rétabli. Retourne `Some(true)` pour confirmer.
+pub fn render_undo_dialog(ctx: &egui::Context, plan: &UndoPlan) -> Option<bool> {
+    let mut choice = None;
+
+    egui::Window::new(tr!("Annuler la dernière conversion"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(tr!("Ces {} fichier(s) créés par la dernière conversion seront supprimés :", format_count(plan.files.len())));
+            egui::ScrollArea::vertical().id_salt("undo_files").max_height(200.0).show(ui, |ui| {
+                for file in &plan.files {
+                    ui.label(format!("• {}", file.display()));
+                }
+            });
+            if !plan.restored.is_empty() {
+                ui.label(tr!("{} original(aux) déplacé(s) seront remis à leur place.", format_count(plan.restored.len())));
+            }
+            if plan.replaced > 0 {
+                ui.weak(tr!("{} sortie(s) ayant remplacé un fichier existant sont conservées : leur version précédente ne peut pas être rétablie.", format_count(plan.replaced)));
+            }
+            if plan.trashed > 0 {
+                ui.weak(tr!("{} original(aux) mis à la corbeille n'en sont pas restaurés.", format_count(plan.trashed)));
+            }
+            ui.add_space(15.0);
+            ui.horizontal(|ui| {
+                if ui.button(tr!("🗑 Supprimer")).clicked() {
+                    choice = Some(true);
+                }
+                if ui.button(tr!("Conserver les fichiers")).clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    choice
+}
+
+/// Rend la fenêtre d'avertissement affichée avant la conversion d'un répertoire quand l'espace libre du volume
+/// de sortie semble insuffisant. Retourne `Some(true)` pour convertir quand même, `Some(false)` pour annuler.
+pub fn render_free_space_dialog(ctx: &egui::Context, estimated: u64, available: u64, output_dir: &Path) -> Option<bool> {
+    let mut choice = None;
+
+    egui::Window::new(tr!("Espace disque insuffisant"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(tr!("La conversion pourrait occuper environ {}, mais il ne reste que {} sur le volume de {}.",
+                format_size(estimated), format_size(available), output_dir.display()));
+            ui.weak(tr!("Estimation d'après la taille des images sources et le format choisi : la taille réelle peut être moindre."));
+            ui.add_space(15.0);
+            ui.horizontal(|ui| {
+                if ui.button(tr!("Convertir quand même")).clicked() {
+                    choice = Some(true);
+                }
+                if ui.button(tr!("Annuler")).clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    choice
+}
+
+/// Rend la fenêtre proposée au lancement quand des lots ont été interrompus lors d'une session précédente.
+/// Retourne `Some(true)` pour les reprendre, `Some(false)` pour les abandonner.
+pub fn render_interrupted_jobs_dialog(ctx: &egui::Context, jobs: &[SavedJob]) -> Option<bool> {
+    let mut choice = None;
+
+    egui::Window::new(tr!("Travail interrompu"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(tr!("Des conversions ont été interrompues avant leur fin :"));
+            egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                for saved in jobs {
+                    let completed = saved.completed().count();
+                    ui.label(tr!("• {} ({} fichier(s) déjà traité(s))", saved.label, completed));
+                }
+            });
+            ui.add_space(15.0);
+            ui.horizontal(|ui| {
+                if ui.button(tr!("Reprendre le travail interrompu"))
+                    .on_hover_text(tr!("Convertir les fichiers restants, sans reconvertir ceux déjà traités"))
+                    .clicked()
+                {
+                    choice = Some(true);
+                }
+                if ui.button(tr!("Ignorer")).clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    choice
+}
+
+/// Rend la fenêtre de conflit du mode Demander : la sortie `output` existe déjà.
+/// Retourne la réponse choisie ; `apply_to_all` reflète la case « Appliquer à tous les fichiers suivants ».
+pub fn render_conflict_dialog(ctx: &egui::Context, output: &Path, apply_to_all: &mut bool) -> Option<ConflictChoice> {
+    let mut choice = None;
+
+    egui::Window::new(tr!("Fichier existant"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(tr!("Le fichier de sortie existe déjà :"));
+            ui.label(egui::RichText::new(output.display().to_string()).strong());
+            if let Ok(metadata) = std::fs::metadata(output) {
+                ui.label(tr!("Taille actuelle : {}", format_size(metadata.len())));
+            }
+            ui.add_space(10.0);
+            ui.checkbox(apply_to_all, tr!("Appliquer à tous les fichiers suivants"));
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(tr!("Écraser")).on_hover_text(tr!("Remplacer le fichier existant")).clicked() {
+                    choice = Some(ConflictChoice::Overwrite);
+                }
+                if ui.button(tr!("Ignorer")).on_hover_text(tr!("Conserver le fichier existant et passer à la suite")).clicked() {
+                    choice = Some(ConflictChoice::Skip);
+                }
+                if ui.button(tr!("Renommer")).on_hover_text(tr!("Écrire le nouveau fichier avec un suffixe (ex: image-1)")).clicked() {
+                    choice = Some(ConflictChoice::Rename);
+                }
+            });
+        });
+
+    choice
+}
+
+/// Rend le contrôle préalable des collisions : le bouton qui relève les sorties déjà présentes, puis leur sort
+/// selon chaque mode d'écrasement. Cliquer un mode le sélectionne. Retourne vrai si un relevé est demandé.
+pub fn render_collision_check(
+    ui: &mut egui::Ui,
+    state: Option<&CollisionCheck>,
+    overwrite_mode: &mut OverwriteMode,
+    run_folder: bool,
+) -> bool {
+    let mut requested = false;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("Sorties existantes:")).strong());
+        if run_folder {
+            ui.weak(tr!("aucune (nouveau sous-dossier d'exécution)"));
+            return;
+        }
+        match state {
+            Some(CollisionCheck::Checking) => {
+                ui.spinner();
+                ui.label(tr!("relevé en cours…"));
+            }
+            _ => {
+                requested = ui
+                    .small_button(tr!("🔍 Vérifier"))
+                    .on_hover_text(tr!("Parcourir la sélection et compter, sans rien écrire, les sorties déjà présentes dans le répertoire de sortie et ce qu'en ferait chaque mode d'écrasement."))
+                    .clicked();
+            }
+        }
+    });
+    match state {
+        Some(CollisionCheck::Done(preflight)) if preflight.existing == 0 => {
+            ui.label(tr!("Aucune sortie existante : {} fichier(s) à écrire.", format_count(preflight.planned)));
+        }
+        Some(CollisionCheck::Done(preflight)) => {
+            ui.label(tr!(
+                "{} sortie(s) existante(s) sur {} prévue(s) :",
+                format_count(preflight.existing),
+                format_count(preflight.planned)
+            ));
+            for mode in OverwriteMode::ALL {
+                let text = format!("{} : {}", mode.label(), collision_outcome_text(&preflight.outcome(mode)));
+                ui.selectable_value(overwrite_mode, mode, text)
+                    .on_hover_text(tr!("Choisir ce mode d'écrasement pour la conversion."));
+            }
+        }
+        Some(CollisionCheck::Failed(error)) => {
+            ui.colored_label(ui.visuals().error_fg_color, tr!("Relevé impossible : {}", error));
+        }
+        _ => {}
+    }
+    requested
+}
+
+/// Résumé du sort des sorties existantes (ex: « 3 ignorée(s), 2 écrasée(s) »).
+fn collision_outcome_text(outcome: &CollisionOutcome) -> String {
+    let parts: Vec<String> = [
+        (outcome.skipped, tr!("{} ignorée(s)", format_count(outcome.skipped))),
+        (outcome.overwritten, tr!("{} écrasée(s)", format_count(outcome.overwritten))),
+        (outcome.renamed, tr!("{} renommée(s)", format_count(outcome.renamed))),
+        (outcome.asked, tr!("{} question(s)", format_count(outcome.asked))),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(_, text)| text)
+    .collect();
+    parts.join(", ")
+}
+
+/// Rend la case Simulation : le lot suivant prévoit chaque fichier sans rien écrire.
+pub fn render_dry_run_option(ui: &mut egui::Ui, dry_run: &mut bool) {
+    ui.checkbox(dry_run, tr!("Simulation (ne rien écrire)"))
+        .on_hover_text(tr!("Parcourir l'entrée avec les filtres et règles de nommage actuels et lister les fichiers qui seraient convertis, ignorés ou écrasés, sans rien écrire."));
+}
+
+/// Rend le bouton de conversion.
+pub fn render_convert_button(ui: &mut egui::Ui, enabled: bool) -> egui::Response {
+    ui.style_mut().text_styles.get_mut(&egui::TextStyle::Button).unwrap().size = 18.0; // Bouton plus grand
+    ui.add_enabled(
+        enabled,
+        egui::Button::new(tr!("🚀 Convertir les images"))
+            .fill(egui::Color32::from_rgb(50, 150, 250)) // Couleur de fond du bouton (bleu vif)
+            .stroke(egui::Stroke::new(0.0, egui::Color32::TRANSPARENT)), // Pas de bordure visible
+    )
+        .on_hover_text(with_shortcut(ui.ctx(), tr!("Lancer la conversion des images dans le format choisi"), &CONVERT_SHORTCUT))
+}
+
+/// Rend la fenêtre modale pour les messages critiques (erreurs graves ou demande d'ouvrir dossier).
+/// Si `retry_count` est non nul, un bouton propose de relancer les fichiers en échec ;
+/// retourne `true` s'il a été cliqué.
+pub fn render_dialog_window(
+    ctx: &egui::Context,
+    show_dialog: &mut bool,
+    dialog_message: &mut Option<String>,
+    output_dir: &Path,
+    retry_count: usize,
+) -> bool {
+    use std::process::exit; // Déplacé ici
+
+    let mut retry_clicked = false;
+
+    egui::Window::new(tr!("Information"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            egui::ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+                ui.label(dialog_message.as_deref().unwrap_or_default());
+            });
+            ui.add_space(15.0);
+            ui.horizontal(|ui| {
+                if retry_count > 0
+                    && ui.button(tr!("Réessayer les échecs ({})", retry_count))
+                        .on_hover_text(tr!("Relancer uniquement les fichiers en échec, avec les mêmes options"))
+                        .clicked()
+                {
+                    retry_clicked = true;
+                    *show_dialog = false;
+                    *dialog_message = None;
+                }
+                if ui.button(tr!("Ouvrir le dossier")).clicked() {
+                    let result = platform_utils::open_output_directory(output_dir);
+                    if let Err(e) = result {
+                        *dialog_message = Some(tr!("Erreur lors de l'ouverture du dossier : {}", e));
+                        // show_dialog reste true pour afficher le nouveau message d'erreur
+                    } else {
+                        *show_dialog = false; // Ferme la modale si l'ouverture réussit
+                        exit(0); // Quitte l'application
+                    }
+                }
+                if ui.button(tr!("Fermer")).clicked() {
+                    *show_dialog = false;
+                    *dialog_message = None;
+                }
+            });
+        });
+
+    retry_clicked
+}
+
+/// Action demandée depuis la fenêtre du bilan.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReportAction {
+    Export,        // Exporter le rapport en CSV ou JSON
+    RetrySelected, // Relancer les fichiers en échec cochés
+}
+
+/// Rend la fenêtre du bilan d'un lot : fichiers convertis, ignorés et en échec, tailles avant et après, gain.
+/// Les fichiers en échec peuvent être cochés (`retry_selection`) pour n'en relancer qu'une partie, éventuellement
+/// avec les réglages actuels (`retry_with_current`). Retourne l'action demandée.
+pub fn render_report_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    report: &ConversionReport,
+    retry_selection: &mut HashSet<PathBuf>,
+    retry_with_current: &mut bool,
+) -> Option<ReportAction> {
+    let mut action = None;
+    egui::Window::new(tr!("Bilan de la conversion"))
+        .open(open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            if report.options.dry_run {
+                render_plan(ui, report);
+            } else {
+                egui::Grid::new("report_grid").num_columns(2).spacing([20.0, 6.0]).show(ui, |ui| {
+                    ui.label(tr!("Fichiers convertis"));
+                    ui.label(format_count(report.converted()));
+                    ui.end_row();
+                    ui.label(tr!("Fichiers ignorés"));
+                    ui.label(format_count(report.skipped()));
+                    ui.end_row();
+                    let chosen_formats: Vec<String> = OutputFormat::SMALLEST_CANDIDATES
+                        .iter()
+                        .filter_map(|format| {
+                            let count = report.files.iter().filter(|file| !file.candidate_sizes.is_empty() && file.format == *format).count();
+                            (count > 0).then(|| format!("{} : {}", format.label(), format_count(count)))
+                        })
+                        .collect();
+                    if !chosen_formats.is_empty() {
+                        ui.label(tr!("Formats retenus"));
+                        ui.label(chosen_formats.join(", "));
+                        ui.end_row();
+                    }
+                    if report.kept_originals() > 0 {
+                        ui.label(tr!("Originaux conservés (sortie plus lourde)"));
+                        ui.label(format_count(report.kept_originals()));
+                        ui.end_row();
+                    }
+                    if report.removed_originals() > 0 {
+                        ui.label(tr!("Originaux retirés"));
+                        ui.label(format_count(report.removed_originals()));
+                        ui.end_row();
+                    }
+                    if report.filtered_by_dimensions > 0 {
+                        ui.label(tr!("Fichiers filtrés"));
+                        ui.label(format_count(report.filtered_by_dimensions));
+                        ui.end_row();
+                    }
+                    if report.filtered_by_file > 0 {
+                        ui.label(tr!("Fichiers filtrés (taille ou date)"));
+                        ui.label(format_count(report.filtered_by_file));
+                        ui.end_row();
+                    }
+                    ui.label(tr!("Fichiers en échec"));
+                    ui.label(format_count(report.failures.len()));
+                    ui.end_row();
+                    if let Some(adjustments) = report.options.adjustments.description() {
+                        ui.label(tr!("Réglages appliqués"));
+                        ui.label(adjustments);
+                        ui.end_row();
+                    }
+                    if report.verification_failures() > 0 {
+                        ui.label(tr!("Vérifications en échec"));
+                        ui.colored_label(egui::Color32::from_rgb(180, 40, 40), format_count(report.verification_failures()));
+                        ui.end_row();
+                    }
+                    if let Some((psnr, ssim)) = report.average_quality() {
+                        ui.label(tr!("Fidélité moyenne"));
+                        ui.label(format!("PSNR {:.1} dB · SSIM {:.4}", psnr, ssim));
+                        ui.end_row();
+                    }
+                    ui.label(tr!("Taille des sources"));
+                    ui.label(format_size(report.input_bytes()));
+                    ui.end_row();
+                    ui.label(tr!("Taille des sorties"));
+                    ui.label(format_size(report.output_bytes()));
+                    ui.end_row();
+                    ui.label(egui::RichText::new(tr!("Gain")).strong());
+                    match report.savings_percent() {
+                        Some(percent) if percent >= 0.0 => ui.label(egui::RichText::new(tr!(
+                            "{} % ({} économisés)",
+                            format!("{:.1}", percent),
+                            format_size(report.input_bytes() - report.output_bytes())
+                        )).strong()),
+                        Some(percent) => ui.colored_label(
+                            egui::Color32::from_rgb(180, 40, 40),
+                            tr!("{} % (sorties plus lourdes de {})", format!("{:.1}", percent), format_size(report.output_bytes() - report.input_bytes())),
+                        ),
+                        None => ui.weak("—"),
+                    };
+                    ui.end_row();
+                });
+                // Confirmation, fichier par fichier, du sort des originaux
+                if report.options.originals != OriginalsMode::Keep && !report.files.is_empty() {
+                    ui.add_space(6.0);
+                    egui::CollapsingHeader::new(tr!("Détail des originaux")).show(ui, |ui| {
+                        egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                            for file in &report.files {
+                                let name = file.source.file_name().unwrap_or_default().to_string_lossy();
+                                let text = format!("{} : {}", name, file.original.description());
+                                match file.original {
+                                    OriginalOutcome::Failed(_) => ui.colored_label(egui::Color32::from_rgb(180, 40, 40), text),
+                                    _ => ui.label(text),
+                                }
+                                .on_hover_text(file.source.display().to_string());
+                            }
+                        });
+                    });
+                }
+                // Taille de la palette retenue pour chaque PNG indexé
+                if report.files.iter().any(|file| file.palette_size.is_some()) {
+                    egui::CollapsingHeader::new(tr!("Palettes des PNG indexés")).show(ui, |ui| {
+                        egui::ScrollArea::vertical().id_salt("palettes").max_height(150.0).show(ui, |ui| {
+                            for file in &report.files {
+                                if let Some(palette_size) = file.palette_size {
+                                    let name = file.output.file_name().unwrap_or_default().to_string_lossy();
+                                    ui.label(format!("{} : {}", name, tr!("{} couleur(s)", palette_size)))
+                                        .on_hover_text(file.output.display().to_string());
+                                }
+                            }
+                        });
+                    });
+                }
+                if report.verification_failures() > 0 {
+                    egui::CollapsingHeader::new(tr!("Sorties en échec de vérification")).default_open(true).show(ui, |ui| {
+                        egui::ScrollArea::vertical().id_salt("verification_failures").max_height(150.0).show(ui, |ui| {
+                            for file in report.files.iter().filter(|file| file.verification.is_failed()) {
+                                let name = file.output.file_name().unwrap_or_default().to_string_lossy();
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(180, 40, 40),
+                                    format!("{} : {}", name, file.verification.description()),
+                                )
+                                .on_hover_text(file.output.display().to_string());
+                            }
+                        });
+                    });
+                }
+                if !report.failures.is_empty() && render_failure_selection(ui, report, retry_selection, retry_with_current) {
+                    action = Some(ReportAction::RetrySelected);
+                }
+                if report.cancelled {
+                    ui.weak(tr!("Conversion annulée avant la fin : seuls les fichiers déjà convertis sont comptés."));
+                }
+            }
+            ui.add_space(10.0);
+            if ui.button(tr!("💾 Exporter le rapport"))
+                .on_hover_text(tr!("Enregistrer le résultat de chaque fichier (source, sortie, tailles, état, erreur) en CSV ou JSON"))
+                .clicked()
+            {
+                action = Some(ReportAction::Export);
+            }
+        });
+    action
+}
+
+/// Rend la liste des fichiers en échec du bilan, chacun avec sa case à cocher, et le bouton « Réessayer ».
+/// Retourne `true` si la relance des fichiers cochés est demandée.
+fn render_failure_selection(
+    ui: &mut egui::Ui,
+    report: &ConversionReport,
+    selection: &mut HashSet<PathBuf>,
+    with_current_settings: &mut bool,
+) -> bool {
+    let mut retry_clicked = false;
+    egui::CollapsingHeader::new(tr!("Fichiers en échec")).id_salt("failed_files").default_open(true).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            if ui.small_button(tr!("Tout cocher")).clicked() {
+                selection.extend(report.failures.iter().map(|failure| failure.source.clone()));
+            }
+            if ui.small_button(tr!("Tout décocher")).clicked() {
+                selection.clear();
+            }
+        });
+        egui::ScrollArea::vertical().id_salt("failed_files_scroll").max_height(150.0).show(ui, |ui| {
+            for failure in &report.failures {
+                let mut checked = selection.contains(&failure.source);
+                let name = failure.source.file_name().unwrap_or_default().to_string_lossy();
+                let text = egui::RichText::new(format!("{} : {}", name, failure.error)).color(egui::Color32::from_rgb(180, 40, 40));
+                if ui.checkbox(&mut checked, text).on_hover_text(failure.source.display().to_string()).changed() {
+                    match checked {
+                        true => selection.insert(failure.source.clone()),
+                        false => selection.remove(&failure.source),
+                    };
+                }
+            }
+        });
+        ui.checkbox(with_current_settings, tr!("Avec les réglages actuels"))
+            .on_hover_text(tr!("Relancer avec le format, la qualité et les traitements choisis maintenant, plutôt qu'avec ceux du lot"));
+        let selected = report.failures.iter().filter(|failure| selection.contains(&failure.source)).count();
+        if ui.add_enabled(selected > 0, egui::Button::new(tr!("Réessayer ({})", format_count(selected))))
+            .on_hover_text(tr!("Relancer uniquement les fichiers cochés"))
+            .clicked()
+        {
+            retry_clicked = true;
+        }
+    });
+    retry_clicked
+}
+
+/// Rend le bilan d'une simulation : décompte, puis action prévue pour chaque source.
+fn render_plan(ui: &mut egui::Ui, report: &ConversionReport) {
+    let (written, overwritten) = report.planned_counts();
+    ui.label(egui::RichText::new(tr!("Simulation : aucun fichier n'a été écrit.")).strong());
+    ui.label(tr!(
+        "{} fichier(s) à convertir, dont {} écrasant une sortie existante ; {} ignoré(s) ; {} en échec",
+        format_count(written),
+        format_count(overwritten),
+        format_count(report.planned.len() - written),
+        format_count(report.failures.len())
+    ));
+    ui.add_space(6.0);
+    egui::ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+        for file in &report.planned {
+            let name = file.source.file_name().unwrap_or_default().to_string_lossy();
+            let output = file.output.as_ref().map_or(String::new(), |output| {
+                format!(" → {}", output.file_name().unwrap_or_default().to_string_lossy())
+            });
+            let text = format!("{}{} : {}", name, output, file.action.description());
+            match file.action {
+                PlannedAction::Overwrite => ui.colored_label(egui::Color32::from_rgb(200, 120, 0), text),
+                PlannedAction::Skip(_) => ui.weak(text),
+                _ => ui.label(text),
+            }
+            .on_hover_text(file.source.display().to_string());
+        }
+        for failure in &report.failures {
+            ui.colored_label(
+                egui::Color32::from_rgb(180, 40, 40),
+                format!("{} : {}", failure.source.file_name().unwrap_or_default().to_string_lossy(), failure.error),
+            );
+        }
+    });
+}
+
+/// Rend un "toast" de notification temporaire, avec un bouton optionnel (`action_label`).
+/// Retourne `true` si le bouton a été cliqué.
+pub fn render_toast(
+    ctx: &egui::Context,
+    show_toast: &mut bool,
+    message: &str,
+    is_error: bool,
+    action_label: Option<&str>,
+) -> bool {
+    let toast_color = if is_error {
+        egui::Color32::from_rgb(255, 100, 100) // Rouge pour les erreurs
+    } else {
+        egui::Color32::from_rgb(100, 200, 100) // Vert pour les succès
+    };
+
+    let text_color = egui::Color32::WHITE;
+    let mut action_clicked = false;
+
+    let window = egui::Window::new("")
+        .id(egui::Id::new("toast_window"))
+        .collapsible(false)
+        .resizable(false)
+        .title_bar(false)
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-20.0, -20.0)) // Coin inférieur droit
+        .frame(egui::Frame::window(&ctx.style()).fill(toast_color).stroke(egui::Stroke::new(0.0, egui::Color32::TRANSPARENT)).corner_radius(8.0)) // Correction de Stroke::none() et rounding()
+        .show(ctx, |ui| {
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.add_space(10.0);
+                let label_response = ui.label(egui::RichText::new(message).color(text_color).strong());
+                ui.ctx().accesskit_node_builder(label_response.id, |node| {
+                    node.set_live(egui::accesskit::Live::Polite);
+                });
+                if let Some(label) = action_label {
+                    if ui.button(label).clicked() {
+                        action_clicked = true;
+                    }
+                }
+                ui.add_space(10.0);
+            });
+            ui.add_space(5.0);
+        });
+
+    // Le toast reste affiché tant que la souris le survole, pour laisser le temps de cliquer sur le bouton.
+    let is_hovered = window.is_some_and(|w| w.response.contains_pointer());
+
+    // Optionnel: faire disparaître le toast après quelques secondes
+    if *show_toast {
+        let current_time = ctx.input(|i| i.time);
+        let start_time_id = egui::Id::new("toast_start_time");
+        if is_hovered {
+            ctx.data_mut(|data| data.insert_temp(start_time_id, current_time));
+        }
+        let start_time: f64 = ctx.data_mut(|data| *data.get_temp_mut_or_insert_with(start_time_id, || current_time));
+
+        if current_time - start_time > 3.0 { // Toast disparaît après 3 secondes
+            *show_toast = false;
+            ctx.data_mut(|data| data.remove::<f64>(start_time_id)); // Supprime le temps de début
+        } else {
+            ctx.request_repaint_after(Duration::from_millis(50)); // Redessine pour le timer
+        }
+    }
+
+    action_clicked
+}
+
+/// Rend le menu de copie du fichier produit par la conversion d'une image unique dans le presse-papiers.
+/// Retourne le contenu à copier choisi par l'utilisateur, s'il y en a un.
+pub fn render_copy_output_menu(ui: &mut egui::Ui) -> Option<CopyContent> {
+    let mut choice = None;
+    ui.menu_button(tr!("📋 Copier dans le presse-papiers"), |ui| {
+        for content in [CopyContent::Image, CopyContent::Path] {
+            if ui.button(content.label()).clicked() {
+                choice = Some(content);
+                ui.close_menu();
+            }
+        }
+    })
+        .response
+        .on_hover_text(tr!("Copier l'image produite, ou son chemin, pour la coller dans une messagerie ou un formulaire d'envoi"));
+    choice
+}
+
+/// Rend la section repliable du mode surveillance : choix du dossier, démarrage/arrêt et journal.
+/// Retourne `true` si l'utilisateur a cliqué sur le bouton de démarrage ou d'arrêt.
+pub fn render_watch_folder_section(ui: &mut egui::Ui, watcher: &FolderWatcher, watch_dir: &mut Option<PathBuf>) -> bool {
+    let mut toggled = false;
+    egui::CollapsingHeader::new(tr!("Surveillance d'un dossier"))
+        .default_open(watcher.is_running())
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let folder = watcher.folder().map(Path::to_path_buf).or_else(|| watch_dir.clone());
+                match &folder {
+                    Some(folder) => ui.label(folder.display().to_string()),
+                    None => ui.weak(tr!("Aucun dossier choisi")),
+                };
+                if ui.add_enabled(!watcher.is_running(), egui::Button::new(tr!("📁 Choisir")))
+                    .on_hover_text(tr!("Choisir le dossier à surveiller"))
+                    .clicked()
+                {
+                    if let Some(path) = FileDialog::new().pick_folder() {
+                        *watch_dir = Some(path);
+                    }
+                }
+                let label = if watcher.is_running() { tr!("⏹ Arrêter") } else { tr!("▶ Démarrer") };
+                if ui.add_enabled(folder.is_some(), egui::Button::new(label))
+                    .on_hover_text(tr!("Convertir automatiquement chaque image déposée dans ce dossier, vers le répertoire de sortie, avec les options actuelles."))
+                    .clicked()
+                {
+                    toggled = true;
+                }
+                if watcher.is_running() {
+                    ui.spinner();
+                }
+            });
+
+            let log = watcher.log();
+            if log.is_empty() {
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(120.0).stick_to_bottom(true).show(ui, |ui| {
+                for entry in &log {
+                    let name = entry.source.file_name().unwrap_or_default().to_string_lossy();
+                    match &entry.result {
+                        Ok(output) => ui.label(format!(
+                            "{} ✔ {} → {}",
+                            entry.time,
+                            name,
+                            output.file_name().unwrap_or_default().to_string_lossy()
+                        )),
+                        Err(error) => ui.colored_label(
+                            egui::Color32::from_rgb(180, 40, 40),
+                            format!("{} ✖ {} : {}", entry.time, name, error),
+                        ),
+                    };
+                }
+            });
+            if ui.small_button(tr!("Vider le journal")).clicked() {
+                watcher.clear_log();
+            }
+        });
+    toggled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::accesskit::{Live, Role};
+    use egui_kittest::kittest::{by, Queryable};
+    use egui_kittest::Harness;
+
+    #[test]
+    fn drag_drop_area_has_accessible_label() {
+        let harness = Harness::new_ui(|ui| {
+            render_drag_drop_area(ui, &mut None, false);
+        });
+        harness.get_by_label_contains("Zone de dépôt");
+    }
+
+    #[test]
+    fn overwrite_options_expose_radio_group() {
+        let mut overwrite_mode = OverwriteMode::Skip;
+        let mut rename_suffix = RenameSuffix::Counter;
+        let harness = Harness::new_ui(|ui| {
+            render_overwrite_options(ui, &mut overwrite_mode, &mut rename_suffix);
+        });
+        let group = harness.get_by_role_and_label(Role::RadioGroup, "Si le fichier existe");
+        assert_eq!(group.query_all(by().role(Role::RadioButton)).count(), 5);
+    }
+
+    #[test]
+    fn completion_toast_is_polite_live_region() {
+        let harness = Harness::new(|ctx| {
+            render_toast(ctx, &mut true, "Conversion terminée", false, None);
+        });
+        assert_eq!(harness.get_by_label("Conversion terminée").live(), Live::Polite);
+    }
+
+    #[test]
+    fn status_announcement_is_live_region() {
+        let harness = Harness::new_ui(|ui| {
+            render_status_announcement(ui, "Conversion terminée", false);
+            render_status_announcement(ui, "Conversion échouée", true);
+        });
+        assert_eq!(harness.get_by_label("Conversion terminée").live(), Live::Polite);
+        assert_eq!(harness.get_by_label("Conversion échouée").live(), Live::Assertive);
     }
 }
@@ -0,0 +1,119 @@
+/// Ce module annule une conversion terminée d'après son bilan : les fichiers qu'elle a créés (sorties, extraits
+/// `srcset`, pages de la planche contact, document PDF, archive ZIP) sont supprimés, les originaux déplacés dans
+/// le dossier de sauvegarde sont remis à leur place, puis les dossiers restés vides sont retirés. Les sorties qui ont
+/// remplacé un fichier existant (mode Écraser) sont conservées : leur version précédente est perdue, les supprimer
+/// n'y changerait rien.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::converter::ConversionReport;
+use crate::originals::OriginalOutcome;
+use crate::run_folder;
+use crate::tr;
+
+/// Modifications qu'annuler la conversion effectuerait, présentées avant confirmation.
+#[derive(Debug, Clone, Default)]
+pub struct UndoPlan {
+    pub files: Vec<PathBuf>,               // Fichiers créés par la conversion, à supprimer
+    pub restored: Vec<(PathBuf, PathBuf)>, // Originaux à remettre en place (sauvegarde, emplacement d'origine)
+    pub replaced: usize,                   // Sorties conservées car elles ont remplacé un fichier existant
+    pub trashed: usize,                    // Originaux mis à la corbeille, qui n'en sont pas restaurés
+    output_root: PathBuf,                  // Racine de la sortie : les dossiers vides sont retirés sous elle
+    run_folder: Option<PathBuf>,           // Sous-dossier de l'exécution, retiré s'il est vide
+}
+
+impl UndoPlan {
+    /// Prépare l'annulation de la conversion décrite par `report`.
+    pub fn from_report(report: &ConversionReport) -> Self {
+        let mut plan = UndoPlan {
+            output_root: report.output_root.clone(),
+            run_folder: report.run_folder.clone(),
+            ..Default::default()
+        };
+        for file in &report.files {
+            match file.replaced {
+                true => plan.replaced += 1,
+                false => plan.files.push(file.output.clone()),
+            }
+            match &file.original {
+                OriginalOutcome::MovedTo(backup) => plan.restored.push((backup.clone(), file.source.clone())),
+                OriginalOutcome::Trashed => plan.trashed += 1,
+                _ => {}
+            }
+        }
+        plan.files.extend(report.srcset_snippets.iter().cloned());
+        plan.files.extend(report.contact_sheets.iter().cloned());
+        plan.files.extend(report.pdf_bundle.iter().cloned());
+        plan.files.extend(report.sprite_sheet.iter().cloned());
+        if let Some(archive) = &report.archive {
+            // Les fichiers produits ne sont plus que dans l'archive : seule celle-ci est supprimée.
+            plan.files = vec![archive.clone()];
+            plan.replaced = 0;
+        }
+        plan
+    }
+}
+
+/// Vrai si la conversion décrite par `report` a créé des fichiers ou déplacé des originaux, et peut donc être annulée.
+pub fn can_undo(report: &ConversionReport) -> bool {
+    !report.srcset_snippets.is_empty()
+        || !report.contact_sheets.is_empty()
+        || report.pdf_bundle.is_some()
+        || !report.sprite_sheet.is_empty()
+        || report.archive.is_some()
+        || report.files.iter().any(|file| !file.replaced || matches!(file.original, OriginalOutcome::MovedTo(_)))
+}
+
+/// Résultat d'une annulation.
+#[derive(Debug, Default)]
+pub struct UndoOutcome {
+    pub removed: usize,      // Fichiers supprimés
+    pub restored: usize,     // Originaux remis en place
+    pub errors: Vec<String>, // Fichiers qui n'ont pas pu être supprimés ou restaurés, avec la raison
+}
+
+/// Annule la conversion selon `plan`. Un fichier déjà absent compte comme supprimé ; un original n'est pas remis
+/// en place si un fichier occupe déjà son emplacement. Les échecs n'interrompent pas l'annulation.
+pub fn undo(plan: &UndoPlan) -> UndoOutcome {
+    let mut outcome = UndoOutcome::default();
+    for file in &plan.files {
+        match fs::remove_file(file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                outcome.errors.push(tr!("{} : suppression impossible ({})", file.display(), e));
+            }
+            _ => {
+                outcome.removed += 1;
+                remove_empty_parents(file, &plan.output_root);
+            }
+        }
+    }
+    for (backup, source) in &plan.restored {
+        if source.exists() {
+            outcome.errors.push(tr!("{} : un fichier occupe déjà l'emplacement de l'original", source.display()));
+            continue;
+        }
+        match fs::rename(backup, source) {
+            Ok(()) => {
+                outcome.restored += 1;
+                if let Some(backup_dir) = backup.parent() {
+                    let _ = fs::remove_dir(backup_dir); // Dossier de sauvegarde retiré une fois vide
+                }
+            }
+            Err(e) => outcome.errors.push(tr!("{} : restauration impossible ({})", source.display(), e)),
+        }
+    }
+    if let Some(run_dir) = &plan.run_folder {
+        run_folder::remove_if_empty(run_dir);
+    }
+    tracing::info!(removed = outcome.removed, restored = outcome.restored, errors = outcome.errors.len(), "conversion annulée");
+    outcome
+}
+
+/// Retire les dossiers vides entre `file` et `output_root` (exclue), du plus profond au moins profond.
+pub fn remove_empty_parents(file: &Path, output_root: &Path) {
+    for dir in file.ancestors().skip(1) {
+        if !dir.starts_with(output_root) || dir == output_root || fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,203 @@
+/// Ce module vérifie les fichiers produits : une fois écrite, chaque sortie est relue sur le disque et décodée,
+/// et ses dimensions sont comparées à celles de l'image encodée. En option, la fidélité de la sortie est mesurée
+/// par rapport à l'image avant encodage (PSNR en dB, SSIM de 0 à 1) et une sortie sous le seuil SSIM est signalée.
+/// Les sorties en échec figurent dans le bilan ; leur source n'est jamais mise à la corbeille ni déplacée.
+use image::{DynamicImage, GenericImageView, ImageFormat, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::encoder;
+use crate::tr;
+
+/// PSNR retenu pour deux images identiques (le PSNR réel est infini).
+const MAX_PSNR: f64 = 100.0;
+
+/// Côté des fenêtres du calcul du SSIM (pixels).
+const SSIM_WINDOW: u32 = 8;
+
+/// Réglages de la vérification des sorties.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerifyOptions {
+    pub enabled: bool,         // Relire et décoder chaque fichier produit.
+    pub measure_quality: bool, // Mesurer le PSNR et le SSIM de la sortie par rapport à l'image avant encodage.
+    pub min_ssim: f64,         // SSIM minimal (0 à 1) en dessous duquel la sortie est signalée, 0 = aucun seuil.
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            measure_quality: false,
+            min_ssim: 0.0,
+        }
+    }
+}
+
+/// Fidélité d'une sortie par rapport à l'image avant encodage.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct QualityScore {
+    pub psnr: f64, // Rapport signal/bruit de crête (dB), `MAX_PSNR` pour une image identique.
+    pub ssim: f64, // Similarité structurelle, de 0 à 1 (1 = identique).
+}
+
+/// Résultat de la vérification d'une sortie.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Verification {
+    NotChecked,                  // Vérification désactivée.
+    Unsupported,                 // Format que la bibliothèque ne sait pas relire (ex: AVIF).
+    Valid(Option<QualityScore>), // Sortie lisible, avec sa fidélité si elle a été mesurée.
+    Failed(String),              // Sortie illisible, incohérente ou sous le seuil SSIM, avec la raison.
+}
+
+impl Verification {
+    /// Indique si la vérification a échoué.
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Verification::Failed(_))
+    }
+
+    /// Description affichée dans le bilan et le rapport exporté.
+    pub fn description(&self) -> String {
+        match self {
+            Verification::NotChecked => tr!("non vérifiée").to_string(),
+            Verification::Unsupported => tr!("format non relu").to_string(),
+            Verification::Valid(None) => tr!("valide").to_string(),
+            Verification::Valid(Some(score)) => {
+                tr!("valide (PSNR {} dB, SSIM {})", format!("{:.1}", score.psnr), format!("{:.4}", score.ssim))
+            }
+            Verification::Failed(reason) => tr!("en échec : {}", reason),
+        }
+    }
+}
+
+/// Vérifie la sortie `output` : elle doit se décoder et, si `expected` (l'image encodée) est fourni, avoir les mêmes
+/// dimensions ; sa fidélité est alors mesurée si `options.measure_quality` est actif. Sans `expected` (source copiée
+/// telle quelle), seule la lecture est vérifiée. Une sortie sans transparence (JPEG...) est comparée à l'image
+/// aplatie sur `background_color`, comme l'a fait l'encodeur.
+pub fn verify_output(
+    output: &Path,
+    expected: Option<&DynamicImage>,
+    options: &VerifyOptions,
+    background_color: [u8; 3],
+) -> Verification {
+    if !options.enabled {
+        return Verification::NotChecked;
+    }
+    if ImageFormat::from_path(output).is_ok_and(|format| !format.reading_enabled()) {
+        return Verification::Unsupported;
+    }
+    let decoded = match image::open(output) {
+        Ok(decoded) => decoded,
+        Err(e) => return Verification::Failed(tr!("sortie illisible ({})", e)),
+    };
+    let Some(expected) = expected else {
+        return Verification::Valid(None);
+    };
+    if decoded.dimensions() != expected.dimensions() {
+        let (width, height) = decoded.dimensions();
+        let (expected_width, expected_height) = expected.dimensions();
+        return Verification::Failed(tr!(
+            "dimensions {}x{} au lieu de {}x{}",
+            width,
+            height,
+            expected_width,
+            expected_height
+        ));
+    }
+    if !options.measure_quality {
+        return Verification::Valid(None);
+    }
+
+    let expected = match expected.color().has_alpha() && !decoded.color().has_alpha() {
+        true => Cow::Owned(DynamicImage::ImageRgb8(encoder::flatten_alpha(expected, background_color))),
+        false => Cow::Borrowed(expected),
+    };
+    let score = quality_score(&expected.to_rgba8(), &decoded.to_rgba8());
+    if options.min_ssim > 0.0 && score.ssim < options.min_ssim {
+        return Verification::Failed(tr!(
+            "SSIM {} inférieur au seuil {}",
+            format!("{:.4}", score.ssim),
+            options.min_ssim
+        ));
+    }
+    Verification::Valid(Some(score))
+}
+
+/// Mesure la fidélité de `actual` par rapport à `reference` (mêmes dimensions). Les couleurs sont prémultipliées
+/// par l'opacité : les couleurs cachées sous des pixels transparents, que les encodeurs peuvent modifier, ne comptent pas.
+fn quality_score(reference: &RgbaImage, actual: &RgbaImage) -> QualityScore {
+    let reference = premultiplied(reference);
+    let actual = premultiplied(actual);
+    QualityScore { psnr: psnr(&reference, &actual), ssim: ssim(reference.width, &reference.luma, &actual.luma) }
+}
+
+/// Pixels d'une image aux couleurs prémultipliées, et leur luminance.
+struct Premultiplied {
+    width: u32,     // Largeur de l'image.
+    rgb: Vec<f64>,  // Composantes rouge, vert, bleu de chaque pixel (0 à 255).
+    luma: Vec<f64>, // Luminance de chaque pixel (0 à 255, pondération BT.601).
+}
+
+/// Prémultiplie les couleurs de `image` par leur opacité et calcule leur luminance.
+fn premultiplied(image: &RgbaImage) -> Premultiplied {
+    let mut rgb = Vec::with_capacity(image.len() / 4 * 3);
+    let mut luma = Vec::with_capacity(image.len() / 4);
+    for pixel in image.pixels() {
+        let [red, green, blue, alpha] = pixel.0.map(f64::from);
+        let (red, green, blue) = (red * alpha / 255.0, green * alpha / 255.0, blue * alpha / 255.0);
+        rgb.extend([red, green, blue]);
+        luma.push(0.299 * red + 0.587 * green + 0.114 * blue);
+    }
+    Premultiplied { width: image.width(), rgb, luma }
+}
+
+/// PSNR (dB) des composantes de couleur, plafonné à `MAX_PSNR`.
+fn psnr(reference: &Premultiplied, actual: &Premultiplied) -> f64 {
+    if reference.rgb.is_empty() {
+        return MAX_PSNR;
+    }
+    let squared_error: f64 = reference.rgb.iter().zip(&actual.rgb).map(|(a, b)| (a - b) * (a - b)).sum();
+    let mse = squared_error / reference.rgb.len() as f64;
+    if mse == 0.0 {
+        return MAX_PSNR;
+    }
+    (10.0 * (255.0 * 255.0 / mse).log10()).min(MAX_PSNR)
+}
+
+/// SSIM moyen de la luminance, calculé sur des fenêtres de `SSIM_WINDOW` pixels de côté
+/// (les fenêtres du bord droit et du bas sont plus petites si les dimensions ne sont pas multiples).
+fn ssim(width: u32, reference: &[f64], actual: &[f64]) -> f64 {
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+    let width = width as usize;
+    if width == 0 || reference.is_empty() {
+        return 1.0;
+    }
+    let height = reference.len() / width;
+    let window = SSIM_WINDOW as usize;
+
+    let (mut total, mut windows) = (0.0, 0usize);
+    for top in (0..height).step_by(window) {
+        for left in (0..width).step_by(window) {
+            let indices = || {
+                (top..(top + window).min(height)).flat_map(move |y| (left..(left + window).min(width)).map(move |x| y * width + x))
+            };
+            let count = indices().count() as f64;
+            let mean_x = indices().map(|i| reference[i]).sum::<f64>() / count;
+            let mean_y = indices().map(|i| actual[i]).sum::<f64>() / count;
+            let (mut variance_x, mut variance_y, mut covariance) = (0.0, 0.0, 0.0);
+            for i in indices() {
+                let (dx, dy) = (reference[i] - mean_x, actual[i] - mean_y);
+                variance_x += dx * dx;
+                variance_y += dy * dy;
+                covariance += dx * dy;
+            }
+            let (variance_x, variance_y, covariance) = (variance_x / count, variance_y / count, covariance / count);
+            total += ((2.0 * mean_x * mean_y + C1) * (2.0 * covariance + C2))
+                / ((mean_x * mean_x + mean_y * mean_y + C1) * (variance_x + variance_y + C2));
+            windows += 1;
+        }
+    }
+    total / windows as f64
+}
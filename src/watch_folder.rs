@@ -0,0 +1,174 @@
+/// Ce module gère le mode surveillance : chaque image déposée dans le dossier surveillé est
+/// convertie automatiquement vers le répertoire de sortie, avec les options en vigueur au démarrage.
+/// Les évènements du système de fichiers sont fournis par `notify` ; les conversions ont lieu dans un thread dédié.
+use eframe::egui;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::converter::{self, BatchControl, ConversionOptions, OverwriteMode};
+use crate::error::ConverterError;
+use crate::platform_utils;
+use crate::tr;
+
+/// Délai sans nouvel évènement après lequel un fichier est considéré comme entièrement copié.
+const SETTLE_DELAY: Duration = Duration::from_millis(1000);
+
+/// Intervalle de vérification des fichiers en attente.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Nombre maximal d'entrées conservées dans le journal.
+const MAX_LOG_ENTRIES: usize = 200;
+
+/// Entrée du journal des conversions automatiques.
+#[derive(Debug, Clone)]
+pub struct WatchLogEntry {
+    pub time: String,                    // Heure de la conversion (HH:MM:SS).
+    pub source: PathBuf,                 // Image déposée.
+    pub result: Result<PathBuf, String>, // Fichier produit, ou message d'erreur.
+}
+
+/// Surveillance en cours (ou non) et journal des conversions automatiques.
+#[derive(Default)]
+pub struct FolderWatcher {
+    watcher: Option<RecommendedWatcher>, // Surveillance active ; la supprimer arrête le thread de conversion.
+    folder: Option<PathBuf>,             // Dossier surveillé.
+    log: Arc<Mutex<Vec<WatchLogEntry>>>, // Journal, du plus ancien au plus récent.
+}
+
+impl FolderWatcher {
+    /// Démarre la surveillance de `folder`. Les images qui y sont créées ou copiées sont converties dans `output_dir`.
+    pub fn start(
+        &mut self,
+        ctx: &egui::Context,
+        folder: &Path,
+        output_dir: &Path,
+        options: &ConversionOptions,
+    ) -> Result<(), ConverterError> {
+        self.stop();
+        // Une sortie dans le dossier surveillé déclencherait de nouvelles conversions sans fin.
+        std::fs::create_dir_all(output_dir).map_err(|e| {
+            ConverterError::io(tr!("Impossible de créer le répertoire de sortie {}", output_dir.display()), e)
+        })?;
+        platform_utils::validate_paths(folder, output_dir)?;
+
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    for path in event.paths {
+                        // Une erreur signifie que la surveillance a été arrêtée : l'évènement est sans objet.
+                        let _ = sender.send(path);
+                    }
+                }
+            }
+        })
+        .map_err(|e| ConverterError::PathValidation(tr!("Impossible de surveiller le dossier : {}", e)))?;
+        watcher
+            .watch(folder, RecursiveMode::NonRecursive)
+            .map_err(|e| ConverterError::PathValidation(tr!("Impossible de surveiller {} : {}", folder.display(), e)))?;
+
+        let log = Arc::clone(&self.log);
+        let ctx_clone = ctx.clone();
+        let output_dir = output_dir.to_path_buf();
+        // Personne ne répond aux questions pendant la surveillance : le mode Demander conserve les sorties existantes.
+        let mut options = options.clone();
+        if options.overwrite_mode == OverwriteMode::Ask {
+            options.overwrite_mode = OverwriteMode::Skip;
+        }
+        std::thread::spawn(move || convert_settled_files(receiver, &output_dir, &options, &log, &ctx_clone));
+
+        self.watcher = Some(watcher);
+        self.folder = Some(folder.to_path_buf());
+        Ok(())
+    }
+
+    /// Arrête la surveillance ; le thread de conversion se termine après le fichier en cours.
+    pub fn stop(&mut self) {
+        self.watcher = None;
+        self.folder = None;
+    }
+
+    /// Vrai si un dossier est surveillé.
+    pub fn is_running(&self) -> bool {
+        self.watcher.is_some()
+    }
+
+    /// Dossier surveillé, s'il y en a un.
+    pub fn folder(&self) -> Option<&Path> {
+        self.folder.as_deref()
+    }
+
+    /// Copie du journal des conversions automatiques.
+    pub fn log(&self) -> Vec<WatchLogEntry> {
+        self.log.lock().unwrap().clone()
+    }
+
+    /// Vide le journal.
+    pub fn clear_log(&self) {
+        self.log.lock().unwrap().clear();
+    }
+}
+
+/// Boucle du thread de conversion : regroupe les évènements par fichier et convertit chaque image
+/// une fois qu'aucun évènement n'est arrivé depuis `SETTLE_DELAY` (copie terminée).
+/// Se termine quand la surveillance est arrêtée (canal fermé).
+fn convert_settled_files(
+    receiver: Receiver<PathBuf>,
+    output_dir: &Path,
+    options: &ConversionOptions,
+    log: &Mutex<Vec<WatchLogEntry>>,
+    ctx: &egui::Context,
+) {
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(path) => {
+                if converter::is_supported_image(&path) {
+                    pending.insert(path, Instant::now());
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let settled: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, last_event)| last_event.elapsed() >= SETTLE_DELAY)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in settled {
+            pending.remove(&path);
+            if !path.is_file() {
+                continue; // Fichier renommé ou supprimé entre-temps.
+            }
+            let entry = WatchLogEntry {
+                time: chrono::Local::now().format("%H:%M:%S").to_string(),
+                result: convert_one(&path, output_dir, options),
+                source: path,
+            };
+            let mut log = log.lock().unwrap();
+            log.push(entry);
+            if log.len() > MAX_LOG_ENTRIES {
+                log.remove(0);
+            }
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Convertit une image déposée et retourne le fichier produit, ou la raison de l'absence de sortie.
+fn convert_one(path: &Path, output_dir: &Path, options: &ConversionOptions) -> Result<PathBuf, String> {
+    let report = converter::convert_single_image(path, output_dir, options, &|_| {}, &BatchControl::default())
+        .map_err(|e| e.to_string())?;
+    if let Some(failure) = report.failures.first() {
+        return Err(failure.error.to_string());
+    }
+    match report.files.first() {
+        Some(file) => Ok(file.output.clone()),
+        None => Err("Ignoré (fichier de sortie existant ou source inchangée)".to_string()),
+    }
+}